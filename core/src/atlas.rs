@@ -0,0 +1,82 @@
+//! Sprite atlas lookup for sheets packed by `rlvgl-creator atlas`.
+//!
+//! The creator emits one [`AtlasRect`] constant per sprite and a table of
+//! `(name, rect)` pairs sorted by name, which [`Atlas`] searches at runtime.
+
+/// Location of a sprite inside an atlas sheet.
+///
+/// `width` and `height` describe the trimmed sprite as it is displayed.
+/// When `rotated` is set the sprite was stored turned 90° clockwise, so it
+/// occupies `height` columns and `width` rows of the sheet starting at
+/// `(x, y)`. Trimmed transparent borders are restored by drawing the sprite
+/// at `(offset_x, offset_y)` inside a `source_width` × `source_height` box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasRect {
+    /// Index of the sheet holding the sprite.
+    pub sheet: u16,
+    /// Left edge within the sheet.
+    pub x: u16,
+    /// Top edge within the sheet.
+    pub y: u16,
+    /// Displayed width of the trimmed sprite.
+    pub width: u16,
+    /// Displayed height of the trimmed sprite.
+    pub height: u16,
+    /// Horizontal offset of the trimmed sprite within the original image.
+    pub offset_x: u16,
+    /// Vertical offset of the trimmed sprite within the original image.
+    pub offset_y: u16,
+    /// Width of the original image before trimming.
+    pub source_width: u16,
+    /// Height of the original image before trimming.
+    pub source_height: u16,
+    /// Whether the sprite is stored rotated 90° clockwise.
+    pub rotated: bool,
+}
+
+impl AtlasRect {
+    /// Sheet coordinates of sprite pixel `(x, y)` in displayed orientation.
+    pub fn sheet_pos(&self, x: u16, y: u16) -> (u16, u16) {
+        if self.rotated {
+            (self.x + self.height - 1 - y, self.y + x)
+        } else {
+            (self.x + x, self.y + y)
+        }
+    }
+}
+
+/// Name-indexed table of sprites.
+#[derive(Debug, Clone, Copy)]
+pub struct Atlas<'a> {
+    entries: &'a [(&'a str, AtlasRect)],
+}
+
+impl<'a> Atlas<'a> {
+    /// Wrap a table of sprites sorted by name.
+    pub const fn new(entries: &'a [(&'a str, AtlasRect)]) -> Self {
+        Self { entries }
+    }
+
+    /// Look up the sprite called `name`.
+    pub fn get(&self, name: &str) -> Option<&'a AtlasRect> {
+        self.entries
+            .binary_search_by(|(n, _)| (*n).cmp(name))
+            .ok()
+            .map(|i| &self.entries[i].1)
+    }
+
+    /// Number of sprites in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the table has no sprites.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over all sprites in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a AtlasRect)> {
+        self.entries.iter().map(|(n, r)| (*n, r))
+    }
+}
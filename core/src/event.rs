@@ -1,7 +1,14 @@
 //! Basic UI events used for widgets.
 
+pub use crate::gesture::{Gesture, GesturePhase};
+
+/// Interval in milliseconds that animated widgets assume between
+/// [`Event::Tick`]s until they are given the real tick period.
+pub const DEFAULT_TICK_MS: u32 = 16;
+
 /// Event types propagated through the widget tree.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// Called periodically to advance animations or timers.
     Tick,
@@ -26,6 +33,48 @@ pub enum Event {
         /// Vertical coordinate relative to the widget origin.
         y: i32,
     },
+    /// A finger touched a multi-touch screen.
+    ///
+    /// Multi-touch devices report every finger with these events and also
+    /// report the first finger down as pointer events.
+    TouchDown {
+        /// Identifier that stays the same until the finger is lifted.
+        id: u8,
+        /// Horizontal coordinate relative to the widget origin.
+        x: i32,
+        /// Vertical coordinate relative to the widget origin.
+        y: i32,
+    },
+    /// A finger moved while touching the screen.
+    TouchMove {
+        /// Identifier given in the matching [`Event::TouchDown`].
+        id: u8,
+        /// Horizontal coordinate relative to the widget origin.
+        x: i32,
+        /// Vertical coordinate relative to the widget origin.
+        y: i32,
+    },
+    /// A finger was lifted.
+    TouchUp {
+        /// Identifier given in the matching [`Event::TouchDown`].
+        id: u8,
+        /// Horizontal coordinate relative to the widget origin.
+        x: i32,
+        /// Vertical coordinate relative to the widget origin.
+        y: i32,
+    },
+    /// A two-finger pinch or rotation, reported by
+    /// [`GestureRecognizer`](crate::gesture::GestureRecognizer).
+    Gesture(Gesture),
+    /// A rotary encoder was turned.
+    ///
+    /// Focus handling moves between widgets with these steps. A widget
+    /// being edited, such as a spinbox, changes its value instead.
+    Encoder {
+        /// Detents turned, positive clockwise. Fast turns may report
+        /// several per detent when the encoder accelerates.
+        steps: i32,
+    },
     /// A keyboard key was pressed.
     KeyDown {
         /// Key that was pressed.
@@ -40,6 +89,7 @@ pub enum Event {
 
 /// Identifiers for keyboard keys.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     /// Escape key.
     Escape,
@@ -0,0 +1,401 @@
+//! Reader for `RLVGLFNT` bitmap fonts produced by `rlvgl-creator fonts pack`.
+//!
+//! All integers are little-endian. The file is laid out as
+//!
+//! ```text
+//! "RLVGLFNT", u8 version, u8 bpp, u8 flags, u8 reserved,
+//!   u16 size_px, i16 ascent, i16 descent, u16 line_height,
+//!   u16 range_count, u16 glyph_count, u32 kern_count, u32 bitmap_len,
+//!   range_count * (u32 first_codepoint, u16 len, u16 first_glyph),
+//!   glyph_count * (u32 offset, u16 advance, u16 width, u16 height,
+//!                  i16 left, i16 top, u16 reserved),
+//!   kern_count * (u16 left_glyph, u16 right_glyph, i16 adjust),
+//!   bitmap_len bytes of glyph bitmaps
+//! ```
+//!
+//! Ranges map sorted runs of codepoints to consecutive glyphs. Advances and
+//! kerning adjustments are in 1/16 pixel; `left` and `top` place the bitmap
+//! relative to the pen position on the baseline, with `top` pointing up.
+//! Kerning pairs are sorted by glyph indices.
+//!
+//! Each bitmap holds `width * height` coverage values of `bpp` bits (1, 2, 4
+//! or 8), packed row-major with the leftmost pixel in the high bits and every
+//! glyph starting on a byte boundary. With bit 0 of `flags` set, bitmaps are
+//! instead `(run, value)` byte pairs. Nothing is allocated while reading, so
+//! fonts can be used straight from flash.
+
+use crate::renderer::Renderer;
+use crate::widget::{Color, Rect};
+
+/// Magic identifier at the start of every font file.
+pub const MAGIC: &[u8; 8] = b"RLVGLFNT";
+
+/// Format version understood by this reader.
+pub const VERSION: u8 = 1;
+
+/// Flag bit set when glyph bitmaps are run-length encoded.
+pub const FLAG_RLE: u8 = 1;
+
+const HEADER_LEN: usize = 32;
+const RANGE_LEN: usize = 8;
+const GLYPH_LEN: usize = 16;
+const KERN_LEN: usize = 6;
+
+/// Errors reported while parsing a font file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The file uses a format version this reader does not support.
+    BadVersion,
+    /// The bit depth is not 1, 2, 4 or 8.
+    BadBpp,
+    /// The data ends before a table is complete.
+    Truncated,
+}
+
+fn u16_at(data: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([data[pos], data[pos + 1]])
+}
+
+fn i16_at(data: &[u8], pos: usize) -> i16 {
+    u16_at(data, pos) as i16
+}
+
+fn u32_at(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+/// Parsed bitmap font borrowing its data.
+#[derive(Debug, Clone, Copy)]
+pub struct BitmapFont<'a> {
+    ranges: &'a [u8],
+    glyphs: &'a [u8],
+    kerning: &'a [u8],
+    bitmaps: &'a [u8],
+    bpp: u8,
+    rle: bool,
+    /// Nominal pixel size the font was rasterized at.
+    pub size: u16,
+    /// Distance from the baseline to the top of the tallest glyph.
+    pub ascent: i16,
+    /// Distance from the baseline to the bottom of the lowest glyph, usually
+    /// negative.
+    pub descent: i16,
+    /// Recommended distance between consecutive baselines.
+    pub line_height: u16,
+}
+
+impl<'a> BitmapFont<'a> {
+    /// Parse the header and tables of `data`.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(..MAGIC.len()) != Some(MAGIC) {
+            return Err(Error::BadMagic);
+        }
+        if data.len() < HEADER_LEN {
+            return Err(Error::Truncated);
+        }
+        if data[8] != VERSION {
+            return Err(Error::BadVersion);
+        }
+        let bpp = data[9];
+        if !matches!(bpp, 1 | 2 | 4 | 8) {
+            return Err(Error::BadBpp);
+        }
+        let ranges_len = u16_at(data, 20) as usize * RANGE_LEN;
+        let glyphs_len = u16_at(data, 22) as usize * GLYPH_LEN;
+        let kern_len = u32_at(data, 24) as usize * KERN_LEN;
+        let bitmap_len = u32_at(data, 28) as usize;
+        let mut pos = HEADER_LEN;
+        let mut table = |len: usize| -> Result<&'a [u8], Error> {
+            let end = pos.checked_add(len).ok_or(Error::Truncated)?;
+            let bytes = data.get(pos..end).ok_or(Error::Truncated)?;
+            pos = end;
+            Ok(bytes)
+        };
+        Ok(Self {
+            ranges: table(ranges_len)?,
+            glyphs: table(glyphs_len)?,
+            kerning: table(kern_len)?,
+            bitmaps: table(bitmap_len)?,
+            bpp,
+            rle: data[10] & FLAG_RLE != 0,
+            size: u16_at(data, 12),
+            ascent: i16_at(data, 14),
+            descent: i16_at(data, 16),
+            line_height: u16_at(data, 18),
+        })
+    }
+
+    /// Bits of coverage stored per pixel.
+    pub fn bpp(&self) -> u8 {
+        self.bpp
+    }
+
+    /// Number of glyphs in the font.
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len() / GLYPH_LEN
+    }
+
+    /// Index of the glyph for `ch`, if the font contains it.
+    pub fn glyph_index(&self, ch: char) -> Option<u16> {
+        let cp = ch as u32;
+        let count = self.ranges.len() / RANGE_LEN;
+        // Ranges are sorted, so find the last one starting at or before `cp`.
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if u32_at(self.ranges, mid * RANGE_LEN) <= cp {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let at = lo.checked_sub(1)? * RANGE_LEN;
+        let first = u32_at(self.ranges, at);
+        let len = u16_at(self.ranges, at + 4) as u32;
+        if cp - first >= len {
+            return None;
+        }
+        let index = u16_at(self.ranges, at + 6).checked_add((cp - first) as u16)?;
+        ((index as usize) < self.glyph_count()).then_some(index)
+    }
+
+    /// Glyph stored at `index`.
+    pub fn glyph_at(&self, index: u16) -> Option<Glyph<'a>> {
+        let at = index as usize * GLYPH_LEN;
+        let g = self.glyphs.get(at..at + GLYPH_LEN)?;
+        let offset = u32_at(g, 0) as usize;
+        let (width, height) = (u16_at(g, 6), u16_at(g, 8));
+        let end = if self.rle {
+            // RLE bitmaps end where the next glyph's begins.
+            self.glyphs
+                .get(at + GLYPH_LEN..at + GLYPH_LEN + 4)
+                .map_or(self.bitmaps.len(), |next| u32_at(next, 0) as usize)
+        } else {
+            offset + (width as usize * height as usize * self.bpp as usize).div_ceil(8)
+        };
+        Some(Glyph {
+            advance: u16_at(g, 4),
+            width,
+            height,
+            left: i16_at(g, 10),
+            top: i16_at(g, 12),
+            bpp: self.bpp,
+            rle: self.rle,
+            data: self.bitmaps.get(offset..end).unwrap_or(&[]),
+        })
+    }
+
+    /// Glyph for `ch`, if the font contains it.
+    pub fn glyph(&self, ch: char) -> Option<Glyph<'a>> {
+        self.glyph_at(self.glyph_index(ch)?)
+    }
+
+    /// Kerning adjustment in 1/16 pixel between glyphs `left` and `right`.
+    pub fn kerning(&self, left: u16, right: u16) -> i32 {
+        let count = self.kerning.len() / KERN_LEN;
+        let key = (left, right);
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let at = mid * KERN_LEN;
+            let pair = (u16_at(self.kerning, at), u16_at(self.kerning, at + 2));
+            match pair.cmp(&key) {
+                core::cmp::Ordering::Less => lo = mid + 1,
+                core::cmp::Ordering::Greater => hi = mid,
+                core::cmp::Ordering::Equal => return i16_at(self.kerning, at + 4) as i32,
+            }
+        }
+        0
+    }
+
+    /// Visit each glyph of `text` with its pen position in 1/16 pixel,
+    /// returning the final pen position. Characters missing from the font are
+    /// skipped.
+    fn layout(&self, text: &str, mut visit: impl FnMut(i32, &Glyph<'a>)) -> i32 {
+        let mut pen = 0i32;
+        let mut prev = None;
+        for ch in text.chars() {
+            let Some(index) = self.glyph_index(ch) else {
+                continue;
+            };
+            let Some(glyph) = self.glyph_at(index) else {
+                continue;
+            };
+            if let Some(p) = prev {
+                pen += self.kerning(p, index);
+            }
+            visit(pen, &glyph);
+            pen += glyph.advance as i32;
+            prev = Some(index);
+        }
+        pen
+    }
+
+    /// Width of `text` in pixels including kerning.
+    pub fn measure(&self, text: &str) -> i32 {
+        (self.layout(text, |_, _| {}) + 8) >> 4
+    }
+
+    /// Draw `text` with its baseline at `position`, blending coverage between
+    /// `color` and `bg`.
+    ///
+    /// Pixels of equal coverage within a glyph row are merged into a single
+    /// [`Renderer::fill_rect`] call.
+    pub fn draw_text(
+        &self,
+        renderer: &mut dyn Renderer,
+        position: (i32, i32),
+        text: &str,
+        color: Color,
+        bg: Color,
+    ) {
+        self.layout(text, |pen, glyph| {
+            let x0 = position.0 + ((pen + 8) >> 4) + glyph.left as i32;
+            let y0 = position.1 - glyph.top as i32;
+            let w = glyph.width as i32;
+            let mut coverage = glyph.coverage();
+            for y in 0..glyph.height as i32 {
+                let mut run = (0, 0u8);
+                for x in 0..=w {
+                    let c = if x < w {
+                        coverage.next().unwrap_or(0)
+                    } else {
+                        0
+                    };
+                    if x < w && c == run.1 {
+                        continue;
+                    }
+                    if run.1 != 0 {
+                        let rect = Rect {
+                            x: x0 + run.0,
+                            y: y0 + y,
+                            width: x - run.0,
+                            height: 1,
+                        };
+                        renderer.fill_rect(rect, blend(color, bg, run.1));
+                    }
+                    run = (x, c);
+                }
+            }
+        });
+    }
+}
+
+/// Mix `fg` over `bg` with coverage `t` (`255` yields `fg`).
+fn blend(fg: Color, bg: Color, t: u8) -> Color {
+    let t = t as u16;
+    let inv = 255 - t;
+    let m = |a: u8, b: u8| ((a as u16 * t + b as u16 * inv) / 255) as u8;
+    Color(m(fg.0, bg.0), m(fg.1, bg.1), m(fg.2, bg.2), m(fg.3, bg.3))
+}
+
+/// Metrics and bitmap of a single glyph.
+#[derive(Debug, Clone, Copy)]
+pub struct Glyph<'a> {
+    /// Horizontal pen advance in 1/16 pixel.
+    pub advance: u16,
+    /// Bitmap width in pixels.
+    pub width: u16,
+    /// Bitmap height in pixels.
+    pub height: u16,
+    /// Offset from the pen position to the left edge of the bitmap.
+    pub left: i16,
+    /// Offset from the baseline up to the top edge of the bitmap.
+    pub top: i16,
+    bpp: u8,
+    rle: bool,
+    data: &'a [u8],
+}
+
+impl<'a> Glyph<'a> {
+    /// Coverage of every bitmap pixel in row-major order, scaled to
+    /// `0..=255`.
+    pub fn coverage(&self) -> Coverage<'a> {
+        Coverage {
+            data: self.data,
+            bpp: self.bpp,
+            rle: self.rle,
+            remaining: self.width as usize * self.height as usize,
+            index: 0,
+            run: 0,
+            value: 0,
+        }
+    }
+}
+
+/// Iterator over the coverage values of a [`Glyph`].
+#[derive(Debug, Clone)]
+pub struct Coverage<'a> {
+    data: &'a [u8],
+    bpp: u8,
+    rle: bool,
+    remaining: usize,
+    /// Next pixel for packed bitmaps, next byte pair for RLE bitmaps.
+    index: usize,
+    run: u8,
+    value: u8,
+}
+
+impl Iterator for Coverage<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let max = (1u16 << self.bpp) - 1;
+        let raw = if self.rle {
+            if self.run == 0 {
+                let pair = self.data.get(self.index..self.index + 2)?;
+                self.index += 2;
+                (self.run, self.value) = (pair[0], pair[1]);
+            }
+            self.run = self.run.saturating_sub(1);
+            self.value
+        } else {
+            let bit = self.index * self.bpp as usize;
+            self.index += 1;
+            let byte = *self.data.get(bit / 8)?;
+            let shift = 8 - self.bpp as usize - bit % 8;
+            (byte >> shift) & max as u8
+        };
+        Some((raw as u16 * 255 / max) as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// Renderer adapter drawing text with a [`BitmapFont`].
+///
+/// Rectangles pass through to the wrapped renderer while
+/// [`Renderer::draw_text`] lays text out with the font, so widgets render
+/// with it unchanged.
+pub struct FontRenderer<'r, 'f> {
+    inner: &'r mut dyn Renderer,
+    font: &'r BitmapFont<'f>,
+    /// Color blended under partially covered text pixels.
+    pub bg: Color,
+}
+
+impl<'r, 'f> FontRenderer<'r, 'f> {
+    /// Wrap `inner` so text is drawn with `font` over `bg`.
+    pub fn new(inner: &'r mut dyn Renderer, font: &'r BitmapFont<'f>, bg: Color) -> Self {
+        Self { inner, font, bg }
+    }
+}
+
+impl Renderer for FontRenderer<'_, '_> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.inner.fill_rect(rect, color);
+    }
+
+    fn draw_text(&mut self, position: (i32, i32), text: &str, color: Color) {
+        self.font
+            .draw_text(&mut *self.inner, position, text, color, self.bg);
+    }
+}
@@ -0,0 +1,102 @@
+//! Frame sources for animated images.
+//!
+//! Decoders such as GIF, APNG and Dash Lottie expose their output through the
+//! [`FrameSource`] trait so a single widget can play any of them. Sources may
+//! keep every frame in memory ([`Frames`]) or decode on demand.
+
+use crate::widget::Color;
+use alloc::vec::Vec;
+
+/// Sequence of equally sized frames that can be shown one at a time.
+pub trait FrameSource {
+    /// Width and height of every frame in pixels.
+    fn size(&self) -> (u32, u32);
+
+    /// Number of frames in one loop of the animation.
+    fn frame_count(&self) -> usize;
+
+    /// Display time of frame `index` in milliseconds.
+    fn delay_ms(&self, index: usize) -> u32;
+
+    /// Number of times the animation plays; `0` loops forever.
+    fn loop_count(&self) -> u32 {
+        0
+    }
+
+    /// Make frame `index` current, decoding it if needed.
+    ///
+    /// Returns `false` if the frame is out of range or could not be decoded.
+    fn seek(&mut self, index: usize) -> bool;
+
+    /// Pixels of the current frame in row-major order.
+    fn pixels(&self) -> &[Color];
+}
+
+/// Single fully decoded frame.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Pixel data in row-major order.
+    pub pixels: Vec<Color>,
+    /// Display time in milliseconds.
+    pub delay_ms: u32,
+}
+
+/// In-memory animation holding every decoded frame.
+#[derive(Debug, Clone)]
+pub struct Frames {
+    /// Width of each frame in pixels.
+    pub width: u32,
+    /// Height of each frame in pixels.
+    pub height: u32,
+    /// Decoded frames in display order.
+    pub frames: Vec<Frame>,
+    /// Number of plays; `0` loops forever.
+    pub loop_count: u32,
+    current: usize,
+}
+
+impl Frames {
+    /// Wrap decoded frames that loop forever.
+    pub fn new(width: u32, height: u32, frames: Vec<Frame>) -> Self {
+        Self {
+            width,
+            height,
+            frames,
+            loop_count: 0,
+            current: 0,
+        }
+    }
+}
+
+impl FrameSource for Frames {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    fn delay_ms(&self, index: usize) -> u32 {
+        self.frames.get(index).map_or(0, |f| f.delay_ms)
+    }
+
+    fn loop_count(&self) -> u32 {
+        self.loop_count
+    }
+
+    fn seek(&mut self, index: usize) -> bool {
+        if index < self.frames.len() {
+            self.current = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn pixels(&self) -> &[Color] {
+        self.frames
+            .get(self.current)
+            .map_or(&[], |f| f.pixels.as_slice())
+    }
+}
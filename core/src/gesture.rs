@@ -0,0 +1,147 @@
+//! Two-finger pinch and rotate gesture recognition.
+//!
+//! [`GestureRecognizer`] follows the first two fingers reported through
+//! [`Event::TouchDown`], [`Event::TouchMove`] and [`Event::TouchUp`]. It
+//! reports how far they have spread and turned since the second finger
+//! touched down. Widgets such as image viewers apply these amounts to the
+//! zoom and angle they had when the gesture started, so rounding errors do
+//! not build up over a long gesture.
+
+use crate::event::Event;
+use crate::math::{atan2, sqrt};
+
+/// [`Gesture::scale`] of fingers as far apart as when the gesture started.
+pub const SCALE_NONE: i32 = 256;
+
+/// Stage of a [`Gesture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GesturePhase {
+    /// The second finger touched down.
+    Start,
+    /// A finger moved.
+    Update,
+    /// One of the two fingers was lifted.
+    End,
+}
+
+/// Two-finger gesture state relative to its start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gesture {
+    /// Stage of the gesture.
+    pub phase: GesturePhase,
+    /// Horizontal midpoint between the fingers.
+    pub x: i32,
+    /// Vertical midpoint between the fingers.
+    pub y: i32,
+    /// Finger distance relative to the start, in 1/256 units
+    /// ([`SCALE_NONE`] is unchanged).
+    pub scale: i32,
+    /// Clockwise turn since the start in degrees, `-179..=180`.
+    pub angle: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Finger {
+    id: u8,
+    x: i32,
+    y: i32,
+}
+
+/// Turns touch events into [`Event::Gesture`] events.
+#[derive(Debug, Default, Clone)]
+pub struct GestureRecognizer {
+    fingers: [Option<Finger>; 2],
+    /// Finger distance and angle when the gesture started.
+    start: Option<(u32, i32)>,
+    last: Option<Gesture>,
+}
+
+impl GestureRecognizer {
+    /// Recognizer with no fingers down.
+    pub const fn new() -> Self {
+        Self {
+            fingers: [None, None],
+            start: None,
+            last: None,
+        }
+    }
+
+    /// Whether two fingers are down.
+    pub fn is_active(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Feed an input event, returning a gesture event when the gesture
+    /// starts, changes or ends. Other events are ignored, as are fingers
+    /// beyond the first two.
+    pub fn handle(&mut self, event: &Event) -> Option<Event> {
+        match *event {
+            Event::TouchDown { id, x, y } => {
+                let slot = self.fingers.iter_mut().find(|f| f.is_none())?;
+                *slot = Some(Finger { id, x, y });
+                let (distance, angle) = self.span()?;
+                self.start = Some((distance.max(1), angle));
+                self.emit(GesturePhase::Start)
+            }
+            Event::TouchMove { id, x, y } => {
+                let finger = self.fingers.iter_mut().flatten().find(|f| f.id == id)?;
+                finger.x = x;
+                finger.y = y;
+                self.emit(GesturePhase::Update)
+            }
+            Event::TouchUp { id, .. } => {
+                let slot = self
+                    .fingers
+                    .iter()
+                    .position(|f| matches!(f, Some(f) if f.id == id))?;
+                let end = self.current(GesturePhase::End);
+                self.fingers[slot] = None;
+                self.start = None;
+                self.last = None;
+                end.map(Event::Gesture)
+            }
+            _ => None,
+        }
+    }
+
+    /// Report the current state unless nothing changed since the last report.
+    fn emit(&mut self, phase: GesturePhase) -> Option<Event> {
+        let gesture = self.current(phase)?;
+        if self.last.map(|last| Gesture { phase, ..last }) == Some(gesture) {
+            return None;
+        }
+        self.last = Some(gesture);
+        Some(Event::Gesture(gesture))
+    }
+
+    fn current(&self, phase: GesturePhase) -> Option<Gesture> {
+        let (start_distance, start_angle) = self.start?;
+        let (distance, angle) = self.span()?;
+        let [Some(a), Some(b)] = self.fingers else {
+            return None;
+        };
+        let mut angle = (angle - start_angle).rem_euclid(360);
+        if angle > 180 {
+            angle -= 360;
+        }
+        Some(Gesture {
+            phase,
+            x: (a.x + b.x) / 2,
+            y: (a.y + b.y) / 2,
+            scale: (distance as u64 * SCALE_NONE as u64 / start_distance as u64) as i32,
+            angle,
+        })
+    }
+
+    /// Distance and angle from the first finger to the second.
+    fn span(&self) -> Option<(u32, i32)> {
+        let [Some(a), Some(b)] = self.fingers else {
+            return None;
+        };
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let squared = (dx as i64 * dx as i64 + dy as i64 * dy as i64).min(u32::MAX as i64);
+        Some((sqrt(squared as u32), atan2(dy, dx)))
+    }
+}
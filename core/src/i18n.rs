@@ -0,0 +1,361 @@
+//! Translated string tables with plural rules and runtime language switching.
+//!
+//! `rlvgl-creator i18n` compiles gettext `.po` or XLIFF files into one
+//! [`Catalog`] per locale, all sharing the same [`StringId`]s. A
+//! [`Translator`] looks strings up in the active locale, then in its base
+//! language and finally in the source locale. Messages use `{0}`-style
+//! positional placeholders filled by [`format`], and [`Bindings`] keep widget
+//! texts in sync with the active language.
+
+use alloc::{rc::Rc, string::String, vec::Vec};
+use core::{cell::RefCell, fmt};
+
+use crate::widget::{Rect, Widget};
+
+/// Index of a message in every [`Catalog`] generated together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StringId(pub u16);
+
+/// CLDR plural category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    /// `zero`.
+    Zero,
+    /// `one`.
+    One,
+    /// `two`.
+    Two,
+    /// `few`.
+    Few,
+    /// `many`.
+    Many,
+    /// `other`.
+    Other,
+}
+
+/// CLDR cardinal plural rule of a language, for integer counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralRule {
+    /// A single form (Chinese, Japanese, Korean, ...).
+    Other,
+    /// `one` for 1 (English, German, Spanish, ...).
+    OneOther,
+    /// `one` for 0 and 1 (French, Portuguese, Hindi, ...).
+    OneWithZero,
+    /// `one` for 1, 21, 31…; `few` for 2–4, 22–24…; `many` otherwise
+    /// (Russian, Ukrainian, Serbian, Croatian, ...).
+    EastSlavic,
+    /// `one` for 1, `few` for 2–4 (Czech, Slovak).
+    Czech,
+    /// `one` for 1; `few` for 2–4, 22–24…; `many` otherwise (Polish).
+    Polish,
+    /// Six forms (Arabic).
+    Arabic,
+}
+
+impl PluralRule {
+    /// Rule for a BCP 47 or POSIX locale tag such as `de`, `pt-BR` or
+    /// `ru_RU`. Unknown languages use [`PluralRule::OneOther`].
+    pub fn for_language(tag: &str) -> Self {
+        match language(tag).to_ascii_lowercase().as_str() {
+            "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "km" | "my" => Self::Other,
+            "fr" | "pt" | "hi" | "bn" | "fa" | "am" | "zu" => Self::OneWithZero,
+            "ru" | "uk" | "be" | "sr" | "hr" | "bs" => Self::EastSlavic,
+            "cs" | "sk" => Self::Czech,
+            "pl" => Self::Polish,
+            "ar" => Self::Arabic,
+            _ => Self::OneOther,
+        }
+    }
+
+    /// Categories in the order catalogs store plural forms, which matches
+    /// the gettext `Plural-Forms` order for the language.
+    pub fn categories(self) -> &'static [PluralCategory] {
+        use PluralCategory::*;
+        match self {
+            Self::Other => &[Other],
+            Self::OneOther | Self::OneWithZero => &[One, Other],
+            Self::EastSlavic | Self::Polish => &[One, Few, Many],
+            Self::Czech => &[One, Few, Other],
+            Self::Arabic => &[Zero, One, Two, Few, Many, Other],
+        }
+    }
+
+    /// Category of the count `n`.
+    pub fn category(self, n: u64) -> PluralCategory {
+        use PluralCategory::*;
+        let (n10, n100) = (n % 10, n % 100);
+        let few = (2..=4).contains(&n10) && !(12..=14).contains(&n100);
+        match self {
+            Self::Other => Other,
+            Self::OneOther if n == 1 => One,
+            Self::OneWithZero if n <= 1 => One,
+            Self::OneOther | Self::OneWithZero => Other,
+            Self::EastSlavic if n10 == 1 && n100 != 11 => One,
+            Self::EastSlavic | Self::Polish if few => Few,
+            Self::Polish if n == 1 => One,
+            Self::EastSlavic | Self::Polish => Many,
+            Self::Czech => match n {
+                1 => One,
+                2..=4 => Few,
+                _ => Other,
+            },
+            Self::Arabic => match (n, n100) {
+                (0, _) => Zero,
+                (1, _) => One,
+                (2, _) => Two,
+                (_, 3..=10) => Few,
+                (_, 11..=99) => Many,
+                _ => Other,
+            },
+        }
+    }
+
+    /// Index of the form used for `n` in [`PluralRule::categories`] order.
+    pub fn form(self, n: u64) -> usize {
+        let category = self.category(n);
+        self.categories()
+            .iter()
+            .position(|&c| c == category)
+            .unwrap_or(0)
+    }
+}
+
+/// Language subtag of a locale tag: `pt` for `pt-BR` or `pt_BR`.
+pub fn language(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
+/// Whether two locale tags are equal, ignoring case and `-`/`_`.
+fn same_tag(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes().zip(b.bytes()).all(|(x, y)| {
+            let norm = |c: u8| {
+                if c == b'_' {
+                    b'-'
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            };
+            norm(x) == norm(y)
+        })
+}
+
+/// Messages of one locale.
+///
+/// Entry `i` holds the forms of [`StringId`]`(i)`: one string for plain
+/// messages, one per [`PluralRule::categories`] entry for plurals, or none
+/// when the message is untranslated.
+#[derive(Debug, Clone, Copy)]
+pub struct Catalog<'a> {
+    locale: &'a str,
+    plural: PluralRule,
+    entries: &'a [&'a [&'a str]],
+}
+
+impl<'a> Catalog<'a> {
+    /// Create a catalog for `locale`.
+    pub const fn new(locale: &'a str, plural: PluralRule, entries: &'a [&'a [&'a str]]) -> Self {
+        Self {
+            locale,
+            plural,
+            entries,
+        }
+    }
+
+    /// Locale tag of the catalog.
+    pub fn locale(&self) -> &'a str {
+        self.locale
+    }
+
+    /// Plural rule of the catalog's language.
+    pub fn plural_rule(&self) -> PluralRule {
+        self.plural
+    }
+
+    /// Translation of `id`, or its singular form for plural messages.
+    pub fn get(&self, id: StringId) -> Option<&'a str> {
+        self.entries.get(id.0 as usize)?.first().copied()
+    }
+
+    /// Form of the plural message `id` for the count `n`.
+    pub fn plural(&self, id: StringId, n: u64) -> Option<&'a str> {
+        let forms = self.entries.get(id.0 as usize)?;
+        match forms.len() {
+            0 => None,
+            1 => Some(forms[0]),
+            _ => forms.get(self.plural.form(n)).copied(),
+        }
+    }
+}
+
+/// Looks messages up in the active locale with fallbacks.
+///
+/// The first catalog is the source locale and the last fallback. Lookups
+/// try the active catalog, then a catalog for its bare language (`de` for
+/// `de-AT`), then the source.
+pub struct Translator<'a> {
+    catalogs: &'a [Catalog<'a>],
+    active: usize,
+    revision: u32,
+}
+
+impl<'a> Translator<'a> {
+    /// Create a translator starting in the source locale.
+    pub fn new(catalogs: &'a [Catalog<'a>]) -> Self {
+        Self {
+            catalogs,
+            active: 0,
+            revision: 0,
+        }
+    }
+
+    /// Tag of the active locale.
+    pub fn locale(&self) -> &'a str {
+        self.catalogs
+            .get(self.active)
+            .map(|c| c.locale)
+            .unwrap_or("")
+    }
+
+    /// Counter bumped whenever the active locale changes.
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    /// Switch to the catalog for `tag`, or for its language if there is no
+    /// exact match. Returns `false` and keeps the current locale if neither
+    /// exists.
+    pub fn set_locale(&mut self, tag: &str) -> bool {
+        let found = self
+            .catalogs
+            .iter()
+            .position(|c| same_tag(c.locale, tag))
+            .or_else(|| {
+                self.catalogs
+                    .iter()
+                    .position(|c| same_tag(language(c.locale), language(tag)))
+            });
+        match found {
+            Some(i) => {
+                if i != self.active {
+                    self.active = i;
+                    self.revision = self.revision.wrapping_add(1);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Catalogs in lookup order.
+    fn chain(&self) -> impl Iterator<Item = &Catalog<'a>> {
+        let active = self.catalogs.get(self.active);
+        let lang = active.map(|c| language(c.locale));
+        let base = self.catalogs.iter().enumerate().filter_map(move |(i, c)| {
+            (i != self.active && lang.is_some_and(|l| same_tag(c.locale, l))).then_some(c)
+        });
+        active.into_iter().chain(base).chain(self.catalogs.first())
+    }
+
+    /// Message `id` in the active locale, or `""` if no catalog has it.
+    pub fn get(&self, id: StringId) -> &'a str {
+        self.chain().find_map(|c| c.get(id)).unwrap_or("")
+    }
+
+    /// Form of the plural message `id` for the count `n`.
+    pub fn plural(&self, id: StringId, n: u64) -> &'a str {
+        self.chain().find_map(|c| c.plural(id, n)).unwrap_or("")
+    }
+}
+
+/// Write `template` to `out`, replacing `{n}` with `args[n]`.
+///
+/// `{{` and `}}` write literal braces. Placeholders without a matching
+/// argument are written unchanged.
+pub fn format(out: &mut dyn fmt::Write, template: &str, args: &[&dyn fmt::Display]) -> fmt::Result {
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.write_str(&rest[..pos])?;
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.write_str(&tail[..1])?;
+            rest = &tail[2..];
+            continue;
+        }
+        let arg = tail
+            .strip_prefix('{')
+            .and_then(|t| t.split_once('}'))
+            .and_then(|(n, after)| Some((args.get(n.parse::<usize>().ok()?)?, after)));
+        match arg {
+            Some((arg, after)) => {
+                write!(out, "{arg}")?;
+                rest = after;
+            }
+            None => {
+                out.write_str(&tail[..1])?;
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.write_str(rest)
+}
+
+/// [`format`] into a new `String`.
+pub fn format_string(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut out = String::new();
+    let _ = format(&mut out, template, args);
+    out
+}
+
+/// Widgets whose text follows a [`Translator`].
+#[derive(Default)]
+pub struct Bindings {
+    entries: Vec<(Rc<RefCell<dyn Widget>>, StringId)>,
+    applied: Option<u32>,
+}
+
+impl Bindings {
+    /// Create an empty set of bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show message `id` in `widget`, starting with the next
+    /// [`Bindings::update`].
+    pub fn bind(&mut self, widget: Rc<RefCell<dyn Widget>>, id: StringId) {
+        self.entries.push((widget, id));
+        self.applied = None;
+    }
+
+    /// Number of bound widgets.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no widget is bound.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Set the text of every bound widget if the language changed since the
+    /// last update, and return the area covering them for re-layout and
+    /// redraw.
+    pub fn update(&mut self, translator: &Translator<'_>) -> Option<Rect> {
+        if self.applied == Some(translator.revision()) {
+            return None;
+        }
+        self.applied = Some(translator.revision());
+        let mut area: Option<Rect> = None;
+        for (widget, id) in &self.entries {
+            let mut widget = widget.borrow_mut();
+            widget.set_text(translator.get(*id));
+            let b = widget.bounds();
+            area = Some(match area {
+                None => b,
+                Some(a) => a.union(&b),
+            });
+        }
+        area
+    }
+}
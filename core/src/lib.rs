@@ -33,9 +33,16 @@ extern crate std;
 extern crate alloc;
 
 pub mod animation;
+pub mod atlas;
 pub mod event;
+pub mod font;
+pub mod frames;
 #[cfg(feature = "fs")]
 pub mod fs;
+pub mod gesture;
+pub mod i18n;
+pub mod math;
+pub mod pix;
 pub mod plugins;
 pub mod renderer;
 pub mod style;
@@ -46,6 +53,10 @@ pub mod widget;
 #[cfg_attr(docsrs, doc(cfg(feature = "canvas")))]
 pub use plugins::canvas;
 
+#[cfg(feature = "dash_lottie")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dash_lottie")))]
+pub use plugins::dash_lottie;
+
 #[cfg(feature = "fatfs")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fatfs")))]
 pub use plugins::fatfs;
@@ -86,6 +97,10 @@ pub use plugins::png;
 #[cfg_attr(docsrs, doc(cfg(feature = "qrcode")))]
 pub use plugins::qrcode;
 
+#[cfg(feature = "raw")]
+#[cfg_attr(docsrs, doc(cfg(feature = "raw")))]
+pub use plugins::raw;
+
 // Pull doc tests from the workspace README
 #[cfg(doctest)]
 doc_comment::doctest!("../../README.md");
@@ -107,6 +122,14 @@ pub struct WidgetNode {
 }
 
 impl WidgetNode {
+    /// Wrap `widget` in a node with the given children.
+    pub fn new<W: widget::Widget + 'static>(widget: W, children: Vec<WidgetNode>) -> Self {
+        Self {
+            widget: Rc::new(RefCell::new(widget)),
+            children,
+        }
+    }
+
     /// Propagate an event to this node and its children.
     ///
     /// Returns `true` if any widget handled the event.
@@ -122,6 +145,19 @@ impl WidgetNode {
         false
     }
 
+    /// Collect areas that changed since the last call, depth-first.
+    ///
+    /// Display drivers can redraw just these rectangles instead of the
+    /// whole screen.
+    pub fn collect_dirty(&self, out: &mut Vec<widget::Rect>) {
+        if let Some(area) = self.widget.borrow_mut().take_dirty() {
+            out.push(area);
+        }
+        for child in &self.children {
+            child.collect_dirty(out);
+        }
+    }
+
     /// Recursively draw this node and all child nodes using the given renderer.
     pub fn draw(&self, renderer: &mut dyn renderer::Renderer) {
         self.widget.borrow().draw(renderer);
@@ -0,0 +1,78 @@
+//! Integer math helpers for `no_std` targets.
+//!
+//! Mirrors LVGL's `lv_math` helpers: trigonometry uses a quarter-wave lookup
+//! table in whole degrees so widgets can draw arcs without `libm`.
+
+/// Fixed-point scale of values returned by [`sin`] and [`cos`].
+pub const TRIG_SCALE: i32 = 32767;
+
+const SIN_TABLE: [i16; 91] = [
+    0, 572, 1144, 1715, 2286, 2856, 3425, 3993, 4560, 5126, 5690, 6252, 6813, 7371, 7927, 8481,
+    9032, 9580, 10126, 10668, 11207, 11743, 12275, 12803, 13328, 13848, 14364, 14876, 15383, 15886,
+    16383, 16876, 17364, 17846, 18323, 18794, 19260, 19720, 20173, 20621, 21062, 21497, 21925,
+    22347, 22762, 23170, 23571, 23964, 24351, 24730, 25101, 25465, 25821, 26169, 26509, 26841,
+    27165, 27481, 27788, 28087, 28377, 28659, 28932, 29196, 29451, 29697, 29934, 30162, 30381,
+    30591, 30791, 30982, 31163, 31335, 31498, 31650, 31794, 31927, 32051, 32165, 32269, 32364,
+    32448, 32523, 32587, 32642, 32687, 32722, 32747, 32762, 32767,
+];
+
+/// Sine of `angle` degrees scaled by [`TRIG_SCALE`].
+pub fn sin(angle: i32) -> i32 {
+    let a = angle.rem_euclid(360);
+    match a {
+        0..=90 => SIN_TABLE[a as usize] as i32,
+        91..=180 => SIN_TABLE[(180 - a) as usize] as i32,
+        181..=270 => -(SIN_TABLE[(a - 180) as usize] as i32),
+        _ => -(SIN_TABLE[(360 - a) as usize] as i32),
+    }
+}
+
+/// Cosine of `angle` degrees scaled by [`TRIG_SCALE`].
+pub fn cos(angle: i32) -> i32 {
+    sin(angle + 90)
+}
+
+/// Angle of the vector `(x, y)` in whole degrees, `0..360`, measured from
+/// the positive x axis towards the positive y axis.
+///
+/// Screen y grows downwards, so angles grow clockwise on screen.
+pub fn atan2(y: i32, x: i32) -> i32 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    let (ax, ay) = (x.unsigned_abs() as u64, y.unsigned_abs() as u64);
+    let (lo, hi) = if ax >= ay { (ay, ax) } else { (ax, ay) };
+    // Largest angle in the first octant whose tangent does not exceed
+    // lo / hi, rounded to the nearer of it and the next degree.
+    let tan_le = |d: usize| lo * SIN_TABLE[90 - d] as u64 >= hi * SIN_TABLE[d] as u64;
+    let mut d = (0..=45).rev().find(|&d| tan_le(d)).unwrap_or(0);
+    if d < 45 {
+        // Compare against the tangent half way to the next degree.
+        let mid_sin = SIN_TABLE[d] as u64 + SIN_TABLE[d + 1] as u64;
+        let mid_cos = SIN_TABLE[90 - d] as u64 + SIN_TABLE[89 - d] as u64;
+        if lo * mid_cos > hi * mid_sin {
+            d += 1;
+        }
+    }
+    let octant = if ax >= ay { d as i32 } else { 90 - d as i32 };
+    match (x >= 0, y >= 0) {
+        (true, true) => octant,
+        (false, true) => 180 - octant,
+        (false, false) => 180 + octant,
+        (true, false) => (360 - octant) % 360,
+    }
+}
+
+/// Integer square root rounded down.
+pub fn sqrt(value: u32) -> u32 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
@@ -0,0 +1,404 @@
+//! Reader for `RLVGLPIX` pixel variants produced by `rlvgl-creator convert`.
+//!
+//! All integers are little-endian. The file is laid out as
+//!
+//! ```text
+//! "RLVGLPIX", u32 width, u32 height, u8 format, u8 flags, u8 compression,
+//!   u8 reserved, [u16 palette_len, palette_len * u32 argb8888,]
+//!   u32 payload_len, payload
+//! ```
+//!
+//! Bit 0 of `flags` marks premultiplied alpha and the palette is present
+//! only for indexed formats. Unpacked, the payload holds `height` rows of
+//! [`Format::stride`] bytes in the layout `rlvgl_platform::Surface` uses for
+//! the matching pixel format, so it can be blitted directly. Compressed
+//! payloads are either `(count, unit)` runs, where the unit is one pixel for
+//! ARGB8888 and RGB565 and one byte otherwise, or a raw LZ4 block.
+
+use crate::widget::Color;
+use alloc::vec::Vec;
+
+/// Magic identifier at the start of every pixel variant file.
+pub const MAGIC: &[u8; 8] = b"RLVGLPIX";
+
+/// Flag bit set when color channels are premultiplied by alpha.
+pub const FLAG_PREMULTIPLIED: u8 = 1;
+
+const HEADER_LEN: usize = 20;
+
+/// Errors reported while parsing or unpacking a pixel variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The data ends before the header, palette or payload is complete.
+    Truncated,
+    /// The header names a pixel format this reader does not know.
+    BadFormat,
+    /// The header names a compression this reader does not know.
+    BadCompression,
+    /// The compressed payload is malformed or does not unpack to the image
+    /// size.
+    BadPayload,
+    /// The output buffer is too small for the unpacked pixels.
+    BufferTooSmall,
+    /// The image is larger than can be addressed or allocated.
+    TooLarge,
+}
+
+/// Stored pixel format, numbered as in the container header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// 32-bit ARGB stored as a little-endian `u32`.
+    Argb8888,
+    /// 16-bit RGB565 stored as a little-endian `u16`.
+    Rgb565,
+    /// 8-bit grayscale.
+    L8,
+    /// 8-bit alpha.
+    A8,
+    /// 4-bit alpha, two pixels per byte with the left pixel in the high nibble.
+    A4,
+    /// 1-bit palette indices, eight pixels per byte.
+    I1,
+    /// 2-bit palette indices, four pixels per byte.
+    I2,
+    /// 4-bit palette indices, two pixels per byte.
+    I4,
+    /// 8-bit palette indices.
+    I8,
+}
+
+impl Format {
+    /// Every format in header id order.
+    pub const ALL: [Format; 9] = [
+        Format::Argb8888,
+        Format::Rgb565,
+        Format::L8,
+        Format::A8,
+        Format::A4,
+        Format::I1,
+        Format::I2,
+        Format::I4,
+        Format::I8,
+    ];
+
+    /// Format stored under header id `id`.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// Identifier stored in the container header.
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Bits stored per pixel.
+    pub fn bits(self) -> usize {
+        match self {
+            Format::Argb8888 => 32,
+            Format::Rgb565 => 16,
+            Format::L8 | Format::A8 | Format::I8 => 8,
+            Format::A4 | Format::I4 => 4,
+            Format::I2 => 2,
+            Format::I1 => 1,
+        }
+    }
+
+    /// Whether pixels are palette indices.
+    pub fn is_indexed(self) -> bool {
+        matches!(self, Format::I1 | Format::I2 | Format::I4 | Format::I8)
+    }
+
+    /// Bytes per row of `width` pixels.
+    pub fn stride(self, width: u32) -> usize {
+        (width as u64 * self.bits() as u64).div_ceil(8) as usize
+    }
+
+    /// Size of the unit repeated by a run: one pixel, or one byte for
+    /// formats below eight bits.
+    fn unit(self) -> usize {
+        self.bits().div_ceil(8)
+    }
+}
+
+/// Compression applied to the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Pixels stored as is.
+    None,
+    /// `(count, unit)` runs.
+    Rle,
+    /// LZ4 block format without a frame header.
+    Lz4,
+}
+
+impl Compression {
+    /// Compression stored under header id `id`.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Rle),
+            2 => Some(Compression::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Identifier stored in the container header.
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+}
+
+fn u16_at(data: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([data[pos], data[pos + 1]])
+}
+
+fn u32_at(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+/// Parsed pixel variant borrowing its data.
+///
+/// Parsing only validates the header; the payload is unpacked by
+/// [`decode_into`](Self::decode_into), so uncompressed images can also be
+/// used straight from flash through [`payload`](Self::payload).
+#[derive(Debug, Clone, Copy)]
+pub struct PixImage<'a> {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Stored pixel format.
+    pub format: Format,
+    /// Compression of the payload.
+    pub compression: Compression,
+    /// Whether color channels are premultiplied by alpha.
+    pub premultiplied: bool,
+    palette: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> PixImage<'a> {
+    /// Parse the header, palette and payload bounds of `data`.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(..MAGIC.len()) != Some(MAGIC) {
+            return Err(Error::BadMagic);
+        }
+        if data.len() < HEADER_LEN {
+            return Err(Error::Truncated);
+        }
+        let format = Format::from_id(data[16]).ok_or(Error::BadFormat)?;
+        let compression = Compression::from_id(data[18]).ok_or(Error::BadCompression)?;
+        let mut pos = HEADER_LEN;
+        let mut table = |len: usize| -> Result<&'a [u8], Error> {
+            let end = pos.checked_add(len).ok_or(Error::Truncated)?;
+            let bytes = data.get(pos..end).ok_or(Error::Truncated)?;
+            pos = end;
+            Ok(bytes)
+        };
+        let palette = if format.is_indexed() {
+            let len = u16_at(table(2)?, 0) as usize;
+            table(len * 4)?
+        } else {
+            &[]
+        };
+        let payload_len = u32_at(table(4)?, 0) as usize;
+        let image = Self {
+            width: u32_at(data, 8),
+            height: u32_at(data, 12),
+            format,
+            compression,
+            premultiplied: data[17] & FLAG_PREMULTIPLIED != 0,
+            palette,
+            payload: table(payload_len)?,
+        };
+        image.byte_len()?;
+        Ok(image)
+    }
+
+    /// Bytes per unpacked row.
+    pub fn stride(&self) -> usize {
+        self.format.stride(self.width)
+    }
+
+    /// Size of the unpacked pixels in bytes.
+    pub fn byte_len(&self) -> Result<usize, Error> {
+        (self.width as usize)
+            .checked_mul(self.format.bits())
+            .map(|bits| bits.div_ceil(8))
+            .and_then(|stride| stride.checked_mul(self.height as usize))
+            .ok_or(Error::TooLarge)
+    }
+
+    /// Number of palette entries.
+    pub fn palette_len(&self) -> usize {
+        self.palette.len() / 4
+    }
+
+    /// Palette entry `index` as ARGB8888.
+    pub fn palette_color(&self, index: usize) -> Option<u32> {
+        let at = index.checked_mul(4)?;
+        self.palette
+            .get(at..at + 4)
+            .map(|_| u32_at(self.palette, at))
+    }
+
+    /// Palette as ARGB8888 values, ready for `Surface::with_palette`.
+    pub fn palette(&self) -> Vec<u32> {
+        (0..self.palette_len())
+            .filter_map(|i| self.palette_color(i))
+            .collect()
+    }
+
+    /// Payload as stored, which is the unpacked pixels when
+    /// [`compression`](Self::compression) is [`Compression::None`].
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Unpack the pixels into the first [`byte_len`](Self::byte_len) bytes of `out`.
+    pub fn decode_into(&self, out: &mut [u8]) -> Result<(), Error> {
+        let len = self.byte_len()?;
+        let out = out.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+        match self.compression {
+            Compression::None => {
+                let pixels = self.payload.get(..len).ok_or(Error::BadPayload)?;
+                out.copy_from_slice(pixels);
+            }
+            Compression::Rle => rle_decode(self.payload, self.format.unit(), out)?,
+            Compression::Lz4 => lz4_decode(self.payload, out)?,
+        }
+        Ok(())
+    }
+
+    /// Unpack the pixels into a new buffer of [`byte_len`](Self::byte_len) bytes.
+    pub fn decode(&self) -> Result<Vec<u8>, Error> {
+        let len = self.byte_len()?;
+        let mut out = Vec::new();
+        out.try_reserve_exact(len).map_err(|_| Error::TooLarge)?;
+        out.resize(len, 0);
+        self.decode_into(&mut out)?;
+        Ok(out)
+    }
+
+    /// Unpack the pixels as RGBA colors, one per pixel in row-major order.
+    ///
+    /// Alpha formats give black pixels of that alpha and missing palette
+    /// entries are transparent. Premultiplied colors are returned as stored.
+    pub fn to_colors(&self) -> Result<Vec<Color>, Error> {
+        let packed = self.decode()?;
+        let (width, stride) = (self.width as usize, self.stride());
+        let count = width
+            .checked_mul(self.height as usize)
+            .ok_or(Error::TooLarge)?;
+        let mut colors = Vec::new();
+        colors
+            .try_reserve_exact(count)
+            .map_err(|_| Error::TooLarge)?;
+        for row in packed
+            .chunks_exact(stride.max(1))
+            .take(self.height as usize)
+        {
+            colors.extend((0..width).map(|x| self.color(row, x)));
+        }
+        Ok(colors)
+    }
+
+    /// Color of pixel `x` in the unpacked `row`.
+    fn color(&self, row: &[u8], x: usize) -> Color {
+        let bits = self.format.bits();
+        let packed = |x: usize| {
+            let shift = 8 - bits - (x * bits) % 8;
+            (row[x * bits / 8] >> shift) & ((1u16 << bits) - 1) as u8
+        };
+        match self.format {
+            Format::Argb8888 => {
+                let [b, g, r, a] = [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]];
+                Color(r, g, b, a)
+            }
+            Format::Rgb565 => {
+                let c = u16_at(row, x * 2);
+                let (r, g, b) = ((c >> 11) as u8, (c >> 5) as u8 & 0x3f, c as u8 & 0x1f);
+                Color(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2, 255)
+            }
+            Format::L8 => Color(row[x], row[x], row[x], 255),
+            Format::A8 => Color(0, 0, 0, row[x]),
+            Format::A4 => Color(0, 0, 0, packed(x) * 0x11),
+            Format::I1 | Format::I2 | Format::I4 | Format::I8 => {
+                let argb = self.palette_color(packed(x) as usize).unwrap_or(0);
+                let [b, g, r, a] = argb.to_le_bytes();
+                Color(r, g, b, a)
+            }
+        }
+    }
+}
+
+/// Expand `(count, unit)` runs of `unit` bytes into exactly fill `out`.
+fn rle_decode(mut src: &[u8], unit: usize, out: &mut [u8]) -> Result<(), Error> {
+    let mut pos = 0;
+    while let Some((&count, rest)) = src.split_first() {
+        let value = rest.get(..unit).ok_or(Error::BadPayload)?;
+        src = &rest[unit..];
+        for _ in 0..count {
+            let dst = out.get_mut(pos..pos + unit).ok_or(Error::BadPayload)?;
+            dst.copy_from_slice(value);
+            pos += unit;
+        }
+    }
+    if pos == out.len() {
+        Ok(())
+    } else {
+        Err(Error::BadPayload)
+    }
+}
+
+/// Decompress a raw LZ4 block that must exactly fill `out`.
+fn lz4_decode(mut src: &[u8], out: &mut [u8]) -> Result<(), Error> {
+    fn take<'a>(src: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+        let bytes = src.get(..len).ok_or(Error::BadPayload)?;
+        *src = &src[len..];
+        Ok(bytes)
+    }
+    fn read_len(src: &mut &[u8], mut len: usize) -> Result<usize, Error> {
+        if len == 15 {
+            loop {
+                let b = take(src, 1)?[0];
+                len = len.checked_add(b as usize).ok_or(Error::BadPayload)?;
+                if b != 255 {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    }
+    let mut pos = 0;
+    while let Some((&token, rest)) = src.split_first() {
+        src = rest;
+        let lit = read_len(&mut src, (token >> 4) as usize)?;
+        let literals = take(&mut src, lit)?;
+        let end = pos + lit;
+        out.get_mut(pos..end)
+            .ok_or(Error::BadPayload)?
+            .copy_from_slice(literals);
+        pos = end;
+        if src.is_empty() {
+            break;
+        }
+        let offset = u16_at(take(&mut src, 2)?, 0) as usize;
+        let len = read_len(&mut src, (token & 0x0f) as usize)? + 4;
+        if offset == 0 || offset > pos || len > out.len() - pos {
+            return Err(Error::BadPayload);
+        }
+        // Matches may overlap the bytes they produce, so copy one by one.
+        for i in pos..pos + len {
+            out[i] = out[i - offset];
+        }
+        pos += len;
+    }
+    if pos == out.len() {
+        Ok(())
+    } else {
+        Err(Error::BadPayload)
+    }
+}
@@ -1,4 +1,5 @@
 //! APNG decoder returning frames.
+use crate::frames::{Frame, Frames};
 use crate::widget::Color;
 use alloc::vec::Vec;
 use image::{AnimationDecoder, ImageDecoder, ImageError, codecs::png::PngDecoder};
@@ -36,6 +37,19 @@ pub fn decode(data: &[u8]) -> Result<(Vec<ApngFrame>, u32, u32), ImageError> {
     Ok((frames_out, width, height))
 }
 
+/// Decode an APNG byte stream into frames ready for playback.
+pub fn decode_frames(data: &[u8]) -> Result<Frames, ImageError> {
+    let (frames, width, height) = decode(data)?;
+    let frames = frames
+        .into_iter()
+        .map(|f| Frame {
+            pixels: f.pixels,
+            delay_ms: f.delay as u32 * 10,
+        })
+        .collect();
+    Ok(Frames::new(width, height, frames))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,7 @@
 //! followed by raw RGB frames. It avoids `dotlottie-rs` at runtime and
 //! is suitable for `no_std` embedded targets.
 
+use crate::frames::{Frame, Frames};
 use crate::widget::Color;
 use alloc::vec::Vec;
 
@@ -67,6 +68,20 @@ pub fn load(data: &[u8]) -> Result<DashAnimation, &'static str> {
     })
 }
 
+impl From<DashAnimation> for Frames {
+    fn from(anim: DashAnimation) -> Self {
+        let frames = anim
+            .frames
+            .into_iter()
+            .map(|f| Frame {
+                pixels: f.pixels,
+                delay_ms: f.delay as u32,
+            })
+            .collect();
+        Frames::new(anim.width as u32, anim.height as u32, frames)
+    }
+}
+
 /// Encode a [`DashAnimation`] into the binary keyframe format.
 pub fn encode(anim: &DashAnimation) -> Vec<u8> {
     let mut out = Vec::new();
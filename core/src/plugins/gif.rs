@@ -1,8 +1,12 @@
 //! GIF decoder returning frames.
+//!
+//! [`decode`] returns every frame at once while [`GifStream`] composites
+//! frames on demand so only one canvas is kept in memory.
+use crate::frames::{self, FrameSource, Frames};
 use crate::widget::Color;
 use alloc::vec::Vec;
 pub use gif::DecodingError;
-use gif::{ColorOutput, DecodeOptions, Frame};
+use gif::{ColorOutput, DecodeOptions, Decoder, DisposalMethod, Frame, Repeat};
 use std::io::Cursor;
 
 #[derive(Debug, Clone)]
@@ -39,6 +43,230 @@ fn convert_frame(frame: &Frame<'_>, width: u16, height: u16) -> GifFrame {
     }
 }
 
+/// Decode a GIF byte stream into composited frames ready for playback.
+///
+/// Unlike [`decode`], frame offsets, transparency and disposal are applied so
+/// each frame holds the full image.
+pub fn decode_frames(data: &[u8]) -> Result<Frames, DecodingError> {
+    let mut stream = GifStream::new(data)?;
+    let (width, height) = stream.size();
+    let mut out = Vec::with_capacity(stream.frame_count());
+    for index in 0..stream.frame_count() {
+        stream.step()?;
+        out.push(frames::Frame {
+            pixels: stream.canvas.clone(),
+            delay_ms: stream.delay_ms(index),
+        });
+    }
+    let mut anim = Frames::new(width, height, out);
+    anim.loop_count = stream.loop_count;
+    Ok(anim)
+}
+
+/// Area of the canvas covered by a frame and how to clear it afterwards.
+#[derive(Clone, Copy)]
+struct Patch {
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+    dispose: DisposalMethod,
+}
+
+/// GIF frame source decoding one frame at a time.
+///
+/// Only the composited canvas, a row buffer and a per-frame delay table are
+/// kept in memory. Seeking backwards restarts decoding from the first frame.
+pub struct GifStream<'a> {
+    data: &'a [u8],
+    decoder: Decoder<Cursor<&'a [u8]>>,
+    width: u32,
+    height: u32,
+    delays: Vec<u16>,
+    loop_count: u32,
+    canvas: Vec<Color>,
+    /// Canvas contents under the last frame when it restores to previous.
+    saved: Vec<Color>,
+    last: Option<Patch>,
+    row: Vec<u8>,
+    /// Index of the next frame the decoder will produce.
+    next: usize,
+}
+
+impl<'a> GifStream<'a> {
+    /// Scan `data` for frame delays and prepare to decode the first frame.
+    pub fn new(data: &'a [u8]) -> Result<Self, DecodingError> {
+        let mut decoder = open(data)?;
+        let mut delays = Vec::new();
+        let mut row = Vec::new();
+        while let Some(frame) = decoder.next_frame_info()? {
+            delays.push(frame.delay);
+            row.resize(decoder.line_length(), 0);
+            while decoder.fill_buffer(&mut row)? {}
+        }
+        // The repeat count is the number of extra plays after the first.
+        let loop_count = match decoder.repeat() {
+            Repeat::Infinite => 0,
+            Repeat::Finite(n) => n as u32 + 1,
+        };
+        let decoder = open(data)?;
+        let width = decoder.width() as u32;
+        let height = decoder.height() as u32;
+        Ok(Self {
+            data,
+            decoder,
+            width,
+            height,
+            delays,
+            loop_count,
+            canvas: alloc::vec![Color(0, 0, 0, 0); (width * height) as usize],
+            saved: Vec::new(),
+            last: None,
+            row,
+            next: 0,
+        })
+    }
+
+    /// Restart decoding from the first frame with a cleared canvas.
+    fn rewind(&mut self) -> Result<(), DecodingError> {
+        self.decoder = open(self.data)?;
+        self.canvas.fill(Color(0, 0, 0, 0));
+        self.last = None;
+        self.next = 0;
+        Ok(())
+    }
+
+    /// Dispose of the previous frame and draw the next one onto the canvas.
+    fn step(&mut self) -> Result<(), DecodingError> {
+        if let Some(last) = self.last.take() {
+            match last.dispose {
+                DisposalMethod::Background => {
+                    self.patch(last, |_, _| Some(Color(0, 0, 0, 0)));
+                }
+                DisposalMethod::Previous => {
+                    let saved = core::mem::take(&mut self.saved);
+                    self.patch(last, |i, _| saved.get(i).copied());
+                    self.saved = saved;
+                }
+                _ => {}
+            }
+        }
+        let Some(frame) = self.decoder.next_frame_info()? else {
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "missing frame").into(),
+            );
+        };
+        let patch = Patch {
+            left: frame.left as u32,
+            top: frame.top as u32,
+            width: frame.width as u32,
+            height: frame.height as u32,
+            dispose: frame.dispose,
+        };
+        let interlaced = frame.interlaced;
+        if patch.dispose == DisposalMethod::Previous {
+            self.saved.clear();
+            let mut saved = core::mem::take(&mut self.saved);
+            self.patch(patch, |_, px| {
+                saved.push(px);
+                None
+            });
+            self.saved = saved;
+        }
+        self.row.resize(self.decoder.line_length(), 0);
+        for y in rows(patch.height, interlaced) {
+            if !self.decoder.fill_buffer(&mut self.row)? {
+                break;
+            }
+            let cy = patch.top + y;
+            if cy >= self.height {
+                continue;
+            }
+            for (x, px) in self.row.chunks_exact(4).enumerate() {
+                let cx = patch.left + x as u32;
+                if cx < self.width && px[3] != 0 {
+                    self.canvas[(cy * self.width + cx) as usize] = Color(px[0], px[1], px[2], 255);
+                }
+            }
+        }
+        self.last = Some(patch);
+        self.next += 1;
+        Ok(())
+    }
+
+    /// Visit the visible pixels under `patch` in row-major order.
+    ///
+    /// `f` receives the visit index and current color and may return a
+    /// replacement.
+    fn patch(&mut self, patch: Patch, mut f: impl FnMut(usize, Color) -> Option<Color>) {
+        let x_end = (patch.left + patch.width).min(self.width);
+        let y_end = (patch.top + patch.height).min(self.height);
+        let mut i = 0;
+        for y in patch.top..y_end {
+            for x in patch.left..x_end {
+                let px = &mut self.canvas[(y * self.width + x) as usize];
+                if let Some(c) = f(i, *px) {
+                    *px = c;
+                }
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Create an RGBA decoder positioned before the first frame.
+fn open(data: &[u8]) -> Result<Decoder<Cursor<&[u8]>>, DecodingError> {
+    let mut options = DecodeOptions::new();
+    options.set_color_output(ColorOutput::RGBA);
+    options.read_info(Cursor::new(data))
+}
+
+/// Row indices of a frame in the order they are stored.
+fn rows(height: u32, interlaced: bool) -> impl Iterator<Item = u32> {
+    const PASSES: [(u32, u32); 4] = [(0, 8), (4, 8), (2, 4), (1, 2)];
+    let passes: &[(u32, u32)] = if interlaced { &PASSES } else { &[(0, 1)] };
+    passes
+        .iter()
+        .flat_map(move |&(start, step)| (start..height).step_by(step as usize))
+}
+
+impl FrameSource for GifStream<'_> {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn frame_count(&self) -> usize {
+        self.delays.len()
+    }
+
+    fn delay_ms(&self, index: usize) -> u32 {
+        self.delays.get(index).map_or(0, |&d| d as u32 * 10)
+    }
+
+    fn loop_count(&self) -> u32 {
+        self.loop_count
+    }
+
+    fn seek(&mut self, index: usize) -> bool {
+        if index >= self.delays.len() {
+            return false;
+        }
+        if index < self.next.saturating_sub(1) && self.rewind().is_err() {
+            return false;
+        }
+        while self.next <= index {
+            if self.step().is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn pixels(&self) -> &[Color] {
+        &self.canvas
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,4 +284,65 @@ mod tests {
         assert_eq!(frames.len(), 1);
         assert_eq!(frames[0].pixels, vec![Color(255, 0, 0, 255)]);
     }
+
+    /// Two-frame 2x1 GIF: red/green, then a blue pixel drawn at x = 1.
+    fn two_frame_gif(repeat: Repeat) -> Vec<u8> {
+        let palette = [255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0];
+        let mut data = Vec::new();
+        {
+            let mut enc = gif::Encoder::new(&mut data, 2, 1, &palette).unwrap();
+            enc.set_repeat(repeat).unwrap();
+            enc.write_frame(&Frame {
+                width: 2,
+                height: 1,
+                delay: 5,
+                buffer: vec![0, 1].into(),
+                ..Frame::default()
+            })
+            .unwrap();
+            enc.write_frame(&Frame {
+                left: 1,
+                width: 1,
+                height: 1,
+                delay: 20,
+                buffer: vec![2].into(),
+                ..Frame::default()
+            })
+            .unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn stream_composites_and_rewinds() {
+        let data = two_frame_gif(Repeat::Finite(2));
+        let mut stream = GifStream::new(&data).unwrap();
+        assert_eq!(stream.size(), (2, 1));
+        assert_eq!(stream.frame_count(), 2);
+        assert_eq!(stream.delay_ms(0), 50);
+        assert_eq!(stream.delay_ms(1), 200);
+        assert_eq!(stream.loop_count(), 3);
+
+        assert!(stream.seek(1));
+        assert_eq!(
+            stream.pixels(),
+            &[Color(255, 0, 0, 255), Color(0, 0, 255, 255)]
+        );
+        assert!(stream.seek(0));
+        assert_eq!(
+            stream.pixels(),
+            &[Color(255, 0, 0, 255), Color(0, 255, 0, 255)]
+        );
+        assert!(!stream.seek(2));
+    }
+
+    #[test]
+    fn decode_frames_matches_stream() {
+        let data = two_frame_gif(Repeat::Infinite);
+        let anim = decode_frames(&data).unwrap();
+        assert_eq!(anim.loop_count, 0);
+        assert_eq!(anim.frames.len(), 2);
+        assert_eq!(anim.frames[1].pixels[0], Color(255, 0, 0, 255));
+        assert_eq!(anim.frames[1].delay_ms, 200);
+    }
 }
@@ -1,10 +1,12 @@
 //! Dynamic Lottie rendering utilities.
 //!
 //! This module exposes helpers built on the `rlottie` crate for loading a
-//! Lottie JSON string and rendering frames into [`Color`] buffers.
+//! Lottie JSON string and rendering frames into [`Color`] buffers. The
+//! `Lottie` widget in `rlvgl-widgets` plays a [`Document`] in place.
 
 use crate::widget::Color;
-use rlottie::{Animation, Size, Surface};
+use alloc::vec::Vec;
+use rlottie::{Animation, Bgra, Size, Surface};
 
 /// Errors that can occur when rendering a Lottie animation frame.
 #[derive(Debug, Clone)]
@@ -13,6 +15,18 @@ pub enum Error {
     InvalidJson,
 }
 
+/// Convert a premultiplied rlottie pixel to a straight-alpha [`Color`].
+fn to_color(px: &Bgra) -> Color {
+    match px.a {
+        0 => Color(0, 0, 0, 0),
+        255 => Color(px.r, px.g, px.b, 255),
+        a => {
+            let un = |c: u8| ((c as u16 * 255 + a as u16 / 2) / a as u16).min(255) as u8;
+            Color(un(px.r), un(px.g), un(px.b), a)
+        }
+    }
+}
+
 /// Render a single frame of a Lottie JSON animation.
 ///
 /// * `json` - Lottie document as UTF-8 text.
@@ -29,13 +43,45 @@ pub fn render_lottie_frame(
     let mut anim = Animation::from_data(json, "mem", ".")?;
     let mut surface = Surface::new(Size::new(width, height));
     anim.render(frame, &mut surface);
-    Some(
-        surface
-            .data()
-            .iter()
-            .map(|px| Color(px.r, px.g, px.b, 255))
-            .collect(),
-    )
+    Some(surface.data().iter().map(to_color).collect())
+}
+
+/// Parsed Lottie document rendering frames at a fixed size.
+///
+/// Parsing happens once, so playing an animation only pays for rendering
+/// each frame.
+pub struct Document {
+    anim: Animation,
+    surface: Surface,
+}
+
+impl Document {
+    /// Parse `json` to render frames of `width` × `height` pixels.
+    pub fn from_json(json: &str, width: usize, height: usize) -> Result<Self, Error> {
+        let anim = Animation::from_data(json, "mem", ".").ok_or(Error::InvalidJson)?;
+        Ok(Self {
+            anim,
+            surface: Surface::new(Size::new(width, height)),
+        })
+    }
+
+    /// Total number of frames in the document.
+    pub fn total_frames(&self) -> usize {
+        self.anim.totalframe()
+    }
+
+    /// Frame rate declared by the document.
+    pub fn frame_rate(&self) -> f64 {
+        self.anim.framerate()
+    }
+
+    /// Render `frame` into `out`, replacing its contents with the pixels in
+    /// row-major order with straight alpha.
+    pub fn render_into(&mut self, frame: usize, out: &mut Vec<Color>) {
+        self.anim.render(frame, &mut self.surface);
+        out.clear();
+        out.extend(self.surface.data().iter().map(to_color));
+    }
 }
 
 #[cfg(test)]
@@ -50,4 +96,25 @@ mod tests {
         let frame = render_lottie_frame(SIMPLE_JSON, 0, 1, 1).unwrap();
         assert_eq!(frame.len(), 1);
     }
+
+    #[test]
+    fn unpremultiplies_alpha() {
+        let px = Bgra {
+            b: 0,
+            g: 0,
+            r: 64,
+            a: 128,
+        };
+        assert_eq!(to_color(&px), Color(128, 0, 0, 128));
+    }
+
+    #[test]
+    fn document_renders_at_its_size() {
+        let mut doc = Document::from_json(SIMPLE_JSON, 3, 2).unwrap();
+        assert_eq!(doc.frame_rate(), 30.0);
+        let mut pixels = Vec::new();
+        doc.render_into(0, &mut pixels);
+        assert_eq!(pixels.len(), 6);
+        assert!(Document::from_json("not json", 1, 1).is_err());
+    }
 }
@@ -6,6 +6,9 @@
 #[cfg(feature = "canvas")]
 pub mod canvas;
 
+#[cfg(feature = "dash_lottie")]
+pub mod dash_lottie;
+
 #[cfg(feature = "fatfs")]
 pub mod fatfs;
 
@@ -35,5 +38,8 @@ pub mod png;
 
 #[cfg(feature = "qrcode")]
 pub mod qrcode;
+
+#[cfg(feature = "raw")]
+pub mod raw;
 #[cfg(feature = "qrcode")]
 pub use qrcode::*;
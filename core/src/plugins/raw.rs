@@ -0,0 +1,455 @@
+//! Decoder for `RLVGLRAW` image sequences produced by `rlvgl-creator`.
+//!
+//! The container starts with the magic `RLVGLRAW` followed by the maximum
+//! width, maximum height and frame count as little-endian `u32`s. A single
+//! frame stores its body directly; sequences prefix each body with the frame's
+//! `x`, `y`, `width` and `height`. A body is either
+//!
+//! ```text
+//! u32 0, width*height*4 bytes RGBA
+//! u32 token_count, token_count*4 bytes RGBA palette,
+//!   u32 rle_len, rle_len bytes of (run, token index) pairs
+//! ```
+//!
+//! Frames decode row by row into a caller buffer, so nothing larger than one
+//! row has to be held. [`RawImage::decode_into`] fills a buffer for the image
+//! widget and [`RawStream`] implements [`FrameSource`] for animated playback.
+
+use crate::frames::FrameSource;
+use crate::widget::Color;
+use alloc::vec::Vec;
+
+/// Magic identifier at the start of every `.raw` file.
+pub const MAGIC: &[u8; 8] = b"RLVGLRAW";
+
+/// Errors reported while parsing or decoding a `.raw` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The data ends before a header or frame body is complete.
+    Truncated,
+    /// A run references a palette entry that does not exist.
+    BadToken,
+    /// The requested frame does not exist.
+    NoFrame,
+    /// The output buffer is too small for the requested pixels.
+    BufferTooSmall,
+    /// The header describes more pixels than can be addressed or allocated.
+    TooLarge,
+}
+
+/// Location and size of a frame inside the sequence bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawFrame {
+    /// X offset within the maximum bounds.
+    pub x: u32,
+    /// Y offset within the maximum bounds.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Byte offset of the frame body in the file.
+    offset: usize,
+}
+
+/// Little-endian reader over a byte slice.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self.pos.checked_add(len).ok_or(Error::Truncated)?;
+        let bytes = self.data.get(self.pos..end).ok_or(Error::Truncated)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+/// Parsed `.raw` file borrowing its data.
+///
+/// Parsing validates the headers and records where each frame body starts;
+/// pixel data is only read when a frame is decoded.
+#[derive(Debug, Clone)]
+pub struct RawImage<'a> {
+    data: &'a [u8],
+    /// Maximum width of any frame.
+    pub width: u32,
+    /// Maximum height of any frame.
+    pub height: u32,
+    frames: Vec<RawFrame>,
+}
+
+impl<'a> RawImage<'a> {
+    /// Parse the header and frame table of `data`.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let mut cur = Cursor { data, pos: 0 };
+        if cur.take(MAGIC.len()).map_err(|_| Error::BadMagic)? != MAGIC {
+            return Err(Error::BadMagic);
+        }
+        let width = cur.u32()?;
+        let height = cur.u32()?;
+        let count = cur.u32()?;
+        // Every body holds at least its token count and sequence frames a
+        // 16 byte placement, so the data bounds how many frames can follow.
+        let min_frame = if count == 1 { 4 } else { 20 };
+        let fit = (data.len() - cur.pos) / min_frame;
+        let mut frames = Vec::with_capacity((count as usize).min(fit));
+        for _ in 0..count {
+            let (x, y, w, h) = if count == 1 {
+                (0, 0, width, height)
+            } else {
+                (cur.u32()?, cur.u32()?, cur.u32()?, cur.u32()?)
+            };
+            let offset = cur.pos;
+            skip_body(&mut cur, w, h)?;
+            frames.push(RawFrame {
+                x,
+                y,
+                width: w,
+                height: h,
+                offset,
+            });
+        }
+        Ok(Self {
+            data,
+            width,
+            height,
+            frames,
+        })
+    }
+
+    /// Number of frames in the file.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Placement of frame `index`.
+    pub fn frame(&self, index: usize) -> Option<&RawFrame> {
+        self.frames.get(index)
+    }
+
+    /// Start decoding frame `index` one row at a time.
+    pub fn rows(&self, index: usize) -> Result<RowDecoder<'a>, Error> {
+        let frame = self.frames.get(index).ok_or(Error::NoFrame)?;
+        let mut cur = Cursor {
+            data: self.data,
+            pos: frame.offset,
+        };
+        let tokens = cur.u32()? as usize;
+        let body = if tokens == 0 {
+            Body::Plain(cur.take(rgba_len(frame.width, frame.height)?)?)
+        } else {
+            let palette = cur.take(tokens.checked_mul(4).ok_or(Error::TooLarge)?)?;
+            let len = cur.u32()? as usize;
+            Body::Rle {
+                palette,
+                runs: cur.take(len)?,
+                run: 0,
+                token: 0,
+            }
+        };
+        Ok(RowDecoder {
+            width: frame.width as usize,
+            rows_left: frame.height,
+            body,
+        })
+    }
+
+    /// Decode frame `index` into `out`, a buffer of `width * height` pixels
+    /// covering the maximum bounds.
+    ///
+    /// Pixels outside the frame are left untouched.
+    pub fn decode_into(&self, index: usize, out: &mut [Color]) -> Result<(), Error> {
+        let canvas_w = self.width as usize;
+        let canvas_len = canvas_w
+            .checked_mul(self.height as usize)
+            .ok_or(Error::TooLarge)?;
+        if out.len() < canvas_len {
+            return Err(Error::BufferTooSmall);
+        }
+        let frame = *self.frames.get(index).ok_or(Error::NoFrame)?;
+        let mut rows = self.rows(index)?;
+        let mut row = transparent(frame.width as usize)?;
+        let x0 = frame.x as usize;
+        let mut y = frame.y as usize;
+        while rows.next_row(&mut row)? {
+            if y < self.height as usize && x0 < canvas_w {
+                let n = row.len().min(canvas_w - x0);
+                let start = y * canvas_w + x0;
+                out[start..start + n].copy_from_slice(&row[..n]);
+            }
+            y += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Advance `cur` past a frame body, checking that it is complete.
+fn skip_body(cur: &mut Cursor<'_>, width: u32, height: u32) -> Result<(), Error> {
+    let tokens = cur.u32()? as usize;
+    if tokens == 0 {
+        cur.take(rgba_len(width, height)?)?;
+    } else {
+        cur.take(tokens.checked_mul(4).ok_or(Error::TooLarge)?)?;
+        let len = cur.u32()? as usize;
+        cur.take(len)?;
+    }
+    Ok(())
+}
+
+/// Byte length of a `width * height` RGBA body.
+fn rgba_len(width: u32, height: u32) -> Result<usize, Error> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|n| n.checked_mul(4))
+        .ok_or(Error::TooLarge)
+}
+
+/// Allocate `len` transparent pixels, failing instead of aborting when the
+/// header asks for more memory than is available.
+fn transparent(len: usize) -> Result<Vec<Color>, Error> {
+    let mut pixels = Vec::new();
+    pixels.try_reserve_exact(len).map_err(|_| Error::TooLarge)?;
+    pixels.resize(len, Color(0, 0, 0, 0));
+    Ok(pixels)
+}
+
+/// Encoded pixel data of one frame.
+#[derive(Debug, Clone)]
+enum Body<'a> {
+    Plain(&'a [u8]),
+    Rle {
+        palette: &'a [u8],
+        runs: &'a [u8],
+        /// Pixels left in the current run.
+        run: u8,
+        /// Palette index of the current run.
+        token: u8,
+    },
+}
+
+/// Streaming decoder producing one row of a frame per call.
+#[derive(Debug, Clone)]
+pub struct RowDecoder<'a> {
+    width: usize,
+    rows_left: u32,
+    body: Body<'a>,
+}
+
+impl<'a> RowDecoder<'a> {
+    /// Width of each row in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Decode the next row into `out`.
+    ///
+    /// Returns `Ok(false)` once every row has been produced. Runs may span
+    /// rows; a stream that ends early leaves the remaining pixels transparent.
+    pub fn next_row(&mut self, out: &mut [Color]) -> Result<bool, Error> {
+        if self.rows_left == 0 {
+            return Ok(false);
+        }
+        let out = out.get_mut(..self.width).ok_or(Error::BufferTooSmall)?;
+        self.rows_left -= 1;
+        match &mut self.body {
+            Body::Plain(data) => {
+                let bytes: &'a [u8] = data;
+                let (row, rest) = bytes.split_at(self.width * 4);
+                for (px, c) in out.iter_mut().zip(row.chunks_exact(4)) {
+                    *px = Color(c[0], c[1], c[2], c[3]);
+                }
+                *data = rest;
+            }
+            Body::Rle {
+                palette,
+                runs,
+                run,
+                token,
+            } => {
+                for px in out.iter_mut() {
+                    while *run == 0 {
+                        let bytes: &'a [u8] = runs;
+                        let Some((pair, rest)) = bytes.split_first_chunk::<2>() else {
+                            break;
+                        };
+                        (*run, *token) = (pair[0], pair[1]);
+                        *runs = rest;
+                    }
+                    if *run == 0 {
+                        *px = Color(0, 0, 0, 0);
+                        continue;
+                    }
+                    let i = *token as usize * 4;
+                    let c = palette.get(i..i + 4).ok_or(Error::BadToken)?;
+                    *px = Color(c[0], c[1], c[2], c[3]);
+                    *run -= 1;
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// [`FrameSource`] decoding `.raw` frames on demand into a single canvas.
+///
+/// The format carries no timing, so every frame is shown for the same delay.
+/// Each frame is drawn on a cleared canvas at its offset.
+pub struct RawStream<'a> {
+    image: RawImage<'a>,
+    canvas: Vec<Color>,
+    delay_ms: u32,
+}
+
+impl<'a> RawStream<'a> {
+    /// Parse `data` and show each frame for `delay_ms` milliseconds.
+    pub fn new(data: &'a [u8], delay_ms: u32) -> Result<Self, Error> {
+        let image = RawImage::parse(data)?;
+        let len = (image.width as usize)
+            .checked_mul(image.height as usize)
+            .ok_or(Error::TooLarge)?;
+        let canvas = transparent(len)?;
+        let mut stream = Self {
+            image,
+            canvas,
+            delay_ms,
+        };
+        if stream.image.frame_count() > 0 {
+            stream.image.decode_into(0, &mut stream.canvas)?;
+        }
+        Ok(stream)
+    }
+
+    /// Parsed file backing this stream.
+    pub fn image(&self) -> &RawImage<'a> {
+        &self.image
+    }
+}
+
+impl FrameSource for RawStream<'_> {
+    fn size(&self) -> (u32, u32) {
+        (self.image.width, self.image.height)
+    }
+
+    fn frame_count(&self) -> usize {
+        self.image.frame_count()
+    }
+
+    fn delay_ms(&self, _index: usize) -> u32 {
+        self.delay_ms
+    }
+
+    fn seek(&mut self, index: usize) -> bool {
+        if index >= self.image.frame_count() {
+            return false;
+        }
+        self.canvas.fill(Color(0, 0, 0, 0));
+        self.image.decode_into(index, &mut self.canvas).is_ok()
+    }
+
+    fn pixels(&self) -> &[Color] {
+        &self.canvas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(w: u32, h: u32, frames: u32) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        for v in [w, h, frames] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        out
+    }
+
+    /// 3x2 image: a red row and a row of two green pixels then one red.
+    fn rle_body() -> Vec<u8> {
+        let mut out = 2u32.to_le_bytes().to_vec();
+        out.extend_from_slice(&[255, 0, 0, 255, 0, 255, 0, 128]);
+        let runs = [3, 0, 2, 1, 1, 0];
+        out.extend_from_slice(&(runs.len() as u32).to_le_bytes());
+        out.extend_from_slice(&runs);
+        out
+    }
+
+    const RED: Color = Color(255, 0, 0, 255);
+    const GREEN: Color = Color(0, 255, 0, 128);
+
+    #[test]
+    fn decodes_rle_rows() {
+        let mut data = header(3, 2, 1);
+        data.extend(rle_body());
+        let img = RawImage::parse(&data).unwrap();
+        assert_eq!(img.frame_count(), 1);
+        let mut rows = img.rows(0).unwrap();
+        let mut row = [Color(0, 0, 0, 0); 3];
+        assert!(rows.next_row(&mut row).unwrap());
+        assert_eq!(row, [RED; 3]);
+        assert!(rows.next_row(&mut row).unwrap());
+        assert_eq!(row, [GREEN, GREEN, RED]);
+        assert!(!rows.next_row(&mut row).unwrap());
+    }
+
+    #[test]
+    fn decodes_plain_sequence_with_offsets() {
+        let mut data = header(2, 2, 2);
+        for (x, color) in [(0u32, [1u8, 2, 3, 4]), (1, [5, 6, 7, 8])] {
+            for v in [x, 1, 1, 1, 0] {
+                data.extend_from_slice(&v.to_le_bytes());
+            }
+            data.extend_from_slice(&color);
+        }
+        let mut stream = RawStream::new(&data, 40).unwrap();
+        assert_eq!(stream.frame_count(), 2);
+        assert_eq!(stream.pixels()[2], Color(1, 2, 3, 4));
+        assert!(stream.seek(1));
+        let clear = Color(0, 0, 0, 0);
+        assert_eq!(stream.pixels(), &[clear, clear, clear, Color(5, 6, 7, 8)]);
+        assert!(!stream.seek(2));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(RawImage::parse(b"NOTRAW").unwrap_err(), Error::BadMagic);
+        let mut data = header(3, 2, 1);
+        let body = rle_body();
+        data.extend_from_slice(&body[..body.len() - 1]);
+        assert_eq!(RawImage::parse(&data).unwrap_err(), Error::Truncated);
+
+        let mut data = header(1, 1, 1);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0, 0, 0, 255]);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[1, 7]);
+        let img = RawImage::parse(&data).unwrap();
+        let mut out = [Color(0, 0, 0, 0)];
+        assert_eq!(img.decode_into(0, &mut out), Err(Error::BadToken));
+    }
+
+    #[test]
+    fn rejects_oversized_headers() {
+        // A huge frame count with no frame data must not reserve memory up front.
+        assert_eq!(
+            RawImage::parse(&header(1, 1, u32::MAX)).unwrap_err(),
+            Error::Truncated
+        );
+
+        let mut data = header(u32::MAX, u32::MAX, 1);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(RawImage::parse(&data).unwrap_err(), Error::TooLarge);
+
+        let data = header(u32::MAX, u32::MAX, 0);
+        assert_eq!(RawStream::new(&data, 40).err(), Some(Error::TooLarge));
+    }
+}
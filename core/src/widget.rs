@@ -23,6 +23,39 @@ pub struct Rect {
     pub height: i32,
 }
 
+impl Rect {
+    /// Check whether the point `(x, y)` lies inside the rectangle.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Overlap of `self` and `other`, or `None` if they do not overlap.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let width = (self.x + self.width).min(other.x + other.width) - x;
+        let height = (self.y + self.height).min(other.y + other.height) - y;
+        (width > 0 && height > 0).then_some(Rect {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    /// Smallest rectangle covering both `self` and `other`.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
 /// RGBA color used by the renderer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Color(
@@ -62,4 +95,18 @@ pub trait Widget {
     /// The default implementation for most widgets will simply ignore the
     /// event and return `false`.
     fn handle_event(&mut self, event: &Event) -> bool;
+    /// Return and clear the area that needs redrawing, if any.
+    ///
+    /// Widgets that change on their own, such as animations, report their
+    /// bounds here so only that area is refreshed. The default reports
+    /// nothing.
+    fn take_dirty(&mut self) -> Option<Rect> {
+        None
+    }
+    /// Replace the text the widget shows, if it has any.
+    ///
+    /// Called by [`Bindings`](crate::i18n::Bindings) when the language
+    /// changes. Widgets with text report their bounds through
+    /// [`Widget::take_dirty`] afterwards; the default ignores the text.
+    fn set_text(&mut self, _text: &str) {}
 }
@@ -0,0 +1,44 @@
+//! Tests for sprite atlas lookup.
+use rlvgl_core::atlas::{Atlas, AtlasRect};
+
+const fn rect(sheet: u16, x: u16, rotated: bool) -> AtlasRect {
+    AtlasRect {
+        sheet,
+        x,
+        y: 4,
+        width: 3,
+        height: 2,
+        offset_x: 1,
+        offset_y: 0,
+        source_width: 5,
+        source_height: 2,
+        rotated,
+    }
+}
+
+static ENTRIES: &[(&str, AtlasRect)] = &[
+    ("arrow", rect(0, 0, false)),
+    ("home", rect(1, 10, true)),
+    ("wifi", rect(0, 20, false)),
+];
+
+#[test]
+fn finds_sprites_by_name() {
+    let atlas = Atlas::new(ENTRIES);
+    assert_eq!(atlas.len(), 3);
+    assert_eq!(atlas.get("home").unwrap().sheet, 1);
+    assert_eq!(atlas.get("wifi").unwrap().x, 20);
+    assert!(atlas.get("missing").is_none());
+    let names: Vec<_> = atlas.iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["arrow", "home", "wifi"]);
+}
+
+#[test]
+fn maps_rotated_pixels_back_to_the_sheet() {
+    let plain = rect(0, 0, false);
+    assert_eq!(plain.sheet_pos(2, 1), (2, 5));
+    // Stored 90° clockwise: the top-left pixel lands in the top-right corner.
+    let rotated = rect(0, 10, true);
+    assert_eq!(rotated.sheet_pos(0, 0), (11, 4));
+    assert_eq!(rotated.sheet_pos(2, 1), (10, 6));
+}
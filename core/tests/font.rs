@@ -0,0 +1,186 @@
+//! Tests for the RLVGLFNT bitmap font reader.
+use rlvgl_core::{
+    font::{BitmapFont, Error, FLAG_RLE, FontRenderer, MAGIC, VERSION},
+    renderer::Renderer,
+    widget::{Color, Rect},
+};
+
+/// Glyph description used to assemble test fonts.
+struct TestGlyph {
+    advance: u16,
+    width: u16,
+    height: u16,
+    left: i16,
+    top: i16,
+    bitmap: Vec<u8>,
+}
+
+/// Assemble a font file from codepoint ranges, glyphs and kerning pairs.
+fn build(
+    bpp: u8,
+    flags: u8,
+    ranges: &[(u32, u16)],
+    glyphs: &[TestGlyph],
+    kerning: &[(u16, u16, i16)],
+) -> Vec<u8> {
+    let bitmap_len: usize = glyphs.iter().map(|g| g.bitmap.len()).sum();
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&[VERSION, bpp, flags, 0]);
+    for v in [10u16, 8, (-2i16) as u16, 12] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(&(ranges.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(glyphs.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(kerning.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(bitmap_len as u32).to_le_bytes());
+    let mut first_glyph = 0u16;
+    for &(first, len) in ranges {
+        out.extend_from_slice(&first.to_le_bytes());
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&first_glyph.to_le_bytes());
+        first_glyph += len;
+    }
+    let mut offset = 0u32;
+    for g in glyphs {
+        out.extend_from_slice(&offset.to_le_bytes());
+        for v in [g.advance, g.width, g.height, g.left as u16, g.top as u16, 0] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        offset += g.bitmap.len() as u32;
+    }
+    for &(l, r, adjust) in kerning {
+        out.extend_from_slice(&l.to_le_bytes());
+        out.extend_from_slice(&r.to_le_bytes());
+        out.extend_from_slice(&adjust.to_le_bytes());
+    }
+    for g in glyphs {
+        out.extend_from_slice(&g.bitmap);
+    }
+    out
+}
+
+fn glyph(advance: u16, width: u16, height: u16, bitmap: Vec<u8>) -> TestGlyph {
+    TestGlyph {
+        advance,
+        width,
+        height,
+        left: 0,
+        top: height as i16,
+        bitmap,
+    }
+}
+
+struct Recorder {
+    rects: Vec<(i32, i32, i32, Color)>,
+}
+
+impl Renderer for Recorder {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        assert_eq!(rect.height, 1);
+        self.rects.push((rect.x, rect.y, rect.width, color));
+    }
+
+    fn draw_text(&mut self, _position: (i32, i32), _text: &str, _color: Color) {}
+}
+
+#[test]
+fn rejects_bad_headers() {
+    assert_eq!(BitmapFont::parse(b"nope").unwrap_err(), Error::BadMagic);
+    let mut data = build(4, 0, &[], &[], &[]);
+    data[9] = 3;
+    assert_eq!(BitmapFont::parse(&data).unwrap_err(), Error::BadBpp);
+    let data = build(1, 0, &[(65, 1)], &[glyph(16, 8, 1, vec![0xff])], &[]);
+    assert_eq!(
+        BitmapFont::parse(&data[..data.len() - 1]).unwrap_err(),
+        Error::Truncated
+    );
+}
+
+#[test]
+fn looks_up_ranges_and_kerning() {
+    let glyphs = [
+        glyph(64, 0, 0, vec![]),
+        glyph(80, 2, 1, vec![0b1011_0000]),
+        glyph(96, 2, 1, vec![0b0111_0000]),
+        glyph(48, 1, 1, vec![0b1100_0000]),
+    ];
+    let data = build(
+        2,
+        0,
+        &[(32, 1), (65, 2), (0x4e2d, 1)],
+        &glyphs,
+        &[(1, 2, -16)],
+    );
+    let font = BitmapFont::parse(&data).unwrap();
+    assert_eq!(font.glyph_count(), 4);
+    assert_eq!((font.size, font.ascent, font.descent), (10, 8, -2));
+    assert_eq!(font.glyph_index(' '), Some(0));
+    assert_eq!(font.glyph_index('B'), Some(2));
+    assert_eq!(font.glyph_index('中'), Some(3));
+    assert_eq!(font.glyph_index('C'), None);
+    assert_eq!(font.glyph_index('\0'), None);
+    assert_eq!(font.kerning(1, 2), -16);
+    assert_eq!(font.kerning(2, 1), 0);
+
+    let b = font.glyph('B').unwrap();
+    assert_eq!(b.coverage().collect::<Vec<_>>(), vec![85, 255]);
+    // 5 + 6 - 1 (kerning) px, skipping the missing `C`.
+    assert_eq!(font.measure("ABC"), 10);
+}
+
+#[test]
+fn ignores_ranges_past_the_glyph_table() {
+    let glyphs = [glyph(64, 0, 0, vec![]), glyph(80, 0, 0, vec![])];
+    let mut data = build(1, 0, &[(65, 2)], &glyphs, &[]);
+    // First glyph index of the only range, after the 24 byte header fields.
+    let at = MAGIC.len() + 24 + 6;
+
+    data[at..at + 2].copy_from_slice(&1u16.to_le_bytes());
+    let font = BitmapFont::parse(&data).unwrap();
+    assert_eq!(font.glyph_index('A'), Some(1));
+    assert_eq!(font.glyph_index('B'), None);
+
+    data[at..at + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+    let font = BitmapFont::parse(&data).unwrap();
+    assert_eq!(font.glyph_index('A'), None);
+    assert_eq!(font.glyph_index('B'), None);
+    assert!(font.glyph('B').is_none());
+}
+
+#[test]
+fn decodes_rle_bitmaps() {
+    let glyphs = [
+        glyph(48, 3, 2, vec![2, 15, 3, 0, 1, 8]),
+        glyph(32, 1, 1, vec![1, 15]),
+    ];
+    let data = build(4, FLAG_RLE, &[(97, 2)], &glyphs, &[]);
+    let font = BitmapFont::parse(&data).unwrap();
+    let a = font.glyph('a').unwrap();
+    assert_eq!(
+        a.coverage().collect::<Vec<_>>(),
+        vec![255, 255, 0, 0, 0, 136]
+    );
+    let b = font.glyph('b').unwrap();
+    assert_eq!(b.coverage().collect::<Vec<_>>(), vec![255]);
+}
+
+#[test]
+fn font_renderer_draws_merged_runs() {
+    let glyphs = [glyph(64, 4, 2, vec![0xf0, 0x1f])];
+    let data = build(2, 0, &[(120, 1)], &glyphs, &[]);
+    let font = BitmapFont::parse(&data).unwrap();
+    let mut rec = Recorder { rects: Vec::new() };
+    let white = Color(255, 255, 255, 255);
+    {
+        let mut r = FontRenderer::new(&mut rec, &font, Color(0, 0, 0, 255));
+        r.draw_text((10, 20), "x", white);
+    }
+    assert_eq!(
+        rec.rects,
+        vec![
+            (10, 18, 2, white),
+            (11, 19, 1, Color(85, 85, 85, 255)),
+            (12, 19, 2, white),
+        ]
+    );
+}
@@ -0,0 +1,82 @@
+//! Tests for two-finger gesture recognition.
+use rlvgl_core::event::{Event, Gesture, GesturePhase};
+use rlvgl_core::gesture::{GestureRecognizer, SCALE_NONE};
+
+fn down(id: u8, x: i32, y: i32) -> Event {
+    Event::TouchDown { id, x, y }
+}
+
+fn moved(id: u8, x: i32, y: i32) -> Event {
+    Event::TouchMove { id, x, y }
+}
+
+fn gesture(phase: GesturePhase, x: i32, y: i32, scale: i32, angle: i32) -> Option<Event> {
+    Some(Event::Gesture(Gesture {
+        phase,
+        x,
+        y,
+        scale,
+        angle,
+    }))
+}
+
+#[test]
+fn pinch_reports_scale_relative_to_start() {
+    let mut rec = GestureRecognizer::new();
+    assert_eq!(rec.handle(&down(0, 100, 100)), None);
+    assert!(!rec.is_active());
+    assert_eq!(
+        rec.handle(&down(1, 200, 100)),
+        gesture(GesturePhase::Start, 150, 100, SCALE_NONE, 0)
+    );
+    assert_eq!(
+        rec.handle(&moved(1, 300, 100)),
+        gesture(GesturePhase::Update, 200, 100, 512, 0)
+    );
+    assert_eq!(
+        rec.handle(&moved(0, 250, 100)),
+        gesture(GesturePhase::Update, 275, 100, 128, 0)
+    );
+    assert_eq!(
+        rec.handle(&Event::TouchUp {
+            id: 0,
+            x: 250,
+            y: 100
+        }),
+        gesture(GesturePhase::End, 275, 100, 128, 0)
+    );
+    assert!(!rec.is_active());
+    assert_eq!(rec.handle(&moved(1, 310, 100)), None);
+}
+
+#[test]
+fn rotation_is_clockwise_and_wraps() {
+    let mut rec = GestureRecognizer::new();
+    rec.handle(&down(3, 0, 0));
+    rec.handle(&down(4, 100, 0));
+    assert_eq!(
+        rec.handle(&moved(4, 0, 100)),
+        gesture(GesturePhase::Update, 0, 50, SCALE_NONE, 90)
+    );
+    assert_eq!(
+        rec.handle(&moved(4, 0, -100)),
+        gesture(GesturePhase::Update, 0, -50, SCALE_NONE, -90)
+    );
+    assert_eq!(
+        rec.handle(&moved(4, -100, 0)),
+        gesture(GesturePhase::Update, -50, 0, SCALE_NONE, 180)
+    );
+}
+
+#[test]
+fn extra_fingers_and_unchanged_moves_are_ignored() {
+    let mut rec = GestureRecognizer::new();
+    rec.handle(&down(0, 0, 0));
+    rec.handle(&down(1, 10, 0));
+    assert_eq!(rec.handle(&down(2, 50, 50)), None);
+    assert_eq!(rec.handle(&moved(2, 60, 60)), None);
+    assert_eq!(rec.handle(&moved(1, 10, 0)), None);
+    assert_eq!(rec.handle(&Event::PointerMove { x: 1, y: 1 }), None);
+    assert_eq!(rec.handle(&Event::TouchUp { id: 2, x: 0, y: 0 }), None);
+    assert!(rec.is_active());
+}
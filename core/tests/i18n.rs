@@ -0,0 +1,82 @@
+//! Tests for translated string tables and plural rules.
+use rlvgl_core::i18n::{Catalog, PluralCategory, PluralRule, StringId, Translator, format_string};
+
+const HELLO: StringId = StringId(0);
+const FILES: StringId = StringId(1);
+const ONLY_EN: StringId = StringId(2);
+
+const EN: Catalog<'static> = Catalog::new(
+    "en",
+    PluralRule::OneOther,
+    &[&["Hello"], &["{0} file", "{0} files"], &["Only English"]],
+);
+const DE: Catalog<'static> = Catalog::new(
+    "de",
+    PluralRule::OneOther,
+    &[&["Hallo"], &["{0} Datei", "{0} Dateien"], &[]],
+);
+const DE_AT: Catalog<'static> = Catalog::new("de-AT", PluralRule::OneOther, &[&["Servus"]]);
+const RU: Catalog<'static> = Catalog::new(
+    "ru",
+    PluralRule::EastSlavic,
+    &[&["Привет"], &["{0} файл", "{0} файла", "{0} файлов"], &[]],
+);
+static CATALOGS: [Catalog<'static>; 4] = [EN, DE, DE_AT, RU];
+
+#[test]
+fn plural_rules_follow_cldr() {
+    use PluralCategory::*;
+    let cat =
+        |rule: PluralRule, ns: &[u64]| ns.iter().map(|&n| rule.category(n)).collect::<Vec<_>>();
+    assert_eq!(cat(PluralRule::OneOther, &[0, 1, 2]), [Other, One, Other]);
+    assert_eq!(cat(PluralRule::OneWithZero, &[0, 1, 2]), [One, One, Other]);
+    assert_eq!(
+        cat(PluralRule::EastSlavic, &[1, 2, 5, 11, 12, 21, 22, 25, 111]),
+        [One, Few, Many, Many, Many, One, Few, Many, Many]
+    );
+    assert_eq!(
+        cat(PluralRule::Polish, &[1, 2, 5, 21, 22]),
+        [One, Few, Many, Many, Few]
+    );
+    assert_eq!(cat(PluralRule::Czech, &[1, 3, 5]), [One, Few, Other]);
+    assert_eq!(
+        cat(PluralRule::Arabic, &[0, 1, 2, 3, 11, 100, 102]),
+        [Zero, One, Two, Few, Many, Other, Other]
+    );
+    assert_eq!(cat(PluralRule::Other, &[1]), [Other]);
+    assert_eq!(PluralRule::for_language("ru_RU"), PluralRule::EastSlavic);
+    assert_eq!(PluralRule::for_language("pt-BR"), PluralRule::OneWithZero);
+    assert_eq!(PluralRule::for_language("ZH"), PluralRule::Other);
+    assert_eq!(PluralRule::for_language("xx"), PluralRule::OneOther);
+    assert_eq!(PluralRule::Czech.form(7), 2);
+}
+
+#[test]
+fn switches_locale_with_fallbacks() {
+    let mut tr = Translator::new(&CATALOGS);
+    assert_eq!((tr.locale(), tr.get(HELLO)), ("en", "Hello"));
+    assert!(tr.set_locale("de_at"));
+    assert_eq!(tr.locale(), "de-AT");
+    assert_eq!(tr.revision(), 1);
+    assert_eq!(tr.get(HELLO), "Servus");
+    // Missing in de-AT, found in de.
+    assert_eq!(tr.plural(FILES, 1), "{0} Datei");
+    // Missing in de-AT and de, found in the source locale.
+    assert_eq!(tr.get(ONLY_EN), "Only English");
+    assert_eq!(tr.get(StringId(9)), "");
+    assert!(tr.set_locale("ru-UA"));
+    assert_eq!(tr.locale(), "ru");
+    assert_eq!(tr.plural(FILES, 3), "{0} файла");
+    assert_eq!(tr.plural(FILES, 5), "{0} файлов");
+    assert!(!tr.set_locale("fr"));
+    assert!(tr.set_locale("ru"));
+    assert_eq!((tr.locale(), tr.revision()), ("ru", 2));
+}
+
+#[test]
+fn formats_positional_placeholders() {
+    assert_eq!(format_string("{0} Dateien", &[&3]), "3 Dateien");
+    assert_eq!(format_string("{1} of {0}", &[&"a", &"b"]), "b of a");
+    assert_eq!(format_string("{{0}} {2} {x} {", &[&1]), "{0} {2} {x} {");
+    assert_eq!(format_string("}} done", &[]), "} done");
+}
@@ -0,0 +1,35 @@
+//! Tests for integer trigonometry and square root helpers.
+use rlvgl_core::math::{TRIG_SCALE, atan2, cos, sin, sqrt};
+
+#[test]
+fn trig_quadrants() {
+    assert_eq!(sin(0), 0);
+    assert_eq!(sin(90), TRIG_SCALE);
+    assert_eq!(sin(270), -TRIG_SCALE);
+    assert_eq!(cos(180), -TRIG_SCALE);
+    assert_eq!(sin(-90), -TRIG_SCALE);
+    assert_eq!(sin(390), sin(30));
+}
+
+#[test]
+fn integer_sqrt() {
+    assert_eq!(sqrt(0), 0);
+    assert_eq!(sqrt(15), 3);
+    assert_eq!(sqrt(16), 4);
+    assert_eq!(sqrt(u32::MAX), 65535);
+}
+
+#[test]
+fn atan2_covers_every_quadrant() {
+    assert_eq!(atan2(0, 0), 0);
+    assert_eq!(atan2(0, 10), 0);
+    assert_eq!(atan2(10, 10), 45);
+    assert_eq!(atan2(10, 0), 90);
+    assert_eq!(atan2(0, -10), 180);
+    assert_eq!(atan2(-10, 0), 270);
+    assert_eq!(atan2(-1, 1000), 0);
+    for angle in (0..360).step_by(7) {
+        let (x, y) = (cos(angle), sin(angle));
+        assert_eq!(atan2(y, x), angle, "angle {angle}");
+    }
+}
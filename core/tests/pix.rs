@@ -0,0 +1,227 @@
+//! Tests for the RLVGLPIX pixel variant reader.
+use rlvgl_core::{
+    pix::{Compression, Error, FLAG_PREMULTIPLIED, Format, MAGIC, PixImage},
+    widget::Color,
+};
+
+/// Assemble a pixel variant file.
+fn build(
+    width: u32,
+    height: u32,
+    format: Format,
+    flags: u8,
+    compression: Compression,
+    palette: &[u32],
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&width.to_le_bytes());
+    out.extend_from_slice(&height.to_le_bytes());
+    out.extend_from_slice(&[format.id(), flags, compression.id(), 0]);
+    if format.is_indexed() {
+        out.extend_from_slice(&(palette.len() as u16).to_le_bytes());
+        for argb in palette {
+            out.extend_from_slice(&argb.to_le_bytes());
+        }
+    }
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+#[test]
+fn parses_header_fields() {
+    let data = build(
+        2,
+        1,
+        Format::Argb8888,
+        FLAG_PREMULTIPLIED,
+        Compression::None,
+        &[],
+        &[1, 2, 3, 4, 5, 6, 7, 8],
+    );
+    let image = PixImage::parse(&data).unwrap();
+    assert_eq!((image.width, image.height), (2, 1));
+    assert_eq!(image.format, Format::Argb8888);
+    assert_eq!(image.compression, Compression::None);
+    assert!(image.premultiplied);
+    assert_eq!(image.stride(), 8);
+    assert_eq!(image.payload(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(image.decode().unwrap(), image.payload());
+}
+
+#[test]
+fn rejects_malformed_headers() {
+    let good = build(1, 1, Format::L8, 0, Compression::None, &[], &[7]);
+    assert_eq!(PixImage::parse(b"RLVGLFNT").unwrap_err(), Error::BadMagic);
+    assert_eq!(PixImage::parse(&good[..12]).unwrap_err(), Error::Truncated);
+    assert_eq!(
+        PixImage::parse(&good[..good.len() - 1]).unwrap_err(),
+        Error::Truncated
+    );
+
+    let mut bad = good.clone();
+    bad[16] = 9;
+    assert_eq!(PixImage::parse(&bad).unwrap_err(), Error::BadFormat);
+    let mut bad = good.clone();
+    bad[18] = 3;
+    assert_eq!(PixImage::parse(&bad).unwrap_err(), Error::BadCompression);
+
+    let indexed = build(1, 1, Format::I8, 0, Compression::None, &[0xff00_00ff], &[0]);
+    assert_eq!(
+        PixImage::parse(&indexed[..24]).unwrap_err(),
+        Error::Truncated
+    );
+}
+
+#[test]
+fn unpacks_run_length_payloads() {
+    // RGB565 runs repeat whole pixels.
+    let data = build(
+        3,
+        1,
+        Format::Rgb565,
+        0,
+        Compression::Rle,
+        &[],
+        &[2, 0x00, 0xf8, 1, 0x1f, 0x00],
+    );
+    let image = PixImage::parse(&data).unwrap();
+    assert_eq!(
+        image.decode().unwrap(),
+        [0x00, 0xf8, 0x00, 0xf8, 0x1f, 0x00]
+    );
+    assert_eq!(
+        image.to_colors().unwrap(),
+        [
+            Color(255, 0, 0, 255),
+            Color(255, 0, 0, 255),
+            Color(0, 0, 255, 255)
+        ]
+    );
+
+    // Runs that stop short or overrun the image are rejected.
+    for payload in [&[2, 0x00, 0xf8][..], &[4, 0x00, 0xf8], &[3, 0x00]] {
+        let data = build(3, 1, Format::Rgb565, 0, Compression::Rle, &[], payload);
+        let image = PixImage::parse(&data).unwrap();
+        assert_eq!(image.decode().unwrap_err(), Error::BadPayload);
+    }
+}
+
+#[test]
+fn unpacks_lz4_payloads() {
+    // Three literals followed by an overlapping match of five bytes.
+    let data = build(
+        8,
+        1,
+        Format::L8,
+        0,
+        Compression::Lz4,
+        &[],
+        &[0x31, 1, 2, 3, 3, 0, 0x00],
+    );
+    let image = PixImage::parse(&data).unwrap();
+    assert_eq!(image.decode().unwrap(), [1, 2, 3, 1, 2, 3, 1, 2]);
+
+    let mut out = [0u8; 10];
+    image.decode_into(&mut out).unwrap();
+    assert_eq!(out, [1, 2, 3, 1, 2, 3, 1, 2, 0, 0]);
+    assert_eq!(
+        image.decode_into(&mut [0u8; 7]).unwrap_err(),
+        Error::BufferTooSmall
+    );
+}
+
+#[test]
+fn rejects_corrupt_lz4_payloads() {
+    for payload in [
+        // Match offset reaching before the start of the output.
+        &[0x11, 1, 4, 0][..],
+        // Zero match offset.
+        &[0x11, 1, 0, 0],
+        // Literal run longer than the data.
+        &[0x40, 1, 2],
+        // Match running past the end of the image.
+        &[0x1f, 1, 1, 0, 10],
+        // Output shorter than the image.
+        &[0x20, 1, 2],
+    ] {
+        let data = build(8, 1, Format::L8, 0, Compression::Lz4, &[], payload);
+        let image = PixImage::parse(&data).unwrap();
+        assert_eq!(
+            image.decode().unwrap_err(),
+            Error::BadPayload,
+            "{payload:?}"
+        );
+    }
+}
+
+#[test]
+fn converts_formats_to_colors() {
+    let colors = |format: Format, width: u32, palette: &[u32], payload: &[u8]| {
+        let data = build(width, 1, format, 0, Compression::None, palette, payload);
+        PixImage::parse(&data).unwrap().to_colors().unwrap()
+    };
+    assert_eq!(
+        colors(Format::Argb8888, 1, &[], &[0x30, 0x20, 0x10, 0x80]),
+        [Color(0x10, 0x20, 0x30, 0x80)]
+    );
+    assert_eq!(
+        colors(Format::L8, 2, &[], &[0, 200]),
+        [Color(0, 0, 0, 255), Color(200, 200, 200, 255)]
+    );
+    assert_eq!(colors(Format::A8, 1, &[], &[99]), [Color(0, 0, 0, 99)]);
+    assert_eq!(
+        colors(Format::A4, 3, &[], &[0xf3, 0x80]),
+        [
+            Color(0, 0, 0, 0xff),
+            Color(0, 0, 0, 0x33),
+            Color(0, 0, 0, 0x88)
+        ]
+    );
+
+    let palette = [0xff10_2030, 0x8040_5060];
+    let dark = Color(0x10, 0x20, 0x30, 0xff);
+    let light = Color(0x40, 0x50, 0x60, 0x80);
+    assert_eq!(
+        colors(Format::I1, 3, &palette, &[0b1010_0000]),
+        [light, dark, light]
+    );
+    assert_eq!(
+        colors(Format::I2, 3, &palette, &[0b0100_1100]),
+        [light, dark, Color(0, 0, 0, 0)]
+    );
+    assert_eq!(colors(Format::I4, 2, &palette, &[0x10]), [light, dark]);
+    assert_eq!(colors(Format::I8, 2, &palette, &[1, 0]), [light, dark]);
+}
+
+#[test]
+fn exposes_palette_entries() {
+    let data = build(
+        2,
+        1,
+        Format::I8,
+        0,
+        Compression::None,
+        &[0xff00_00ff, 0x00ff_ffff],
+        &[0, 1],
+    );
+    let image = PixImage::parse(&data).unwrap();
+    assert_eq!(image.palette_len(), 2);
+    assert_eq!(image.palette(), [0xff00_00ff, 0x00ff_ffff]);
+    assert_eq!(image.palette_color(2), None);
+}
+
+#[test]
+fn rejects_sizes_that_overflow() {
+    let data = build(
+        u32::MAX,
+        u32::MAX,
+        Format::Argb8888,
+        0,
+        Compression::None,
+        &[],
+        &[],
+    );
+    assert_eq!(PixImage::parse(&data).unwrap_err(), Error::TooLarge);
+}
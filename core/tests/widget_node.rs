@@ -178,3 +178,85 @@ fn stop_propagation() {
     // child should not receive the event
     assert_eq!(*counter_child.borrow(), 0);
 }
+
+struct DirtyWidget {
+    bounds: Rect,
+    dirty: bool,
+}
+
+impl Widget for DirtyWidget {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+    fn draw(&self, _renderer: &mut dyn Renderer) {}
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+    fn take_dirty(&mut self) -> Option<Rect> {
+        std::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+}
+
+#[test]
+fn collect_dirty_areas_once() {
+    let counter = Rc::new(RefCell::new(0));
+    let rect = |x| Rect {
+        x,
+        y: 0,
+        width: 4,
+        height: 4,
+    };
+    let root = WidgetNode {
+        widget: Rc::new(RefCell::new(TestWidget::new(
+            rect(0),
+            counter.clone(),
+            counter.clone(),
+        ))),
+        children: vec![WidgetNode {
+            widget: Rc::new(RefCell::new(DirtyWidget {
+                bounds: rect(5),
+                dirty: true,
+            })),
+            children: Vec::new(),
+        }],
+    };
+    let mut areas = Vec::new();
+    root.collect_dirty(&mut areas);
+    assert_eq!(areas.len(), 1);
+    assert_eq!(areas[0].x, 5);
+    areas.clear();
+    root.collect_dirty(&mut areas);
+    assert!(areas.is_empty());
+}
+
+#[test]
+fn rect_contains_excludes_far_edges() {
+    let r = Rect {
+        x: -2,
+        y: 3,
+        width: 4,
+        height: 2,
+    };
+    assert!(r.contains(-2, 3));
+    assert!(r.contains(1, 4));
+    assert!(!r.contains(2, 4));
+    assert!(!r.contains(0, 5));
+    assert!(!r.contains(-3, 3));
+}
+
+#[test]
+fn rect_intersect_and_union() {
+    let rect = |x, y, width, height| Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+    let fields = |r: Rect| (r.x, r.y, r.width, r.height);
+    let a = rect(0, 0, 4, 4);
+    let b = rect(2, -1, 4, 3);
+    assert_eq!(a.intersect(&b).map(fields), Some((2, 0, 2, 2)));
+    assert_eq!(fields(a.union(&b)), (0, -1, 6, 5));
+    // Touching edges do not overlap.
+    assert!(a.intersect(&rect(4, 0, 2, 2)).is_none());
+}
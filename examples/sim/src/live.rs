@@ -0,0 +1,147 @@
+//! Live-edited screen for the simulator.
+//!
+//! `--layout` builds a screen file, its images and an optional `--font` from
+//! the `--assets` directory instead of the demo. On Unix, files announced on
+//! the reload socket (for example by `rlvgl-creator`) are reread in the
+//! interactive window and the screen is rebuilt without restarting.
+
+#[cfg(unix)]
+use std::path::Path;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    rc::Rc,
+};
+
+use rlvgl::core::{WidgetNode, jpeg, png, widget::Color};
+#[cfg(unix)]
+use rlvgl::platform::hot_reload::{AssetKind, Change, HotReload};
+use rlvgl::ui::screen::{ImageData, Screen};
+
+/// Layout, images and font served from an asset directory.
+pub struct Live {
+    root: PathBuf,
+    layout: Option<String>,
+    font_name: Option<String>,
+    images: Rc<RefCell<BTreeMap<String, ImageData>>>,
+    font: Rc<Cell<Option<&'static [u8]>>>,
+    stale: Rc<Cell<bool>>,
+    #[cfg(unix)]
+    reload: Option<HotReload>,
+}
+
+impl Live {
+    /// Serve `layout` and `font`, both relative to `root`.
+    pub fn new(root: PathBuf, layout: Option<String>, font: Option<String>) -> Self {
+        let live = Self {
+            layout,
+            font_name: font,
+            images: Rc::default(),
+            font: Rc::default(),
+            stale: Rc::new(Cell::new(true)),
+            #[cfg(unix)]
+            reload: None,
+            root,
+        };
+        if let Some(name) = &live.font_name {
+            match fs::read(live.root.join(name)) {
+                Ok(data) => live.font.set(Some(data.leak())),
+                Err(e) => eprintln!("{name}: {e}"),
+            }
+        }
+        live
+    }
+
+    /// Reload the layout, the images it uses and the font when their names
+    /// arrive on `socket`.
+    #[cfg(unix)]
+    pub fn listen(&mut self, socket: &Path) {
+        let mut reload = match HotReload::new(&self.root, socket) {
+            Ok(reload) => reload,
+            Err(e) => {
+                eprintln!("Hot reload disabled: {e}");
+                return;
+            }
+        };
+        let layout = self.layout.clone();
+        let font = self.font_name.clone();
+        let images = self.images.clone();
+        let font_data = self.font.clone();
+        let stale = self.stale.clone();
+        reload.watch("", move |change: &Change, data: &[u8]| match change.kind {
+            AssetKind::Layout if layout.as_deref() == Some(&change.name) => stale.set(true),
+            AssetKind::Image if images.borrow_mut().remove(&change.name).is_some() => {
+                stale.set(true)
+            }
+            // Renderers borrow the font for `'static`, so replaced fonts leak.
+            AssetKind::Font if font.as_deref() == Some(&change.name) => {
+                font_data.set(Some(data.to_vec().leak()))
+            }
+            _ => {}
+        });
+        println!(
+            "Listening for asset changes on {}",
+            reload.socket().display()
+        );
+        self.reload = Some(reload);
+    }
+
+    /// Font to draw text with, if one was loaded.
+    pub fn font(&self) -> Option<&'static [u8]> {
+        self.font.get()
+    }
+
+    /// Apply announced changes and return the rebuilt screen if it is new or
+    /// its layout or images changed. Errors are printed and keep the
+    /// current screen.
+    pub fn update(&mut self) -> Option<WidgetNode> {
+        #[cfg(unix)]
+        if let Some(reload) = &mut self.reload {
+            for change in reload.poll() {
+                println!("Reloaded {}", change.name);
+            }
+        }
+        if !self.stale.replace(false) {
+            return None;
+        }
+        let name = self.layout.as_deref()?;
+        let text = fs::read_to_string(self.root.join(name))
+            .map_err(|e| eprintln!("{name}: {e}"))
+            .ok()?;
+        let screen = if name.ends_with(".json") {
+            Screen::from_json(&text).map_err(|e| e.to_string())
+        } else {
+            serde_yaml::from_str::<Screen>(&text).map_err(|e| e.to_string())
+        };
+        let images = |path: &str| self.image(path);
+        let built = screen.and_then(|s| s.build(&images).map_err(|e| e.to_string()));
+        match built {
+            Ok(built) => Some(built.root),
+            Err(e) => {
+                eprintln!("{name}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Decoded image `path`, cached until it changes.
+    fn image(&self, path: &str) -> Option<ImageData> {
+        if let Some(image) = self.images.borrow().get(path) {
+            return Some(*image);
+        }
+        let data = fs::read(self.root.join(path)).ok()?;
+        let (pixels, w, h): (Vec<Color>, i32, i32) = if path.ends_with(".png") {
+            let (p, w, h) = png::decode(&data).ok()?;
+            (p, w as i32, h as i32)
+        } else {
+            let (p, w, h) = jpeg::decode(&data).ok()?;
+            (p, w as i32, h as i32)
+        };
+        // Widgets keep `'static` pixels; replaced images leak like fonts.
+        let image = (w, h, &*pixels.leak());
+        self.images.borrow_mut().insert(path.into(), image);
+        Some(image)
+    }
+}
@@ -1,11 +1,23 @@
 //! Runs the rlvgl simulator with demonstrations of core widgets and plugin features.
 #[path = "../../common_demo/lib.rs"]
 mod common_demo;
+mod live;
 use common_demo::{build_demo, flush_pending};
+use live::Live;
+use rlvgl::core::event::DEFAULT_TICK_MS;
+#[cfg(unix)]
+use rlvgl::platform::hot_reload;
 use rlvgl::platform::{
-    BlitRect, BlitterRenderer, CpuBlitter, InputEvent, PixelFmt, Surface, WgpuBlitter, WgpuDisplay,
+    BlitRect, BlitterRenderer, CpuBlitter, InputDevice, InputEvent, PixelFmt, Recorder, Recording,
+    Replay, Surface, WgpuBlitter, WgpuDisplay, input::DummyInput,
+};
+use std::{
+    cell::RefCell,
+    env, fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Instant,
 };
-use std::{env, fs, path::Path};
 
 /// Default screen width in pixels.
 const DEFAULT_WIDTH: usize = 320;
@@ -42,6 +54,34 @@ fn dump_ascii_frame(buffer: &[u8], width: usize, height: usize) -> String {
     out
 }
 
+/// Whether `path` names a JSON file.
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Read a recording, as JSON when the file name ends in `.json`.
+fn load_recording(path: &Path) -> Result<Recording, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    if is_json(path) {
+        #[cfg(feature = "json")]
+        return Recording::from_json(&String::from_utf8_lossy(&bytes)).map_err(|e| e.to_string());
+        #[cfg(not(feature = "json"))]
+        return Err("JSON recordings need the `json` feature".into());
+    }
+    Recording::from_bytes(&bytes).map_err(|e| format!("{e:?}"))
+}
+
+/// Write a recording, as JSON when the file name ends in `.json`.
+fn save_recording(path: &Path, recording: &Recording) -> io::Result<()> {
+    if is_json(path) {
+        #[cfg(feature = "json")]
+        return fs::write(path, recording.to_json());
+        #[cfg(not(feature = "json"))]
+        return Err(io::Error::other("JSON recordings need the `json` feature"));
+    }
+    fs::write(path, recording.to_bytes())
+}
+
 fn main() {
     let mut width = DEFAULT_WIDTH;
     let mut height = DEFAULT_HEIGHT;
@@ -51,6 +91,13 @@ fn main() {
     let mut show_qr = false;
     let mut show_png = false;
     let mut show_gif = false;
+    let mut assets = PathBuf::from(".");
+    let mut layout = None;
+    let mut font = None;
+    #[cfg(unix)]
+    let mut socket = hot_reload::default_socket_path();
+    let mut record_path = None;
+    let mut replay_path = None;
 
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
@@ -75,6 +122,23 @@ fn main() {
             show_png = true;
         } else if arg == "--gif" {
             show_gif = true;
+        } else if let Some(dir) = arg.strip_prefix("--assets=") {
+            assets = PathBuf::from(dir);
+        } else if let Some(name) = arg.strip_prefix("--layout=") {
+            layout = Some(name.to_string());
+        } else if let Some(name) = arg.strip_prefix("--font=") {
+            font = Some(name.to_string());
+        } else if let Some(path) = arg.strip_prefix("--reload-socket=") {
+            #[cfg(unix)]
+            {
+                socket = PathBuf::from(path);
+            }
+            #[cfg(not(unix))]
+            eprintln!("Ignoring --reload-socket={path}: hot reload needs Unix sockets");
+        } else if let Some(path) = arg.strip_prefix("--record=") {
+            record_path = Some(PathBuf::from(path));
+        } else if let Some(path) = arg.strip_prefix("--replay=") {
+            replay_path = Some(PathBuf::from(path));
         } else if arg.starts_with("--headless") {
             if let Some(eq) = arg.split_once('=') {
                 headless_path = Some(eq.1.to_string());
@@ -119,14 +183,94 @@ fn main() {
             .push(common_demo::build_gif_demo(width as u32, height as u32));
     }
 
-    let frame_cb = {
+    let mut replay = match replay_path.map(|path| load_recording(&path)) {
+        Some(Ok(recording)) => Some(Replay::new(recording)),
+        Some(Err(e)) => {
+            eprintln!("Failed to read recording: {e}");
+            return;
+        }
+        None => None,
+    };
+    // Dispatch an event and apply the tree changes it schedules.
+    let dispatcher = || {
         let root = root.clone();
+        let pending = pending.clone();
+        let to_remove = to_remove.clone();
+        move |evt: &InputEvent| {
+            root.borrow_mut().dispatch_event(evt);
+            flush_pending(&root, &pending, &to_remove);
+        }
+    };
+    if headless || path.is_some() {
+        // Frame dumps show the state at the end of the recording.
+        if let Some(mut replay) = replay.take() {
+            flush_pending(&root, &pending, &to_remove);
+            replay.finish();
+            let dispatch = dispatcher();
+            while let Some(evt) = replay.poll() {
+                dispatch(&evt);
+            }
+        }
+    }
+
+    let start = Instant::now();
+    // Window events are pushed to the dispatcher rather than polled, so the
+    // recorder wraps no device and is fed through `record` and `tick`.
+    let recorder = record_path.is_some().then(|| {
+        Rc::new(RefCell::new(Recorder::new(DummyInput, move || {
+            start.elapsed().as_millis() as u32
+        })))
+    });
+    let interactive = !headless && path.is_none();
+
+    let mut live = (layout.is_some() || font.is_some()).then(|| Live::new(assets, layout, font));
+    #[cfg(unix)]
+    if let Some(live) = &mut live
+        && !headless
+    {
+        live.listen(&socket);
+    }
+
+    let mut frame_cb = {
+        let root = root.clone();
+        let dispatch = dispatcher();
+        let recorder = recorder.clone();
+        let mut started = None;
+        let mut next_tick = 0;
         move |frame: &mut [u8], w: usize, h: usize| {
+            if let Some(replay) = &mut replay {
+                let start = *started.get_or_insert_with(Instant::now);
+                replay.set_time(start.elapsed().as_millis() as u32);
+                while let Some(evt) = replay.poll() {
+                    dispatch(&evt);
+                }
+            } else if interactive {
+                // Replays carry their own ticks; live runs tick once per
+                // default animation period and record each tick.
+                let now = start.elapsed().as_millis() as u32;
+                while next_tick <= now {
+                    let tick = match &recorder {
+                        Some(recorder) => recorder.borrow_mut().tick(),
+                        None => InputEvent::Tick,
+                    };
+                    dispatch(&tick);
+                    next_tick += DEFAULT_TICK_MS;
+                }
+            }
+            let font = live.as_mut().and_then(|live| {
+                if let Some(screen) = live.update() {
+                    root.borrow_mut().children = vec![screen];
+                }
+                live.font()
+            });
             if use_wgpi {
                 let mut blitter = WgpuBlitter::new();
                 let surface = Surface::new(frame, w * 4, PixelFmt::Argb8888, w as u32, h as u32);
                 let mut renderer: BlitterRenderer<'_, WgpuBlitter, 16> =
                     BlitterRenderer::new(&mut blitter, surface);
+                if let Some(font) = font {
+                    renderer = renderer.with_font(font);
+                }
                 root.borrow().draw(&mut renderer);
                 renderer.planner().add(BlitRect {
                     x: 0,
@@ -139,6 +283,9 @@ fn main() {
                 let surface = Surface::new(frame, w * 4, PixelFmt::Argb8888, w as u32, h as u32);
                 let mut renderer: BlitterRenderer<'_, CpuBlitter, 16> =
                     BlitterRenderer::new(&mut blitter, surface);
+                if let Some(font) = font {
+                    renderer = renderer.with_font(font);
+                }
                 root.borrow().draw(&mut renderer);
                 renderer.planner().add(BlitRect {
                     x: 0,
@@ -169,12 +316,18 @@ fn main() {
 
     flush_pending(&root, &pending, &to_remove);
     WgpuDisplay::new(width, height).run(frame_cb, {
-        let root = root.clone();
-        let pending = pending.clone();
-        let to_remove = to_remove.clone();
+        let dispatch = dispatcher();
+        let recorder = recorder.clone();
         move |evt: InputEvent| {
-            root.borrow_mut().dispatch_event(&evt);
-            flush_pending(&root, &pending, &to_remove);
+            if let Some(recorder) = &recorder {
+                recorder.borrow_mut().record(&evt);
+            }
+            dispatch(&evt);
         }
     });
+    if let (Some(path), Some(recorder)) = (record_path, recorder)
+        && let Err(e) = save_recording(&path, recorder.borrow().recording())
+    {
+        eprintln!("Failed to write recording: {e}");
+    }
 }
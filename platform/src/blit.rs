@@ -3,6 +3,8 @@
 //! These types describe pixel surfaces and operations that can be
 //! accelerated by different platform implementations.
 
+#[cfg(feature = "fontdue")]
+use alloc::collections::BTreeMap;
 #[cfg(any(
     feature = "png",
     feature = "jpeg",
@@ -14,11 +16,10 @@
     feature = "nes",
     feature = "pinyin",
     feature = "fatfs",
+    feature = "fontdue",
     test,
 ))]
 use alloc::vec::Vec;
-#[cfg(feature = "fontdue")]
-use alloc::{collections::BTreeMap, vec};
 use bitflags::bitflags;
 use heapless::Vec as HVec;
 #[cfg(feature = "fontdue")]
@@ -26,6 +27,8 @@ use rlvgl_core::fontdue::{Metrics, line_metrics, rasterize_glyph};
 use rlvgl_core::renderer::Renderer;
 use rlvgl_core::widget::{Color, Rect as WidgetRect};
 
+use crate::cpu_blitter::CpuBlitter;
+
 #[cfg(feature = "fontdue")]
 const FONT_DATA: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
 
@@ -54,6 +57,36 @@ pub enum PixelFmt {
     A8,
     /// 4-bit alpha-only format.
     A4,
+    /// 1-bit palette indices, eight pixels per byte, leftmost in the high bit.
+    I1,
+    /// 2-bit palette indices, four pixels per byte, leftmost in the high bits.
+    I2,
+    /// 4-bit palette indices, two pixels per byte, leftmost in the high nibble.
+    I4,
+    /// 8-bit palette indices.
+    I8,
+}
+
+impl PixelFmt {
+    /// Number of bits used to store one pixel.
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFmt::Argb8888 => 32,
+            PixelFmt::Rgb565 => 16,
+            PixelFmt::L8 | PixelFmt::A8 | PixelFmt::I8 => 8,
+            PixelFmt::A4 | PixelFmt::I4 => 4,
+            PixelFmt::I2 => 2,
+            PixelFmt::I1 => 1,
+        }
+    }
+
+    /// Whether pixels are indices into the surface palette.
+    pub fn is_indexed(self) -> bool {
+        matches!(
+            self,
+            PixelFmt::I1 | PixelFmt::I2 | PixelFmt::I4 | PixelFmt::I8
+        )
+    }
 }
 
 /// Rectangular region within a surface.
@@ -69,6 +102,22 @@ pub struct Rect {
     pub h: u32,
 }
 
+impl Rect {
+    /// Overlap of `self` and `other`, or `None` if they do not overlap.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x0 = self.x.max(other.x);
+        let y0 = self.y.max(other.y);
+        let x1 = (self.x as i64 + self.w as i64).min(other.x as i64 + other.w as i64);
+        let y1 = (self.y as i64 + self.h as i64).min(other.y as i64 + other.h as i64);
+        (x1 > x0 as i64 && y1 > y0 as i64).then(|| Rect {
+            x: x0,
+            y: y0,
+            w: (x1 - x0 as i64) as u32,
+            h: (y1 - y0 as i64) as u32,
+        })
+    }
+}
+
 /// A pixel buffer with dimension and format metadata.
 pub struct Surface<'a> {
     /// Underlying pixel storage.
@@ -81,6 +130,14 @@ pub struct Surface<'a> {
     pub width: u32,
     /// Height of the surface in pixels.
     pub height: u32,
+    /// ARGB8888 colors looked up by indexed formats; empty otherwise.
+    pub palette: &'a [u32],
+    /// Ordered-dither colors written to indexed formats instead of picking
+    /// the nearest palette entry.
+    pub dither: bool,
+    /// Region outside of which blitters leave a destination untouched, in
+    /// addition to the surface bounds.
+    pub clip: Option<Rect>,
 }
 
 impl<'a> Surface<'a> {
@@ -98,8 +155,104 @@ impl<'a> Surface<'a> {
             format,
             width,
             height,
+            palette: &[],
+            dither: false,
+            clip: None,
         }
     }
+
+    /// Attach the palette used to decode indexed pixel formats.
+    pub fn with_palette(mut self, palette: &'a [u32]) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Dither colors written to indexed formats, for example to show
+    /// images on 1-bit panels.
+    pub fn with_dither(mut self) -> Self {
+        self.dither = true;
+        self
+    }
+
+    /// Restrict drawing into this surface to `clip`.
+    pub fn with_clip(mut self, clip: Rect) -> Self {
+        self.clip = Some(clip);
+        self
+    }
+
+    /// Area of the surface that can be read, ignoring the clip rect.
+    pub fn bounds(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            w: self.width,
+            h: self.height,
+        }
+    }
+
+    /// Area that drawing may change: the surface bounds narrowed to the
+    /// clip rect, or `None` if nothing can be drawn.
+    pub fn drawable(&self) -> Option<Rect> {
+        let bounds = self.bounds();
+        bounds.intersect(&self.clip.unwrap_or(bounds))
+    }
+
+    /// Clip `area` to the drawable region of this surface.
+    pub fn clip_area(&self, area: Rect) -> Option<Rect> {
+        self.drawable()?.intersect(&area)
+    }
+}
+
+/// Clip a copy of `src_area` from `src` to `dst_pos` in `dst`.
+///
+/// Both the source area and its destination are cut to the parts inside
+/// their surfaces and the destination clip rect. Returns the remaining
+/// source area and where it lands, or `None` if nothing is left to copy.
+pub fn clip_blit(
+    src: &Surface,
+    src_area: Rect,
+    dst: &Surface,
+    dst_pos: (i32, i32),
+) -> Option<(Rect, (i32, i32))> {
+    // Offsets between far apart coordinates can exceed `i32`.
+    let shift = |pos: i32, to: i32, from: i32| {
+        (pos as i64 + to as i64 - from as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    };
+    let area = src.bounds().intersect(&src_area)?;
+    let target = Rect {
+        x: shift(dst_pos.0, area.x, src_area.x),
+        y: shift(dst_pos.1, area.y, src_area.y),
+        w: area.w,
+        h: area.h,
+    };
+    let clipped = dst.clip_area(target)?;
+    let area = Rect {
+        x: shift(area.x, clipped.x, target.x),
+        y: shift(area.y, clipped.y, target.y),
+        w: clipped.w,
+        h: clipped.h,
+    };
+    Some((area, (clipped.x, clipped.y)))
+}
+
+/// How transformed blits sample the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Filter {
+    /// Take the closest source pixel, keeping hard pixel edges.
+    #[default]
+    Nearest,
+    /// Interpolate between the four closest source pixels.
+    Bilinear,
+}
+
+/// Rotation applied by [`Blitter::blit_rotated`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotate {
+    /// Point that stays in place, relative to the top-left corner of the
+    /// source area. `(w / 2, h / 2)` turns the area around its center.
+    pub pivot: (i32, i32),
+    /// Clockwise rotation in degrees.
+    pub angle: i32,
 }
 
 bitflags! {
@@ -114,10 +267,25 @@ bitflags! {
         const BLEND = 0b0100;
         /// Ability to convert between pixel formats.
         const PFC = 0b1000;
+        /// Hardware scaled blits.
+        const SCALE = 0b1_0000;
+        /// Hardware rotated blits.
+        const ROTATE = 0b10_0000;
+        /// Hardware blending with a global opacity.
+        const OPACITY = 0b100_0000;
+        /// Hardware recoloring of alpha masks.
+        const RECOLOR = 0b1000_0000;
     }
 }
 
 /// Trait implemented by types capable of transferring pixel data.
+///
+/// Every operation clips against the bounds of both surfaces and the clip
+/// rect of the destination, so areas may extend past the edges or have
+/// negative coordinates. See [`clip_blit`].
+///
+/// The transformed operations fall back to [`CpuBlitter`] unless a blitter
+/// accelerates them, which it reports through [`caps`](Self::caps).
 pub trait Blitter {
     /// Return the capabilities supported by this blitter.
     fn caps(&self) -> BlitCaps;
@@ -130,6 +298,61 @@ pub trait Blitter {
 
     /// Blend pixels from `src` over `dst`.
     fn blend(&mut self, src: &Surface, src_area: Rect, dst: &mut Surface, dst_pos: (i32, i32));
+
+    /// Copy `src_area` of `src` stretched to cover `dst_area` of `dst`.
+    fn blit_scaled(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_area: Rect,
+        filter: Filter,
+    ) {
+        CpuBlitter.blit_scaled(src, src_area, dst, dst_area, filter);
+    }
+
+    /// Copy `src_area` of `src` turned by `rotate`.
+    ///
+    /// Unrotated, the area would be copied to `dst_pos` like
+    /// [`blit`](Self::blit). Destination pixels outside the rotated area
+    /// are left untouched.
+    fn blit_rotated(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        rotate: Rotate,
+        filter: Filter,
+    ) {
+        CpuBlitter.blit_rotated(src, src_area, dst, dst_pos, rotate, filter);
+    }
+
+    /// Blend pixels from `src` over `dst` with their alpha scaled by
+    /// `opacity`, where 255 is [`blend`](Self::blend) and 0 draws nothing.
+    fn blend_opacity(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        opacity: u8,
+    ) {
+        CpuBlitter.blend_opacity(src, src_area, dst, dst_pos, opacity);
+    }
+
+    /// Blend `color` over `dst` through the alpha of `mask`, usually an
+    /// [`A8`](PixelFmt::A8) or [`A4`](PixelFmt::A4) glyph or icon.
+    fn blend_mask(
+        &mut self,
+        mask: &Surface,
+        mask_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        color: u32,
+    ) {
+        CpuBlitter.blend_mask(mask, mask_area, dst, dst_pos, color);
+    }
 }
 
 /// Collects dirty rectangles for a frame and optionally coalesces them.
@@ -195,6 +418,8 @@ pub struct BlitterRenderer<'a, B: Blitter, const N: usize> {
     scratch: Option<Vec<u8>>,
     #[cfg(feature = "fontdue")]
     glyph_cache: BTreeMap<GlyphKey, (Metrics, Vec<u8>)>,
+    #[cfg(feature = "fontdue")]
+    font: &'a [u8],
 }
 
 impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
@@ -220,9 +445,19 @@ impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
             scratch: None,
             #[cfg(feature = "fontdue")]
             glyph_cache: BTreeMap::new(),
+            #[cfg(feature = "fontdue")]
+            font: FONT_DATA,
         }
     }
 
+    /// Draw widget text with the TTF/OTF font `data` instead of the
+    /// built-in DejaVu Sans.
+    #[cfg(feature = "fontdue")]
+    pub fn with_font(mut self, data: &'a [u8]) -> Self {
+        self.font = data;
+        self
+    }
+
     /// Access the internal dirty-rectangle planner.
     pub fn planner(&mut self) -> &mut BlitPlanner<N> {
         &mut self.planner
@@ -457,7 +692,7 @@ impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
                 size: px.to_bits(),
                 ch,
             };
-            let (metrics, bitmap) = {
+            let (metrics, mut bitmap) = {
                 let entry = self
                     .glyph_cache
                     .entry(key)
@@ -470,21 +705,10 @@ impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
                 x_cursor += metrics.advance_width.round() as i32;
                 continue;
             }
-            let mut argb = vec![0u8; (w * h * 4) as usize];
-            for y in 0..h {
-                for x in 0..w {
-                    let alpha = bitmap[(y) as usize * metrics.width + x as usize];
-                    let idx = ((y * w + x) * 4) as usize;
-                    argb[idx] = (color.0 as u16 * alpha as u16 / 255) as u8;
-                    argb[idx + 1] = (color.1 as u16 * alpha as u16 / 255) as u8;
-                    argb[idx + 2] = (color.2 as u16 * alpha as u16 / 255) as u8;
-                    argb[idx + 3] = alpha;
-                }
-            }
-            let src = Surface::new(
-                argb.as_mut_slice(),
-                (w * 4) as usize,
-                PixelFmt::Argb8888,
+            let mask = Surface::new(
+                bitmap.as_mut_slice(),
+                w as usize,
+                PixelFmt::A8,
                 w as u32,
                 h as u32,
             );
@@ -492,8 +716,8 @@ impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
                 x_cursor + metrics.xmin,
                 baseline - ascent - metrics.ymin - (h - 1),
             );
-            self.blitter.blend(
-                &src,
+            self.blitter.blend_mask(
+                &mask,
                 Rect {
                     x: 0,
                     y: 0,
@@ -502,6 +726,7 @@ impl<'a, B: Blitter, const N: usize> BlitterRenderer<'a, B, N> {
                 },
                 &mut self.surface,
                 dst_pos,
+                color.to_argb8888(),
             );
             self.planner.add(Rect {
                 x: dst_pos.0,
@@ -532,8 +757,8 @@ impl<B: Blitter, const N: usize> Renderer for BlitterRenderer<'_, B, N> {
         let r = Rect {
             x: rect.x,
             y: rect.y,
-            w: rect.width as u32,
-            h: rect.height as u32,
+            w: rect.width.max(0) as u32,
+            h: rect.height.max(0) as u32,
         };
         self.planner.add(r);
         self.blitter.fill(&mut self.surface, r, color.to_argb8888());
@@ -543,7 +768,7 @@ impl<B: Blitter, const N: usize> Renderer for BlitterRenderer<'_, B, N> {
         #[cfg(feature = "fontdue")]
         {
             const PX: f32 = 16.0;
-            BlitterRenderer::draw_text(self, position, text, color, FONT_DATA, PX);
+            BlitterRenderer::draw_text(self, position, text, color, self.font, PX);
         }
         #[cfg(not(feature = "fontdue"))]
         {
@@ -867,6 +1092,7 @@ mod pinyin_tests {
 mod fatfs_tests {
     use super::*;
     use crate::cpu_blitter::CpuBlitter;
+    use alloc::vec;
     use fatfs::{FileSystem, FormatVolumeOptions, FsOptions};
     use fscommon::BufStream;
     use std::io::{Cursor, Seek, SeekFrom, Write};
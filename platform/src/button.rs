@@ -0,0 +1,84 @@
+//! Hardware buttons that press points on the screen.
+//!
+//! Each button is bound to a screen position. Pressing it reports
+//! [`Event::PointerDown`] there and releasing it [`Event::PointerUp`], as
+//! if the screen were touched at that point. Devices without a touchscreen
+//! can place buttons next to the display and draw the matching on-screen
+//! widgets above them.
+
+use alloc::collections::VecDeque;
+use embedded_hal::digital::InputPin;
+use rlvgl_core::event::Event;
+
+use crate::input::{Debouncer, InputDevice};
+
+/// Buttons on their own input pins, each bound to a screen point.
+pub struct ScreenButtons<P, const N: usize> {
+    pins: [P; N],
+    points: [(i32, i32); N],
+    debouncers: [Debouncer; N],
+    active_low: bool,
+    queue: VecDeque<Event>,
+}
+
+impl<P: InputPin, const N: usize> ScreenButtons<P, N> {
+    /// Buttons that connect `pins` to ground when pressed, pressing the
+    /// point with the same index.
+    pub fn new(pins: [P; N], points: [(i32, i32); N]) -> Self {
+        Self {
+            pins,
+            points,
+            debouncers: [Debouncer::default(); N],
+            active_low: true,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Set how many polls a change must last for.
+    pub fn with_debounce(mut self, polls: u8) -> Self {
+        self.debouncers = [Debouncer::new(polls); N];
+        self
+    }
+
+    /// Treat high pins as pressed.
+    pub fn with_active_high(mut self) -> Self {
+        self.active_low = false;
+        self
+    }
+
+    /// Move the point pressed by button `index`, for example after the
+    /// layout changes.
+    pub fn set_point(&mut self, index: usize, point: (i32, i32)) {
+        if let Some(p) = self.points.get_mut(index) {
+            *p = point;
+        }
+    }
+
+    /// Release the pins.
+    pub fn release(self) -> [P; N] {
+        self.pins
+    }
+}
+
+impl<P: InputPin, const N: usize> InputDevice for ScreenButtons<P, N> {
+    fn poll(&mut self) -> Option<Event> {
+        if self.queue.is_empty() {
+            for ((pin, &(x, y)), debouncer) in self
+                .pins
+                .iter_mut()
+                .zip(&self.points)
+                .zip(&mut self.debouncers)
+            {
+                let Ok(high) = pin.is_high() else {
+                    continue;
+                };
+                match debouncer.update(high != self.active_low) {
+                    Some(true) => self.queue.push_back(Event::PointerDown { x, y }),
+                    Some(false) => self.queue.push_back(Event::PointerUp { x, y }),
+                    None => {}
+                }
+            }
+        }
+        self.queue.pop_front()
+    }
+}
@@ -0,0 +1,201 @@
+//! Affine touch calibration.
+//!
+//! Resistive panels report raw ADC readings, and capacitive controllers can
+//! be mounted with swapped or mirrored axes. A [`Calibration`] maps raw
+//! samples to screen coordinates:
+//!
+//! ```text
+//! x = a·rx + b·ry + c
+//! y = d·rx + e·ry + f
+//! ```
+//!
+//! The coefficients are fitted from three or more touches on known targets.
+//! Three points solve the mapping exactly. Five points fit it by least
+//! squares, which averages out inaccurate touches. A fitted calibration can
+//! be persisted with [`Calibration::to_bytes`].
+
+use rlvgl_core::event::Event;
+
+use crate::input::{InputDevice, map_position};
+
+/// Mapping from raw touch samples to screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    coefficients: [f32; 6],
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Calibration {
+    /// Passes samples through unchanged.
+    pub const IDENTITY: Self = Self {
+        coefficients: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    };
+
+    /// Size of [`to_bytes`](Self::to_bytes) output.
+    pub const BYTES: usize = 24;
+
+    /// Calibration with coefficients `[a, b, c, d, e, f]`.
+    pub const fn from_coefficients(coefficients: [f32; 6]) -> Self {
+        Self { coefficients }
+    }
+
+    /// Coefficients `[a, b, c, d, e, f]`.
+    pub fn coefficients(&self) -> [f32; 6] {
+        self.coefficients
+    }
+
+    /// Scale raw readings spanning `raw_min..=raw_max` on each axis to a
+    /// `width` × `height` screen, without sampling touches.
+    pub fn from_range(raw_min: (i32, i32), raw_max: (i32, i32), width: i32, height: i32) -> Self {
+        let sx = (width - 1) as f32 / (raw_max.0 - raw_min.0) as f32;
+        let sy = (height - 1) as f32 / (raw_max.1 - raw_min.1) as f32;
+        Self::from_coefficients([
+            sx,
+            0.0,
+            -sx * raw_min.0 as f32,
+            0.0,
+            sy,
+            -sy * raw_min.1 as f32,
+        ])
+    }
+
+    /// Screen positions to ask the user to touch: the four corners inset by
+    /// an eighth of the screen, then the center. Three-point calibration
+    /// uses the first three.
+    pub fn targets(width: i32, height: i32) -> [(i32, i32); 5] {
+        let (l, t) = (width / 8, height / 8);
+        let (r, b) = (width - 1 - l, height - 1 - t);
+        [(l, t), (r, t), (r, b), (l, b), (width / 2, height / 2)]
+    }
+
+    /// Fit the mapping from `raw` samples taken on the `screen` targets
+    /// with the same index.
+    ///
+    /// Returns `None` unless there are at least three samples, one per
+    /// target, and they do not all lie on one line.
+    pub fn from_points(raw: &[(i32, i32)], screen: &[(i32, i32)]) -> Option<Self> {
+        if raw.len() < 3 || raw.len() != screen.len() {
+            return None;
+        }
+        // Normal equations of the least-squares fit, shared by both axes.
+        let mut m = [[0f64; 3]; 3];
+        let mut vx = [0f64; 3];
+        let mut vy = [0f64; 3];
+        for (&(rx, ry), &(sx, sy)) in raw.iter().zip(screen) {
+            let row = [rx as f64, ry as f64, 1.0];
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] += row[i] * row[j];
+                }
+                vx[i] += row[i] * sx as f64;
+                vy[i] += row[i] * sy as f64;
+            }
+        }
+        let [a, b, c] = solve(m, vx)?;
+        let [d, e, f] = solve(m, vy)?;
+        Some(Self::from_coefficients(
+            [a, b, c, d, e, f].map(|v| v as f32),
+        ))
+    }
+
+    /// Screen position of the raw sample `(x, y)`.
+    pub fn apply(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        let [a, b, c, d, e, f] = self.coefficients;
+        let (x, y) = (x as f32, y as f32);
+        (round(a * x + b * y + c), round(d * x + e * y + f))
+    }
+
+    /// Little-endian encoding for storage in flash or on disk.
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let mut out = [0; Self::BYTES];
+        for (chunk, c) in out.chunks_exact_mut(4).zip(self.coefficients) {
+            chunk.copy_from_slice(&c.to_le_bytes());
+        }
+        out
+    }
+
+    /// Decode [`to_bytes`](Self::to_bytes) output. Returns `None` if any
+    /// coefficient is not finite, as when reading erased flash.
+    pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Option<Self> {
+        let mut coefficients = [0f32; 6];
+        for (c, chunk) in coefficients.iter_mut().zip(bytes.chunks_exact(4)) {
+            *c = f32::from_le_bytes(chunk.try_into().ok()?);
+        }
+        coefficients
+            .iter()
+            .all(|c| c.is_finite())
+            .then_some(Self { coefficients })
+    }
+}
+
+/// Solve `m · x = v` by Cramer's rule.
+fn solve(m: [[f64; 3]; 3], v: [f64; 3]) -> Option<[f64; 3]> {
+    let det = det3(m);
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    let mut out = [0.0; 3];
+    for (col, o) in out.iter_mut().enumerate() {
+        let mut mc = m;
+        for row in 0..3 {
+            mc[row][col] = v[row];
+        }
+        *o = det3(mc) / det;
+    }
+    Some(out)
+}
+
+fn det3(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn round(v: f32) -> i32 {
+    if v < 0.0 {
+        (v - 0.5) as i32
+    } else {
+        (v + 0.5) as i32
+    }
+}
+
+/// Input device reporting pointer and touch samples mapped through a
+/// [`Calibration`].
+pub struct CalibratedInput<I> {
+    inner: I,
+    calibration: Calibration,
+}
+
+impl<I: InputDevice> CalibratedInput<I> {
+    /// Wrap `inner`, whose pointer events carry raw samples.
+    pub fn new(inner: I, calibration: Calibration) -> Self {
+        Self { inner, calibration }
+    }
+
+    /// Use a newly fitted calibration.
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Calibration in use.
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Wrapped device, which yields raw samples while calibrating.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<I: InputDevice> InputDevice for CalibratedInput<I> {
+    fn poll(&mut self) -> Option<Event> {
+        let event = self.inner.poll()?;
+        Some(map_position(event, |x, y| self.calibration.apply((x, y))))
+    }
+}
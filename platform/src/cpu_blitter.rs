@@ -3,19 +3,55 @@
 //! Provides a pure software implementation of the [`Blitter`] trait used for
 //! testing and as a baseline on platforms lacking acceleration.
 
-use crate::blit::{BlitCaps, Blitter, PixelFmt, Rect, Surface};
+use rlvgl_core::math::{TRIG_SCALE, cos, sin};
+
+use crate::blit::{BlitCaps, Blitter, Filter, PixelFmt, Rect, Rotate, Surface, clip_blit};
+use crate::mono::bayer;
+use crate::orientation::Orientation;
 
 /// Blitter that performs all operations on the CPU using scalar loops.
 pub struct CpuBlitter;
 
 impl CpuBlitter {
     fn pixel_size(fmt: PixelFmt) -> usize {
-        match fmt {
-            PixelFmt::Argb8888 => 4,
-            PixelFmt::Rgb565 => 2,
-            PixelFmt::L8 | PixelFmt::A8 => 1,
-            PixelFmt::A4 => 1,
+        fmt.bits_per_pixel().div_ceil(8)
+    }
+
+    /// Byte offset of pixel `(x, y)` and, for formats packing several pixels
+    /// per byte, the right shift of its bits within that byte.
+    fn locate(surf: &Surface, x: i32, y: i32) -> (usize, u32) {
+        let bits = surf.format.bits_per_pixel();
+        let bit = x as usize * bits;
+        let shift = if bits < 8 { 8 - bits - bit % 8 } else { 0 };
+        (y as usize * surf.stride + bit / 8, shift as u32)
+    }
+
+    /// Index of the palette entry closest to `color`.
+    fn nearest_index(palette: &[u32], color: u32) -> u8 {
+        let dist = |c: u32| {
+            (0..4)
+                .map(|i| {
+                    let d = ((c >> (i * 8)) & 0xff) as i32 - ((color >> (i * 8)) & 0xff) as i32;
+                    (d * d) as u32
+                })
+                .sum::<u32>()
+        };
+        (0..palette.len())
+            .min_by_key(|&i| dist(palette[i]))
+            .unwrap_or(0) as u8
+    }
+
+    /// Shift the channels of `color` by the ordered-dither offset at
+    /// `(x, y)`, scaled to the gap between levels of `fmt`.
+    fn dither(fmt: PixelFmt, color: u32, x: i32, y: i32) -> u32 {
+        let steps = (1i32 << fmt.bits_per_pixel()) - 1;
+        let bias = (bayer(x, y) as i32 - 128) / steps;
+        let mut out = color & 0xff00_0000;
+        for shift in [0, 8, 16] {
+            let c = ((color >> shift) & 0xff) as i32;
+            out |= ((c + bias).clamp(0, 255) as u32) << shift;
         }
+        out
     }
 
     fn argb8888_to_rgb565(c: u32) -> u16 {
@@ -33,11 +69,7 @@ impl CpuBlitter {
     }
 
     fn read_pixel(surf: &Surface, x: i32, y: i32) -> u32 {
-        let bpp = Self::pixel_size(surf.format);
-        let offset = match surf.format {
-            PixelFmt::A4 => (y as usize * surf.stride) + (x as usize / 2),
-            _ => (y as usize * surf.stride) + (x as usize * bpp),
-        };
+        let (offset, shift) = Self::locate(surf, x, y);
         match surf.format {
             PixelFmt::Argb8888 => {
                 let bytes: [u8; 4] = surf.buf[offset..offset + 4].try_into().unwrap();
@@ -56,47 +88,50 @@ impl CpuBlitter {
                 a << 24
             }
             PixelFmt::A4 => {
-                let byte = surf.buf[offset];
-                let nib = if x & 1 == 0 { byte >> 4 } else { byte & 0x0f } as u32;
+                let nib = ((surf.buf[offset] >> shift) & 0x0f) as u32;
                 let a = (nib << 4) | nib;
                 a << 24
             }
+            PixelFmt::I1 | PixelFmt::I2 | PixelFmt::I4 | PixelFmt::I8 => {
+                let mask = (1u16 << surf.format.bits_per_pixel()) - 1;
+                let index = (surf.buf[offset] >> shift) as u16 & mask;
+                surf.palette.get(index as usize).copied().unwrap_or(0)
+            }
         }
     }
 
     fn write_pixel(surf: &mut Surface, x: i32, y: i32, color: u32) {
-        let bpp = Self::pixel_size(surf.format);
-        let offset = match surf.format {
-            PixelFmt::A4 => (y as usize * surf.stride) + (x as usize / 2),
-            _ => (y as usize * surf.stride) + (x as usize * bpp),
-        };
-        match surf.format {
+        let (offset, shift) = Self::locate(surf, x, y);
+        let packed = match surf.format {
             PixelFmt::Argb8888 => {
                 surf.buf[offset..offset + 4].copy_from_slice(&color.to_le_bytes());
+                return;
             }
             PixelFmt::Rgb565 => {
                 let c = Self::argb8888_to_rgb565(color);
                 surf.buf[offset..offset + 2].copy_from_slice(&c.to_le_bytes());
+                return;
             }
             PixelFmt::L8 => {
                 let r = (color >> 16) & 0xff;
                 let g = (color >> 8) & 0xff;
                 let b = color & 0xff;
-                surf.buf[offset] = ((r + g + b) / 3) as u8;
-            }
-            PixelFmt::A8 => {
-                surf.buf[offset] = (color >> 24) as u8;
+                ((r + g + b) / 3) as u8
             }
-            PixelFmt::A4 => {
-                let a = (color >> 24) as u8 >> 4;
-                let byte = &mut surf.buf[offset];
-                if x & 1 == 0 {
-                    *byte = (*byte & 0x0f) | (a << 4);
+            PixelFmt::A8 => (color >> 24) as u8,
+            PixelFmt::A4 => (color >> 24) as u8 >> 4,
+            PixelFmt::I1 | PixelFmt::I2 | PixelFmt::I4 | PixelFmt::I8 => {
+                let color = if surf.dither {
+                    Self::dither(surf.format, color, x, y)
                 } else {
-                    *byte = (*byte & 0xf0) | a;
-                }
+                    color
+                };
+                Self::nearest_index(surf.palette, color)
             }
-        }
+        };
+        let mask = (((1u16 << surf.format.bits_per_pixel()) - 1) << shift) as u8;
+        let byte = &mut surf.buf[offset];
+        *byte = (*byte & !mask) | ((packed << shift) & mask);
     }
 
     fn blend_pixel(src: u32, dst: u32) -> u32 {
@@ -113,14 +148,121 @@ impl CpuBlitter {
         let b = (sb * sa + db * inv) / 255;
         0xff00_0000 | (r << 16) | (g << 8) | b
     }
+
+    /// Mix each channel of `a` and `b`, `t` / 256 of the way to `b`.
+    fn lerp(a: u32, b: u32, t: u32) -> u32 {
+        let mut out = 0;
+        for shift in [0, 8, 16, 24] {
+            let ca = (a >> shift) & 0xff;
+            let cb = (b >> shift) & 0xff;
+            out |= ((ca * (256 - t) + cb * t) >> 8) << shift;
+        }
+        out
+    }
+
+    /// Color of `src` at `(u, v)`, in 1/256 pixels from the top-left corner
+    /// of the surface, or `None` if that falls outside `area`.
+    fn sample(src: &Surface, area: Rect, u: i64, v: i64, filter: Filter) -> Option<u32> {
+        let (x, y) = (u >> 8, v >> 8);
+        let (x0, y0) = (area.x as i64, area.y as i64);
+        let (x1, y1) = (x0 + area.w as i64 - 1, y0 + area.h as i64 - 1);
+        if x < x0 || y < y0 || x > x1 || y > y1 {
+            return None;
+        }
+        match filter {
+            Filter::Nearest => Some(Self::read_pixel(src, x as i32, y as i32)),
+            Filter::Bilinear => {
+                // Pixel centers sit half a pixel from their corners; edge
+                // pixels are repeated rather than mixed with transparency.
+                let (u, v) = (u - 128, v - 128);
+                let (left, top) = (u >> 8, v >> 8);
+                let xs = [left.clamp(x0, x1) as i32, (left + 1).clamp(x0, x1) as i32];
+                let ys = [top.clamp(y0, y1) as i32, (top + 1).clamp(y0, y1) as i32];
+                let (fx, fy) = ((u & 0xff) as u32, (v & 0xff) as u32);
+                let row = |y| {
+                    Self::lerp(
+                        Self::read_pixel(src, xs[0], y),
+                        Self::read_pixel(src, xs[1], y),
+                        fx,
+                    )
+                };
+                Some(Self::lerp(row(ys[0]), row(ys[1]), fy))
+            }
+        }
+    }
+
+    /// Copy `src_area` of `src` into `dst` with `orientation` applied, so a
+    /// frame drawn in logical coordinates can be shown on a rotated or
+    /// mirrored panel.
+    ///
+    /// `dst_pos` is the top-left corner of the transformed area, whose width
+    /// and height are swapped for quarter turns. Pixels outside `src`, `dst`
+    /// or the destination clip rect are skipped.
+    pub fn blit_oriented(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        orientation: Orientation,
+    ) {
+        let Some(readable) = src.bounds().intersect(&src_area) else {
+            return;
+        };
+        let size = (
+            src_area.w.min(i32::MAX as u32) as i32,
+            src_area.h.min(i32::MAX as u32) as i32,
+        );
+        let (w, h) = if orientation.swaps_axes() {
+            (size.1, size.0)
+        } else {
+            size
+        };
+        let target = Rect {
+            x: dst_pos.0,
+            y: dst_pos.1,
+            w: w as u32,
+            h: h as u32,
+        };
+        let Some(clipped) = dst.clip_area(target) else {
+            return;
+        };
+        let inside = |x: i64, y: i64| {
+            x >= readable.x as i64
+                && y >= readable.y as i64
+                && x < readable.x as i64 + readable.w as i64
+                && y < readable.y as i64 + readable.h as i64
+        };
+        for y in clipped.y..clipped.y + clipped.h as i32 {
+            for x in clipped.x..clipped.x + clipped.w as i32 {
+                // Both offsets lie inside the target, so they fit in `i32`.
+                let offset = (
+                    (x as i64 - dst_pos.0 as i64) as i32,
+                    (y as i64 - dst_pos.1 as i64) as i32,
+                );
+                let (col, row) = orientation.to_logical(offset, size);
+                let (sx, sy) = (
+                    src_area.x as i64 + col as i64,
+                    src_area.y as i64 + row as i64,
+                );
+                if inside(sx, sy) {
+                    let px = Self::read_pixel(src, sx as i32, sy as i32);
+                    Self::write_pixel(dst, x, y, px);
+                }
+            }
+        }
+    }
 }
 
 impl Blitter for CpuBlitter {
     fn caps(&self) -> BlitCaps {
-        BlitCaps::FILL | BlitCaps::BLIT | BlitCaps::BLEND | BlitCaps::PFC
+        BlitCaps::all()
     }
 
     fn fill(&mut self, dst: &mut Surface, area: Rect, color: u32) {
+        let Some(area) = dst.clip_area(area) else {
+            return;
+        };
         match dst.format {
             PixelFmt::Argb8888 => {
                 for row in 0..area.h as i32 {
@@ -152,7 +294,15 @@ impl Blitter for CpuBlitter {
     }
 
     fn blit(&mut self, src: &Surface, src_area: Rect, dst: &mut Surface, dst_pos: (i32, i32)) {
-        if src.format == dst.format {
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
+        // Packed formats may start mid-byte, so only whole-byte pixels with
+        // the same palette are copied row by row.
+        if src.format == dst.format
+            && src.format.bits_per_pixel() >= 8
+            && src.palette == dst.palette
+        {
             let bpp = Self::pixel_size(src.format);
             for row in 0..src_area.h as i32 {
                 let src_start =
@@ -185,11 +335,153 @@ impl Blitter for CpuBlitter {
     }
 
     fn blend(&mut self, src: &Surface, src_area: Rect, dst: &mut Surface, dst_pos: (i32, i32)) {
+        self.blend_opacity(src, src_area, dst, dst_pos, 255);
+    }
+
+    fn blit_scaled(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_area: Rect,
+        filter: Filter,
+    ) {
+        let (Some(readable), Some(target)) =
+            (src.bounds().intersect(&src_area), dst.clip_area(dst_area))
+        else {
+            return;
+        };
+        // Map destination pixel centers, in half pixels, into the source.
+        let map = |pos: i32, start: i32, src_start: i32, src_len: u32, dst_len: u32| {
+            let half = 2 * (pos as i64 - start as i64) + 1;
+            src_start as i64 * 256 + half * src_len as i64 * 128 / dst_len as i64
+        };
+        for y in target.y..target.y + target.h as i32 {
+            let v = map(y, dst_area.y, src_area.y, src_area.h, dst_area.h);
+            for x in target.x..target.x + target.w as i32 {
+                let u = map(x, dst_area.x, src_area.x, src_area.w, dst_area.w);
+                if let Some(px) = Self::sample(src, readable, u, v, filter) {
+                    Self::write_pixel(dst, x, y, px);
+                }
+            }
+        }
+    }
+
+    fn blit_rotated(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        rotate: Rotate,
+        filter: Filter,
+    ) {
+        let Some(readable) = src.bounds().intersect(&src_area) else {
+            return;
+        };
+        let angle = rotate.angle.rem_euclid(360);
+        let (sin, cos) = (sin(angle) as i64, cos(angle) as i64);
+        let scale = TRIG_SCALE as i64;
+        let pivot_src = (
+            src_area.x as i64 + rotate.pivot.0 as i64,
+            src_area.y as i64 + rotate.pivot.1 as i64,
+        );
+        let pivot_dst = (
+            dst_pos.0 as i64 + rotate.pivot.0 as i64,
+            dst_pos.1 as i64 + rotate.pivot.1 as i64,
+        );
+
+        // Bounding box of the turned corners of the readable area.
+        let (mut min, mut max) = ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN));
+        for (cx, cy) in [
+            (readable.x as i64, readable.y as i64),
+            (readable.x as i64 + readable.w as i64, readable.y as i64),
+            (readable.x as i64, readable.y as i64 + readable.h as i64),
+            (
+                readable.x as i64 + readable.w as i64,
+                readable.y as i64 + readable.h as i64,
+            ),
+        ] {
+            let (dx, dy) = (cx - pivot_src.0, cy - pivot_src.1);
+            let x = dx * cos - dy * sin;
+            let y = dx * sin + dy * cos;
+            min = (
+                min.0.min(x.div_euclid(scale)),
+                min.1.min(y.div_euclid(scale)),
+            );
+            max = (
+                max.0.max(-(-x).div_euclid(scale)),
+                max.1.max(-(-y).div_euclid(scale)),
+            );
+        }
+        let clamp = |v: i64| v.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let Some(target) = dst.clip_area(Rect {
+            x: clamp(pivot_dst.0 + min.0),
+            y: clamp(pivot_dst.1 + min.1),
+            w: (max.0 - min.0) as u32,
+            h: (max.1 - min.1) as u32,
+        }) else {
+            return;
+        };
+
+        for y in target.y..target.y + target.h as i32 {
+            for x in target.x..target.x + target.w as i32 {
+                // Pixel center relative to the pivot, in half pixels, turned
+                // back into the source.
+                let dx = 2 * (x as i64 - pivot_dst.0) + 1;
+                let dy = 2 * (y as i64 - pivot_dst.1) + 1;
+                let u = pivot_src.0 * 256 + ((dx * cos + dy * sin) * 128).div_euclid(scale);
+                let v = pivot_src.1 * 256 + ((dy * cos - dx * sin) * 128).div_euclid(scale);
+                if let Some(px) = Self::sample(src, readable, u, v, filter) {
+                    Self::write_pixel(dst, x, y, px);
+                }
+            }
+        }
+    }
+
+    fn blend_opacity(
+        &mut self,
+        src: &Surface,
+        src_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        opacity: u8,
+    ) {
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
+        if opacity == 0 {
+            return;
+        }
         for row in 0..src_area.h as i32 {
             for col in 0..src_area.w as i32 {
                 let s = Self::read_pixel(src, src_area.x + col, src_area.y + row);
+                let a = (s >> 24) * opacity as u32 / 255;
+                let d = Self::read_pixel(dst, dst_pos.0 + col, dst_pos.1 + row);
+                let out = Self::blend_pixel((s & 0x00ff_ffff) | (a << 24), d);
+                Self::write_pixel(dst, dst_pos.0 + col, dst_pos.1 + row, out);
+            }
+        }
+    }
+
+    fn blend_mask(
+        &mut self,
+        mask: &Surface,
+        mask_area: Rect,
+        dst: &mut Surface,
+        dst_pos: (i32, i32),
+        color: u32,
+    ) {
+        let Some((mask_area, dst_pos)) = clip_blit(mask, mask_area, dst, dst_pos) else {
+            return;
+        };
+        let alpha = color >> 24;
+        for row in 0..mask_area.h as i32 {
+            for col in 0..mask_area.w as i32 {
+                let coverage = Self::read_pixel(mask, mask_area.x + col, mask_area.y + row) >> 24;
+                let a = coverage * alpha / 255;
                 let d = Self::read_pixel(dst, dst_pos.0 + col, dst_pos.1 + row);
-                let out = Self::blend_pixel(s, d);
+                let out = Self::blend_pixel((color & 0x00ff_ffff) | (a << 24), d);
                 Self::write_pixel(dst, dst_pos.0 + col, dst_pos.1 + row, out);
             }
         }
@@ -287,4 +579,426 @@ mod tests {
             assert_eq!(u32::from_le_bytes(chunk.try_into().unwrap()), 0xff800000);
         }
     }
+
+    #[test]
+    fn blit_indexed_to_argb8888() {
+        let palette = [0xff000000u32, 0xffff0000, 0xff00ff00, 0x800000ff];
+        // Two rows of 2-bit indices: 0 1 2 3 / 3 2 1 0.
+        let mut src_buf = [0b0001_1011u8, 0b1110_0100];
+        let mut dst_buf = [0u8; 32];
+        let src = Surface::new(&mut src_buf, 1, PixelFmt::I2, 4, 2).with_palette(&palette);
+        let mut dst = Surface::new(&mut dst_buf, 16, PixelFmt::Argb8888, 4, 2);
+        let mut blit = CpuBlitter;
+        blit.blit(
+            &src,
+            Rect {
+                x: 1,
+                y: 0,
+                w: 3,
+                h: 2,
+            },
+            &mut dst,
+            (0, 0),
+        );
+        let px = |i: usize| u32::from_le_bytes(dst_buf[i * 4..i * 4 + 4].try_into().unwrap());
+        assert_eq!([px(0), px(1), px(2)], [palette[1], palette[2], palette[3]]);
+        assert_eq!([px(4), px(5), px(6)], [palette[2], palette[1], palette[0]]);
+    }
+
+    #[test]
+    fn blit_oriented_rotates_and_mirrors() {
+        use crate::orientation::Rotation;
+
+        // 3x2 L8 image: 1 2 3 / 4 5 6.
+        let mut src_buf = [1u8, 2, 3, 4, 5, 6];
+        let src = Surface::new(&mut src_buf, 3, PixelFmt::L8, 3, 2);
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 3,
+            h: 2,
+        };
+        let mut blit = CpuBlitter;
+        let mut rotate = |orientation: Orientation| {
+            let mut dst_buf = [0u8; 6];
+            let (w, h) = if orientation.swaps_axes() {
+                (2, 3)
+            } else {
+                (3, 2)
+            };
+            let mut dst = Surface::new(&mut dst_buf, w as usize, PixelFmt::L8, w, h);
+            blit.blit_oriented(&src, area, &mut dst, (0, 0), orientation);
+            dst_buf
+        };
+        assert_eq!(
+            rotate(Orientation::new(Rotation::Deg90)),
+            [4, 1, 5, 2, 6, 3]
+        );
+        assert_eq!(
+            rotate(Orientation::new(Rotation::Deg180)),
+            [6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            rotate(Orientation::new(Rotation::Deg270).flipped(true, false)),
+            [1, 4, 2, 5, 3, 6]
+        );
+    }
+
+    #[test]
+    fn blit_oriented_clips_partly_offscreen_areas() {
+        use crate::orientation::Rotation;
+
+        // 3x2 L8 image: 1 2 3 / 4 5 6, turned into 2x3: 4 1 / 5 2 / 6 3.
+        let mut src_buf = [1u8, 2, 3, 4, 5, 6];
+        let src = Surface::new(&mut src_buf, 3, PixelFmt::L8, 3, 2);
+        let beyond_src = Rect {
+            x: 0,
+            y: 0,
+            w: 3,
+            h: 4,
+        };
+        let mut dst_buf = [0u8; 9];
+        let clip = Rect {
+            x: 0,
+            y: 0,
+            w: 3,
+            h: 2,
+        };
+        let mut dst = Surface::new(&mut dst_buf, 3, PixelFmt::L8, 3, 3).with_clip(clip);
+        let mut blit = CpuBlitter;
+        let quarter = Orientation::new(Rotation::Deg90);
+        // The missing source rows are the two left columns of the result.
+        blit.blit_oriented(&src, beyond_src, &mut dst, (-1, 1), quarter);
+        blit.blit_oriented(&src, beyond_src, &mut dst, (i32::MAX, i32::MIN), quarter);
+        assert_eq!(dst_buf, [0, 0, 0, 0, 4, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_i1_dithers_mid_gray() {
+        let palette = [0xff000000u32, 0xffffffff];
+        let mut buf = [0u8; 4];
+        let mut surf = Surface::new(&mut buf, 1, PixelFmt::I1, 4, 4)
+            .with_palette(&palette)
+            .with_dither();
+        let mut blit = CpuBlitter;
+        blit.fill(
+            &mut surf,
+            Rect {
+                x: 0,
+                y: 0,
+                w: 4,
+                h: 4,
+            },
+            0xff808080,
+        );
+        let lit: u32 = buf.iter().map(|b| (b >> 4).count_ones()).sum();
+        assert_eq!(lit, 8);
+    }
+
+    #[test]
+    fn fill_indexed_picks_nearest_entry() {
+        let palette = [0xff000000u32, 0xffffffff];
+        let mut buf = [0u8; 2];
+        let mut surf = Surface::new(&mut buf, 1, PixelFmt::I1, 8, 2).with_palette(&palette);
+        let mut blit = CpuBlitter;
+        blit.fill(
+            &mut surf,
+            Rect {
+                x: 2,
+                y: 1,
+                w: 3,
+                h: 1,
+            },
+            0xffe0e0e0,
+        );
+        assert_eq!(buf, [0, 0b0011_1000]);
+    }
+
+    #[test]
+    fn fill_clips_to_bounds_and_clip_rect() {
+        let mut buf = [0u8; 16];
+        let mut surf = Surface::new(&mut buf, 4, PixelFmt::L8, 4, 4);
+        let mut blit = CpuBlitter;
+        blit.fill(
+            &mut surf,
+            Rect {
+                x: -2,
+                y: -2,
+                w: 4,
+                h: 4,
+            },
+            0xffffffff,
+        );
+        blit.fill(
+            &mut surf,
+            Rect {
+                x: 3,
+                y: 3,
+                w: u32::MAX,
+                h: u32::MAX,
+            },
+            0xff404040,
+        );
+        let clip = Rect {
+            x: 2,
+            y: 2,
+            w: 5,
+            h: 1,
+        };
+        let mut surf = Surface::new(&mut buf, 4, PixelFmt::L8, 4, 4).with_clip(clip);
+        blit.fill(
+            &mut surf,
+            Rect {
+                x: 0,
+                y: 0,
+                w: 100,
+                h: 100,
+            },
+            0xff808080,
+        );
+        assert_eq!(
+            buf,
+            [255, 255, 0, 0, 255, 255, 0, 0, 0, 0, 128, 128, 0, 0, 0, 64]
+        );
+    }
+
+    #[test]
+    fn blit_and_blend_clip_partly_offscreen_areas() {
+        let mut src_buf = [1u8, 2, 3, 4, 5, 6];
+        let src = Surface::new(&mut src_buf, 3, PixelFmt::L8, 3, 2);
+        let mut dst_buf = [0u8; 9];
+        let mut dst = Surface::new(&mut dst_buf, 3, PixelFmt::L8, 3, 3);
+        let mut blit = CpuBlitter;
+        let beyond_src = Rect {
+            x: 0,
+            y: 0,
+            w: 10,
+            h: 10,
+        };
+        blit.blit(&src, beyond_src, &mut dst, (-1, 2));
+        blit.blend(&src, beyond_src, &mut dst, (5, -5));
+        blit.blend(&src, beyond_src, &mut dst, (i32::MAX, i32::MIN));
+        assert_eq!(dst_buf, [0, 0, 0, 0, 0, 0, 2, 3, 0]);
+
+        // The source starts 2^31 pixels before its surface, so the visible
+        // part lands 2^31 pixels after the destination position.
+        let mut dst_buf = [0u8; 9];
+        let mut dst = Surface::new(&mut dst_buf, 3, PixelFmt::L8, 3, 3);
+        let everything = Rect {
+            x: i32::MIN,
+            y: i32::MIN,
+            w: u32::MAX,
+            h: u32::MAX,
+        };
+        blit.blit(&src, everything, &mut dst, (i32::MIN, i32::MIN + 1));
+        assert_eq!(dst_buf, [0, 0, 0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn blit_scaled_nearest_and_bilinear() {
+        let mut src_buf = [10u8, 20, 30, 40];
+        let src = Surface::new(&mut src_buf, 2, PixelFmt::L8, 2, 2);
+        let mut dst_buf = [0u8; 16];
+        let mut dst = Surface::new(&mut dst_buf, 4, PixelFmt::L8, 4, 4);
+        let mut blit = CpuBlitter;
+        let src_area = Rect {
+            x: 0,
+            y: 0,
+            w: 2,
+            h: 2,
+        };
+        let dst_area = Rect {
+            x: 0,
+            y: 0,
+            w: 4,
+            h: 4,
+        };
+        blit.blit_scaled(&src, src_area, &mut dst, dst_area, Filter::Nearest);
+        assert_eq!(
+            dst_buf,
+            [
+                10, 10, 20, 20, 10, 10, 20, 20, 30, 30, 40, 40, 30, 30, 40, 40
+            ]
+        );
+
+        let mut src_buf = [0u8, 255];
+        let src = Surface::new(&mut src_buf, 2, PixelFmt::L8, 2, 1);
+        let mut dst_buf = [0u8; 4];
+        let mut dst = Surface::new(&mut dst_buf, 4, PixelFmt::L8, 4, 1);
+        let src_area = Rect {
+            x: 0,
+            y: 0,
+            w: 2,
+            h: 1,
+        };
+        let dst_area = Rect {
+            x: -4,
+            y: 0,
+            w: 8,
+            h: 1,
+        };
+        // Only the right half of the stretched area is on the surface.
+        blit.blit_scaled(&src, src_area, &mut dst, dst_area, Filter::Bilinear);
+        assert_eq!(dst_buf, [159, 223, 255, 255]);
+    }
+
+    #[test]
+    fn blit_rotated_turns_around_the_pivot() {
+        let mut src_buf = [1u8, 2, 3, 4];
+        let src = Surface::new(&mut src_buf, 2, PixelFmt::L8, 2, 2);
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 2,
+            h: 2,
+        };
+        let mut dst_buf = [0u8; 4];
+        let mut dst = Surface::new(&mut dst_buf, 2, PixelFmt::L8, 2, 2);
+        let mut blit = CpuBlitter;
+        let quarter = Rotate {
+            pivot: (1, 1),
+            angle: 90,
+        };
+        blit.blit_rotated(&src, area, &mut dst, (0, 0), quarter, Filter::Nearest);
+        assert_eq!(dst_buf, [3, 1, 4, 2]);
+        // Angles are taken modulo a full turn, even next to `i32::MAX`.
+        for angle in [-270, i32::MAX - 37] {
+            let mut dst_buf = [0u8; 4];
+            let mut dst = Surface::new(&mut dst_buf, 2, PixelFmt::L8, 2, 2);
+            let turn = Rotate { angle, ..quarter };
+            blit.blit_rotated(&src, area, &mut dst, (0, 0), turn, Filter::Nearest);
+            assert_eq!(dst_buf, [3, 1, 4, 2]);
+        }
+
+        let mut src_buf = [255u8; 16];
+        let src = Surface::new(&mut src_buf, 4, PixelFmt::L8, 4, 4);
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 4,
+            h: 4,
+        };
+        let mut dst_buf = [0u8; 64];
+        let mut dst = Surface::new(&mut dst_buf, 8, PixelFmt::L8, 8, 8);
+        let eighth = Rotate {
+            pivot: (2, 2),
+            angle: 45,
+        };
+        blit.blit_rotated(&src, area, &mut dst, (2, 2), eighth, Filter::Bilinear);
+        blit.blit_rotated(&src, area, &mut dst, (-3, 6), eighth, Filter::Nearest);
+        blit.blit_rotated(&src, area, &mut dst, (-100, -100), eighth, Filter::Nearest);
+        let px = |x: usize, y: usize| dst_buf[y * 8 + x];
+        // The square's corners turn into the tips of a diamond.
+        assert_eq!([px(4, 4), px(4, 2), px(2, 4)], [255, 255, 255]);
+        assert_eq!([px(2, 2), px(5, 2), px(0, 0)], [0, 0, 0]);
+        assert_eq!([px(0, 7), px(1, 7)], [255, 0]);
+    }
+
+    #[test]
+    fn blend_opacity_scales_source_alpha() {
+        let mut src_buf = 0xffff0000u32.to_le_bytes();
+        let src = Surface::new(&mut src_buf, 4, PixelFmt::Argb8888, 1, 1);
+        let mut dst_buf = 0xff000000u32.to_le_bytes();
+        let mut dst = Surface::new(&mut dst_buf, 4, PixelFmt::Argb8888, 1, 1);
+        let mut blit = CpuBlitter;
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 1,
+            h: 1,
+        };
+        blit.blend_opacity(&src, area, &mut dst, (0, 0), 0);
+        assert_eq!(u32::from_le_bytes(dst_buf), 0xff000000);
+        let mut dst = Surface::new(&mut dst_buf, 4, PixelFmt::Argb8888, 1, 1);
+        blit.blend_opacity(&src, area, &mut dst, (0, 0), 128);
+        assert_eq!(u32::from_le_bytes(dst_buf), 0xff800000);
+    }
+
+    #[test]
+    fn blend_mask_tints_alpha_masks() {
+        // A4 coverage of 0xF and 0x8.
+        let mut mask_buf = [0xf8u8];
+        let mask = Surface::new(&mut mask_buf, 1, PixelFmt::A4, 2, 1);
+        let mut dst_buf = [0u8; 8];
+        for px in dst_buf.chunks_exact_mut(4) {
+            px.copy_from_slice(&0xff000000u32.to_le_bytes());
+        }
+        let mut dst = Surface::new(&mut dst_buf, 8, PixelFmt::Argb8888, 2, 1);
+        let mut blit = CpuBlitter;
+        let area = Rect {
+            x: 0,
+            y: 0,
+            w: 2,
+            h: 1,
+        };
+        blit.blend_mask(&mask, area, &mut dst, (0, 0), 0xff00ff00);
+        let px = |i: usize| u32::from_le_bytes(dst_buf[i * 4..i * 4 + 4].try_into().unwrap());
+        assert_eq!([px(0), px(1)], [0xff00ff00, 0xff008800]);
+    }
+
+    #[test]
+    fn transformed_ops_fall_back_to_the_cpu() {
+        /// Blitter accelerating only the required operations.
+        struct Basic(CpuBlitter);
+
+        impl Blitter for Basic {
+            fn caps(&self) -> BlitCaps {
+                BlitCaps::FILL | BlitCaps::BLIT | BlitCaps::BLEND
+            }
+
+            fn fill(&mut self, dst: &mut Surface, area: Rect, color: u32) {
+                self.0.fill(dst, area, color);
+            }
+
+            fn blit(
+                &mut self,
+                src: &Surface,
+                src_area: Rect,
+                dst: &mut Surface,
+                dst_pos: (i32, i32),
+            ) {
+                self.0.blit(src, src_area, dst, dst_pos);
+            }
+
+            fn blend(
+                &mut self,
+                src: &Surface,
+                src_area: Rect,
+                dst: &mut Surface,
+                dst_pos: (i32, i32),
+            ) {
+                self.0.blend(src, src_area, dst, dst_pos);
+            }
+        }
+
+        let mut blit = Basic(CpuBlitter);
+        assert!(!blit.caps().contains(BlitCaps::SCALE));
+        assert!(
+            CpuBlitter
+                .caps()
+                .contains(BlitCaps::SCALE | BlitCaps::RECOLOR)
+        );
+        let mut src_buf = [7u8];
+        let src = Surface::new(&mut src_buf, 1, PixelFmt::L8, 1, 1);
+        let mut dst_buf = [0u8; 4];
+        let mut dst = Surface::new(&mut dst_buf, 2, PixelFmt::L8, 2, 2);
+        blit.blit_scaled(
+            &src,
+            Rect {
+                x: 0,
+                y: 0,
+                w: 1,
+                h: 1,
+            },
+            &mut dst,
+            Rect {
+                x: 0,
+                y: 0,
+                w: 2,
+                h: 2,
+            },
+            Filter::Bilinear,
+        );
+        assert_eq!(dst_buf, [7; 4]);
+    }
 }
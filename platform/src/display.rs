@@ -1,8 +1,11 @@
 //! Traits and helpers for display drivers.
 use alloc::vec;
 use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
 use rlvgl_core::widget::{Color, Rect};
 
+use crate::orientation::Orientation;
+
 /// Trait implemented by display drivers.
 pub trait DisplayDriver {
     /// Flush a rectangular region of pixels to the display.
@@ -10,6 +13,71 @@ pub trait DisplayDriver {
 
     /// Optional vertical sync hook.
     fn vsync(&mut self) {}
+
+    /// Whether the pixels passed to the last [`flush`](Self::flush) have
+    /// been consumed.
+    ///
+    /// Drivers that return from `flush` while a DMA transfer still reads the
+    /// buffer report its completion here, usually through a
+    /// [`FlushSignal`]. [`Display`](crate::refresh::Display) does not touch
+    /// that buffer again until this returns `true`. Synchronous drivers keep
+    /// the default.
+    fn flush_ready(&mut self) -> bool {
+        true
+    }
+
+    /// Rotate and mirror the panel in hardware.
+    ///
+    /// Returns `true` if the controller applies `orientation` itself, after
+    /// which [`flush`](Self::flush) receives logical areas. Returning `false`
+    /// (the default) makes [`Display`](crate::refresh::Display) transform
+    /// pixels in software and flush panel areas.
+    fn set_orientation(&mut self, _orientation: Orientation) -> bool {
+        false
+    }
+}
+
+/// Flush-complete flag shared between a driver and its transfer-complete
+/// interrupt.
+///
+/// The driver calls [`start`](Self::start) when it hands a buffer to the
+/// hardware, the interrupt handler calls [`complete`](Self::complete), and
+/// [`DisplayDriver::flush_ready`] returns [`is_ready`](Self::is_ready). Only
+/// atomic loads and stores are used, so the flag also works on cores
+/// without compare-and-swap.
+#[derive(Debug)]
+pub struct FlushSignal {
+    busy: AtomicBool,
+}
+
+impl FlushSignal {
+    /// Create a signal with no transfer in flight.
+    pub const fn new() -> Self {
+        Self {
+            busy: AtomicBool::new(false),
+        }
+    }
+
+    /// Mark a transfer as started.
+    pub fn start(&self) {
+        self.busy.store(true, Ordering::Release);
+    }
+
+    /// Mark the transfer as complete. Safe to call from an interrupt.
+    pub fn complete(&self) {
+        self.busy.store(false, Ordering::Release);
+    }
+
+    /// Whether no transfer is in flight.
+    pub fn is_ready(&self) -> bool {
+        !self.busy.load(Ordering::Acquire)
+    }
+}
+
+impl Default for FlushSignal {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Dummy headless driver used for tests.
@@ -3,7 +3,8 @@
 //! Provides hardware-accelerated fills, pixel format conversions, and blending
 //! using the DMA2D engine.
 
-use crate::blit::{BlitCaps, Blitter, PixelFmt, Rect, Surface};
+use crate::blit::{BlitCaps, Blitter, PixelFmt, Rect, Surface, clip_blit};
+use crate::cpu_blitter::CpuBlitter;
 #[cfg(feature = "dma2d")]
 use stm32h7::stm32h747::DMA2D;
 
@@ -26,24 +27,72 @@ impl Dma2dBlitter {
         Self { regs }
     }
 
-    fn pixel_size(fmt: PixelFmt) -> usize {
+    /// DMA2D input color mode for `fmt`.
+    ///
+    /// The engine has no 1- or 2-bit CLUT modes, so [`PixelFmt::I1`] and
+    /// [`PixelFmt::I2`] sources are left to the CPU blitter.
+    fn input_mode(fmt: PixelFmt) -> Option<u32> {
         match fmt {
-            PixelFmt::Argb8888 => 4,
-            PixelFmt::Rgb565 => 2,
-            PixelFmt::L8 | PixelFmt::A8 => 1,
-            PixelFmt::A4 => 1,
+            PixelFmt::Argb8888 => Some(0),
+            PixelFmt::Rgb565 => Some(2),
+            PixelFmt::L8 | PixelFmt::I8 => Some(5),
+            PixelFmt::I4 => Some(8),
+            PixelFmt::A8 => Some(9),
+            PixelFmt::A4 => Some(10),
+            PixelFmt::I1 | PixelFmt::I2 => None,
         }
     }
 
-    /// Translate [`PixelFmt`] to the DMA2D color mode value.
-    fn dma2d_fmt(fmt: PixelFmt) -> u32 {
+    /// DMA2D output color mode for `fmt`.
+    ///
+    /// The engine only writes direct-color pixels, so indexed, luminance and
+    /// alpha destinations are left to the CPU blitter.
+    fn output_mode(fmt: PixelFmt) -> Option<u32> {
         match fmt {
-            PixelFmt::Argb8888 => 0,
-            PixelFmt::Rgb565 => 2,
-            PixelFmt::L8 => 5,
-            PixelFmt::A8 => 9,
-            PixelFmt::A4 => 10,
+            PixelFmt::Argb8888 => Some(0),
+            PixelFmt::Rgb565 => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Input and output modes for copying `src_area` of `src` into `dst`.
+    ///
+    /// Returns `None` when the transfer must fall back to the CPU: either
+    /// format is unsupported, or a 4-bit source row does not start on a byte
+    /// boundary or spans an odd number of pixels.
+    fn modes(src: &Surface, src_area: Rect, dst: &Surface) -> Option<(u32, u32)> {
+        let input = Self::input_mode(src.format)?;
+        let output = Self::output_mode(dst.format)?;
+        if src.format.bits_per_pixel() == 4 && (src_area.x % 2 != 0 || src_area.w % 2 != 0) {
+            return None;
+        }
+        Some((input, output))
+    }
+
+    /// Byte offset of pixel `(x, y)`; `x` must start on a byte boundary.
+    fn pixel_offset(surface: &Surface, x: i32, y: i32) -> usize {
+        y as usize * surface.stride + x as usize * surface.format.bits_per_pixel() / 8
+    }
+
+    /// Line offset in pixels from the end of a `w` pixel row to the next row.
+    fn line_offset(surface: &Surface, w: u32) -> u32 {
+        let row = surface.stride * 8 / surface.format.bits_per_pixel();
+        (row - w as usize) as u32
+    }
+
+    /// Foreground PFC value for `src` in `mode`, loading its palette into
+    /// the CLUT before the transfer when the format is indexed.
+    fn fg_pfc(&mut self, src: &Surface, mode: u32) -> u32 {
+        if !src.format.is_indexed() || src.palette.is_empty() {
+            return mode;
+        }
+        unsafe {
+            self.regs
+                .fgcmar
+                .write(|w| w.bits(src.palette.as_ptr() as u32));
         }
+        let size = (src.palette.len().min(256) - 1) as u32;
+        mode | Self::PFCCR_CLUT_START | (size << 8)
     }
 
     const CR_START: u32 = 1 << 0;
@@ -51,6 +100,7 @@ impl Dma2dBlitter {
     const CR_MODE_M2M_BLEND: u32 = 0x0002_0000;
     const CR_MODE_R2M: u32 = 0x0003_0000;
     const CR_TCIE: u32 = 1 << 9;
+    const PFCCR_CLUT_START: u32 = 1 << 5;
     const ISR_TC: u32 = 1;
 
     /// Enable the transfer-complete interrupt.
@@ -87,19 +137,16 @@ impl Dma2dBlitter {
         self.clear_complete();
     }
 
-    fn start_fill(&mut self, dst: &mut Surface, area: Rect, color: u32) {
-        let bpp = Self::pixel_size(dst.format);
-        let start = unsafe {
-            dst.buf
-                .as_mut_ptr()
-                .add((area.y as usize * dst.stride) + (area.x as usize * bpp))
-        } as u32;
-        let line_offset = dst.stride - (area.w as usize * bpp);
+    fn start_fill(&mut self, dst: &mut Surface, area: Rect, color: u32, mode: u32) {
+        let offset = Self::pixel_offset(dst, area.x, area.y);
+        let start = dst.buf[offset..].as_mut_ptr() as u32;
+        let line_offset = Self::line_offset(dst, area.w);
 
         unsafe {
             self.regs.omar.write(|w| w.bits(start));
+            self.regs.opfccr.write(|w| w.bits(mode));
             self.regs.ocolr.write(|w| w.bits(color));
-            self.regs.oor.write(|w| w.bits(line_offset as u32));
+            self.regs.oor.write(|w| w.bits(line_offset));
             self.regs
                 .nlr
                 .write(|w| w.bits(((area.h as u32) << 16) | area.w as u32));
@@ -116,35 +163,23 @@ impl Dma2dBlitter {
         src_area: Rect,
         dst: &mut Surface,
         dst_pos: (i32, i32),
+        (input, output): (u32, u32),
     ) {
-        let src_bpp = Self::pixel_size(src.format);
-        let dst_bpp = Self::pixel_size(dst.format);
-
-        let src_start = unsafe {
-            src.buf
-                .as_ptr()
-                .add((src_area.y as usize * src.stride) + (src_area.x as usize * src_bpp))
-        } as u32;
-        let dst_start = unsafe {
-            dst.buf
-                .as_mut_ptr()
-                .add((dst_pos.1 as usize * dst.stride) + (dst_pos.0 as usize * dst_bpp))
-        } as u32;
-
-        let src_offset = src.stride - (src_area.w as usize * src_bpp);
-        let dst_offset = dst.stride - (src_area.w as usize * dst_bpp);
+        let src_at = Self::pixel_offset(src, src_area.x, src_area.y);
+        let dst_at = Self::pixel_offset(dst, dst_pos.0, dst_pos.1);
+        let src_start = src.buf[src_at..].as_ptr() as u32;
+        let dst_start = dst.buf[dst_at..].as_mut_ptr() as u32;
+        let src_offset = Self::line_offset(src, src_area.w);
+        let dst_offset = Self::line_offset(dst, src_area.w);
 
+        let fg_pfc = self.fg_pfc(src, input);
         unsafe {
             self.regs.fgmar.write(|w| w.bits(src_start));
-            self.regs.fgor.write(|w| w.bits(src_offset as u32));
-            self.regs
-                .fgpfccr
-                .write(|w| w.bits(Self::dma2d_fmt(src.format)));
+            self.regs.fgor.write(|w| w.bits(src_offset));
+            self.regs.fgpfccr.write(|w| w.bits(fg_pfc));
             self.regs.omar.write(|w| w.bits(dst_start));
-            self.regs.oor.write(|w| w.bits(dst_offset as u32));
-            self.regs
-                .opfccr
-                .write(|w| w.bits(Self::dma2d_fmt(dst.format)));
+            self.regs.oor.write(|w| w.bits(dst_offset));
+            self.regs.opfccr.write(|w| w.bits(output));
             self.regs
                 .nlr
                 .write(|w| w.bits(((src_area.h as u32) << 16) | src_area.w as u32));
@@ -163,37 +198,26 @@ impl Dma2dBlitter {
         src_area: Rect,
         dst: &mut Surface,
         dst_pos: (i32, i32),
+        (input, output): (u32, u32),
     ) {
-        let src_bpp = Self::pixel_size(src.format);
-        let dst_bpp = Self::pixel_size(dst.format);
-
-        let fg_start = unsafe {
-            src.buf
-                .as_ptr()
-                .add((src_area.y as usize * src.stride) + (src_area.x as usize * src_bpp))
-        } as u32;
-        let bg_start = unsafe {
-            dst.buf
-                .as_mut_ptr()
-                .add((dst_pos.1 as usize * dst.stride) + (dst_pos.0 as usize * dst_bpp))
-        } as u32;
-
-        let fg_offset = src.stride - (src_area.w as usize * src_bpp);
-        let bg_offset = dst.stride - (src_area.w as usize * dst_bpp);
+        let src_at = Self::pixel_offset(src, src_area.x, src_area.y);
+        let dst_at = Self::pixel_offset(dst, dst_pos.0, dst_pos.1);
+        let fg_start = src.buf[src_at..].as_ptr() as u32;
+        let bg_start = dst.buf[dst_at..].as_mut_ptr() as u32;
+        let fg_offset = Self::line_offset(src, src_area.w);
+        let bg_offset = Self::line_offset(dst, src_area.w);
 
+        let fg_pfc = self.fg_pfc(src, input);
         unsafe {
             self.regs.fgmar.write(|w| w.bits(fg_start));
-            self.regs.fgor.write(|w| w.bits(fg_offset as u32));
-            self.regs
-                .fgpfccr
-                .write(|w| w.bits(Self::dma2d_fmt(src.format)));
+            self.regs.fgor.write(|w| w.bits(fg_offset));
+            self.regs.fgpfccr.write(|w| w.bits(fg_pfc));
             self.regs.bgmar.write(|w| w.bits(bg_start));
-            self.regs.bgor.write(|w| w.bits(bg_offset as u32));
-            self.regs
-                .bgpfccr
-                .write(|w| w.bits(Self::dma2d_fmt(dst.format)));
+            self.regs.bgor.write(|w| w.bits(bg_offset));
+            self.regs.bgpfccr.write(|w| w.bits(output));
             self.regs.omar.write(|w| w.bits(bg_start));
-            self.regs.oor.write(|w| w.bits(bg_offset as u32));
+            self.regs.oor.write(|w| w.bits(bg_offset));
+            self.regs.opfccr.write(|w| w.bits(output));
             self.regs
                 .nlr
                 .write(|w| w.bits(((src_area.h as u32) << 16) | src_area.w as u32));
@@ -214,17 +238,38 @@ impl Blitter for Dma2dBlitter {
     }
 
     fn fill(&mut self, dst: &mut Surface, area: Rect, color: u32) {
-        self.start_fill(dst, area, color);
+        let Some(area) = dst.clip_area(area) else {
+            return;
+        };
+        let Some(mode) = Self::output_mode(dst.format) else {
+            CpuBlitter.fill(dst, area, color);
+            return;
+        };
+        self.start_fill(dst, area, color, mode);
         self.wait();
     }
 
     fn blit(&mut self, src: &Surface, src_area: Rect, dst: &mut Surface, dst_pos: (i32, i32)) {
-        self.start_blit(src, src_area, dst, dst_pos);
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
+        let Some(modes) = Self::modes(src, src_area, dst) else {
+            CpuBlitter.blit(src, src_area, dst, dst_pos);
+            return;
+        };
+        self.start_blit(src, src_area, dst, dst_pos, modes);
         self.wait();
     }
 
     fn blend(&mut self, src: &Surface, src_area: Rect, dst: &mut Surface, dst_pos: (i32, i32)) {
-        self.start_blend(src, src_area, dst, dst_pos);
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
+        let Some(modes) = Self::modes(src, src_area, dst) else {
+            CpuBlitter.blend(src, src_area, dst, dst_pos);
+            return;
+        };
+        self.start_blend(src, src_area, dst, dst_pos, modes);
         self.wait();
     }
 }
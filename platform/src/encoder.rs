@@ -0,0 +1,192 @@
+//! Quadrature rotary encoder with an optional push button.
+//!
+//! The A and B contacts are sampled on every [`InputDevice::poll`] and
+//! decoded with a transition table, which ignores the invalid transitions
+//! that contact bounce produces. Poll at least as often as the fastest
+//! expected transition, typically every millisecond or two. Turns are
+//! reported as [`Event::Encoder`] steps and the push button as
+//! [`Key::Enter`] presses.
+
+use embedded_hal::digital::{ErrorType, InputPin};
+use rlvgl_core::event::{Event, Key};
+
+use crate::input::{Debouncer, InputDevice};
+
+/// Quarter steps by previous and current `A << 1 | B` state, positive when
+/// A leads B.
+const TRANSITIONS: [i8; 16] = [0, -1, 1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, 1, -1, 0];
+
+/// Placeholder for an encoder without a push button.
+pub struct NoButton;
+
+impl ErrorType for NoButton {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for NoButton {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Rotary encoder input device.
+pub struct Encoder<A, B, BTN = NoButton> {
+    a: A,
+    b: B,
+    button: Option<BTN>,
+    debouncer: Debouncer,
+    active_low: bool,
+    key: Key,
+    state: Option<u8>,
+    quarters: i8,
+    per_detent: i8,
+    reversed: bool,
+    fast_polls: u32,
+    factor: i32,
+    since_detent: u32,
+    last_dir: i32,
+    /// Steps turned since the last report.
+    steps: i32,
+}
+
+impl<A: InputPin, B: InputPin> Encoder<A, B> {
+    /// Encoder on the `a` and `b` contacts, with four transitions per
+    /// detent and no acceleration.
+    pub fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            button: None,
+            debouncer: Debouncer::default(),
+            active_low: true,
+            key: Key::Enter,
+            state: None,
+            quarters: 0,
+            per_detent: 4,
+            reversed: false,
+            fast_polls: 0,
+            factor: 1,
+            since_detent: u32::MAX,
+            last_dir: 0,
+            steps: 0,
+        }
+    }
+
+    /// Add the push button, which connects `button` to ground when pressed.
+    pub fn with_button<BTN: InputPin>(self, button: BTN) -> Encoder<A, B, BTN> {
+        Encoder {
+            a: self.a,
+            b: self.b,
+            button: Some(button),
+            debouncer: self.debouncer,
+            active_low: self.active_low,
+            key: self.key,
+            state: self.state,
+            quarters: self.quarters,
+            per_detent: self.per_detent,
+            reversed: self.reversed,
+            fast_polls: self.fast_polls,
+            factor: self.factor,
+            since_detent: self.since_detent,
+            last_dir: self.last_dir,
+            steps: self.steps,
+        }
+    }
+}
+
+impl<A: InputPin, B: InputPin, BTN: InputPin> Encoder<A, B, BTN> {
+    /// Set how many transitions make one detent: 4, 2 or 1 depending on
+    /// the encoder.
+    pub fn with_steps_per_detent(mut self, transitions: u8) -> Self {
+        self.per_detent = transitions.clamp(1, 4) as i8;
+        self
+    }
+
+    /// Swap the turning direction, for encoders wired B before A.
+    pub fn reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Report `factor` steps for a detent that follows the previous one in
+    /// the same direction within `fast_polls` polls.
+    pub fn with_acceleration(mut self, fast_polls: u32, factor: i32) -> Self {
+        self.fast_polls = fast_polls;
+        self.factor = factor.max(1);
+        self
+    }
+
+    /// Set how many polls a button change must last for.
+    pub fn with_debounce(mut self, polls: u8) -> Self {
+        self.debouncer = Debouncer::new(polls);
+        self
+    }
+
+    /// Treat a high button pin as pressed.
+    pub fn with_active_high(mut self) -> Self {
+        self.active_low = false;
+        self
+    }
+
+    /// Report the button as `key` instead of [`Key::Enter`].
+    pub fn with_key(mut self, key: Key) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Release the pins.
+    pub fn release(self) -> (A, B, Option<BTN>) {
+        (self.a, self.b, self.button)
+    }
+
+    /// Sample the contacts, adding a completed detent to the steps.
+    fn sample_rotation(&mut self) {
+        let (Ok(a), Ok(b)) = (self.a.is_high(), self.b.is_high()) else {
+            return;
+        };
+        let state = ((a as u8) << 1) | b as u8;
+        self.since_detent = self.since_detent.saturating_add(1);
+        let Some(prev) = self.state.replace(state) else {
+            return;
+        };
+        self.quarters += TRANSITIONS[((prev << 2) | state) as usize];
+        if self.quarters.abs() < self.per_detent {
+            return;
+        }
+        let dir = self.quarters.signum() as i32;
+        self.quarters = 0;
+        let fast = dir == self.last_dir && self.since_detent <= self.fast_polls;
+        self.since_detent = 0;
+        self.last_dir = dir;
+        let steps = if fast { dir * self.factor } else { dir };
+        self.steps += if self.reversed { -steps } else { steps };
+    }
+
+    /// Sample the button, returning its event when the debounced state
+    /// changes.
+    fn sample_button(&mut self) -> Option<Event> {
+        let high = self.button.as_mut()?.is_high().ok()?;
+        let key = self.key.clone();
+        match self.debouncer.update(high != self.active_low)? {
+            true => Some(Event::KeyDown { key }),
+            false => Some(Event::KeyUp { key }),
+        }
+    }
+}
+
+impl<A: InputPin, B: InputPin, BTN: InputPin> InputDevice for Encoder<A, B, BTN> {
+    /// Report a button change first. Steps turned meanwhile add up and
+    /// are reported by the next poll.
+    fn poll(&mut self) -> Option<Event> {
+        self.sample_rotation();
+        if let Some(event) = self.sample_button() {
+            return Some(event);
+        }
+        let steps = core::mem::take(&mut self.steps);
+        (steps != 0).then_some(Event::Encoder { steps })
+    }
+}
@@ -0,0 +1,370 @@
+//! Driver for SSD1680 and UC8151 black-and-white e-paper controllers.
+//!
+//! E-paper keeps its image without power but takes hundreds of milliseconds
+//! to update and ghosts after repeated partial updates. [`EPaper::flush`]
+//! therefore only converts pixels into a row-ordered [`MonoBuffer`].
+//! [`EPaper::refresh`] sends the changes at most once per configured
+//! interval. It updates the changed window with a partial refresh and, every
+//! few partial refreshes, repaints the whole panel to clear ghosting. Every
+//! update waits on the controller's busy pin with a timeout.
+//!
+//! [`EPaper::flush`]: DisplayDriver::flush
+
+use alloc::vec::Vec;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::spi::{self, SpiDevice};
+use rlvgl_core::widget::{Color, Rect};
+
+use crate::display::DisplayDriver;
+use crate::mono::{MonoBuffer, MonoLayout, MonoMode};
+
+/// Supported e-paper controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpdController {
+    /// Solomon SSD1680, used by 2.13" and 2.9" panels. Busy pin is high
+    /// while busy.
+    Ssd1680,
+    /// UltraChip UC8151 (IL0373 family). Busy pin is low while busy.
+    Uc8151,
+}
+
+/// Kind of update [`EPaper::refresh`] performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refresh {
+    /// Nothing changed, or the minimum interval has not passed yet.
+    Skipped,
+    /// Only the changed window was updated.
+    Partial,
+    /// The whole panel was repainted.
+    Full,
+}
+
+/// Error raised by the bus, a pin or a stuck controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpdError {
+    /// SPI transfer failed.
+    Spi(spi::ErrorKind),
+    /// Control or busy pin failed.
+    Pin(digital::ErrorKind),
+    /// The busy pin did not clear in time.
+    Timeout,
+}
+
+fn spi_error(e: impl spi::Error) -> EpdError {
+    EpdError::Spi(e.kind())
+}
+
+fn pin_error(e: impl digital::Error) -> EpdError {
+    EpdError::Pin(e.kind())
+}
+
+/// E-paper panel on an SPI bus with data/command, reset and busy pins.
+pub struct EPaper<SPI, DC, RST, BUSY> {
+    spi: SPI,
+    dc: DC,
+    rst: RST,
+    busy: BUSY,
+    controller: EpdController,
+    buffer: MonoBuffer,
+    invert: bool,
+    min_interval_ms: u32,
+    full_every: u32,
+    busy_timeout_ms: u32,
+    partials: u32,
+    last: Option<u64>,
+    full_pending: bool,
+}
+
+impl<SPI, DC, RST, BUSY> EPaper<SPI, DC, RST, BUSY>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+    RST: OutputPin,
+    BUSY: InputPin,
+{
+    /// Drive a `width` × `height` panel. Call [`init`](Self::init) before
+    /// refreshing.
+    ///
+    /// Defaults to at most one refresh per second, a full refresh after
+    /// every ten partial ones and a five second busy timeout.
+    pub fn new(
+        spi: SPI,
+        dc: DC,
+        rst: RST,
+        busy: BUSY,
+        controller: EpdController,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        let mut buffer = MonoBuffer::new(width, height, MonoLayout::Rows);
+        buffer.fill(true);
+        Self {
+            spi,
+            dc,
+            rst,
+            busy,
+            controller,
+            buffer,
+            invert: controller == EpdController::Uc8151,
+            min_interval_ms: 1000,
+            full_every: 10,
+            busy_timeout_ms: 5000,
+            partials: 0,
+            last: None,
+            full_pending: true,
+        }
+    }
+
+    /// Refresh at most once every `ms` milliseconds.
+    pub fn with_min_interval(mut self, ms: u32) -> Self {
+        self.min_interval_ms = ms;
+        self
+    }
+
+    /// Repaint the whole panel after `n` partial refreshes; `0` makes every
+    /// refresh a full one.
+    pub fn with_full_every(mut self, n: u32) -> Self {
+        self.full_every = n;
+        self
+    }
+
+    /// Give up waiting on the busy pin after `ms` milliseconds.
+    pub fn with_busy_timeout(mut self, ms: u32) -> Self {
+        self.busy_timeout_ms = ms;
+        self
+    }
+
+    /// Whether lit (white) pixels are sent as 0 bits. Defaults to `false` on
+    /// the SSD1680 and `true` on the UC8151; some panels wire it the other
+    /// way.
+    pub fn with_inverted(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Framebuffer holding the pixels last drawn; lit pixels are white.
+    pub fn buffer(&self) -> &MonoBuffer {
+        &self.buffer
+    }
+
+    /// Mutable framebuffer; changes are sent by [`refresh`](Self::refresh).
+    pub fn buffer_mut(&mut self) -> &mut MonoBuffer {
+        &mut self.buffer
+    }
+
+    /// Convert flushed colors with `mode`.
+    pub fn set_mode(&mut self, mode: MonoMode) {
+        self.buffer.set_mode(mode);
+    }
+
+    /// Make the next refresh a full one, even if nothing changed.
+    pub fn request_full(&mut self) {
+        self.full_pending = true;
+        self.buffer.invalidate(Rect {
+            x: 0,
+            y: 0,
+            width: self.buffer.width() as i32,
+            height: self.buffer.height() as i32,
+        });
+    }
+
+    /// Give back the bus and pins.
+    pub fn release(self) -> (SPI, DC, RST, BUSY) {
+        (self.spi, self.dc, self.rst, self.busy)
+    }
+
+    /// Reset the controller and configure it for the panel size.
+    pub fn init(&mut self, delay: &mut impl DelayNs) -> Result<(), EpdError> {
+        self.rst.set_low().map_err(pin_error)?;
+        delay.delay_ms(10);
+        self.rst.set_high().map_err(pin_error)?;
+        delay.delay_ms(10);
+        self.wait(delay)?;
+        let w = self.buffer.width() as u16;
+        let h = self.buffer.height() as u16;
+        match self.controller {
+            EpdController::Ssd1680 => {
+                self.command(0x12, &[])?;
+                self.wait(delay)?;
+                let [lo, hi] = (h - 1).to_le_bytes();
+                self.command(0x01, &[lo, hi, 0x00])?;
+                self.command(0x11, &[0x03])?;
+                self.command(0x3C, &[0x05])?;
+                self.command(0x18, &[0x80])?;
+            }
+            EpdController::Uc8151 => {
+                self.command(0x00, &[0x1F])?;
+                self.command(0x06, &[0x17, 0x17, 0x17])?;
+                self.command(0x04, &[])?;
+                self.wait(delay)?;
+                self.command(0x50, &[0x97])?;
+                let [hh, hl] = h.to_be_bytes();
+                self.command(0x61, &[w as u8, hh, hl])?;
+            }
+        }
+        self.full_pending = true;
+        Ok(())
+    }
+
+    /// Send the changes to the panel if the minimum interval since the last
+    /// refresh has passed, given the current time `now_ms`.
+    pub fn refresh(&mut self, now_ms: u64, delay: &mut impl DelayNs) -> Result<Refresh, EpdError> {
+        let Some(dirty) = self.buffer.dirty() else {
+            return Ok(Refresh::Skipped);
+        };
+        if let Some(last) = self.last
+            && now_ms.saturating_sub(last) < self.min_interval_ms as u64
+        {
+            return Ok(Refresh::Skipped);
+        }
+        let full = self.full_pending || self.partials >= self.full_every;
+        if full {
+            let all = Rect {
+                x: 0,
+                y: 0,
+                width: self.buffer.width() as i32,
+                height: self.buffer.height() as i32,
+            };
+            self.update(all, true, delay)?;
+            self.partials = 0;
+            self.full_pending = false;
+        } else {
+            // Controllers address whole bytes of eight columns.
+            let x0 = dirty.x & !7;
+            let x1 = (dirty.x + dirty.width + 7) & !7;
+            let area = Rect {
+                x: x0,
+                width: (x1 - x0).min(self.buffer.width() as i32 - x0),
+                ..dirty
+            };
+            self.update(area, false, delay)?;
+            self.partials += 1;
+        }
+        // Only forget the changes once they reached the panel, so a failed
+        // transfer is retried by the next refresh.
+        self.buffer.take_dirty();
+        self.last = Some(now_ms);
+        Ok(if full {
+            Refresh::Full
+        } else {
+            Refresh::Partial
+        })
+    }
+
+    /// Power the controller down. [`init`](Self::init) wakes it again.
+    pub fn sleep(&mut self, delay: &mut impl DelayNs) -> Result<(), EpdError> {
+        match self.controller {
+            EpdController::Ssd1680 => self.command(0x10, &[0x01]),
+            EpdController::Uc8151 => {
+                self.command(0x02, &[])?;
+                self.wait(delay)?;
+                self.command(0x07, &[0xA5])
+            }
+        }
+    }
+
+    fn update(&mut self, area: Rect, full: bool, delay: &mut impl DelayNs) -> Result<(), EpdError> {
+        match self.controller {
+            EpdController::Ssd1680 => {
+                self.ssd1680_window(area)?;
+                self.write_ram(0x24, area)?;
+                if full {
+                    self.ssd1680_window(area)?;
+                    self.write_ram(0x26, area)?;
+                }
+                self.command(0x22, &[if full { 0xF7 } else { 0xFF }])?;
+                self.command(0x20, &[])?;
+                self.wait(delay)?;
+                if !full {
+                    // The controller diffs against the previous image in
+                    // its second RAM, so bring it up to date.
+                    self.ssd1680_window(area)?;
+                    self.write_ram(0x26, area)?;
+                }
+            }
+            EpdController::Uc8151 => {
+                if full {
+                    self.write_ram(0x13, area)?;
+                    self.command(0x12, &[])?;
+                    self.wait(delay)?;
+                } else {
+                    let x1 = (area.x + area.width - 1) as u16;
+                    let [y0h, y0l] = (area.y as u16).to_be_bytes();
+                    let [y1h, y1l] = ((area.y + area.height - 1) as u16).to_be_bytes();
+                    self.command(0x91, &[])?;
+                    self.command(
+                        0x90,
+                        &[area.x as u8, x1 as u8 | 0x07, y0h, y0l, y1h, y1l, 0x01],
+                    )?;
+                    self.write_ram(0x13, area)?;
+                    self.command(0x12, &[])?;
+                    self.wait(delay)?;
+                    self.command(0x92, &[])?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Address `area` in the SSD1680 RAM and move the write cursor there.
+    fn ssd1680_window(&mut self, area: Rect) -> Result<(), EpdError> {
+        let x0 = (area.x / 8) as u8;
+        let x1 = ((area.x + area.width - 1) / 8) as u8;
+        let [y0l, y0h] = (area.y as u16).to_le_bytes();
+        let [y1l, y1h] = ((area.y + area.height - 1) as u16).to_le_bytes();
+        self.command(0x44, &[x0, x1])?;
+        self.command(0x45, &[y0l, y0h, y1l, y1h])?;
+        self.command(0x4E, &[x0])?;
+        self.command(0x4F, &[y0l, y0h])
+    }
+
+    /// Send the byte-aligned `area` of the framebuffer after `cmd`.
+    fn write_ram(&mut self, cmd: u8, area: Rect) -> Result<(), EpdError> {
+        let stride = self.buffer.stride();
+        let x0 = area.x as usize / 8;
+        let x1 = (area.x + area.width - 1) as usize / 8;
+        let mut data = Vec::with_capacity((x1 - x0 + 1) * area.height as usize);
+        for y in area.y as usize..(area.y + area.height) as usize {
+            let row = &self.buffer.data()[y * stride + x0..=y * stride + x1];
+            data.extend(row.iter().map(|b| if self.invert { !b } else { *b }));
+        }
+        self.command(cmd, &data)
+    }
+
+    fn command(&mut self, cmd: u8, params: &[u8]) -> Result<(), EpdError> {
+        self.dc.set_low().map_err(pin_error)?;
+        self.spi.write(&[cmd]).map_err(spi_error)?;
+        if !params.is_empty() {
+            self.dc.set_high().map_err(pin_error)?;
+            self.spi.write(params).map_err(spi_error)?;
+        }
+        Ok(())
+    }
+
+    /// Poll the busy pin every millisecond until it clears.
+    fn wait(&mut self, delay: &mut impl DelayNs) -> Result<(), EpdError> {
+        let busy_level = self.controller == EpdController::Ssd1680;
+        for _ in 0..=self.busy_timeout_ms {
+            if self.busy.is_high().map_err(pin_error)? != busy_level {
+                return Ok(());
+            }
+            delay.delay_ms(1);
+        }
+        Err(EpdError::Timeout)
+    }
+}
+
+impl<SPI, DC, RST, BUSY> DisplayDriver for EPaper<SPI, DC, RST, BUSY>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+    RST: OutputPin,
+    BUSY: InputPin,
+{
+    /// Convert the pixels into the framebuffer; [`EPaper::refresh`] shows
+    /// them.
+    fn flush(&mut self, area: Rect, colors: &[Color]) {
+        self.buffer.draw(area, colors);
+    }
+}
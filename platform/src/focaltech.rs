@@ -0,0 +1,43 @@
+//! Report parsing shared by the FocalTech FT5x06 and FT6x06 touch
+//! controllers.
+//!
+//! Both read a touch count from `TD_STATUS` followed by six registers per
+//! point: event flag and X high bits, X low, touch ID and Y high bits, Y low,
+//! weight and area.
+
+use crate::input::TouchPoint;
+
+/// Register holding the touch count, followed by the point registers.
+pub(crate) const TD_STATUS: u8 = 0x02;
+/// Bytes per point starting at `P1_XH`.
+pub(crate) const POINT_LEN: usize = 6;
+/// Event flag of a finger leaving the panel.
+const LIFT_UP: u8 = 1;
+/// Event flag of an unused point slot.
+const NO_EVENT: u8 = 3;
+
+/// Parse a report read from [`TD_STATUS`] into `points` and return how many
+/// are in contact.
+///
+/// Lifted and empty points are skipped. Returns `None` when the touch count
+/// exceeds `points.len()`, which happens with garbage read while the
+/// controller resets.
+pub(crate) fn parse_report(report: &[u8], points: &mut [TouchPoint]) -> Option<usize> {
+    let touches = (report[0] & 0x0F) as usize;
+    if touches > points.len() {
+        return None;
+    }
+    let mut count = 0;
+    for raw in report[1..].chunks_exact(POINT_LEN).take(touches) {
+        if matches!(raw[0] >> 6, LIFT_UP | NO_EVENT) {
+            continue;
+        }
+        points[count] = TouchPoint {
+            id: raw[2] >> 4,
+            x: (((raw[0] & 0x0F) as u16) << 8) | raw[1] as u16,
+            y: (((raw[2] & 0x0F) as u16) << 8) | raw[3] as u16,
+        };
+        count += 1;
+    }
+    Some(count)
+}
@@ -4,9 +4,16 @@
 
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 
+use crate::focaltech::{POINT_LEN, TD_STATUS, parse_report};
+use crate::input::TouchPoint;
+
+const MAX_TOUCHES: usize = 5;
+
 /// FT5336 touch controller driver.
 pub struct Ft5336<I2C> {
     i2c: I2C,
+    points: [TouchPoint; MAX_TOUCHES],
+    count: usize,
 }
 
 impl<I2C> Ft5336<I2C>
@@ -18,7 +25,11 @@ where
 
     /// Create a new driver from an I²C peripheral.
     pub fn new(i2c: I2C) -> Self {
-        Self { i2c }
+        Self {
+            i2c,
+            points: [TouchPoint::default(); MAX_TOUCHES],
+            count: 0,
+        }
     }
 
     /// Read the first touch point from the controller.
@@ -28,10 +39,13 @@ where
     pub fn read_touch(&mut self) -> Result<Option<(u16, u16)>, I2C::Error> {
         let mut buf = [0u8; 5];
         // Register 0x02 contains the number of touch points (low nibble). The
-        // following bytes hold X and Y for the first touch.
+        // following bytes hold X and Y for the first touch, with its event
+        // flag in the top bits of the X high byte.
         self.i2c.write_read(Self::ADDRESS, &[0x02], &mut buf)?;
         let touches = buf[0] & 0x0F;
-        if touches == 0 {
+        // Counts above five are garbage read while the controller resets;
+        // event flags 1 and 3 mark a lifted finger and an empty slot.
+        if touches == 0 || touches > 5 || matches!(buf[1] >> 6, 1 | 3) {
             return Ok(None);
         }
         let x = (((buf[1] & 0x0F) as u16) << 8) | buf[2] as u16;
@@ -39,6 +53,21 @@ where
         Ok(Some((x, y)))
     }
 
+    /// Read every point touching the panel, up to five.
+    ///
+    /// Each point occupies six registers from 0x03: event flag and X high
+    /// bits, X low, touch ID and Y high bits, Y low, weight and area.
+    /// Returns `Ok(None)` for a report with an out-of-range touch count.
+    pub fn read_touches(&mut self) -> Result<Option<&[TouchPoint]>, I2C::Error> {
+        let mut buf = [0u8; 1 + POINT_LEN * MAX_TOUCHES];
+        self.i2c.write_read(Self::ADDRESS, &[TD_STATUS], &mut buf)?;
+        let Some(count) = parse_report(&buf, &mut self.points) else {
+            return Ok(None);
+        };
+        self.count = count;
+        Ok(Some(&self.points[..self.count]))
+    }
+
     /// Release the underlying I²C peripheral.
     pub fn release(self) -> I2C {
         self.i2c
@@ -0,0 +1,98 @@
+//! Driver for the FocalTech FT6x06 family (FT6206, FT6236, FT6336)
+//! capacitive touch controllers.
+//!
+//! The controllers track up to two touches. Each point carries an event
+//! flag; points flagged as lifted or empty are skipped, and a report whose
+//! touch count is out of range is ignored as a whole.
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use rlvgl_core::event::Event;
+
+use crate::focaltech::{POINT_LEN, TD_STATUS, parse_report};
+use crate::input::{InputDevice, TouchPoint, TouchTracker};
+
+const THRESHOLD: u8 = 0x80;
+const CHIP_ID: u8 = 0xA3;
+const MAX_TOUCHES: usize = 2;
+
+/// FT6x06 touch controller driver.
+pub struct Ft6x06<I2C> {
+    i2c: I2C,
+    points: [TouchPoint; MAX_TOUCHES],
+    count: usize,
+    tracker: TouchTracker,
+}
+
+impl<I2C> Ft6x06<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    /// 7-bit I²C address of the FT6x06.
+    pub const ADDRESS: SevenBitAddress = 0x38;
+    /// Touches the controller tracks at once.
+    pub const MAX_TOUCHES: usize = MAX_TOUCHES;
+
+    /// Create a new driver from an I²C peripheral.
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            points: [TouchPoint::default(); MAX_TOUCHES],
+            count: 0,
+            tracker: TouchTracker::new(),
+        }
+    }
+
+    /// Chip identifier: `0x06` for FT6206, `0x36` for FT6236 and `0x64`
+    /// for FT6336.
+    pub fn chip_id(&mut self) -> Result<u8, I2C::Error> {
+        let mut id = [0];
+        self.i2c.write_read(Self::ADDRESS, &[CHIP_ID], &mut id)?;
+        Ok(id[0])
+    }
+
+    /// Set the touch detection threshold; lower values are more sensitive.
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(Self::ADDRESS, &[THRESHOLD, threshold])
+    }
+
+    /// Read the points touching the panel.
+    ///
+    /// Returns `Ok(None)` for a report with an out-of-range touch count,
+    /// keeping the points of the last valid one.
+    pub fn read_touches(&mut self) -> Result<Option<&[TouchPoint]>, I2C::Error> {
+        let mut buf = [0u8; 1 + POINT_LEN * MAX_TOUCHES];
+        self.i2c.write_read(Self::ADDRESS, &[TD_STATUS], &mut buf)?;
+        let Some(count) = parse_report(&buf, &mut self.points) else {
+            return Ok(None);
+        };
+        self.count = count;
+        Ok(Some(&self.points[..self.count]))
+    }
+
+    /// Points from the last valid read.
+    pub fn touches(&self) -> &[TouchPoint] {
+        &self.points[..self.count]
+    }
+
+    /// Release the underlying I²C peripheral.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C> InputDevice for Ft6x06<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    /// Report every finger with touch events and the first finger down
+    /// with pointer events.
+    fn poll(&mut self) -> Option<Event> {
+        if let Some(event) = self.tracker.next_event() {
+            return Some(event);
+        }
+        if let Ok(Some(_)) = self.read_touches() {
+            self.tracker.update(&self.points[..self.count]);
+        }
+        self.tracker.next_event()
+    }
+}
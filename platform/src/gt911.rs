@@ -0,0 +1,134 @@
+//! Driver for the Goodix GT911 capacitive touch controller.
+//!
+//! The GT911 tracks up to five touches and flags each new report in its
+//! status register. The host reads the points and then clears the flag.
+
+use embedded_hal::i2c::{I2c, SevenBitAddress};
+use rlvgl_core::event::Event;
+
+use crate::input::{InputDevice, TouchPoint, TouchTracker};
+
+const PRODUCT_ID: u16 = 0x8140;
+const X_RESOLUTION: u16 = 0x8048;
+const STATUS: u16 = 0x814E;
+const POINTS: u16 = 0x814F;
+const BUFFER_READY: u8 = 0x80;
+const MAX_TOUCHES: usize = 5;
+
+/// GT911 touch controller driver.
+pub struct Gt911<I2C> {
+    i2c: I2C,
+    address: SevenBitAddress,
+    points: [TouchPoint; MAX_TOUCHES],
+    count: usize,
+    tracker: TouchTracker,
+}
+
+impl<I2C> Gt911<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    /// Address selected when INT is low during reset.
+    pub const ADDRESS: SevenBitAddress = 0x5D;
+    /// Address selected when INT is high during reset.
+    pub const ALT_ADDRESS: SevenBitAddress = 0x14;
+    /// Touches the controller tracks at once.
+    pub const MAX_TOUCHES: usize = MAX_TOUCHES;
+
+    /// Create a new driver at [`ADDRESS`](Self::ADDRESS).
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            address: Self::ADDRESS,
+            points: [TouchPoint::default(); MAX_TOUCHES],
+            count: 0,
+            tracker: TouchTracker::new(),
+        }
+    }
+
+    /// Talk to the controller at `address` instead.
+    pub fn with_address(mut self, address: SevenBitAddress) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Product identifier, `b"911\0"` for a GT911.
+    pub fn product_id(&mut self) -> Result<[u8; 4], I2C::Error> {
+        let mut id = [0; 4];
+        self.read(PRODUCT_ID, &mut id)?;
+        Ok(id)
+    }
+
+    /// Touch resolution from the controller's configuration.
+    pub fn resolution(&mut self) -> Result<(u16, u16), I2C::Error> {
+        let mut buf = [0; 4];
+        self.read(X_RESOLUTION, &mut buf)?;
+        Ok((
+            u16::from_le_bytes([buf[0], buf[1]]),
+            u16::from_le_bytes([buf[2], buf[3]]),
+        ))
+    }
+
+    /// Read a new report if the controller has one.
+    ///
+    /// Returns `Ok(None)` while the controller has nothing new, otherwise
+    /// the points touching the panel, which may be none.
+    pub fn read_touches(&mut self) -> Result<Option<&[TouchPoint]>, I2C::Error> {
+        let mut status = [0];
+        self.read(STATUS, &mut status)?;
+        if status[0] & BUFFER_READY == 0 {
+            return Ok(None);
+        }
+        let count = (status[0] & 0x0F) as usize;
+        self.count = 0;
+        // Counts above the maximum come from reads during a reset.
+        if (1..=MAX_TOUCHES).contains(&count) {
+            let mut buf = [0; 8 * MAX_TOUCHES];
+            self.read(POINTS, &mut buf[..8 * count])?;
+            for (point, raw) in self.points.iter_mut().zip(buf.chunks_exact(8).take(count)) {
+                *point = TouchPoint {
+                    id: raw[0],
+                    x: u16::from_le_bytes([raw[1], raw[2]]),
+                    y: u16::from_le_bytes([raw[3], raw[4]]),
+                };
+            }
+            self.count = count;
+        }
+        // Acknowledge the report so the controller fills in the next one.
+        let [hi, lo] = STATUS.to_be_bytes();
+        self.i2c.write(self.address, &[hi, lo, 0])?;
+        Ok(Some(&self.points[..self.count]))
+    }
+
+    /// Points from the last report.
+    pub fn touches(&self) -> &[TouchPoint] {
+        &self.points[..self.count]
+    }
+
+    /// Release the underlying I²C peripheral.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    fn read(&mut self, register: u16, buf: &mut [u8]) -> Result<(), I2C::Error> {
+        self.i2c
+            .write_read(self.address, &register.to_be_bytes(), buf)
+    }
+}
+
+impl<I2C> InputDevice for Gt911<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    /// Report every finger with touch events and the first finger down
+    /// with pointer events.
+    fn poll(&mut self) -> Option<Event> {
+        if let Some(event) = self.tracker.next_event() {
+            return Some(event);
+        }
+        if let Ok(Some(_)) = self.read_touches() {
+            self.tracker.update(&self.points[..self.count]);
+        }
+        self.tracker.next_event()
+    }
+}
@@ -0,0 +1,244 @@
+//! Asset hot-reload over a local Unix socket.
+//!
+//! Tools such as `rlvgl-creator` announce changed files by connecting to the
+//! socket and writing their names, one per line and relative to the asset
+//! root. A running simulator polls a [`HotReload`] once per frame. It rereads
+//! each announced file and passes the new bytes to the callbacks watching
+//! that name, which rebuild the widgets that use the asset.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Component, Path, PathBuf};
+use std::{env, fs};
+
+/// Environment variable overriding [`default_socket_path`].
+pub const SOCKET_ENV: &str = "RLVGL_RELOAD_SOCKET";
+
+/// Socket used when none is given: `$RLVGL_RELOAD_SOCKET`, or
+/// `rlvgl-sim.sock` in the temporary directory.
+pub fn default_socket_path() -> PathBuf {
+    env::var_os(SOCKET_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join("rlvgl-sim.sock"))
+}
+
+/// Kind of asset a changed file holds, derived from its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    /// Bitmap or vector image.
+    Image,
+    /// TTF/OTF font, or a packed font under `fonts/`.
+    Font,
+    /// Screen layout description.
+    Layout,
+    /// Anything else.
+    Other,
+}
+
+impl AssetKind {
+    /// Kind of the asset `name`.
+    pub fn of(name: &str) -> Self {
+        let ext = name
+            .rsplit_once('.')
+            .map(|(_, e)| e.to_ascii_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "apng" | "raw" | "svg" => Self::Image,
+            "ttf" | "otf" => Self::Font,
+            "bin" if name.starts_with("fonts/") => Self::Font,
+            "yml" | "yaml" | "json" => Self::Layout,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A changed asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Name relative to the asset root, with `/` separators.
+    pub name: String,
+    /// Kind of the asset.
+    pub kind: AssetKind,
+}
+
+/// Non-blocking listener collecting announced asset names.
+pub struct ReloadListener {
+    listener: UnixListener,
+    path: PathBuf,
+    clients: Vec<(UnixStream, Vec<u8>)>,
+}
+
+impl ReloadListener {
+    /// Listen on `path`, replacing a stale socket left by an earlier run.
+    ///
+    /// Fails with [`ErrorKind::AddrInUse`] if another listener is active.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if UnixStream::connect(&path).is_ok() {
+            return Err(io::Error::new(
+                ErrorKind::AddrInUse,
+                "another process listens on the reload socket",
+            ));
+        }
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            path,
+            clients: Vec::new(),
+        })
+    }
+
+    /// Path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Names received since the last call, in arrival order without
+    /// duplicates. Never blocks.
+    pub fn poll(&mut self) -> Vec<String> {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                self.clients.push((stream, Vec::new()));
+            }
+        }
+        let mut names = Vec::new();
+        let mut push = |line: &[u8]| {
+            if let Some(name) = normalize(&String::from_utf8_lossy(line))
+                && !names.contains(&name)
+            {
+                names.push(name);
+            }
+        };
+        self.clients.retain_mut(|(stream, pending)| {
+            let mut buf = [0u8; 512];
+            let open = loop {
+                match stream.read(&mut buf) {
+                    Ok(0) => break false,
+                    Ok(n) => pending.extend_from_slice(&buf[..n]),
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => break e.kind() == ErrorKind::WouldBlock,
+                }
+            };
+            while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                push(&pending[..end]);
+                pending.drain(..=end);
+            }
+            if !open {
+                push(pending);
+            }
+            open
+        });
+        names
+    }
+}
+
+impl Drop for ReloadListener {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Announce the changed assets `names` to the listener at `socket`.
+pub fn send(socket: impl AsRef<Path>, names: &[&str]) -> io::Result<()> {
+    let mut stream = UnixStream::connect(socket)?;
+    let mut msg = String::new();
+    for name in names {
+        if name.contains('\n') {
+            return Err(io::Error::new(ErrorKind::InvalidInput, "newline in name"));
+        }
+        msg.push_str(name);
+        msg.push('\n');
+    }
+    stream.write_all(msg.as_bytes())
+}
+
+/// Asset name with `/` separators, or `None` if it is empty or leaves the
+/// asset root.
+fn normalize(name: &str) -> Option<String> {
+    let name = name.trim().replace('\\', "/");
+    let mut parts = Vec::new();
+    for part in Path::new(&name).components() {
+        match part {
+            Component::Normal(p) => parts.push(p.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Callback receiving a changed asset and its new contents.
+type Watcher = Box<dyn FnMut(&Change, &[u8])>;
+
+/// Assets under a root directory, reloaded when announced on a socket.
+pub struct HotReload {
+    root: PathBuf,
+    listener: ReloadListener,
+    watchers: Vec<(String, Watcher)>,
+}
+
+impl HotReload {
+    /// Serve assets from `root`, listening for changes on `socket`.
+    pub fn new(root: impl Into<PathBuf>, socket: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self {
+            root: root.into(),
+            listener: ReloadListener::bind(socket)?,
+            watchers: Vec::new(),
+        })
+    }
+
+    /// Asset root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the socket.
+    pub fn socket(&self) -> &Path {
+        self.listener.path()
+    }
+
+    /// Read the asset `name`.
+    pub fn load(&self, name: &str) -> io::Result<Vec<u8>> {
+        let name = normalize(name).ok_or_else(|| io::Error::from(ErrorKind::InvalidInput))?;
+        fs::read(self.root.join(name))
+    }
+
+    /// Call `f` with the new contents whenever `name` changes.
+    ///
+    /// A `name` ending in `/` watches every asset below that directory, and
+    /// an empty `name` watches all assets.
+    pub fn watch(&mut self, name: impl Into<String>, f: impl FnMut(&Change, &[u8]) + 'static) {
+        self.watchers.push((name.into(), Box::new(f)));
+    }
+
+    /// Reload the assets announced since the last call and run their
+    /// watchers. Returns the changes that had watchers; assets that cannot
+    /// be read are skipped.
+    pub fn poll(&mut self) -> Vec<Change> {
+        let mut changes = Vec::new();
+        for name in self.listener.poll() {
+            let matches =
+                |w: &str| w == name || w.is_empty() || (w.ends_with('/') && name.starts_with(w));
+            if !self.watchers.iter().any(|(w, _)| matches(w)) {
+                continue;
+            }
+            let Ok(data) = fs::read(self.root.join(&name)) else {
+                continue;
+            };
+            let change = Change {
+                kind: AssetKind::of(&name),
+                name: name.clone(),
+            };
+            for (w, f) in &mut self.watchers {
+                if matches(w) {
+                    f(&change, &data);
+                }
+            }
+            changes.push(change);
+        }
+        changes
+    }
+}
@@ -1,5 +1,10 @@
 //! Abstractions for input devices.
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
 use rlvgl_core::event::Event;
+use rlvgl_core::gesture::GestureRecognizer;
+
+use crate::orientation::Orientation;
 
 /// Trait for input devices such as touchscreens or mice.
 pub trait InputDevice {
@@ -16,5 +21,286 @@ impl InputDevice for DummyInput {
     }
 }
 
+/// Touch point reported by a multi-touch controller, in panel coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TouchPoint {
+    /// Identifier that stays the same while a finger stays down.
+    pub id: u8,
+    /// Horizontal position.
+    pub x: u16,
+    /// Vertical position.
+    pub y: u16,
+}
+
+/// Turns successive touch samples into pointer events.
+///
+/// Touch controllers report whether the panel is touched and where; this
+/// emits [`Event::PointerDown`] on first contact, [`Event::PointerMove`]
+/// when the point moves and [`Event::PointerUp`] at the last position once
+/// the touch ends.
+#[derive(Debug, Default, Clone)]
+pub struct PointerTracker {
+    last: Option<(i32, i32)>,
+}
+
+impl PointerTracker {
+    /// Tracker with no touch in progress.
+    pub const fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Position of the touch in progress.
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.last
+    }
+
+    /// Feed the latest sample, `None` when nothing touches the panel.
+    pub fn update(&mut self, point: Option<(i32, i32)>) -> Option<Event> {
+        let last = core::mem::replace(&mut self.last, point);
+        match (point, last) {
+            (Some((x, y)), None) => Some(Event::PointerDown { x, y }),
+            (Some((x, y)), Some(prev)) if (x, y) != prev => Some(Event::PointerMove { x, y }),
+            (None, Some((x, y))) => Some(Event::PointerUp { x, y }),
+            _ => None,
+        }
+    }
+}
+
+/// Turns successive multi-touch reports into touch and pointer events.
+///
+/// Every finger produces [`Event::TouchDown`], [`Event::TouchMove`] and
+/// [`Event::TouchUp`]. The first finger down also produces pointer events
+/// until it is lifted, so widgets that follow a single pointer keep working.
+/// One report can produce several events; drivers return them one per
+/// [`InputDevice::poll`] from [`next_event`](Self::next_event).
+#[derive(Debug, Default, Clone)]
+pub struct TouchTracker {
+    points: Vec<TouchPoint>,
+    primary: Option<u8>,
+    queue: VecDeque<Event>,
+}
+
+impl TouchTracker {
+    /// Tracker with no fingers down.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fingers down as of the last report.
+    pub fn points(&self) -> &[TouchPoint] {
+        &self.points
+    }
+
+    /// Feed the points touching the panel in the latest report.
+    pub fn update(&mut self, points: &[TouchPoint]) {
+        for old in &self.points {
+            if points.iter().any(|p| p.id == old.id) {
+                continue;
+            }
+            let (id, x, y) = (old.id, old.x as i32, old.y as i32);
+            self.queue.push_back(Event::TouchUp { id, x, y });
+            if self.primary == Some(id) {
+                self.primary = None;
+                self.queue.push_back(Event::PointerUp { x, y });
+            }
+        }
+        for p in points {
+            let (id, x, y) = (p.id, p.x as i32, p.y as i32);
+            match self.points.iter().find(|old| old.id == id) {
+                Some(old) if old == p => {}
+                Some(_) => {
+                    self.queue.push_back(Event::TouchMove { id, x, y });
+                    if self.primary == Some(id) {
+                        self.queue.push_back(Event::PointerMove { x, y });
+                    }
+                }
+                None => {
+                    self.queue.push_back(Event::TouchDown { id, x, y });
+                    if self.primary.is_none() {
+                        self.primary = Some(id);
+                        self.queue.push_back(Event::PointerDown { x, y });
+                    }
+                }
+            }
+        }
+        self.points.clear();
+        self.points.extend_from_slice(points);
+    }
+
+    /// Next event produced by the reports so far.
+    pub fn next_event(&mut self) -> Option<Event> {
+        self.queue.pop_front()
+    }
+}
+
+/// Filters contact bounce from a sampled switch.
+///
+/// A new level is accepted once it has been read on `polls` consecutive
+/// samples, so the debounce time is `polls` times the sampling period.
+#[derive(Debug, Clone, Copy)]
+pub struct Debouncer {
+    pressed: bool,
+    count: u8,
+    polls: u8,
+}
+
+impl Debouncer {
+    /// Samples a change must last for by default.
+    pub const DEFAULT_POLLS: u8 = 3;
+
+    /// Released switch that changes after `polls` consecutive samples.
+    pub const fn new(polls: u8) -> Self {
+        Self {
+            pressed: false,
+            count: 0,
+            polls: if polls == 0 { 1 } else { polls },
+        }
+    }
+
+    /// Debounced state.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Feed a raw sample, returning the new state when it changes.
+    pub fn update(&mut self, pressed: bool) -> Option<bool> {
+        if pressed == self.pressed {
+            self.count = 0;
+            return None;
+        }
+        self.count += 1;
+        if self.count < self.polls {
+            return None;
+        }
+        self.count = 0;
+        self.pressed = pressed;
+        Some(pressed)
+    }
+}
+
+impl Default for Debouncer {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_POLLS)
+    }
+}
+
+/// Input device adding [`Event::Gesture`] events recognized from the touch
+/// events of a multi-touch device.
+///
+/// Wrap any [`OrientedInput`] or
+/// [`CalibratedInput`](crate::calibration::CalibratedInput) in this rather
+/// than the other way round, so gestures use screen coordinates.
+pub struct GestureInput<I> {
+    inner: I,
+    recognizer: GestureRecognizer,
+    pending: Option<Event>,
+}
+
+impl<I: InputDevice> GestureInput<I> {
+    /// Wrap `inner`.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            recognizer: GestureRecognizer::new(),
+            pending: None,
+        }
+    }
+
+    /// Wrapped device.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<I: InputDevice> InputDevice for GestureInput<I> {
+    /// Pass events through, following each touch event that changes the
+    /// gesture with the gesture event.
+    fn poll(&mut self) -> Option<Event> {
+        if let Some(gesture) = self.pending.take() {
+            return Some(gesture);
+        }
+        let event = self.inner.poll()?;
+        self.pending = self.recognizer.handle(&event);
+        Some(event)
+    }
+}
+
+/// Map the position of pointer and touch events through `f`.
+pub(crate) fn map_position(event: Event, f: impl Fn(i32, i32) -> (i32, i32)) -> Event {
+    match event {
+        Event::PointerDown { x, y } => {
+            let (x, y) = f(x, y);
+            Event::PointerDown { x, y }
+        }
+        Event::PointerUp { x, y } => {
+            let (x, y) = f(x, y);
+            Event::PointerUp { x, y }
+        }
+        Event::PointerMove { x, y } => {
+            let (x, y) = f(x, y);
+            Event::PointerMove { x, y }
+        }
+        Event::TouchDown { id, x, y } => {
+            let (x, y) = f(x, y);
+            Event::TouchDown { id, x, y }
+        }
+        Event::TouchMove { id, x, y } => {
+            let (x, y) = f(x, y);
+            Event::TouchMove { id, x, y }
+        }
+        Event::TouchUp { id, x, y } => {
+            let (x, y) = f(x, y);
+            Event::TouchUp { id, x, y }
+        }
+        other => other,
+    }
+}
+
+/// Input device reporting pointer and touch positions in the logical
+/// coordinates of a rotated or mirrored display.
+///
+/// The wrapped device reports panel coordinates, as touch controllers
+/// mounted on the panel do.
+pub struct OrientedInput<I> {
+    inner: I,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+}
+
+impl<I: InputDevice> OrientedInput<I> {
+    /// Wrap `inner`, which covers a `width` × `height` panel.
+    pub fn new(inner: I, width: usize, height: usize, orientation: Orientation) -> Self {
+        Self {
+            inner,
+            width,
+            height,
+            orientation,
+        }
+    }
+
+    /// Follow a change of the display orientation.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Wrapped device.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    fn map(&self, x: i32, y: i32) -> (i32, i32) {
+        let (w, h) = self.orientation.logical_size(self.width, self.height);
+        self.orientation.to_logical((x, y), (w as i32, h as i32))
+    }
+}
+
+impl<I: InputDevice> InputDevice for OrientedInput<I> {
+    fn poll(&mut self) -> Option<Event> {
+        let event = self.inner.poll()?;
+        Some(map_position(event, |x, y| self.map(x, y)))
+    }
+}
+
 /// Alias used by platform backends for standard events.
 pub type InputEvent = Event;
@@ -0,0 +1,181 @@
+//! GPIO keypads and scanned button matrices.
+//!
+//! Both report debounced presses as [`Event::KeyDown`] and [`Event::KeyUp`]
+//! with the [`Key`] bound to each button. Arrow keys, `Enter` and `Escape`
+//! drive the same widget handling as a keyboard. Pins are sampled on every
+//! [`InputDevice::poll`], so debounce times are counted in polls.
+//!
+//! Each driver takes an array of pins of one type. HALs whose pins have a
+//! type per pin number provide an erased type to collect them in.
+
+use alloc::collections::VecDeque;
+use embedded_hal::digital::{InputPin, OutputPin};
+use rlvgl_core::event::{Event, Key};
+
+use crate::input::{Debouncer, InputDevice};
+
+/// Queue the event for a debounced change of `key`.
+fn push_change(queue: &mut VecDeque<Event>, key: &Key, pressed: Option<bool>) {
+    let key = key.clone();
+    match pressed {
+        Some(true) => queue.push_back(Event::KeyDown { key }),
+        Some(false) => queue.push_back(Event::KeyUp { key }),
+        None => {}
+    }
+}
+
+/// Buttons on their own input pins, each bound to a key.
+pub struct Keypad<P, const N: usize> {
+    pins: [P; N],
+    keys: [Key; N],
+    debouncers: [Debouncer; N],
+    active_low: bool,
+    queue: VecDeque<Event>,
+}
+
+impl<P: InputPin, const N: usize> Keypad<P, N> {
+    /// Buttons that connect `pins` to ground when pressed, reported as the
+    /// key with the same index.
+    pub fn new(pins: [P; N], keys: [Key; N]) -> Self {
+        Self {
+            pins,
+            keys,
+            debouncers: [Debouncer::default(); N],
+            active_low: true,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Set how many polls a change must last for.
+    pub fn with_debounce(mut self, polls: u8) -> Self {
+        self.debouncers = [Debouncer::new(polls); N];
+        self
+    }
+
+    /// Treat high pins as pressed.
+    pub fn with_active_high(mut self) -> Self {
+        self.active_low = false;
+        self
+    }
+
+    /// Whether the button bound to `key` is held, after debouncing.
+    pub fn is_pressed(&self, key: &Key) -> bool {
+        self.keys
+            .iter()
+            .zip(&self.debouncers)
+            .any(|(k, d)| k == key && d.is_pressed())
+    }
+
+    /// Release the pins.
+    pub fn release(self) -> [P; N] {
+        self.pins
+    }
+}
+
+impl<P: InputPin, const N: usize> InputDevice for Keypad<P, N> {
+    fn poll(&mut self) -> Option<Event> {
+        if self.queue.is_empty() {
+            for ((pin, key), debouncer) in self
+                .pins
+                .iter_mut()
+                .zip(&self.keys)
+                .zip(&mut self.debouncers)
+            {
+                let Ok(high) = pin.is_high() else {
+                    continue;
+                };
+                let change = debouncer.update(high != self.active_low);
+                push_change(&mut self.queue, key, change);
+            }
+        }
+        self.queue.pop_front()
+    }
+}
+
+/// Button matrix scanned one row at a time.
+///
+/// Rows are outputs driven low in turn while the columns, inputs with
+/// pull-ups, are read; a low column means the button joining it to the
+/// driven row is pressed. Without a diode per button, three buttons held
+/// on the corners of a rectangle also make the fourth corner read pressed.
+pub struct KeyMatrix<R, C, const ROWS: usize, const COLS: usize> {
+    rows: [R; ROWS],
+    cols: [C; COLS],
+    keys: [[Key; COLS]; ROWS],
+    debouncers: [[Debouncer; COLS]; ROWS],
+    queue: VecDeque<Event>,
+}
+
+impl<R, C, const ROWS: usize, const COLS: usize> KeyMatrix<R, C, ROWS, COLS>
+where
+    R: OutputPin,
+    C: InputPin,
+{
+    /// Matrix reporting the button at `row` and `col` as `keys[row][col]`.
+    /// All rows are driven high until scanned.
+    pub fn new(mut rows: [R; ROWS], cols: [C; COLS], keys: [[Key; COLS]; ROWS]) -> Self {
+        for row in &mut rows {
+            // A row that fails to go high shows up as a stuck key.
+            let _ = row.set_high();
+        }
+        Self {
+            rows,
+            cols,
+            keys,
+            debouncers: [[Debouncer::default(); COLS]; ROWS],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Set how many scans a change must last for.
+    pub fn with_debounce(mut self, polls: u8) -> Self {
+        self.debouncers = [[Debouncer::new(polls); COLS]; ROWS];
+        self
+    }
+
+    /// Whether the button bound to `key` is held, after debouncing.
+    pub fn is_pressed(&self, key: &Key) -> bool {
+        self.keys
+            .iter()
+            .flatten()
+            .zip(self.debouncers.iter().flatten())
+            .any(|(k, d)| k == key && d.is_pressed())
+    }
+
+    /// Release the row and column pins.
+    pub fn release(self) -> ([R; ROWS], [C; COLS]) {
+        (self.rows, self.cols)
+    }
+
+    /// Read every button once.
+    fn scan(&mut self) {
+        for (r, row) in self.rows.iter_mut().enumerate() {
+            if row.set_low().is_err() {
+                continue;
+            }
+            for (c, col) in self.cols.iter_mut().enumerate() {
+                let Ok(low) = col.is_low() else {
+                    continue;
+                };
+                let change = self.debouncers[r][c].update(low);
+                push_change(&mut self.queue, &self.keys[r][c], change);
+            }
+            let _ = row.set_high();
+        }
+    }
+}
+
+impl<R, C, const ROWS: usize, const COLS: usize> InputDevice for KeyMatrix<R, C, ROWS, COLS>
+where
+    R: OutputPin,
+    C: InputPin,
+{
+    /// Scan the matrix once the changes found by the previous scan have
+    /// been reported.
+    fn poll(&mut self) -> Option<Event> {
+        if self.queue.is_empty() {
+            self.scan();
+        }
+        self.queue.pop_front()
+    }
+}
@@ -4,24 +4,74 @@
 
 extern crate alloc;
 
-#[cfg(any(feature = "simulator", feature = "fatfs"))]
+#[cfg(any(
+    feature = "simulator",
+    feature = "fatfs",
+    feature = "hot_reload",
+    feature = "snapshot"
+))]
 extern crate std;
 
 /// Blitter traits and helpers.
 pub mod blit;
+/// GPIO buttons bound to screen points.
+#[cfg(feature = "gpio_input")]
+pub mod button;
+/// Affine touch calibration.
+pub mod calibration;
 /// CPU fallback blitter.
 pub mod cpu_blitter;
 /// Display driver traits and implementations.
 pub mod display;
 #[cfg(all(feature = "dma2d", any(target_arch = "arm", target_arch = "aarch64")))]
 pub mod dma2d;
+/// Quadrature rotary encoder input.
+#[cfg(feature = "gpio_input")]
+pub mod encoder;
+/// SPI driver for SSD1680 and UC8151 e-paper controllers.
+#[cfg(feature = "epaper")]
+pub mod epaper;
+#[cfg(any(
+    feature = "touch",
+    all(
+        feature = "stm32h747i_disco",
+        any(target_arch = "arm", target_arch = "aarch64")
+    )
+))]
+mod focaltech;
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
 ))]
 pub mod ft5336;
+/// I²C driver for FT6x06 capacitive touch controllers.
+#[cfg(feature = "touch")]
+pub mod ft6x06;
+/// I²C driver for GT911 capacitive touch controllers.
+#[cfg(feature = "touch")]
+pub mod gt911;
+/// Asset hot-reload over a local socket.
+#[cfg(all(feature = "hot_reload", unix))]
+pub mod hot_reload;
 /// Input device abstractions.
 pub mod input;
+/// GPIO keypads and button matrices.
+#[cfg(feature = "gpio_input")]
+pub mod keypad;
+/// SPI panel driver for MIPI-DCS controllers.
+#[cfg(feature = "mipi_dcs")]
+pub mod mipi_dcs;
+/// Former home of the ST7789 driver, now part of [`mipi_dcs`].
+#[cfg(feature = "st7789")]
+#[deprecated(note = "use `rlvgl_platform::mipi_dcs`")]
+pub mod st7789 {
+    #[allow(deprecated)]
+    pub use crate::mipi_dcs::St7789Display;
+}
+/// 1-bit framebuffers for monochrome panels.
+pub mod mono;
+/// Display rotation and mirroring.
+pub mod orientation;
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
@@ -29,10 +79,18 @@ pub mod input;
 mod otm8009a;
 #[cfg(feature = "simulator")]
 pub mod pixels_renderer;
+/// Input event recording and replay.
+pub mod record;
+/// Display refresh pipeline with partial draw buffers.
+pub mod refresh;
 #[cfg(feature = "simulator")]
 pub mod simulator;
-#[cfg(feature = "st7789")]
-pub mod st7789;
+/// Golden-image snapshot testing.
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+/// I²C driver for SSD1306 and SH1106 monochrome OLED controllers.
+#[cfg(feature = "ssd1306")]
+pub mod ssd1306;
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
@@ -45,27 +103,58 @@ pub mod stm32h747i_disco;
 pub mod stm32h747i_disco_sd;
 #[cfg(feature = "simulator")]
 pub mod wgpu_blitter;
+/// SPI driver for XPT2046 resistive touch controllers.
+#[cfg(feature = "touch")]
+pub mod xpt2046;
 
 pub use blit::{
-    BlitCaps, BlitPlanner, Blitter, BlitterRenderer, PixelFmt, Rect as BlitRect, Surface,
+    BlitCaps, BlitPlanner, Blitter, BlitterRenderer, Filter, PixelFmt, Rect as BlitRect, Rotate,
+    Surface,
 };
+#[cfg(feature = "gpio_input")]
+pub use button::ScreenButtons;
+pub use calibration::{CalibratedInput, Calibration};
 pub use cpu_blitter::CpuBlitter;
-pub use display::DisplayDriver;
+pub use display::{DisplayDriver, FlushSignal};
 #[cfg(all(feature = "dma2d", any(target_arch = "arm", target_arch = "aarch64")))]
 pub use dma2d::Dma2dBlitter;
+#[cfg(feature = "gpio_input")]
+pub use encoder::Encoder;
+#[cfg(feature = "epaper")]
+pub use epaper::{EPaper, EpdController, Refresh};
+#[cfg(feature = "touch")]
+pub use ft6x06::Ft6x06;
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
 ))]
 pub use ft5336::Ft5336;
-pub use input::{InputDevice, InputEvent};
+#[cfg(feature = "touch")]
+pub use gt911::Gt911;
+pub use input::{
+    Debouncer, GestureInput, InputDevice, InputEvent, OrientedInput, PointerTracker, TouchPoint,
+    TouchTracker,
+};
+#[cfg(feature = "gpio_input")]
+pub use keypad::{KeyMatrix, Keypad};
+#[cfg(feature = "st7789")]
+#[allow(deprecated)]
+pub use mipi_dcs::St7789Display;
+#[cfg(feature = "mipi_dcs")]
+pub use mipi_dcs::{Controller, PanelConfig, SpiPanel};
+pub use mono::{MonoBuffer, MonoLayout, MonoMode};
+pub use orientation::{Orientation, Rotation};
 #[cfg(feature = "simulator")]
 pub use pixels_renderer::PixelsRenderer;
+pub use record::{Recorder, Recording, Replay};
+pub use refresh::{Display, RenderMode};
 pub use rlvgl_core::event::Key;
 #[cfg(feature = "simulator")]
 pub use simulator::WgpuDisplay;
-#[cfg(feature = "st7789")]
-pub use st7789::St7789Display;
+#[cfg(feature = "snapshot")]
+pub use snapshot::Snapshot;
+#[cfg(feature = "ssd1306")]
+pub use ssd1306::{Oled, OledController};
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
@@ -78,3 +167,5 @@ pub use stm32h747i_disco::{Stm32h747iDiscoDisplay, Stm32h747iDiscoInput};
 pub use stm32h747i_disco_sd::DiscoSdBlockDevice;
 #[cfg(feature = "simulator")]
 pub use wgpu_blitter::WgpuBlitter;
+#[cfg(feature = "touch")]
+pub use xpt2046::Xpt2046;
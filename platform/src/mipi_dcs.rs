@@ -0,0 +1,590 @@
+//! SPI panel driver for MIPI-DCS controllers.
+//!
+//! [`SpiPanel`] drives ST7789, ILI9341 and ILI9488 controllers over a
+//! 4-wire SPI bus with a data/command pin. It resets and initializes the
+//! controller, maps orientations onto MADCTL, and streams pixels as RGB565
+//! or RGB666 through a line buffer in bulk writes. With the
+//! `mipi_dcs_async` feature the same panel can be driven over an
+//! `embedded-hal-async` bus, which lets DMA-backed SPI implementations
+//! transfer while the CPU does other work.
+//!
+//! Common modules are described by the `PanelConfig` constants, which place
+//! the visible area inside the controller's frame memory.
+
+use alloc::{vec, vec::Vec};
+use core::convert::Infallible;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{self, SpiDevice};
+use rlvgl_core::widget::{Color, Rect};
+
+use crate::display::DisplayDriver;
+use crate::orientation::{Orientation, Rotation};
+
+const SWRESET: u8 = 0x01;
+const SLPOUT: u8 = 0x11;
+const NORON: u8 = 0x13;
+const INVOFF: u8 = 0x20;
+const INVON: u8 = 0x21;
+const DISPON: u8 = 0x29;
+const CASET: u8 = 0x2A;
+const RASET: u8 = 0x2B;
+const RAMWR: u8 = 0x2C;
+const MADCTL: u8 = 0x36;
+const COLMOD: u8 = 0x3A;
+
+/// Power, frame rate and gamma setup of the ILI9341.
+const ILI9341_INIT: &[(u8, &[u8])] = &[
+    (0xCF, &[0x00, 0xC1, 0x30]),
+    (0xED, &[0x64, 0x03, 0x12, 0x81]),
+    (0xE8, &[0x85, 0x00, 0x78]),
+    (0xCB, &[0x39, 0x2C, 0x00, 0x34, 0x02]),
+    (0xF7, &[0x20]),
+    (0xEA, &[0x00, 0x00]),
+    (0xC0, &[0x23]),
+    (0xC1, &[0x10]),
+    (0xC5, &[0x3E, 0x28]),
+    (0xC7, &[0x86]),
+    (0xB1, &[0x00, 0x18]),
+    (0xB6, &[0x08, 0x82, 0x27]),
+    (0xF2, &[0x00]),
+    (0x26, &[0x01]),
+    (
+        0xE0,
+        &[
+            0x0F, 0x31, 0x2B, 0x0C, 0x0E, 0x08, 0x4E, 0xF1, 0x37, 0x07, 0x10, 0x03, 0x0E, 0x09,
+            0x00,
+        ],
+    ),
+    (
+        0xE1,
+        &[
+            0x00, 0x0E, 0x14, 0x03, 0x11, 0x07, 0x31, 0xC1, 0x48, 0x08, 0x0F, 0x0C, 0x31, 0x36,
+            0x0F,
+        ],
+    ),
+];
+
+/// Power, frame rate and gamma setup of the ILI9488.
+const ILI9488_INIT: &[(u8, &[u8])] = &[
+    (
+        0xE0,
+        &[
+            0x00, 0x03, 0x09, 0x08, 0x16, 0x0A, 0x3F, 0x78, 0x4C, 0x09, 0x0A, 0x08, 0x16, 0x1A,
+            0x0F,
+        ],
+    ),
+    (
+        0xE1,
+        &[
+            0x00, 0x16, 0x19, 0x03, 0x0F, 0x05, 0x32, 0x45, 0x46, 0x04, 0x0E, 0x0D, 0x35, 0x37,
+            0x0F,
+        ],
+    ),
+    (0xC0, &[0x17, 0x15]),
+    (0xC1, &[0x41]),
+    (0xC5, &[0x00, 0x12, 0x80]),
+    (0xB0, &[0x00]),
+    (0xB1, &[0xA0]),
+    (0xB4, &[0x02]),
+    (0xB6, &[0x02, 0x02, 0x3B]),
+    (0xE9, &[0x00]),
+    (0xF7, &[0xA9, 0x51, 0x2C, 0x82]),
+];
+
+/// Supported display controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    /// Sitronix ST7789, 240×320 frame memory.
+    St7789,
+    /// Ilitek ILI9341, 240×320 frame memory.
+    Ili9341,
+    /// Ilitek ILI9488, 320×480 frame memory. Accepts only RGB666 over SPI.
+    Ili9488,
+}
+
+impl Controller {
+    /// Width and height of the frame memory in native orientation.
+    pub fn memory_size(self) -> (u16, u16) {
+        match self {
+            Controller::St7789 | Controller::Ili9341 => (240, 320),
+            Controller::Ili9488 => (320, 480),
+        }
+    }
+
+    /// Controller-specific setup sent after the software reset.
+    fn init_sequence(self) -> &'static [(u8, &'static [u8])] {
+        match self {
+            Controller::St7789 => &[],
+            Controller::Ili9341 => ILI9341_INIT,
+            Controller::Ili9488 => ILI9488_INIT,
+        }
+    }
+
+    /// Whether modules wire the source driver right to left, so columns
+    /// must be mirrored to show the native orientation.
+    fn mirrored(self) -> bool {
+        matches!(self, Controller::Ili9341 | Controller::Ili9488)
+    }
+}
+
+/// Pixel format sent over the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// Two bytes per pixel.
+    Rgb565,
+    /// Three bytes per pixel, six significant bits each.
+    Rgb666,
+}
+
+impl ColorFormat {
+    /// Bytes sent per pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            ColorFormat::Rgb565 => 2,
+            ColorFormat::Rgb666 => 3,
+        }
+    }
+
+    /// COLMOD parameter selecting this format.
+    fn colmod(self) -> u8 {
+        match self {
+            ColorFormat::Rgb565 => 0x55,
+            ColorFormat::Rgb666 => 0x66,
+        }
+    }
+}
+
+/// Controller and module geometry of a panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelConfig {
+    /// Display controller.
+    pub controller: Controller,
+    /// Visible width in native orientation.
+    pub width: u16,
+    /// Visible height in native orientation.
+    pub height: u16,
+    /// Position of the visible area in frame memory, in native orientation.
+    pub offset: (u16, u16),
+    /// Pixel format sent over the bus.
+    pub format: ColorFormat,
+    /// Whether the module wires blue and red subpixels swapped.
+    pub bgr: bool,
+    /// Whether the module needs inverted colors, as most IPS panels do.
+    pub invert: bool,
+}
+
+impl PanelConfig {
+    /// Adafruit/Waveshare 1.3" and 1.54" 240×240 ST7789 modules.
+    pub const ST7789_240X240: Self = Self::new(Controller::St7789, 240, 240).inverted();
+    /// 2" and 2.4" 240×320 ST7789 modules.
+    pub const ST7789_240X320: Self = Self::new(Controller::St7789, 240, 320).inverted();
+    /// 1.14" 135×240 ST7789 modules such as the TTGO T-Display.
+    pub const ST7789_135X240: Self = Self::new(Controller::St7789, 135, 240)
+        .with_offset(52, 40)
+        .inverted();
+    /// 1.47" 172×320 ST7789 modules.
+    pub const ST7789_172X320: Self = Self::new(Controller::St7789, 172, 320)
+        .with_offset(34, 0)
+        .inverted();
+    /// 1.9" 170×320 ST7789 modules.
+    pub const ST7789_170X320: Self = Self::new(Controller::St7789, 170, 320)
+        .with_offset(35, 0)
+        .inverted();
+    /// 2.4" and 2.8" 240×320 ILI9341 modules.
+    pub const ILI9341_240X320: Self = Self::new(Controller::Ili9341, 240, 320);
+    /// 3.5" 320×480 ILI9488 modules.
+    pub const ILI9488_320X480: Self = Self::new(Controller::Ili9488, 320, 480);
+
+    /// A `width` × `height` panel filling the top-left corner of the frame
+    /// memory, with the controller's usual format and subpixel order.
+    pub const fn new(controller: Controller, width: u16, height: u16) -> Self {
+        let (format, bgr) = match controller {
+            Controller::St7789 => (ColorFormat::Rgb565, false),
+            Controller::Ili9341 => (ColorFormat::Rgb565, true),
+            Controller::Ili9488 => (ColorFormat::Rgb666, true),
+        };
+        Self {
+            controller,
+            width,
+            height,
+            offset: (0, 0),
+            format,
+            bgr,
+            invert: false,
+        }
+    }
+
+    /// Place the visible area at `(x, y)` in frame memory.
+    pub const fn with_offset(mut self, x: u16, y: u16) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Send pixels in `format`.
+    pub const fn with_format(mut self, format: ColorFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Swap the red and blue subpixel order.
+    pub const fn with_bgr(mut self, bgr: bool) -> Self {
+        self.bgr = bgr;
+        self
+    }
+
+    /// Invert colors.
+    pub const fn inverted(mut self) -> Self {
+        self.invert = true;
+        self
+    }
+}
+
+/// Error raised by the bus or a control pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelError {
+    /// SPI transfer failed.
+    Spi(spi::ErrorKind),
+    /// Data/command or reset pin failed.
+    Pin(digital::ErrorKind),
+}
+
+fn spi_error(e: impl spi::Error) -> PanelError {
+    PanelError::Spi(e.kind())
+}
+
+fn pin_error(e: impl digital::Error) -> PanelError {
+    PanelError::Pin(e.kind())
+}
+
+/// Placeholder for panels whose reset line is not wired to the MCU.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoResetPin;
+
+impl digital::ErrorType for NoResetPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoResetPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// One initialization command: opcode, parameters and delay afterwards.
+type Step = (u8, Vec<u8>, u32);
+
+/// MIPI-DCS panel on an SPI bus.
+pub struct SpiPanel<SPI, DC, RST = NoResetPin> {
+    spi: SPI,
+    dc: DC,
+    rst: RST,
+    config: PanelConfig,
+    orientation: Orientation,
+    /// Packed pixels of up to one panel line.
+    line: Vec<u8>,
+}
+
+impl<SPI, DC, RST> SpiPanel<SPI, DC, RST> {
+    /// Drive the panel described by `config`. Call `init` (or `init_async`)
+    /// before drawing.
+    pub fn new(spi: SPI, dc: DC, rst: RST, config: PanelConfig) -> Self {
+        let line = config.width.max(config.height) as usize * config.format.bytes_per_pixel();
+        Self {
+            spi,
+            dc,
+            rst,
+            config,
+            orientation: Orientation::IDENTITY,
+            line: vec![0; line],
+        }
+    }
+
+    /// Panel configuration.
+    pub fn config(&self) -> &PanelConfig {
+        &self.config
+    }
+
+    /// Current orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Logical width and height in the current orientation.
+    pub fn size(&self) -> (usize, usize) {
+        self.orientation
+            .logical_size(self.config.width as usize, self.config.height as usize)
+    }
+
+    /// Give back the bus and pins.
+    pub fn release(self) -> (SPI, DC, RST) {
+        (self.spi, self.dc, self.rst)
+    }
+
+    /// Column mirror, row mirror and exchange bits for the orientation.
+    fn scan(&self) -> (bool, bool, bool) {
+        let o = self.orientation;
+        let (mx, my, mv) = match o.rotation {
+            Rotation::Deg0 => (false, false, false),
+            Rotation::Deg90 => (true, false, true),
+            Rotation::Deg180 => (true, true, false),
+            Rotation::Deg270 => (false, true, true),
+        };
+        // Logical x runs along panel columns once MV exchanges the axes.
+        let (flip_x, flip_y) = if mv {
+            (o.flip_v, o.flip_h)
+        } else {
+            (o.flip_h, o.flip_v)
+        };
+        (mx ^ flip_x, my ^ flip_y, mv)
+    }
+
+    /// MADCTL value for the orientation: MY (0x80) and MX (0x40) mirror
+    /// rows and columns, MV (0x20) exchanges them and BGR (0x08) swaps
+    /// subpixels.
+    fn madctl(&self) -> u8 {
+        let (mx, my, mv) = self.scan();
+        let mx = mx ^ self.config.controller.mirrored();
+        (my as u8) << 7 | (mx as u8) << 6 | (mv as u8) << 5 | (self.config.bgr as u8) << 3
+    }
+
+    /// Frame memory address of the logical origin.
+    fn origin(&self) -> (u16, u16) {
+        let c = &self.config;
+        let (mw, mh) = c.controller.memory_size();
+        let (mx, my, mv) = self.scan();
+        let x = if mx {
+            mw - c.width - c.offset.0
+        } else {
+            c.offset.0
+        };
+        let y = if my {
+            mh - c.height - c.offset.1
+        } else {
+            c.offset.1
+        };
+        if mv { (y, x) } else { (x, y) }
+    }
+
+    /// CASET and RASET parameters addressing the logical `area`.
+    fn window(&self, area: Rect) -> [[u8; 4]; 2] {
+        let (ox, oy) = self.origin();
+        let span = |start: i32, len: i32, offset: u16| {
+            let a = (start + offset as i32) as u16;
+            let b = (start + len - 1 + offset as i32) as u16;
+            [(a >> 8) as u8, a as u8, (b >> 8) as u8, b as u8]
+        };
+        [span(area.x, area.width, ox), span(area.y, area.height, oy)]
+    }
+
+    /// Commands bringing the controller from reset to display on.
+    fn init_sequence(&self) -> Vec<Step> {
+        let c = &self.config;
+        let mut steps = vec![(SWRESET, Vec::new(), 150)];
+        steps.extend(
+            c.controller
+                .init_sequence()
+                .iter()
+                .map(|(cmd, params)| (*cmd, params.to_vec(), 0)),
+        );
+        steps.extend([
+            (SLPOUT, Vec::new(), 120),
+            (COLMOD, vec![c.format.colmod()], 0),
+            (MADCTL, vec![self.madctl()], 0),
+            (if c.invert { INVON } else { INVOFF }, Vec::new(), 0),
+            (NORON, Vec::new(), 10),
+            (DISPON, Vec::new(), 20),
+        ]);
+        steps
+    }
+
+    /// Pack as many of `colors` as fit into the line buffer and return
+    /// their count and the number of packed bytes.
+    fn pack(&mut self, colors: &[Color]) -> (usize, usize) {
+        let bpp = self.config.format.bytes_per_pixel();
+        let n = colors.len().min(self.line.len() / bpp);
+        let out = &mut self.line[..n * bpp];
+        match self.config.format {
+            ColorFormat::Rgb565 => {
+                for (px, c) in out.chunks_exact_mut(2).zip(colors) {
+                    px[0] = (c.0 & 0xF8) | (c.1 >> 5);
+                    px[1] = ((c.1 & 0x1C) << 3) | (c.2 >> 3);
+                }
+            }
+            ColorFormat::Rgb666 => {
+                for (px, c) in out.chunks_exact_mut(3).zip(colors) {
+                    px.copy_from_slice(&[c.0 & 0xFC, c.1 & 0xFC, c.2 & 0xFC]);
+                }
+            }
+        }
+        (n, n * bpp)
+    }
+}
+
+impl<SPI: SpiDevice, DC: OutputPin, RST: OutputPin> SpiPanel<SPI, DC, RST> {
+    /// Pulse the reset line, then send the initialization sequence.
+    pub fn init(&mut self, delay: &mut impl DelayNs) -> Result<(), PanelError> {
+        self.rst.set_low().map_err(pin_error)?;
+        delay.delay_ms(10);
+        self.rst.set_high().map_err(pin_error)?;
+        delay.delay_ms(120);
+        for (cmd, params, wait) in self.init_sequence() {
+            self.command(cmd, &params)?;
+            if wait > 0 {
+                delay.delay_ms(wait);
+            }
+        }
+        Ok(())
+    }
+
+    /// Send `cmd` followed by its `params`.
+    pub fn command(&mut self, cmd: u8, params: &[u8]) -> Result<(), PanelError> {
+        self.dc.set_low().map_err(pin_error)?;
+        self.spi.write(&[cmd]).map_err(spi_error)?;
+        if !params.is_empty() {
+            self.dc.set_high().map_err(pin_error)?;
+            self.spi.write(params).map_err(spi_error)?;
+        }
+        Ok(())
+    }
+
+    /// Rotate and mirror the scan direction.
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), PanelError> {
+        self.orientation = orientation;
+        self.command(MADCTL, &[self.madctl()])
+    }
+
+    /// Write `colors` into the logical `area`, one line buffer per transfer.
+    pub fn write_area(&mut self, area: Rect, colors: &[Color]) -> Result<(), PanelError> {
+        let [cols, rows] = self.window(area);
+        self.command(CASET, &cols)?;
+        self.command(RASET, &rows)?;
+        self.command(RAMWR, &[])?;
+        self.dc.set_high().map_err(pin_error)?;
+        let mut rest = colors;
+        while !rest.is_empty() {
+            let (n, len) = self.pack(rest);
+            self.spi.write(&self.line[..len]).map_err(spi_error)?;
+            rest = &rest[n..];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mipi_dcs_async")]
+impl<SPI, DC, RST> SpiPanel<SPI, DC, RST>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+    DC: OutputPin,
+    RST: OutputPin,
+{
+    /// Pulse the reset line, then send the initialization sequence without
+    /// blocking.
+    pub async fn init_async(
+        &mut self,
+        delay: &mut impl embedded_hal_async::delay::DelayNs,
+    ) -> Result<(), PanelError> {
+        self.rst.set_low().map_err(pin_error)?;
+        delay.delay_ms(10).await;
+        self.rst.set_high().map_err(pin_error)?;
+        delay.delay_ms(120).await;
+        for (cmd, params, wait) in self.init_sequence() {
+            self.command_async(cmd, &params).await?;
+            if wait > 0 {
+                delay.delay_ms(wait).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Send `cmd` followed by its `params` without blocking.
+    pub async fn command_async(&mut self, cmd: u8, params: &[u8]) -> Result<(), PanelError> {
+        self.dc.set_low().map_err(pin_error)?;
+        self.spi.write(&[cmd]).await.map_err(spi_error)?;
+        if !params.is_empty() {
+            self.dc.set_high().map_err(pin_error)?;
+            self.spi.write(params).await.map_err(spi_error)?;
+        }
+        Ok(())
+    }
+
+    /// Rotate and mirror the scan direction without blocking.
+    pub async fn set_orientation_async(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), PanelError> {
+        self.orientation = orientation;
+        self.command_async(MADCTL, &[self.madctl()]).await
+    }
+
+    /// Write `colors` into the logical `area` without blocking, one line
+    /// buffer per transfer.
+    pub async fn write_area_async(
+        &mut self,
+        area: Rect,
+        colors: &[Color],
+    ) -> Result<(), PanelError> {
+        let [cols, rows] = self.window(area);
+        self.command_async(CASET, &cols).await?;
+        self.command_async(RASET, &rows).await?;
+        self.command_async(RAMWR, &[]).await?;
+        self.dc.set_high().map_err(pin_error)?;
+        let mut rest = colors;
+        while !rest.is_empty() {
+            let (n, len) = self.pack(rest);
+            self.spi.write(&self.line[..len]).await.map_err(spi_error)?;
+            rest = &rest[n..];
+        }
+        Ok(())
+    }
+}
+
+impl<SPI: SpiDevice, DC: OutputPin, RST: OutputPin> DisplayDriver for SpiPanel<SPI, DC, RST> {
+    fn flush(&mut self, area: Rect, colors: &[Color]) {
+        let _ = self.write_area(area, colors);
+    }
+
+    fn set_orientation(&mut self, orientation: Orientation) -> bool {
+        SpiPanel::set_orientation(self, orientation).is_ok()
+    }
+}
+
+/// ST7789 driver under the name used by the old `st7789` module.
+///
+/// It wraps a [`SpiPanel`] without a reset line or frame memory offset and,
+/// like the old driver, does not initialize the controller in `new`; call
+/// [`SpiPanel::init`] through [`St7789Display::panel`] to do so.
+#[cfg(feature = "st7789")]
+#[deprecated(note = "use `SpiPanel` with a `PanelConfig::ST7789_*` constant")]
+pub struct St7789Display<SPI, DC>(SpiPanel<SPI, DC>);
+
+#[cfg(feature = "st7789")]
+#[allow(deprecated)]
+impl<SPI, DC> St7789Display<SPI, DC> {
+    /// Drive a `width` x `height` ST7789 panel.
+    pub fn new(spi: SPI, dc: DC, width: u16, height: u16) -> Result<Self, PanelError> {
+        let config = PanelConfig::new(Controller::St7789, width, height);
+        Ok(Self(SpiPanel::new(spi, dc, NoResetPin, config)))
+    }
+
+    /// Panel driver doing the work.
+    pub fn panel(&mut self) -> &mut SpiPanel<SPI, DC> {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "st7789")]
+#[allow(deprecated)]
+impl<SPI: SpiDevice, DC: OutputPin> DisplayDriver for St7789Display<SPI, DC> {
+    fn flush(&mut self, area: Rect, colors: &[Color]) {
+        self.0.flush(area, colors);
+    }
+
+    fn set_orientation(&mut self, orientation: Orientation) -> bool {
+        DisplayDriver::set_orientation(&mut self.0, orientation)
+    }
+}
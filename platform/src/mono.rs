@@ -0,0 +1,202 @@
+//! 1-bit framebuffers for monochrome OLED and e-paper panels.
+//!
+//! Widgets draw in full color, so mono drivers convert flushed pixels with a
+//! [`MonoMode`] into a [`MonoBuffer`] laid out the way their controller
+//! stores pixels. Dithering uses a 4×4 ordered (Bayer) matrix indexed by
+//! screen position. Unlike error diffusion, it gives the same result no
+//! matter how an area is split into bands or partial refreshes.
+
+use alloc::{vec, vec::Vec};
+use rlvgl_core::widget::{Color, Rect};
+
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Ordered dither threshold at `(x, y)`, evenly spread over `8..=248`.
+pub(crate) fn bayer(x: i32, y: i32) -> u8 {
+    BAYER[(y & 3) as usize][(x & 3) as usize] * 16 + 8
+}
+
+/// Perceived brightness of `color` (ITU-R BT.601 weights).
+pub fn luma(color: Color) -> u8 {
+    ((color.0 as u32 * 77 + color.1 as u32 * 150 + color.2 as u32 * 29) >> 8) as u8
+}
+
+/// How colors become lit or unlit pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonoMode {
+    /// Pixels at least this bright are lit.
+    Threshold(u8),
+    /// Ordered dithering, which renders gradients and images as patterns.
+    Dither,
+}
+
+impl Default for MonoMode {
+    fn default() -> Self {
+        MonoMode::Threshold(128)
+    }
+}
+
+impl MonoMode {
+    /// Whether `color` at screen position `(x, y)` is lit.
+    pub fn is_on(self, color: Color, x: i32, y: i32) -> bool {
+        let threshold = match self {
+            MonoMode::Threshold(t) => t,
+            MonoMode::Dither => bayer(x, y),
+        };
+        luma(color) >= threshold
+    }
+}
+
+/// Bit order of a [`MonoBuffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonoLayout {
+    /// Each byte holds eight vertical pixels, least significant bit on top,
+    /// in pages of eight rows. Used by SSD1306-style OLED controllers.
+    Pages,
+    /// Each byte holds eight horizontal pixels, most significant bit on the
+    /// left, in rows padded to whole bytes. Used by e-paper controllers.
+    Rows,
+}
+
+/// 1-bit framebuffer tracking the area changed since it was last sent.
+#[derive(Debug, Clone)]
+pub struct MonoBuffer {
+    width: usize,
+    height: usize,
+    layout: MonoLayout,
+    mode: MonoMode,
+    bits: Vec<u8>,
+    dirty: Option<Rect>,
+}
+
+impl MonoBuffer {
+    /// Unlit `width` × `height` buffer in `layout`.
+    pub fn new(width: usize, height: usize, layout: MonoLayout) -> Self {
+        let len = match layout {
+            MonoLayout::Pages => width * height.div_ceil(8),
+            MonoLayout::Rows => width.div_ceil(8) * height,
+        };
+        Self {
+            width,
+            height,
+            layout,
+            mode: MonoMode::default(),
+            bits: vec![0; len],
+            dirty: None,
+        }
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Bit order of [`data`](Self::data).
+    pub fn layout(&self) -> MonoLayout {
+        self.layout
+    }
+
+    /// Conversion applied by [`draw`](Self::draw).
+    pub fn mode(&self) -> MonoMode {
+        self.mode
+    }
+
+    /// Convert later draws with `mode`.
+    pub fn set_mode(&mut self, mode: MonoMode) {
+        self.mode = mode;
+    }
+
+    /// Packed pixels in [`layout`](Self::layout) order.
+    pub fn data(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Bytes per row in [`MonoLayout::Rows`], or per page in
+    /// [`MonoLayout::Pages`].
+    pub fn stride(&self) -> usize {
+        match self.layout {
+            MonoLayout::Pages => self.width,
+            MonoLayout::Rows => self.width.div_ceil(8),
+        }
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u8) {
+        match self.layout {
+            MonoLayout::Pages => ((y / 8) * self.width + x, 1 << (y % 8)),
+            MonoLayout::Rows => (y * self.stride() + x / 8, 0x80 >> (x % 8)),
+        }
+    }
+
+    /// Whether the pixel at `(x, y)` is lit.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (i, mask) = self.locate(x, y);
+        self.bits[i] & mask != 0
+    }
+
+    /// Light or clear the pixel at `(x, y)` without marking it dirty.
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        let (i, mask) = self.locate(x, y);
+        if on {
+            self.bits[i] |= mask;
+        } else {
+            self.bits[i] &= !mask;
+        }
+    }
+
+    /// Light or clear every pixel and mark the whole buffer dirty.
+    pub fn fill(&mut self, on: bool) {
+        self.bits.fill(if on { 0xFF } else { 0 });
+        self.dirty = Some(self.bounds());
+    }
+
+    /// Convert `colors`, row-major over `area`, and mark the area dirty.
+    /// Pixels outside the buffer are ignored.
+    pub fn draw(&mut self, area: Rect, colors: &[Color]) {
+        let Some(clipped) = area.intersect(&self.bounds()) else {
+            return;
+        };
+        for y in clipped.y..clipped.y + clipped.height {
+            for x in clipped.x..clipped.x + clipped.width {
+                let color = colors[((y - area.y) * area.width + x - area.x) as usize];
+                let on = self.mode.is_on(color, x, y);
+                self.set(x as usize, y as usize, on);
+            }
+        }
+        self.invalidate(clipped);
+    }
+
+    /// Mark `area` as changed.
+    pub fn invalidate(&mut self, area: Rect) {
+        let Some(area) = area.intersect(&self.bounds()) else {
+            return;
+        };
+        self.dirty = Some(match self.dirty {
+            Some(d) => d.union(&area),
+            None => area,
+        });
+    }
+
+    /// Area changed since it was last taken.
+    pub fn dirty(&self) -> Option<Rect> {
+        self.dirty
+    }
+
+    /// Area changed since the last call.
+    pub fn take_dirty(&mut self) -> Option<Rect> {
+        self.dirty.take()
+    }
+
+    fn bounds(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: self.width as i32,
+            height: self.height as i32,
+        }
+    }
+}
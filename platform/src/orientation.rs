@@ -0,0 +1,111 @@
+//! Display rotation and mirroring.
+//!
+//! Widgets always draw in logical coordinates. An [`Orientation`] describes
+//! how that logical image lands on the panel: it is mirrored first, then
+//! rotated clockwise. Drivers that rotate in hardware take it through
+//! [`DisplayDriver::set_orientation`](crate::display::DisplayDriver::set_orientation).
+//! Otherwise [`Display`](crate::refresh::Display) and
+//! [`CpuBlitter::blit_oriented`](crate::cpu_blitter::CpuBlitter::blit_oriented)
+//! transform pixels in software, and
+//! [`OrientedInput`](crate::input::OrientedInput) maps touches back.
+
+use rlvgl_core::widget::Rect;
+
+/// Clockwise rotation of the logical image on the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// Native panel orientation.
+    #[default]
+    Deg0,
+    /// Rotated a quarter turn clockwise.
+    Deg90,
+    /// Upside down.
+    Deg180,
+    /// Rotated a quarter turn counterclockwise.
+    Deg270,
+}
+
+/// Rotation and mirroring applied between logical and panel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Orientation {
+    /// Rotation applied after mirroring.
+    pub rotation: Rotation,
+    /// Mirror the logical image left to right.
+    pub flip_h: bool,
+    /// Mirror the logical image top to bottom.
+    pub flip_v: bool,
+}
+
+impl Orientation {
+    /// Native panel orientation without mirroring.
+    pub const IDENTITY: Self = Self::new(Rotation::Deg0);
+
+    /// Rotate by `rotation` without mirroring.
+    pub const fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            flip_h: false,
+            flip_v: false,
+        }
+    }
+
+    /// Also mirror horizontally and/or vertically.
+    pub const fn flipped(mut self, flip_h: bool, flip_v: bool) -> Self {
+        self.flip_h = flip_h;
+        self.flip_v = flip_v;
+        self
+    }
+
+    /// Whether rows of the logical image become panel columns.
+    pub fn swaps_axes(self) -> bool {
+        matches!(self.rotation, Rotation::Deg90 | Rotation::Deg270)
+    }
+
+    /// Logical size of a `width` × `height` panel.
+    pub fn logical_size(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Panel position of the logical point `(x, y)` on a screen whose
+    /// logical size is `size`.
+    pub fn to_physical(self, (x, y): (i32, i32), (w, h): (i32, i32)) -> (i32, i32) {
+        let x = if self.flip_h { w - 1 - x } else { x };
+        let y = if self.flip_v { h - 1 - y } else { y };
+        match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (h - 1 - y, x),
+            Rotation::Deg180 => (w - 1 - x, h - 1 - y),
+            Rotation::Deg270 => (y, w - 1 - x),
+        }
+    }
+
+    /// Logical position of the panel point `(x, y)`, inverting
+    /// [`to_physical`](Self::to_physical) for the same logical `size`.
+    pub fn to_logical(self, (x, y): (i32, i32), (w, h): (i32, i32)) -> (i32, i32) {
+        let (x, y) = match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (y, h - 1 - x),
+            Rotation::Deg180 => (w - 1 - x, h - 1 - y),
+            Rotation::Deg270 => (w - 1 - y, x),
+        };
+        let x = if self.flip_h { w - 1 - x } else { x };
+        let y = if self.flip_v { h - 1 - y } else { y };
+        (x, y)
+    }
+
+    /// Panel area covered by the logical `rect`.
+    pub fn to_physical_rect(self, rect: Rect, size: (i32, i32)) -> Rect {
+        let (ax, ay) = self.to_physical((rect.x, rect.y), size);
+        let (bx, by) = self.to_physical((rect.x + rect.width - 1, rect.y + rect.height - 1), size);
+        Rect {
+            x: ax.min(bx),
+            y: ay.min(by),
+            width: (ax - bx).abs() + 1,
+            height: (ay - by).abs() + 1,
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Input event recording and deterministic replay.
+//!
+//! A [`Recording`] is the stream of events an application dispatched, each
+//! stamped with the milliseconds since recording started. Record input with
+//! [`Recorder`], which wraps any [`InputDevice`], and record the
+//! [`Event::Tick`]s that drive animations with [`Recorder::tick`]. Widgets
+//! advance a fixed amount per tick, so dispatching the same events in the
+//! same order to the same widget tree produces the same frames.
+//!
+//! [`Replay`] feeds a recording back as an input device, either following a
+//! clock or all at once in headless runs. Recordings are stored in a compact
+//! binary form with [`Recording::to_bytes`], or as JSON with the `json`
+//! feature.
+
+use alloc::vec::Vec;
+use rlvgl_core::event::{Event, Gesture, GesturePhase, Key};
+
+use crate::input::InputDevice;
+
+/// Leading bytes of an encoded recording.
+const MAGIC: &[u8; 4] = b"RLVR";
+/// Version of the encoding written by [`Recording::to_bytes`].
+const VERSION: u8 = 1;
+
+/// Event with the time it was dispatched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedEvent {
+    /// Milliseconds since recording started.
+    pub time_ms: u32,
+    /// Dispatched event.
+    pub event: Event,
+}
+
+/// Error decoding a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data does not start with a recording header of a known version.
+    BadHeader,
+    /// The data ends inside an event.
+    Truncated,
+    /// Unknown event or key at the given byte offset.
+    Invalid(usize),
+}
+
+/// Events in the order they were dispatched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    events: Vec<TimedEvent>,
+}
+
+impl Recording {
+    /// Empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `event` dispatched at `time_ms`. Times earlier than the last
+    /// event's are raised to it, so the recording stays in order.
+    pub fn push(&mut self, time_ms: u32, event: Event) {
+        let time_ms = time_ms.max(self.duration_ms());
+        self.events.push(TimedEvent { time_ms, event });
+    }
+
+    /// Recorded events.
+    pub fn events(&self) -> &[TimedEvent] {
+        &self.events
+    }
+
+    /// Time of the last event.
+    pub fn duration_ms(&self) -> u32 {
+        self.events.last().map_or(0, |e| e.time_ms)
+    }
+
+    /// Encode as a header followed by each event's time since the previous
+    /// one and its fields, in variable-length integers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(5 + self.events.len() * 4);
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        let mut last = 0;
+        for e in &self.events {
+            write_uint(&mut out, e.time_ms.saturating_sub(last));
+            last = e.time_ms;
+            encode_event(&mut out, &e.event);
+        }
+        out
+    }
+
+    /// Decode [`to_bytes`](Self::to_bytes) output.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() < 5 || &bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(DecodeError::BadHeader);
+        }
+        let mut r = Reader { bytes, pos: 5 };
+        let mut recording = Self::new();
+        let mut time_ms = 0u32;
+        while r.pos < bytes.len() {
+            time_ms = time_ms.saturating_add(r.uint()?);
+            let event = r.event()?;
+            recording.events.push(TimedEvent { time_ms, event });
+        }
+        Ok(recording)
+    }
+
+    /// Encode as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> alloc::string::String {
+        // Serializing plain data to a string cannot fail.
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Decode [`to_json`](Self::to_json) output. As with [`push`](Self::push),
+    /// times earlier than the previous event's are raised to it.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let parsed: Self = serde_json::from_str(json)?;
+        let mut recording = Self::new();
+        for e in parsed.events {
+            recording.push(e.time_ms, e.event);
+        }
+        Ok(recording)
+    }
+}
+
+fn write_uint(out: &mut Vec<u8>, mut v: u32) {
+    while v >= 0x80 {
+        out.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+fn write_int(out: &mut Vec<u8>, v: i32) {
+    write_uint(out, ((v << 1) ^ (v >> 31)) as u32);
+}
+
+fn encode_key(out: &mut Vec<u8>, key: &Key) {
+    match *key {
+        Key::Escape => out.push(0),
+        Key::Enter => out.push(1),
+        Key::Space => out.push(2),
+        Key::ArrowUp => out.push(3),
+        Key::ArrowDown => out.push(4),
+        Key::ArrowLeft => out.push(5),
+        Key::ArrowRight => out.push(6),
+        Key::Function(n) => out.extend_from_slice(&[7, n]),
+        Key::Character(c) => {
+            out.push(8);
+            write_uint(out, c as u32);
+        }
+        Key::Other(code) => {
+            out.push(9);
+            write_uint(out, code);
+        }
+    }
+}
+
+fn encode_event(out: &mut Vec<u8>, event: &Event) {
+    let point = |out: &mut Vec<u8>, tag: u8, id: Option<u8>, x: i32, y: i32| {
+        out.push(tag);
+        out.extend(id);
+        write_int(out, x);
+        write_int(out, y);
+    };
+    match *event {
+        Event::Tick => out.push(0),
+        Event::PointerDown { x, y } => point(out, 1, None, x, y),
+        Event::PointerUp { x, y } => point(out, 2, None, x, y),
+        Event::PointerMove { x, y } => point(out, 3, None, x, y),
+        Event::TouchDown { id, x, y } => point(out, 4, Some(id), x, y),
+        Event::TouchMove { id, x, y } => point(out, 5, Some(id), x, y),
+        Event::TouchUp { id, x, y } => point(out, 6, Some(id), x, y),
+        Event::Gesture(g) => {
+            let phase = match g.phase {
+                GesturePhase::Start => 0,
+                GesturePhase::Update => 1,
+                GesturePhase::End => 2,
+            };
+            out.extend_from_slice(&[7, phase]);
+            for v in [g.x, g.y, g.scale, g.angle] {
+                write_int(out, v);
+            }
+        }
+        Event::Encoder { steps } => {
+            out.push(8);
+            write_int(out, steps);
+        }
+        Event::KeyDown { ref key } => {
+            out.push(9);
+            encode_key(out, key);
+        }
+        Event::KeyUp { ref key } => {
+            out.push(10);
+            encode_key(out, key);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let b = *self.bytes.get(self.pos).ok_or(DecodeError::Truncated)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn uint(&mut self) -> Result<u32, DecodeError> {
+        let start = self.pos;
+        let mut v = 0u32;
+        for shift in (0..35).step_by(7) {
+            let b = self.byte()?;
+            v |= ((b & 0x7F) as u32) << shift;
+            if b & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err(DecodeError::Invalid(start))
+    }
+
+    fn int(&mut self) -> Result<i32, DecodeError> {
+        let v = self.uint()?;
+        Ok((v >> 1) as i32 ^ -((v & 1) as i32))
+    }
+
+    fn key(&mut self) -> Result<Key, DecodeError> {
+        let start = self.pos;
+        Ok(match self.byte()? {
+            0 => Key::Escape,
+            1 => Key::Enter,
+            2 => Key::Space,
+            3 => Key::ArrowUp,
+            4 => Key::ArrowDown,
+            5 => Key::ArrowLeft,
+            6 => Key::ArrowRight,
+            7 => Key::Function(self.byte()?),
+            8 => Key::Character(char::from_u32(self.uint()?).ok_or(DecodeError::Invalid(start))?),
+            9 => Key::Other(self.uint()?),
+            _ => return Err(DecodeError::Invalid(start)),
+        })
+    }
+
+    fn event(&mut self) -> Result<Event, DecodeError> {
+        let start = self.pos;
+        Ok(match self.byte()? {
+            0 => Event::Tick,
+            1 => Event::PointerDown {
+                x: self.int()?,
+                y: self.int()?,
+            },
+            2 => Event::PointerUp {
+                x: self.int()?,
+                y: self.int()?,
+            },
+            3 => Event::PointerMove {
+                x: self.int()?,
+                y: self.int()?,
+            },
+            4 => Event::TouchDown {
+                id: self.byte()?,
+                x: self.int()?,
+                y: self.int()?,
+            },
+            5 => Event::TouchMove {
+                id: self.byte()?,
+                x: self.int()?,
+                y: self.int()?,
+            },
+            6 => Event::TouchUp {
+                id: self.byte()?,
+                x: self.int()?,
+                y: self.int()?,
+            },
+            7 => {
+                let phase = match self.byte()? {
+                    0 => GesturePhase::Start,
+                    1 => GesturePhase::Update,
+                    2 => GesturePhase::End,
+                    _ => return Err(DecodeError::Invalid(start)),
+                };
+                Event::Gesture(Gesture {
+                    phase,
+                    x: self.int()?,
+                    y: self.int()?,
+                    scale: self.int()?,
+                    angle: self.int()?,
+                })
+            }
+            8 => Event::Encoder { steps: self.int()? },
+            9 => Event::KeyDown { key: self.key()? },
+            10 => Event::KeyUp { key: self.key()? },
+            _ => return Err(DecodeError::Invalid(start)),
+        })
+    }
+}
+
+/// Input device that records the events it passes through.
+///
+/// `clock` returns the current time in milliseconds; recorded times count
+/// from its value when the recorder is created.
+pub struct Recorder<I, C> {
+    inner: I,
+    clock: C,
+    start: u32,
+    recording: Recording,
+}
+
+impl<I: InputDevice, C: FnMut() -> u32> Recorder<I, C> {
+    /// Start recording the events of `inner`.
+    pub fn new(inner: I, mut clock: C) -> Self {
+        let start = clock();
+        Self {
+            inner,
+            clock,
+            start,
+            recording: Recording::new(),
+        }
+    }
+
+    /// Record `event`, which the application dispatches without polling
+    /// this device.
+    pub fn record(&mut self, event: &Event) {
+        let now = (self.clock)().wrapping_sub(self.start);
+        self.recording.push(now, event.clone());
+    }
+
+    /// Record a [`Event::Tick`] and return it for dispatch.
+    pub fn tick(&mut self) -> Event {
+        self.record(&Event::Tick);
+        Event::Tick
+    }
+
+    /// Events recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Take the events recorded so far, leaving the recording empty.
+    pub fn take_recording(&mut self) -> Recording {
+        core::mem::take(&mut self.recording)
+    }
+
+    /// Wrapped device.
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<I: InputDevice, C: FnMut() -> u32> InputDevice for Recorder<I, C> {
+    fn poll(&mut self) -> Option<Event> {
+        let event = self.inner.poll()?;
+        self.record(&event);
+        Some(event)
+    }
+}
+
+/// Input device playing back a [`Recording`].
+///
+/// [`poll`](InputDevice::poll) returns the events recorded up to the time
+/// given to [`set_time`](Self::set_time). Interactive runs set the time
+/// from a clock to replay at the recorded pace; headless runs can call
+/// [`finish`](Self::finish) instead and process every event at once.
+pub struct Replay {
+    recording: Recording,
+    next: usize,
+    now_ms: u32,
+}
+
+impl Replay {
+    /// Replay `recording` from its start.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            next: 0,
+            now_ms: 0,
+        }
+    }
+
+    /// Set the milliseconds elapsed since the replay started.
+    pub fn set_time(&mut self, now_ms: u32) {
+        self.now_ms = now_ms;
+    }
+
+    /// Make every remaining event due.
+    pub fn finish(&mut self) {
+        self.now_ms = u32::MAX;
+    }
+
+    /// Time of the next event to replay.
+    pub fn next_time(&self) -> Option<u32> {
+        self.recording.events.get(self.next).map(|e| e.time_ms)
+    }
+
+    /// Whether every event has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+
+    /// Start again from the beginning.
+    pub fn rewind(&mut self) {
+        self.next = 0;
+        self.now_ms = 0;
+    }
+}
+
+impl InputDevice for Replay {
+    fn poll(&mut self) -> Option<Event> {
+        let e = self.recording.events.get(self.next)?;
+        if e.time_ms > self.now_ms {
+            return None;
+        }
+        self.next += 1;
+        Some(e.event.clone())
+    }
+}
@@ -0,0 +1,428 @@
+//! Display refresh pipeline.
+//!
+//! A [`Display`] draws a widget tree through one or two caller-provided draw
+//! buffers and hands the pixels to a [`DisplayDriver`]. In
+//! [`RenderMode::Partial`] the buffers may be much smaller than the screen:
+//! each invalid area is drawn in horizontal bands that fit a buffer and every
+//! band is flushed on its own. With two buffers the next band is drawn while
+//! the driver still transfers the previous one. A buffer is only reused once
+//! [`DisplayDriver::flush_ready`] reports its transfer complete.
+//!
+//! Widgets draw in logical coordinates. When the driver cannot apply the
+//! configured [`Orientation`] itself, bands are written into the buffers
+//! already rotated and flushed as panel areas.
+
+use alloc::{vec, vec::Vec};
+use core::hint::spin_loop;
+#[cfg(feature = "fontdue")]
+use rlvgl_core::fontdue::{FontdueRenderTarget, render_text};
+use rlvgl_core::{
+    WidgetNode,
+    renderer::Renderer,
+    widget::{Color, Rect},
+};
+
+use crate::display::DisplayDriver;
+use crate::orientation::Orientation;
+
+#[cfg(feature = "fontdue")]
+const FONT_DATA: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+
+/// Invalid areas tracked separately before they collapse into their
+/// bounding box.
+const MAX_AREAS: usize = 16;
+
+/// How draw buffers map onto the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Buffers hold at least one panel row or column. Invalid areas are drawn in bands
+    /// of as many rows as fit and each band is flushed as its own area.
+    Partial,
+    /// Buffers hold the whole screen, which is redrawn and flushed whenever
+    /// any part of it is invalid.
+    Full,
+    /// Buffers hold the whole screen and keep every pixel at its screen
+    /// position. Only invalid areas are redrawn, then the whole buffer is
+    /// flushed, for drivers that scan out of it directly. With two buffers
+    /// the redrawn areas are copied into the other one before it is drawn
+    /// into, so both stay current.
+    Direct,
+}
+
+/// Widget tree renderer driving a [`DisplayDriver`] through draw buffers.
+pub struct Display<'b, D: DisplayDriver> {
+    driver: D,
+    width: usize,
+    height: usize,
+    orientation: Orientation,
+    /// Part of `orientation` not applied by the driver.
+    software: Orientation,
+    mode: RenderMode,
+    buffers: Vec<&'b mut [Color]>,
+    next: usize,
+    invalid: Vec<Rect>,
+    /// Areas of the last drawn buffer missing from the next one, in
+    /// [`RenderMode::Direct`] with two buffers.
+    stale: Vec<Rect>,
+    background: Color,
+    #[cfg(feature = "fontdue")]
+    font: &'b [u8],
+}
+
+impl<'b, D: DisplayDriver> Display<'b, D> {
+    /// Drive a `width` × `height` panel through `driver`, drawing into
+    /// `buf1` and, for double buffering, `buf2`.
+    ///
+    /// The whole screen starts invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a buffer holds less than the longer panel side in
+    /// [`RenderMode::Partial`], or less than the whole panel otherwise.
+    pub fn new(
+        driver: D,
+        width: usize,
+        height: usize,
+        mode: RenderMode,
+        buf1: &'b mut [Color],
+        buf2: Option<&'b mut [Color]>,
+    ) -> Self {
+        let min = match mode {
+            RenderMode::Partial => width.max(height),
+            RenderMode::Full | RenderMode::Direct => width * height,
+        };
+        let mut buffers = vec![buf1];
+        buffers.extend(buf2);
+        assert!(
+            buffers.iter().all(|b| b.len() >= min),
+            "draw buffer smaller than {min} pixels"
+        );
+        Self {
+            driver,
+            width,
+            height,
+            orientation: Orientation::IDENTITY,
+            software: Orientation::IDENTITY,
+            mode,
+            buffers,
+            next: 0,
+            invalid: vec![Rect {
+                x: 0,
+                y: 0,
+                width: width as i32,
+                height: height as i32,
+            }],
+            stale: Vec::new(),
+            background: Color(0, 0, 0, 255),
+            #[cfg(feature = "fontdue")]
+            font: FONT_DATA,
+        }
+    }
+
+    /// Use `data` as the font for text instead of the bundled DejaVu Sans.
+    #[cfg(feature = "fontdue")]
+    pub fn with_font(mut self, data: &'b [u8]) -> Self {
+        self.font = data;
+        self
+    }
+
+    /// Logical screen width in pixels.
+    pub fn width(&self) -> usize {
+        self.orientation.logical_size(self.width, self.height).0
+    }
+
+    /// Logical screen height in pixels.
+    pub fn height(&self) -> usize {
+        self.orientation.logical_size(self.width, self.height).1
+    }
+
+    /// Configured rotation and mirroring.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Rotate and mirror the screen.
+    ///
+    /// Widgets keep drawing in logical coordinates, whose width and height
+    /// swap for quarter turns. The driver applies `orientation` if it can;
+    /// otherwise bands are transformed in software. The whole screen is
+    /// invalidated.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.wait_flush();
+        self.orientation = orientation;
+        self.software = if self.driver.set_orientation(orientation) {
+            Orientation::IDENTITY
+        } else {
+            orientation
+        };
+        self.invalid.clear();
+        self.stale.clear();
+        self.invalidate_all();
+    }
+
+    /// Configured render mode.
+    pub fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    /// Wrapped driver.
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+
+    /// Mutable access to the wrapped driver.
+    pub fn driver_mut(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Color drawn under the widgets in every refreshed area.
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+        self.invalidate_all();
+    }
+
+    /// Mark `area` for redrawing on the next [`refresh`](Self::refresh).
+    ///
+    /// Overlapping areas are merged, and once too many are pending they
+    /// collapse into their bounding box.
+    pub fn invalidate(&mut self, area: Rect) {
+        let Some(mut area) = area.intersect(&self.screen()) else {
+            return;
+        };
+        while let Some(i) = self
+            .invalid
+            .iter()
+            .position(|r| r.intersect(&area).is_some())
+        {
+            area = self.invalid.swap_remove(i).union(&area);
+        }
+        if self.invalid.len() == MAX_AREAS {
+            area = self.invalid.drain(..).fold(area, |a, r| a.union(&r));
+        }
+        self.invalid.push(area);
+    }
+
+    /// Mark the whole screen for redrawing.
+    pub fn invalidate_all(&mut self) {
+        self.invalidate(self.screen());
+    }
+
+    /// Areas waiting for the next refresh.
+    pub fn invalid_areas(&self) -> &[Rect] {
+        &self.invalid
+    }
+
+    /// Redraw the invalid areas and those reported dirty by `root`, and
+    /// flush them to the driver.
+    ///
+    /// Returns the number of flushes issued. The last flush may still be in
+    /// progress on return; see [`wait_flush`](Self::wait_flush).
+    pub fn refresh(&mut self, root: &WidgetNode) -> usize {
+        let mut dirty = Vec::new();
+        root.collect_dirty(&mut dirty);
+        for area in dirty {
+            self.invalidate(area);
+        }
+        if self.invalid.is_empty() {
+            return 0;
+        }
+        let areas = core::mem::take(&mut self.invalid);
+        match self.mode {
+            RenderMode::Partial => {
+                let mut flushes = 0;
+                for area in areas {
+                    let mut y = area.y;
+                    while y < area.y + area.height {
+                        let rows = (self.buffers[self.next].len() / area.width as usize)
+                            .min((area.y + area.height - y) as usize);
+                        let band = Rect {
+                            x: area.x,
+                            y,
+                            width: area.width,
+                            height: rows as i32,
+                        };
+                        let panel = self.to_panel(band);
+                        self.draw(root, panel, band);
+                        self.flush(panel);
+                        flushes += 1;
+                        y += rows as i32;
+                    }
+                }
+                flushes
+            }
+            RenderMode::Full => {
+                let screen = self.screen();
+                let panel = self.to_panel(screen);
+                self.draw(root, panel, screen);
+                self.flush(panel);
+                1
+            }
+            RenderMode::Direct => {
+                let panel = self.to_panel(self.screen());
+                if self.buffers.len() == 1 {
+                    self.wait_flush();
+                } else {
+                    self.sync(panel.width as usize);
+                }
+                for &area in &areas {
+                    self.draw(root, panel, area);
+                }
+                self.stale = areas.into_iter().map(|a| self.to_panel(a)).collect();
+                self.flush(panel);
+                1
+            }
+        }
+    }
+
+    /// Block until the driver has consumed the last flushed buffer.
+    pub fn wait_flush(&mut self) {
+        while !self.driver.flush_ready() {
+            spin_loop();
+        }
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.width() as i32, self.height() as i32)
+    }
+
+    /// Logical screen area.
+    fn screen(&self) -> Rect {
+        let (width, height) = self.size();
+        Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    /// Area the driver receives for the logical `area`.
+    fn to_panel(&self, area: Rect) -> Rect {
+        self.software.to_physical_rect(area, self.size())
+    }
+
+    /// Draw `root` clipped to the logical `clip` into the next buffer, which
+    /// holds the rows of the driver area `origin`.
+    fn draw(&mut self, root: &WidgetNode, origin: Rect, clip: Rect) {
+        // A single buffer may still be in flight from the previous flush.
+        if self.buffers.len() == 1 && self.mode != RenderMode::Direct {
+            self.wait_flush();
+        }
+        let size = self.size();
+        let mut band = Band {
+            buf: &mut *self.buffers[self.next],
+            origin,
+            clip,
+            orientation: self.software,
+            size,
+            #[cfg(feature = "fontdue")]
+            font: self.font,
+        };
+        band.fill_rect(clip, self.background);
+        root.draw(&mut band);
+    }
+
+    /// Hand the first `area` pixels of the next buffer to the driver once
+    /// the previous transfer is done, then switch buffers.
+    fn flush(&mut self, area: Rect) {
+        self.wait_flush();
+        let len = (area.width * area.height) as usize;
+        self.driver.flush(area, &self.buffers[self.next][..len]);
+        self.next = (self.next + 1) % self.buffers.len();
+    }
+
+    /// Copy the areas drawn into the other buffer into the next one, once
+    /// the driver no longer reads the next buffer. Both hold frames with
+    /// `stride` pixels per row.
+    fn sync(&mut self, stride: usize) {
+        if self.stale.is_empty() {
+            return;
+        }
+        self.wait_flush();
+        let (a, b) = self.buffers.split_at_mut(1);
+        let (src, dst) = if self.next == 0 {
+            (&*b[0], &mut *a[0])
+        } else {
+            (&*a[0], &mut *b[0])
+        };
+        for area in self.stale.drain(..) {
+            for y in area.y..area.y + area.height {
+                let start = y as usize * stride + area.x as usize;
+                let end = start + area.width as usize;
+                dst[start..end].copy_from_slice(&src[start..end]);
+            }
+        }
+    }
+}
+
+/// Renderer writing into a draw buffer that covers part of the screen.
+struct Band<'a> {
+    buf: &'a mut [Color],
+    /// Driver area held by `buf`, row by row.
+    origin: Rect,
+    /// Logical area that may be written.
+    clip: Rect,
+    /// Transform from logical to driver coordinates.
+    orientation: Orientation,
+    /// Logical screen size.
+    size: (i32, i32),
+    #[cfg(feature = "fontdue")]
+    font: &'a [u8],
+}
+
+impl Band<'_> {
+    /// Buffer index of the driver pixel `(x, y)`.
+    fn offset(&self, x: i32, y: i32) -> usize {
+        (y - self.origin.y) as usize * self.origin.width as usize + (x - self.origin.x) as usize
+    }
+}
+
+impl Renderer for Band<'_> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let Some(r) = rect.intersect(&self.clip) else {
+            return;
+        };
+        let r = self.orientation.to_physical_rect(r, self.size);
+        for y in r.y..r.y + r.height {
+            let start = self.offset(r.x, y);
+            self.buf[start..start + r.width as usize].fill(color);
+        }
+    }
+
+    fn draw_text(&mut self, position: (i32, i32), text: &str, color: Color) {
+        #[cfg(feature = "fontdue")]
+        {
+            let font = self.font;
+            let _ = render_text(self, font, position, text, color, 16.0);
+        }
+        #[cfg(not(feature = "fontdue"))]
+        {
+            let _ = (position, text, color);
+        }
+    }
+}
+
+#[cfg(feature = "fontdue")]
+impl FontdueRenderTarget for Band<'_> {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.size.0 as usize, self.size.1 as usize)
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, color: Color, alpha: u8) {
+        let c = self.clip;
+        if x < c.x || y < c.y || x >= c.x + c.width || y >= c.y + c.height {
+            return;
+        }
+        let (x, y) = self.orientation.to_physical((x, y), self.size);
+        let idx = self.offset(x, y);
+        let bg = self.buf[idx];
+        let a = alpha as u16;
+        let mix = |fg: u8, bg: u8| ((fg as u16 * a + bg as u16 * (255 - a)) / 255) as u8;
+        self.buf[idx] = Color(
+            mix(color.0, bg.0),
+            mix(color.1, bg.1),
+            mix(color.2, bg.2),
+            bg.3,
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Golden-image snapshot testing.
+//!
+//! [`Snapshot`] renders a widget tree off-screen with [`CpuBlitter`] at a
+//! chosen size and pixel format and compares the result with a golden PNG.
+//! Small rendering differences can be allowed with a per-channel tolerance
+//! and a number of pixels that may exceed it. When a comparison fails, the
+//! actual, expected and diff images are written next to the goldens under
+//! `failures/` for inspection.
+//!
+//! Run tests with the [`BLESS_VAR`] environment variable set to write the
+//! current output as the new goldens:
+//!
+//! ```sh
+//! RLVGL_BLESS=1 cargo test
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use std::path::{Path, PathBuf};
+
+pub use image::{Rgba, RgbaImage};
+use rlvgl_core::WidgetNode;
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect as WidgetRect};
+
+use crate::blit::{Blitter, BlitterRenderer, PixelFmt, Rect, Surface};
+use crate::cpu_blitter::CpuBlitter;
+
+/// Environment variable that makes failing or missing snapshots overwrite
+/// their goldens instead of failing. Any value other than empty or `0`
+/// enables it.
+pub const BLESS_VAR: &str = "RLVGL_BLESS";
+
+/// Reason a snapshot does not match its golden.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// No golden image exists at the path.
+    Missing(PathBuf),
+    /// The golden image has a different size.
+    SizeMismatch {
+        /// Size of the golden image.
+        expected: (u32, u32),
+        /// Size of the rendered image.
+        actual: (u32, u32),
+    },
+    /// More pixels differ than allowed.
+    Mismatch {
+        /// Pixels with a channel differing by more than the tolerance.
+        differing: usize,
+        /// Largest channel difference found.
+        max_delta: u8,
+    },
+    /// Reading or writing an image failed.
+    Image(image::ImageError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(path) => write!(
+                f,
+                "no golden image at {}; set {BLESS_VAR}=1 to create it",
+                path.display()
+            ),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "golden is {}x{} but the snapshot is {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Self::Mismatch {
+                differing,
+                max_delta,
+            } => write!(
+                f,
+                "{differing} pixels differ, by up to {max_delta} per channel"
+            ),
+            Self::Image(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<image::ImageError> for SnapshotError {
+    fn from(e: image::ImageError) -> Self {
+        Self::Image(e)
+    }
+}
+
+/// Pixel differences between two images of the same size.
+#[derive(Debug, Clone)]
+pub struct Diff {
+    /// Pixels with a channel differing by more than the tolerance.
+    pub differing: usize,
+    /// Largest channel difference found.
+    pub max_delta: u8,
+    /// The expected image dimmed, with differing pixels in red.
+    pub image: RgbaImage,
+}
+
+/// Compare `actual` with `expected`, counting pixels with a channel that
+/// differs by more than `tolerance`. Returns `None` if the sizes differ.
+pub fn compare(actual: &RgbaImage, expected: &RgbaImage, tolerance: u8) -> Option<Diff> {
+    if actual.dimensions() != expected.dimensions() {
+        return None;
+    }
+    let mut differing = 0;
+    let mut max_delta = 0;
+    let mut image = RgbaImage::new(actual.width(), actual.height());
+    for ((a, e), out) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(image.pixels_mut())
+    {
+        let delta = (0..4).map(|i| a[i].abs_diff(e[i])).max().unwrap_or(0);
+        max_delta = max_delta.max(delta);
+        *out = if delta > tolerance {
+            differing += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let luma = ((e[0] as u16 + e[1] as u16 + e[2] as u16) / 9) as u8;
+            Rgba([luma, luma, luma, 255])
+        };
+    }
+    Some(Diff {
+        differing,
+        max_delta,
+        image,
+    })
+}
+
+/// Settings for rendering and comparing snapshots.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    width: u32,
+    height: u32,
+    format: PixelFmt,
+    palette: Vec<u32>,
+    background: Color,
+    tolerance: u8,
+    max_diff_pixels: usize,
+    dir: PathBuf,
+}
+
+impl Snapshot {
+    /// Snapshots of a `width` × `height` ARGB8888 screen cleared to opaque
+    /// black, that must match their goldens in `tests/snapshots` exactly.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            format: PixelFmt::Argb8888,
+            palette: Vec::new(),
+            background: Color(0, 0, 0, 255),
+            tolerance: 0,
+            max_diff_pixels: 0,
+            dir: PathBuf::from("tests/snapshots"),
+        }
+    }
+
+    /// Render in `format`, as a display with that framebuffer would show.
+    pub fn with_format(mut self, format: PixelFmt) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Palette of indexed formats, as ARGB8888 colors.
+    pub fn with_palette(mut self, palette: &[u32]) -> Self {
+        self.palette = palette.to_vec();
+        self
+    }
+
+    /// Clear the screen to `color` before drawing.
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Allow each channel to differ from the golden by up to `tolerance`.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Allow up to `pixels` pixels to exceed the tolerance.
+    pub fn with_max_diff_pixels(mut self, pixels: usize) -> Self {
+        self.max_diff_pixels = pixels;
+        self
+    }
+
+    /// Directory holding the golden images, relative to the working
+    /// directory, which is the package root when running `cargo test`.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Path of the golden image for `name`.
+    pub fn golden_path(&self, name: &str) -> PathBuf {
+        self.dir.join(alloc::format!("{name}.png"))
+    }
+
+    /// Render `root` and its children.
+    pub fn render(&self, root: &WidgetNode) -> RgbaImage {
+        self.render_with(|renderer| root.draw(renderer))
+    }
+
+    /// Render whatever `draw` draws.
+    pub fn render_with(&self, draw: impl FnOnce(&mut dyn Renderer)) -> RgbaImage {
+        let (w, h) = (self.width, self.height);
+        let stride = (w as usize * self.format.bits_per_pixel()).div_ceil(8);
+        let mut buf = vec![0u8; stride * h as usize];
+        let mut blitter = CpuBlitter;
+        {
+            let mut surface = Surface::new(&mut buf, stride, self.format, w, h);
+            surface.palette = &self.palette;
+            let mut renderer: BlitterRenderer<'_, CpuBlitter, 16> =
+                BlitterRenderer::new(&mut blitter, surface);
+            renderer.fill_rect(
+                WidgetRect {
+                    x: 0,
+                    y: 0,
+                    width: w as i32,
+                    height: h as i32,
+                },
+                self.background,
+            );
+            draw(&mut renderer);
+        }
+        let mut argb = vec![0u8; w as usize * h as usize * 4];
+        let mut src = Surface::new(&mut buf, stride, self.format, w, h);
+        src.palette = &self.palette;
+        let mut dst = Surface::new(&mut argb, w as usize * 4, PixelFmt::Argb8888, w, h);
+        blitter.blit(&src, Rect { x: 0, y: 0, w, h }, &mut dst, (0, 0));
+        for px in argb.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+        RgbaImage::from_raw(w, h, argb).expect("buffer matches the image size")
+    }
+
+    /// Compare `actual` with the golden image for `name`.
+    ///
+    /// With [`BLESS_VAR`] set, a missing or differing golden is replaced by
+    /// `actual`. Otherwise a failure writes `actual`, the golden and a diff
+    /// to `failures/` in the golden directory.
+    pub fn check(&self, name: &str, actual: &RgbaImage) -> Result<(), SnapshotError> {
+        let path = self.golden_path(name);
+        let result = self.compare_golden(&path, actual);
+        let failures = self.dir.join("failures");
+        let output = |suffix: &str| failures.join(alloc::format!("{name}.{suffix}.png"));
+        match result {
+            Ok(()) => {
+                for suffix in ["actual", "expected", "diff"] {
+                    let _ = std::fs::remove_file(output(suffix));
+                }
+                Ok(())
+            }
+            Err(_) if blessing() => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(image::ImageError::IoError)?;
+                }
+                actual.save(&path)?;
+                Ok(())
+            }
+            Err(SnapshotError::Image(e)) => Err(SnapshotError::Image(e)),
+            Err(e) => {
+                std::fs::create_dir_all(&failures).map_err(image::ImageError::IoError)?;
+                actual.save(output("actual"))?;
+                if let Ok(expected) = image::open(&path) {
+                    let expected = expected.to_rgba8();
+                    expected.save(output("expected"))?;
+                    if let Some(diff) = compare(actual, &expected, self.tolerance) {
+                        diff.image.save(output("diff"))?;
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Render `root` and compare it with the golden image for `name`,
+    /// panicking with the reason and output paths if it does not match.
+    #[track_caller]
+    pub fn assert_matches(&self, name: &str, root: &WidgetNode) {
+        let actual = self.render(root);
+        if let Err(e) = self.check(name, &actual) {
+            let failures = self.dir.join("failures");
+            panic!(
+                "snapshot `{name}` failed: {e}\n  actual, expected and diff images: {}",
+                failures.join(alloc::format!("{name}.*.png")).display()
+            );
+        }
+    }
+
+    fn compare_golden(&self, path: &Path, actual: &RgbaImage) -> Result<(), SnapshotError> {
+        if !path.exists() {
+            return Err(SnapshotError::Missing(path.to_path_buf()));
+        }
+        let expected = image::open(path)?.to_rgba8();
+        let Some(diff) = compare(actual, &expected, self.tolerance) else {
+            return Err(SnapshotError::SizeMismatch {
+                expected: expected.dimensions(),
+                actual: actual.dimensions(),
+            });
+        };
+        if diff.differing > self.max_diff_pixels {
+            return Err(SnapshotError::Mismatch {
+                differing: diff.differing,
+                max_delta: diff.max_delta,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Whether [`BLESS_VAR`] asks for goldens to be rewritten.
+fn blessing() -> bool {
+    std::env::var(BLESS_VAR).is_ok_and(|v| !v.is_empty() && v != "0")
+}
@@ -0,0 +1,182 @@
+//! Driver for SSD1306 and SH1106 monochrome OLED controllers over I²C.
+//!
+//! Flushed pixels are converted into a page-ordered [`MonoBuffer`]. Only the
+//! pages and columns that changed are sent, using page addressing, which
+//! both controllers support.
+
+use alloc::{vec, vec::Vec};
+use embedded_hal::i2c::{self, I2c};
+use rlvgl_core::widget::{Color, Rect};
+
+use crate::display::DisplayDriver;
+use crate::mono::{MonoBuffer, MonoLayout, MonoMode};
+
+/// Control byte announcing command bytes.
+const COMMANDS: u8 = 0x00;
+/// Control byte announcing display RAM data.
+const DATA: u8 = 0x40;
+
+/// Supported OLED controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OledController {
+    /// Solomon SSD1306, up to 128×64.
+    Ssd1306,
+    /// Sino Wealth SH1106, 132-column RAM with 128 visible columns.
+    Sh1106,
+}
+
+impl OledController {
+    /// First RAM column shown on the glass.
+    fn column_offset(self) -> usize {
+        match self {
+            OledController::Ssd1306 => 0,
+            OledController::Sh1106 => 2,
+        }
+    }
+}
+
+/// Error raised by the I²C bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OledError {
+    /// I²C transfer failed.
+    I2c(i2c::ErrorKind),
+}
+
+/// Monochrome OLED panel on an I²C bus.
+pub struct Oled<I2C> {
+    i2c: I2C,
+    address: u8,
+    controller: OledController,
+    buffer: MonoBuffer,
+}
+
+impl<I2C: I2c> Oled<I2C> {
+    /// Usual 7-bit address of OLED modules; some strap it to `0x3D`.
+    pub const DEFAULT_ADDRESS: u8 = 0x3C;
+
+    /// Drive a `width` × `height` panel at [`DEFAULT_ADDRESS`](Self::DEFAULT_ADDRESS).
+    /// Call [`init`](Self::init) before drawing.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the panel is 1 to 128 columns wide and 1 to 64 rows
+    /// high, the most either controller drives.
+    pub fn new(i2c: I2C, controller: OledController, width: usize, height: usize) -> Self {
+        assert!(
+            (1..=128).contains(&width) && (1..=64).contains(&height),
+            "unsupported OLED size {width}x{height}"
+        );
+        Self {
+            i2c,
+            address: Self::DEFAULT_ADDRESS,
+            controller,
+            buffer: MonoBuffer::new(width, height, MonoLayout::Pages),
+        }
+    }
+
+    /// Talk to the panel at `address` instead.
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Framebuffer holding the pixels last drawn.
+    pub fn buffer(&self) -> &MonoBuffer {
+        &self.buffer
+    }
+
+    /// Mutable framebuffer; changes are sent by [`update`](Self::update).
+    pub fn buffer_mut(&mut self) -> &mut MonoBuffer {
+        &mut self.buffer
+    }
+
+    /// Convert flushed colors with `mode`.
+    pub fn set_mode(&mut self, mode: MonoMode) {
+        self.buffer.set_mode(mode);
+    }
+
+    /// Give back the bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Configure the controller, clear the panel and switch it on.
+    pub fn init(&mut self) -> Result<(), OledError> {
+        let mux = self.buffer.height() as u8 - 1;
+        let com_pins = if self.buffer.height() > 32 {
+            0x12
+        } else {
+            0x02
+        };
+        let init: &[u8] = match self.controller {
+            OledController::Ssd1306 => &[
+                0xAE, 0xD5, 0x80, 0xA8, mux, 0xD3, 0x00, 0x40, 0x8D, 0x14, 0x20, 0x02, 0xA1, 0xC8,
+                0xDA, com_pins, 0x81, 0xCF, 0xD9, 0xF1, 0xDB, 0x40, 0xA4, 0xA6,
+            ],
+            OledController::Sh1106 => &[
+                0xAE, 0xD5, 0x80, 0xA8, mux, 0xD3, 0x00, 0x40, 0xAD, 0x8B, 0xA1, 0xC8, 0xDA,
+                com_pins, 0x81, 0x80, 0xD9, 0x22, 0xDB, 0x35, 0xA4, 0xA6,
+            ],
+        };
+        self.commands(init)?;
+        self.buffer.fill(false);
+        self.update()?;
+        self.set_on(true)
+    }
+
+    /// Switch the panel on or off, keeping its RAM.
+    pub fn set_on(&mut self, on: bool) -> Result<(), OledError> {
+        self.commands(&[if on { 0xAF } else { 0xAE }])
+    }
+
+    /// Set the brightness.
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), OledError> {
+        self.commands(&[0x81, contrast])
+    }
+
+    /// Send the pages and columns changed since the last update.
+    pub fn update(&mut self) -> Result<(), OledError> {
+        let Some(area) = self.buffer.dirty() else {
+            return Ok(());
+        };
+        let first = area.y as usize / 8;
+        let last = (area.y + area.height - 1) as usize / 8;
+        let x = area.x as usize;
+        let col = x + self.controller.column_offset();
+        let stride = self.buffer.stride();
+        let mut data = vec![DATA];
+        for page in first..=last {
+            self.commands(&[
+                0xB0 | page as u8,
+                (col & 0x0F) as u8,
+                0x10 | (col >> 4) as u8,
+            ])?;
+            let start = page * stride + x;
+            data.truncate(1);
+            data.extend_from_slice(&self.buffer.data()[start..start + area.width as usize]);
+            self.i2c
+                .write(self.address, &data)
+                .map_err(|e| OledError::I2c(i2c::Error::kind(&e)))?;
+        }
+        // Keep the area dirty until every page was sent, so a failed
+        // transfer is retried by the next update.
+        self.buffer.take_dirty();
+        Ok(())
+    }
+
+    fn commands(&mut self, cmds: &[u8]) -> Result<(), OledError> {
+        let mut bytes = Vec::with_capacity(cmds.len() + 1);
+        bytes.push(COMMANDS);
+        bytes.extend_from_slice(cmds);
+        self.i2c
+            .write(self.address, &bytes)
+            .map_err(|e| OledError::I2c(i2c::Error::kind(&e)))
+    }
+}
+
+impl<I2C: I2c> DisplayDriver for Oled<I2C> {
+    fn flush(&mut self, area: Rect, colors: &[Color]) {
+        self.buffer.draw(area, colors);
+        let _ = self.update();
+    }
+}
@@ -9,6 +9,8 @@
 
 #[cfg(feature = "stm32h747i_disco")]
 use crate::ft5336::Ft5336;
+#[cfg(feature = "stm32h747i_disco")]
+use crate::input::TouchTracker;
 #[cfg(all(
     feature = "stm32h747i_disco",
     any(target_arch = "arm", target_arch = "aarch64")
@@ -150,7 +152,7 @@ impl<B: Blitter> DisplayDriver for Stm32h747iDiscoDisplay<B> {
 pub struct Stm32h747iDiscoInput<I2C, INT> {
     touch: Ft5336<I2C>,
     int: INT,
-    last: Option<(u16, u16)>,
+    tracker: TouchTracker,
 }
 
 #[cfg(feature = "stm32h747i_disco")]
@@ -202,7 +204,7 @@ where
         Self {
             touch: Ft5336::new(i2c),
             int: DummyPin,
-            last: None,
+            tracker: TouchTracker::new(),
         }
     }
 }
@@ -218,7 +220,7 @@ where
         Self {
             touch: Ft5336::new(i2c),
             int,
-            last: None,
+            tracker: TouchTracker::new(),
         }
     }
 
@@ -233,38 +235,17 @@ where
     I2C: I2c<SevenBitAddress>,
     INT: InputPin,
 {
+    /// Report every finger with touch events and the first finger down
+    /// with pointer events.
     fn poll(&mut self) -> Option<Event> {
+        if let Some(event) = self.tracker.next_event() {
+            return Some(event);
+        }
         if !self.int_active() {
             return None;
         }
-        let touch = self.touch.read_touch().ok()?;
-        match (touch, self.last) {
-            (Some((x, y)), Some((lx, ly))) => {
-                self.last = Some((x, y));
-                if (x, y) != (lx, ly) {
-                    Some(Event::PointerMove {
-                        x: x as i32,
-                        y: y as i32,
-                    })
-                } else {
-                    None
-                }
-            }
-            (Some((x, y)), None) => {
-                self.last = Some((x, y));
-                Some(Event::PointerDown {
-                    x: x as i32,
-                    y: y as i32,
-                })
-            }
-            (None, Some((lx, ly))) => {
-                self.last = None;
-                Some(Event::PointerUp {
-                    x: lx as i32,
-                    y: ly as i32,
-                })
-            }
-            (None, None) => None,
-        }
+        let points = self.touch.read_touches().ok()??;
+        self.tracker.update(points);
+        self.tracker.next_event()
     }
 }
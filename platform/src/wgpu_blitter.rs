@@ -7,7 +7,7 @@
 //! Results are read back into the caller provided buffers so the remainder of
 //! the simulator can operate on plain memory.
 
-use crate::blit::{BlitCaps, Blitter, PixelFmt, Rect, Surface};
+use crate::blit::{BlitCaps, Blitter, PixelFmt, Rect, Surface, clip_blit};
 use alloc::vec;
 use pollster::block_on;
 
@@ -448,6 +448,9 @@ impl Blitter for WgpuBlitter {
         if dst.format != PixelFmt::Argb8888 {
             return;
         }
+        let Some(area) = dst.clip_area(area) else {
+            return;
+        };
         let dst_tex = self.upload_surface(dst);
         let color_vec = [
             ((color >> 16) & 0xff) as f32 / 255.0,
@@ -496,9 +499,7 @@ impl Blitter for WgpuBlitter {
             pass.set_pipeline(&self.fill_pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
             pass.set_viewport(0.0, 0.0, dst.width as f32, dst.height as f32, 0.0, 1.0);
-            let x = area.x.max(0) as u32;
-            let y = area.y.max(0) as u32;
-            pass.set_scissor_rect(x, y, area.w, area.h);
+            pass.set_scissor_rect(area.x as u32, area.y as u32, area.w, area.h);
             pass.draw(0..6, 0..1);
         }
         self.queue.submit(Some(encoder.finish()));
@@ -510,6 +511,9 @@ impl Blitter for WgpuBlitter {
         if src.format != PixelFmt::Argb8888 || dst.format != PixelFmt::Argb8888 {
             return;
         }
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
         let src_tex = self.upload_area(src, src_area);
         let dst_tex = self.upload_surface(dst);
         let src_view = src_tex.create_view(&wgpu::TextureViewDescriptor::default());
@@ -556,12 +560,7 @@ impl Blitter for WgpuBlitter {
                 0.0,
                 1.0,
             );
-            pass.set_scissor_rect(
-                dst_pos.0.max(0) as u32,
-                dst_pos.1.max(0) as u32,
-                src_area.w,
-                src_area.h,
-            );
+            pass.set_scissor_rect(dst_pos.0 as u32, dst_pos.1 as u32, src_area.w, src_area.h);
             pass.draw(0..6, 0..1);
         }
         self.queue.submit(Some(encoder.finish()));
@@ -573,6 +572,9 @@ impl Blitter for WgpuBlitter {
         if src.format != PixelFmt::Argb8888 || dst.format != PixelFmt::Argb8888 {
             return;
         }
+        let Some((src_area, dst_pos)) = clip_blit(src, src_area, dst, dst_pos) else {
+            return;
+        };
         let src_tex = self.upload_area(src, src_area);
         let dst_tex = self.upload_surface(dst);
         let src_view = src_tex.create_view(&wgpu::TextureViewDescriptor::default());
@@ -619,12 +621,7 @@ impl Blitter for WgpuBlitter {
                 0.0,
                 1.0,
             );
-            pass.set_scissor_rect(
-                dst_pos.0.max(0) as u32,
-                dst_pos.1.max(0) as u32,
-                src_area.w,
-                src_area.h,
-            );
+            pass.set_scissor_rect(dst_pos.0 as u32, dst_pos.1 as u32, src_area.w, src_area.h);
             pass.draw(0..6, 0..1);
         }
         self.queue.submit(Some(encoder.finish()));
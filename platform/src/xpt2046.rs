@@ -0,0 +1,107 @@
+//! Driver for the XPT2046 (ADS7843-compatible) resistive touch controller.
+//!
+//! The controller digitizes the panel's X and Y voltages and two pressure
+//! readings over SPI. Resistive samples are noisy, so each position is the
+//! median of several conversions. A position is reported only while the
+//! pressure is above a threshold, both before and after sampling, so
+//! readings taken while the stylus lifts are dropped.
+//!
+//! Positions are raw 12-bit readings; wrap the driver in a
+//! [`CalibratedInput`](crate::calibration::CalibratedInput) to map them to
+//! the screen.
+
+use embedded_hal::spi::SpiDevice;
+use rlvgl_core::event::Event;
+
+use crate::input::{InputDevice, PointerTracker};
+
+/// Start bit, 12-bit differential conversion, power down between
+/// conversions with the pen interrupt enabled.
+const READ_X: u8 = 0xD0;
+const READ_Y: u8 = 0x90;
+const READ_Z1: u8 = 0xB0;
+const READ_Z2: u8 = 0xC0;
+const MAX_SAMPLES: usize = 9;
+
+/// XPT2046 touch controller driver.
+pub struct Xpt2046<SPI> {
+    spi: SPI,
+    samples: usize,
+    threshold: u16,
+    tracker: PointerTracker,
+}
+
+impl<SPI: SpiDevice> Xpt2046<SPI> {
+    /// Create a driver taking the median of five samples and a pressure
+    /// threshold of 300.
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            samples: 5,
+            threshold: 300,
+            tracker: PointerTracker::new(),
+        }
+    }
+
+    /// Take the median of `samples` conversions per axis, at most nine.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.clamp(1, MAX_SAMPLES);
+        self
+    }
+
+    /// Report touches only while [`pressure`](Self::pressure) is at least
+    /// `threshold`.
+    pub fn with_threshold(mut self, threshold: u16) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Touch pressure, from 0 when untouched up to about 4095 when pressed
+    /// hard.
+    pub fn pressure(&mut self) -> Result<u16, SPI::Error> {
+        let z1 = self.convert(READ_Z1)?;
+        let z2 = self.convert(READ_Z2)?;
+        Ok((z1 + 4095).saturating_sub(z2))
+    }
+
+    /// Raw position of the touch, or `None` while the panel is not pressed.
+    pub fn read(&mut self) -> Result<Option<(u16, u16)>, SPI::Error> {
+        if self.pressure()? < self.threshold {
+            return Ok(None);
+        }
+        let x = self.median(READ_X)?;
+        let y = self.median(READ_Y)?;
+        if self.pressure()? < self.threshold {
+            return Ok(None);
+        }
+        Ok(Some((x, y)))
+    }
+
+    /// Release the underlying SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    fn median(&mut self, cmd: u8) -> Result<u16, SPI::Error> {
+        let mut values = [0u16; MAX_SAMPLES];
+        let values = &mut values[..self.samples];
+        for v in values.iter_mut() {
+            *v = self.convert(cmd)?;
+        }
+        values.sort_unstable();
+        Ok(values[values.len() / 2])
+    }
+
+    fn convert(&mut self, cmd: u8) -> Result<u16, SPI::Error> {
+        let mut buf = [0u8; 3];
+        self.spi.transfer(&mut buf, &[cmd, 0, 0])?;
+        Ok(u16::from_be_bytes([buf[1], buf[2]]) >> 3)
+    }
+}
+
+impl<SPI: SpiDevice> InputDevice for Xpt2046<SPI> {
+    fn poll(&mut self) -> Option<Event> {
+        let point = self.read().ok()?.map(|(x, y)| (x as i32, y as i32));
+        self.tracker.update(point)
+    }
+}
@@ -0,0 +1,79 @@
+//! Tests for affine touch calibration.
+use rlvgl_core::event::{Event, Key};
+use rlvgl_platform::input::InputDevice;
+use rlvgl_platform::{CalibratedInput, Calibration};
+
+/// Raw reading a resistive panel with swapped axes and offsets would give.
+fn raw((x, y): (i32, i32)) -> (i32, i32) {
+    (3900 - y * 12, 250 + x * 15)
+}
+
+#[test]
+fn three_points_solve_the_mapping_exactly() {
+    let targets = &Calibration::targets(240, 320)[..3];
+    let samples: Vec<_> = targets.iter().map(|&p| raw(p)).collect();
+    let cal = Calibration::from_points(&samples, targets).unwrap();
+    for p in [(0, 0), (239, 319), (120, 17), (5, 300)] {
+        assert_eq!(cal.apply(raw(p)), p);
+    }
+}
+
+#[test]
+fn five_points_average_out_inaccurate_touches() {
+    let targets = Calibration::targets(240, 320);
+    let noise = [(24, -12), (-24, 12), (12, 24), (-12, -24), (0, 0)];
+    let samples: Vec<_> = targets
+        .iter()
+        .zip(noise)
+        .map(|(&p, (nx, ny))| {
+            let (x, y) = raw(p);
+            (x + nx, y + ny)
+        })
+        .collect();
+    let cal = Calibration::from_points(&samples, &targets).unwrap();
+    let (x, y) = cal.apply(raw((120, 160)));
+    assert!((x - 120).abs() <= 1 && (y - 160).abs() <= 1, "{x},{y}");
+}
+
+#[test]
+fn degenerate_samples_are_rejected() {
+    let line = [(0, 0), (10, 10), (20, 20)];
+    let targets = [(0, 0), (100, 0), (0, 100)];
+    assert!(Calibration::from_points(&line, &targets).is_none());
+    assert!(Calibration::from_points(&line[..2], &targets[..2]).is_none());
+    assert!(Calibration::from_points(&line, &targets[..2]).is_none());
+}
+
+#[test]
+fn calibration_round_trips_through_bytes() {
+    let cal = Calibration::from_range((200, 300), (3900, 3800), 320, 240);
+    assert_eq!(cal.apply((200, 300)), (0, 0));
+    assert_eq!(cal.apply((3900, 3800)), (319, 239));
+    assert_eq!(Calibration::from_bytes(&cal.to_bytes()), Some(cal));
+    assert_eq!(Calibration::from_bytes(&[0xFF; Calibration::BYTES]), None);
+}
+
+struct Events(Vec<Event>);
+
+impl InputDevice for Events {
+    fn poll(&mut self) -> Option<Event> {
+        (!self.0.is_empty()).then(|| self.0.remove(0))
+    }
+}
+
+#[test]
+fn calibrated_input_maps_pointer_events_only() {
+    let cal = Calibration::from_coefficients([0.0, 1.0, 0.0, 0.5, 0.0, 10.0]);
+    let mut input = CalibratedInput::new(
+        Events(vec![
+            Event::PointerDown { x: 40, y: 7 },
+            Event::KeyDown { key: Key::Enter },
+            Event::PointerUp { x: 41, y: 8 },
+        ]),
+        cal,
+    );
+    assert_eq!(input.poll(), Some(Event::PointerDown { x: 7, y: 30 }));
+    assert_eq!(input.poll(), Some(Event::KeyDown { key: Key::Enter }));
+    assert_eq!(input.poll(), Some(Event::PointerUp { x: 8, y: 31 }));
+    assert_eq!(input.poll(), None);
+}
@@ -0,0 +1,203 @@
+//! Tests for the banded display refresh pipeline.
+use std::{cell::RefCell, rc::Rc};
+
+use rlvgl_core::{
+    WidgetNode,
+    event::Event,
+    renderer::Renderer,
+    widget::{Color, Rect, Widget},
+};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_platform::{Display, RenderMode};
+
+const W: usize = 16;
+const H: usize = 12;
+const RED: Color = Color(255, 0, 0, 255);
+const BLUE: Color = Color(0, 0, 255, 255);
+
+struct Block {
+    bounds: Rect,
+    color: Color,
+    dirty: bool,
+}
+
+impl Widget for Block {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.color);
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+}
+
+/// Buffer display recording flushed areas and simulating a slow transfer
+/// that completes after `delay` readiness polls.
+struct SlowDisplay {
+    inner: BufferDisplay,
+    areas: Vec<(i32, i32, i32, i32)>,
+    delay: u32,
+    busy: u32,
+    /// Flushes issued while the previous transfer was still busy.
+    overlapped: u32,
+}
+
+impl SlowDisplay {
+    fn new(delay: u32) -> Self {
+        Self {
+            inner: BufferDisplay::new(W, H),
+            areas: Vec::new(),
+            delay,
+            busy: 0,
+            overlapped: 0,
+        }
+    }
+}
+
+impl DisplayDriver for SlowDisplay {
+    fn flush(&mut self, area: Rect, colors: &[Color]) {
+        if self.busy > 0 {
+            self.overlapped += 1;
+        }
+        self.busy = self.delay;
+        self.areas.push((area.x, area.y, area.width, area.height));
+        self.inner.flush(area, colors);
+    }
+
+    fn flush_ready(&mut self) -> bool {
+        self.busy = self.busy.saturating_sub(1);
+        self.busy == 0
+    }
+}
+
+fn scene() -> (WidgetNode, Rc<RefCell<Block>>) {
+    let block = Rc::new(RefCell::new(Block {
+        bounds: Rect {
+            x: 4,
+            y: 3,
+            width: 6,
+            height: 5,
+        },
+        color: RED,
+        dirty: false,
+    }));
+    let root = WidgetNode {
+        widget: block.clone(),
+        children: Vec::new(),
+    };
+    (root, block)
+}
+
+fn expected(color: Color) -> Vec<Color> {
+    let mut pixels = vec![Color(0, 0, 0, 255); W * H];
+    for y in 3..8 {
+        for x in 4..10 {
+            pixels[y * W + x] = color;
+        }
+    }
+    pixels
+}
+
+#[test]
+fn partial_mode_flushes_bands_that_fit_the_buffer() {
+    let (root, block) = scene();
+    let mut buf1 = [Color(0, 0, 0, 0); W * 4];
+    let mut buf2 = [Color(0, 0, 0, 0); W * 4];
+    let mut display = Display::new(
+        SlowDisplay::new(3),
+        W,
+        H,
+        RenderMode::Partial,
+        &mut buf1,
+        Some(&mut buf2),
+    );
+    assert_eq!(display.refresh(&root), 3);
+    assert_eq!(display.driver().inner.buffer, expected(RED));
+    assert_eq!(display.driver().overlapped, 0);
+    assert_eq!(display.refresh(&root), 0);
+
+    block.borrow_mut().color = BLUE;
+    block.borrow_mut().dirty = true;
+    display.driver_mut().areas.clear();
+    assert_eq!(display.refresh(&root), 1);
+    assert_eq!(display.driver().areas, [(4, 3, 6, 5)]);
+    assert_eq!(display.driver().inner.buffer, expected(BLUE));
+    assert_eq!(display.driver().overlapped, 0);
+}
+
+#[test]
+fn overlapping_invalid_areas_merge() {
+    let (root, _) = scene();
+    let mut buf = vec![Color(0, 0, 0, 0); W * H];
+    let mut display = Display::new(
+        SlowDisplay::new(0),
+        W,
+        H,
+        RenderMode::Partial,
+        &mut buf,
+        None,
+    );
+    display.refresh(&root);
+    display.invalidate(Rect {
+        x: 0,
+        y: 0,
+        width: 4,
+        height: 4,
+    });
+    display.invalidate(Rect {
+        x: 2,
+        y: 2,
+        width: 4,
+        height: 4,
+    });
+    display.invalidate(Rect {
+        x: 12,
+        y: 10,
+        width: 8,
+        height: 8,
+    });
+    let areas: Vec<_> = display
+        .invalid_areas()
+        .iter()
+        .map(|r| (r.x, r.y, r.width, r.height))
+        .collect();
+    assert_eq!(areas, [(0, 0, 6, 6), (12, 10, 4, 2)]);
+}
+
+#[test]
+fn full_and_direct_modes_flush_the_whole_screen() {
+    for mode in [RenderMode::Full, RenderMode::Direct] {
+        let (root, block) = scene();
+        let mut buf1 = vec![Color(0, 0, 0, 0); W * H];
+        let mut buf2 = vec![Color(0, 0, 0, 0); W * H];
+        let mut display = Display::new(SlowDisplay::new(2), W, H, mode, &mut buf1, Some(&mut buf2));
+        assert_eq!(display.refresh(&root), 1);
+        assert_eq!(display.driver().inner.buffer, expected(RED));
+
+        block.borrow_mut().color = BLUE;
+        block.borrow_mut().dirty = true;
+        assert_eq!(display.refresh(&root), 1);
+        assert_eq!(display.driver().inner.buffer, expected(BLUE));
+
+        // The first buffer still holds the red block. Direct mode copies the
+        // blue one over before redrawing only the invalidated pixel.
+        display.invalidate(Rect {
+            x: 0,
+            y: 0,
+            width: 1,
+            height: 1,
+        });
+        display.refresh(&root);
+        assert_eq!(display.driver().inner.buffer, expected(BLUE));
+        assert_eq!(display.driver().areas, [(0, 0, W as i32, H as i32); 3]);
+        assert_eq!(display.driver().overlapped, 0);
+    }
+}
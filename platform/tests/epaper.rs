@@ -0,0 +1,276 @@
+//! Tests for the e-paper driver against a recording bus and busy pin.
+#![cfg(feature = "epaper")]
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::spi::{self, Operation, SpiDevice};
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::display::DisplayDriver;
+use rlvgl_platform::epaper::EpdError;
+use rlvgl_platform::{EPaper, EpdController, Refresh};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Op {
+    Cmd(u8),
+    Data(Vec<u8>),
+}
+
+use Op::{Cmd, Data};
+
+#[derive(Default, Clone)]
+struct Log {
+    ops: Rc<RefCell<Vec<Op>>>,
+    dc: Rc<Cell<bool>>,
+    /// Polls the busy pin still reports busy for.
+    busy_polls: Rc<Cell<u32>>,
+    /// Whether SPI transfers fail.
+    fail: Rc<Cell<bool>>,
+}
+
+impl Log {
+    fn take(&self) -> Vec<Op> {
+        self.ops.take()
+    }
+}
+
+struct Bus(Log);
+
+impl spi::ErrorType for Bus {
+    type Error = spi::ErrorKind;
+}
+
+impl SpiDevice for Bus {
+    fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) -> Result<(), spi::ErrorKind> {
+        if self.0.fail.get() {
+            return Err(spi::ErrorKind::Other);
+        }
+        for op in ops {
+            if let Operation::Write(bytes) = op {
+                let mut log = self.0.ops.borrow_mut();
+                if self.0.dc.get() {
+                    log.push(Data(bytes.to_vec()));
+                } else {
+                    log.extend(bytes.iter().map(|&b| Cmd(b)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Dc(Log);
+
+impl digital::ErrorType for Dc {
+    type Error = Infallible;
+}
+
+impl OutputPin for Dc {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.dc.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.dc.set(true);
+        Ok(())
+    }
+}
+
+struct Rst;
+
+impl digital::ErrorType for Rst {
+    type Error = Infallible;
+}
+
+impl OutputPin for Rst {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+struct Busy {
+    log: Log,
+    busy_high: bool,
+}
+
+impl digital::ErrorType for Busy {
+    type Error = Infallible;
+}
+
+impl InputPin for Busy {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        let polls = self.log.busy_polls.get();
+        self.log.busy_polls.set(polls.saturating_sub(1));
+        Ok(if polls > 0 {
+            self.busy_high
+        } else {
+            !self.busy_high
+        })
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+type Panel = EPaper<Bus, Dc, Rst, Busy>;
+
+fn panel(controller: EpdController, width: usize, height: usize) -> (Panel, Log) {
+    let log = Log::default();
+    let busy = Busy {
+        log: log.clone(),
+        busy_high: controller == EpdController::Ssd1680,
+    };
+    let panel = EPaper::new(
+        Bus(log.clone()),
+        Dc(log.clone()),
+        Rst,
+        busy,
+        controller,
+        width,
+        height,
+    );
+    (panel, log)
+}
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+const BLACK: Color = Color(0, 0, 0, 255);
+
+#[test]
+fn first_refresh_writes_both_rams_and_waits_for_busy() {
+    let (mut epd, log) = panel(EpdController::Ssd1680, 16, 2);
+    log.busy_polls.set(3);
+    assert_eq!(epd.refresh(0, &mut NoDelay).unwrap(), Refresh::Full);
+    assert_eq!(log.busy_polls.get(), 0);
+    let window = [
+        Cmd(0x44),
+        Data(vec![0, 1]),
+        Cmd(0x45),
+        Data(vec![0, 0, 1, 0]),
+        Cmd(0x4E),
+        Data(vec![0]),
+        Cmd(0x4F),
+        Data(vec![0, 0]),
+    ];
+    let white = Data(vec![0xFF; 4]);
+    let mut expected = window.to_vec();
+    expected.extend([Cmd(0x24), white.clone()]);
+    expected.extend(window);
+    expected.extend([Cmd(0x26), white, Cmd(0x22), Data(vec![0xF7]), Cmd(0x20)]);
+    assert_eq!(log.take(), expected);
+}
+
+#[test]
+fn partial_refresh_sends_byte_aligned_window() {
+    let (mut epd, log) = panel(EpdController::Ssd1680, 32, 4);
+    epd.refresh(0, &mut NoDelay).unwrap();
+    log.take();
+    epd.flush(rect(9, 1, 2, 2), &[BLACK; 4]);
+    assert_eq!(epd.refresh(5000, &mut NoDelay).unwrap(), Refresh::Partial);
+    let ops = log.take();
+    assert_eq!(
+        ops[..10],
+        [
+            Cmd(0x44),
+            Data(vec![1, 1]),
+            Cmd(0x45),
+            Data(vec![1, 0, 2, 0]),
+            Cmd(0x4E),
+            Data(vec![1]),
+            Cmd(0x4F),
+            Data(vec![1, 0]),
+            Cmd(0x24),
+            Data(vec![0x9F, 0x9F]),
+        ]
+    );
+    assert_eq!(ops[10..13], [Cmd(0x22), Data(vec![0xFF]), Cmd(0x20)]);
+    assert_eq!(ops.last(), Some(&Data(vec![0x9F, 0x9F])));
+}
+
+#[test]
+fn refreshes_are_rate_limited_and_periodically_full() {
+    let (epd, _log) = panel(EpdController::Ssd1680, 8, 8);
+    let mut epd = epd.with_min_interval(500).with_full_every(2);
+    let draw_and_refresh = |epd: &mut Panel, now| {
+        epd.flush(rect(0, 0, 1, 1), &[BLACK]);
+        epd.refresh(now, &mut NoDelay).unwrap()
+    };
+    assert_eq!(draw_and_refresh(&mut epd, 0), Refresh::Full);
+    assert_eq!(draw_and_refresh(&mut epd, 100), Refresh::Skipped);
+    assert_eq!(epd.refresh(499, &mut NoDelay).unwrap(), Refresh::Skipped);
+    assert_eq!(epd.refresh(500, &mut NoDelay).unwrap(), Refresh::Partial);
+    assert_eq!(epd.refresh(5000, &mut NoDelay).unwrap(), Refresh::Skipped);
+    assert_eq!(draw_and_refresh(&mut epd, 1000), Refresh::Partial);
+    assert_eq!(draw_and_refresh(&mut epd, 1500), Refresh::Full);
+    epd.request_full();
+    assert_eq!(epd.refresh(2000, &mut NoDelay).unwrap(), Refresh::Full);
+}
+
+#[test]
+fn stuck_busy_pin_times_out() {
+    let (epd, log) = panel(EpdController::Ssd1680, 8, 8);
+    let mut epd = epd.with_busy_timeout(3);
+    log.busy_polls.set(u32::MAX);
+    assert_eq!(epd.init(&mut NoDelay), Err(EpdError::Timeout));
+    assert_eq!(log.busy_polls.get(), u32::MAX - 4);
+}
+
+#[test]
+fn uc8151_partial_refresh_uses_partial_window_and_inverts_data() {
+    let (mut epd, log) = panel(EpdController::Uc8151, 16, 2);
+    assert_eq!(epd.refresh(0, &mut NoDelay).unwrap(), Refresh::Full);
+    assert_eq!(log.take(), [Cmd(0x13), Data(vec![0; 4]), Cmd(0x12)]);
+    epd.flush(rect(12, 1, 1, 1), &[BLACK]);
+    assert_eq!(epd.refresh(1000, &mut NoDelay).unwrap(), Refresh::Partial);
+    assert_eq!(
+        log.take(),
+        [
+            Cmd(0x91),
+            Cmd(0x90),
+            Data(vec![8, 15, 0, 1, 0, 1, 0x01]),
+            Cmd(0x13),
+            Data(vec![0x08]),
+            Cmd(0x12),
+            Cmd(0x92),
+        ]
+    );
+}
+
+#[test]
+fn failed_refresh_keeps_changes_for_the_next_one() {
+    let (mut epd, log) = panel(EpdController::Ssd1680, 16, 2);
+    epd.refresh(0, &mut NoDelay).unwrap();
+    epd.flush(rect(0, 0, 1, 1), &[BLACK]);
+    log.fail.set(true);
+    assert_eq!(
+        epd.refresh(1000, &mut NoDelay),
+        Err(EpdError::Spi(spi::ErrorKind::Other))
+    );
+    assert!(epd.buffer().dirty().is_some());
+    log.fail.set(false);
+    log.take();
+    assert_eq!(epd.refresh(1000, &mut NoDelay).unwrap(), Refresh::Partial);
+    assert!(log.take().contains(&Data(vec![0x7F])));
+    assert!(epd.buffer().dirty().is_none());
+}
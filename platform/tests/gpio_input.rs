@@ -0,0 +1,234 @@
+//! Tests for the rotary encoder, keypad and button drivers against mocked
+//! pins.
+#![cfg(feature = "gpio_input")]
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+use rlvgl_core::event::{Event, Key};
+use rlvgl_platform::input::InputDevice;
+use rlvgl_platform::{Encoder, KeyMatrix, Keypad, ScreenButtons};
+
+/// Input pin whose level tests set through a shared handle.
+#[derive(Clone, Default)]
+struct Pin(Rc<Cell<bool>>);
+
+impl Pin {
+    fn high() -> Self {
+        let pin = Self::default();
+        pin.set(true);
+        pin
+    }
+
+    fn set(&self, high: bool) {
+        self.0.set(high);
+    }
+}
+
+impl ErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl InputPin for Pin {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        Ok(self.0.get())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        Ok(!self.0.get())
+    }
+}
+
+fn drain(input: &mut impl InputDevice) -> Vec<Event> {
+    std::iter::from_fn(|| input.poll()).collect()
+}
+
+/// Drive the contacts through one detent clockwise (A leading B) or
+/// counter-clockwise, polling after every transition.
+fn turn(enc: &mut impl InputDevice, a: &Pin, b: &Pin, clockwise: bool, out: &mut Vec<Event>) {
+    let seq = [(true, false), (true, true), (false, true), (false, false)];
+    for (x, y) in seq {
+        if clockwise {
+            a.set(x);
+            b.set(y);
+        } else {
+            a.set(y);
+            b.set(x);
+        }
+        out.extend(enc.poll());
+    }
+}
+
+#[test]
+fn encoder_counts_detents_in_both_directions() {
+    let (a, b) = (Pin::default(), Pin::default());
+    let mut enc = Encoder::new(a.clone(), b.clone());
+    let mut events = drain(&mut enc);
+    turn(&mut enc, &a, &b, true, &mut events);
+    turn(&mut enc, &a, &b, false, &mut events);
+    assert_eq!(
+        events,
+        [Event::Encoder { steps: 1 }, Event::Encoder { steps: -1 }]
+    );
+    // Bounce on one contact moves back and forth without a detent.
+    for high in [true, false, true, false] {
+        a.set(high);
+        assert_eq!(enc.poll(), None);
+    }
+}
+
+#[test]
+fn encoder_accelerates_fast_turns() {
+    let (a, b) = (Pin::default(), Pin::default());
+    let mut enc = Encoder::new(a.clone(), b.clone())
+        .with_acceleration(8, 5)
+        .reversed();
+    enc.poll();
+    let mut events = Vec::new();
+    turn(&mut enc, &a, &b, true, &mut events);
+    turn(&mut enc, &a, &b, true, &mut events);
+    for _ in 0..10 {
+        enc.poll();
+    }
+    turn(&mut enc, &a, &b, true, &mut events);
+    assert_eq!(
+        events,
+        [
+            Event::Encoder { steps: -1 },
+            Event::Encoder { steps: -5 },
+            Event::Encoder { steps: -1 },
+        ]
+    );
+}
+
+#[test]
+fn encoder_button_is_debounced_enter() {
+    let (a, b, btn) = (Pin::default(), Pin::default(), Pin::high());
+    let mut enc = Encoder::new(a, b).with_button(btn.clone()).with_debounce(2);
+    btn.set(false);
+    assert_eq!(enc.poll(), None);
+    btn.set(true);
+    assert_eq!(enc.poll(), None);
+    btn.set(false);
+    assert_eq!(enc.poll(), None);
+    assert_eq!(enc.poll(), Some(Event::KeyDown { key: Key::Enter }));
+    btn.set(true);
+    enc.poll();
+    assert_eq!(enc.poll(), Some(Event::KeyUp { key: Key::Enter }));
+}
+
+#[test]
+fn keypad_maps_pins_to_keys() {
+    let pins = [Pin::high(), Pin::high(), Pin::high()];
+    let mut pad = Keypad::new(
+        pins.clone(),
+        [Key::ArrowUp, Key::ArrowDown, Key::Character('#')],
+    )
+    .with_debounce(1);
+    pins[0].set(false);
+    pins[2].set(false);
+    assert_eq!(
+        drain(&mut pad),
+        [
+            Event::KeyDown { key: Key::ArrowUp },
+            Event::KeyDown {
+                key: Key::Character('#')
+            },
+        ]
+    );
+    assert!(pad.is_pressed(&Key::ArrowUp));
+    pins[0].set(true);
+    assert_eq!(drain(&mut pad), [Event::KeyUp { key: Key::ArrowUp }]);
+    assert!(!pad.is_pressed(&Key::ArrowUp));
+}
+
+/// Button matrix wiring: the columns read low where a pressed button joins
+/// them to the row driven low.
+#[derive(Clone, Default)]
+struct Matrix {
+    pressed: Rc<RefCell<HashSet<(usize, usize)>>>,
+    low_rows: Rc<RefCell<HashSet<usize>>>,
+}
+
+struct Row(usize, Matrix);
+struct Col(usize, Matrix);
+
+impl ErrorType for Row {
+    type Error = Infallible;
+}
+
+impl OutputPin for Row {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.1.low_rows.borrow_mut().insert(self.0);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.1.low_rows.borrow_mut().remove(&self.0);
+        Ok(())
+    }
+}
+
+impl ErrorType for Col {
+    type Error = Infallible;
+}
+
+impl InputPin for Col {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        self.is_low().map(|low| !low)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        let pressed = self.1.pressed.borrow();
+        Ok(self
+            .1
+            .low_rows
+            .borrow()
+            .iter()
+            .any(|&r| pressed.contains(&(r, self.0))))
+    }
+}
+
+#[test]
+fn key_matrix_scans_one_row_at_a_time() {
+    let m = Matrix::default();
+    m.low_rows.borrow_mut().extend([0, 1]);
+    let mut keys = KeyMatrix::new(
+        [Row(0, m.clone()), Row(1, m.clone())],
+        [Col(0, m.clone()), Col(1, m.clone()), Col(2, m.clone())],
+        [
+            [Key::Character('1'), Key::Character('2'), Key::ArrowUp],
+            [Key::Escape, Key::Character('0'), Key::Enter],
+        ],
+    )
+    .with_debounce(2);
+    assert!(m.low_rows.borrow().is_empty());
+    m.pressed.borrow_mut().extend([(0, 2), (1, 0)]);
+    assert_eq!(keys.poll(), None);
+    assert_eq!(
+        drain(&mut keys),
+        [
+            Event::KeyDown { key: Key::ArrowUp },
+            Event::KeyDown { key: Key::Escape },
+        ]
+    );
+    assert!(keys.is_pressed(&Key::Escape));
+    assert!(!keys.is_pressed(&Key::Character('2')));
+    m.pressed.borrow_mut().remove(&(0, 2));
+    assert_eq!(keys.poll(), None);
+    assert_eq!(keys.poll(), Some(Event::KeyUp { key: Key::ArrowUp }));
+}
+
+#[test]
+fn screen_buttons_press_their_points() {
+    let pins = [Pin::high(), Pin::high()];
+    let mut buttons = ScreenButtons::new(pins.clone(), [(10, 200), (230, 200)]).with_debounce(1);
+    pins[1].set(false);
+    assert_eq!(buttons.poll(), Some(Event::PointerDown { x: 230, y: 200 }));
+    buttons.set_point(1, (220, 190));
+    pins[1].set(true);
+    assert_eq!(buttons.poll(), Some(Event::PointerUp { x: 220, y: 190 }));
+    assert_eq!(buttons.poll(), None);
+}
@@ -0,0 +1,96 @@
+//! Tests for asset hot-reload over a Unix socket.
+#![cfg(all(feature = "hot_reload", unix))]
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use rlvgl_platform::hot_reload::{AssetKind, Change, HotReload, ReloadListener, send};
+
+/// Asset names and contents recorded by a watch callback.
+type Seen = Rc<RefCell<Vec<(String, Vec<u8>)>>>;
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlvgl-reload-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("icons")).unwrap();
+    dir
+}
+
+#[test]
+fn classifies_assets_by_name() {
+    assert_eq!(AssetKind::of("icons/ok.PNG"), AssetKind::Image);
+    assert_eq!(AssetKind::of("fonts/ui.ttf"), AssetKind::Font);
+    assert_eq!(AssetKind::of("fonts/ui-16.bin"), AssetKind::Font);
+    assert_eq!(AssetKind::of("media/clip.bin"), AssetKind::Other);
+    assert_eq!(AssetKind::of("screens/main.yml"), AssetKind::Layout);
+}
+
+#[test]
+fn listener_collects_names_from_clients() {
+    let dir = scratch("listener");
+    let socket = dir.join("sim.sock");
+    let mut listener = ReloadListener::bind(&socket).unwrap();
+    assert!(listener.poll().is_empty());
+    assert!(ReloadListener::bind(&socket).is_err());
+
+    send(
+        &socket,
+        &["icons/a.png", "./icons/a.png", "../secret", "fonts\\ui.ttf"],
+    )
+    .unwrap();
+    send(&socket, &["screens/main.json"]).unwrap();
+    assert_eq!(
+        listener.poll(),
+        ["icons/a.png", "fonts/ui.ttf", "screens/main.json"]
+    );
+    assert!(listener.poll().is_empty());
+    assert!(send(&socket, &["a\nb"]).is_err());
+
+    drop(listener);
+    assert!(!socket.exists());
+    assert!(send(&socket, &["icons/a.png"]).is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn reloads_watched_assets() {
+    let dir = scratch("watch");
+    fs::write(dir.join("icons/a.png"), b"old").unwrap();
+    let mut reload = HotReload::new(&dir, dir.join("sim.sock")).unwrap();
+    assert_eq!(reload.load("icons/a.png").unwrap(), b"old");
+    assert!(reload.load("../x").is_err());
+
+    let seen: Seen = Rc::default();
+    let log = seen.clone();
+    reload.watch("icons/", move |c: &Change, data: &[u8]| {
+        log.borrow_mut().push((c.name.clone(), data.to_vec()));
+    });
+    let exact = Rc::new(RefCell::new(0));
+    let count = exact.clone();
+    reload.watch("icons/a.png", move |_: &Change, _: &[u8]| {
+        *count.borrow_mut() += 1
+    });
+
+    fs::write(dir.join("icons/a.png"), b"new").unwrap();
+    send(
+        reload.socket(),
+        &["icons/a.png", "icons/missing.png", "fonts/ui.ttf"],
+    )
+    .unwrap();
+    let changes = reload.poll();
+    assert_eq!(
+        changes,
+        [Change {
+            name: "icons/a.png".into(),
+            kind: AssetKind::Image
+        }]
+    );
+    assert_eq!(
+        *seen.borrow(),
+        [("icons/a.png".to_string(), b"new".to_vec())]
+    );
+    assert_eq!(*exact.borrow(), 1);
+    drop(reload);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -1,6 +1,9 @@
 //! Tests for input device implementations.
 use rlvgl_core::event::Event;
-use rlvgl_platform::input::{DummyInput, InputDevice};
+use rlvgl_core::event::{Gesture, GesturePhase};
+use rlvgl_platform::input::{
+    Debouncer, DummyInput, GestureInput, InputDevice, PointerTracker, TouchPoint, TouchTracker,
+};
 
 struct VecInput {
     events: Vec<Event>,
@@ -34,3 +37,145 @@ fn vec_input_yields_events() {
     assert_eq!(input.poll(), Some(Event::PointerUp { x: 1, y: 2 }));
     assert_eq!(input.poll(), None);
 }
+
+#[test]
+fn pointer_tracker_turns_samples_into_events() {
+    let mut tracker = PointerTracker::new();
+    assert_eq!(tracker.update(None), None);
+    assert_eq!(
+        tracker.update(Some((3, 4))),
+        Some(Event::PointerDown { x: 3, y: 4 })
+    );
+    assert_eq!(tracker.update(Some((3, 4))), None);
+    assert_eq!(
+        tracker.update(Some((5, 4))),
+        Some(Event::PointerMove { x: 5, y: 4 })
+    );
+    assert_eq!(tracker.position(), Some((5, 4)));
+    assert_eq!(tracker.update(None), Some(Event::PointerUp { x: 5, y: 4 }));
+    assert_eq!(tracker.position(), None);
+}
+
+fn point(id: u8, x: u16, y: u16) -> TouchPoint {
+    TouchPoint { id, x, y }
+}
+
+fn drain(tracker: &mut TouchTracker) -> Vec<Event> {
+    std::iter::from_fn(|| tracker.next_event()).collect()
+}
+
+#[test]
+fn touch_tracker_reports_every_finger_and_a_primary_pointer() {
+    let mut tracker = TouchTracker::new();
+    tracker.update(&[point(4, 10, 10)]);
+    assert_eq!(
+        drain(&mut tracker),
+        [
+            Event::TouchDown {
+                id: 4,
+                x: 10,
+                y: 10
+            },
+            Event::PointerDown { x: 10, y: 10 },
+        ]
+    );
+    tracker.update(&[point(4, 10, 10), point(7, 50, 60)]);
+    assert_eq!(
+        drain(&mut tracker),
+        [Event::TouchDown {
+            id: 7,
+            x: 50,
+            y: 60
+        }]
+    );
+    tracker.update(&[point(7, 55, 60)]);
+    assert_eq!(
+        drain(&mut tracker),
+        [
+            Event::TouchUp {
+                id: 4,
+                x: 10,
+                y: 10
+            },
+            Event::PointerUp { x: 10, y: 10 },
+            Event::TouchMove {
+                id: 7,
+                x: 55,
+                y: 60
+            },
+        ]
+    );
+    // The remaining finger does not take over the pointer.
+    tracker.update(&[point(7, 56, 60)]);
+    assert_eq!(
+        drain(&mut tracker),
+        [Event::TouchMove {
+            id: 7,
+            x: 56,
+            y: 60
+        }]
+    );
+    tracker.update(&[]);
+    assert_eq!(
+        drain(&mut tracker),
+        [Event::TouchUp {
+            id: 7,
+            x: 56,
+            y: 60
+        }]
+    );
+    assert!(tracker.points().is_empty());
+}
+
+#[test]
+fn gesture_input_follows_touch_events_with_gestures() {
+    let mut input = GestureInput::new(VecInput {
+        events: vec![
+            Event::TouchDown { id: 0, x: 0, y: 0 },
+            Event::TouchDown { id: 1, x: 10, y: 0 },
+            Event::TouchMove { id: 1, x: 20, y: 0 },
+        ],
+    });
+    let events: Vec<_> = std::iter::from_fn(|| input.poll()).collect();
+    let gesture = |phase, x, scale| {
+        Event::Gesture(Gesture {
+            phase,
+            x,
+            y: 0,
+            scale,
+            angle: 0,
+        })
+    };
+    assert_eq!(
+        events,
+        [
+            Event::TouchDown { id: 0, x: 0, y: 0 },
+            Event::TouchDown { id: 1, x: 10, y: 0 },
+            gesture(GesturePhase::Start, 5, 256),
+            Event::TouchMove { id: 1, x: 20, y: 0 },
+            gesture(GesturePhase::Update, 10, 512),
+        ]
+    );
+}
+
+#[test]
+fn debouncer_waits_for_a_stable_level() {
+    let mut d = Debouncer::new(3);
+    let samples = [true, false, true, true, true, true, false, false, false];
+    let changes: Vec<_> = samples.iter().map(|&s| d.update(s)).collect();
+    assert_eq!(
+        changes,
+        [
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            Some(false)
+        ]
+    );
+    assert!(!d.is_pressed());
+}
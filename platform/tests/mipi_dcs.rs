@@ -0,0 +1,282 @@
+//! Tests for the MIPI-DCS SPI panel driver against a recording bus.
+#![cfg(feature = "mipi_dcs")]
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{self, Operation, SpiDevice};
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::display::DisplayDriver;
+use rlvgl_platform::{Orientation, PanelConfig, Rotation, SpiPanel};
+
+#[derive(Debug, PartialEq)]
+enum Op {
+    Reset(bool),
+    Cmd(u8),
+    Data(Vec<u8>),
+}
+
+use Op::{Cmd, Data};
+
+#[derive(Default, Clone)]
+struct Log {
+    ops: Rc<RefCell<Vec<Op>>>,
+    dc: Rc<Cell<bool>>,
+}
+
+impl Log {
+    fn take(&self) -> Vec<Op> {
+        self.ops.take()
+    }
+
+    fn record(&self, bytes: &[u8]) {
+        let mut ops = self.ops.borrow_mut();
+        if self.dc.get() {
+            ops.push(Data(bytes.to_vec()));
+        } else {
+            ops.extend(bytes.iter().map(|&b| Cmd(b)));
+        }
+    }
+}
+
+struct Bus(Log);
+
+impl spi::ErrorType for Bus {
+    type Error = Infallible;
+}
+
+impl SpiDevice for Bus {
+    fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        for op in ops {
+            if let Operation::Write(bytes) = op {
+                self.0.record(bytes);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mipi_dcs_async")]
+impl embedded_hal_async::spi::SpiDevice for Bus {
+    async fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        SpiDevice::transaction(self, ops)
+    }
+}
+
+struct Dc(Log);
+
+impl digital::ErrorType for Dc {
+    type Error = Infallible;
+}
+
+impl OutputPin for Dc {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.dc.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.dc.set(true);
+        Ok(())
+    }
+}
+
+struct Rst(Log);
+
+impl digital::ErrorType for Rst {
+    type Error = Infallible;
+}
+
+impl OutputPin for Rst {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.ops.borrow_mut().push(Op::Reset(false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.ops.borrow_mut().push(Op::Reset(true));
+        Ok(())
+    }
+}
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "mipi_dcs_async")]
+impl embedded_hal_async::delay::DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+fn panel(config: PanelConfig) -> (SpiPanel<Bus, Dc, Rst>, Log) {
+    let log = Log::default();
+    let panel = SpiPanel::new(Bus(log.clone()), Dc(log.clone()), Rst(log.clone()), config);
+    (panel, log)
+}
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[cfg(feature = "st7789")]
+#[test]
+#[allow(deprecated)]
+fn deprecated_st7789_display_flushes_through_spi_panel() {
+    use rlvgl_platform::St7789Display;
+
+    let log = Log::default();
+    let mut lcd = St7789Display::new(Bus(log.clone()), Dc(log.clone()), 240, 240).unwrap();
+    lcd.flush(rect(1, 2, 1, 1), &[Color(255, 0, 0, 255)]);
+    assert_eq!(
+        log.take(),
+        [
+            Cmd(0x2A),
+            Data(vec![0, 1, 0, 1]),
+            Cmd(0x2B),
+            Data(vec![0, 2, 0, 2]),
+            Cmd(0x2C),
+            Data(vec![0xF8, 0x00]),
+        ]
+    );
+    lcd.panel().init(&mut NoDelay).unwrap();
+    assert_eq!(log.take()[0], Cmd(0x01));
+}
+
+#[cfg(feature = "st7789")]
+#[test]
+#[allow(deprecated)]
+fn st7789_module_path_still_resolves() {
+    let log = Log::default();
+    let mut lcd: rlvgl_platform::st7789::St7789Display<Bus, Dc> =
+        rlvgl_platform::St7789Display::new(Bus(log.clone()), Dc(log.clone()), 1, 1).unwrap();
+    lcd.flush(rect(0, 0, 1, 1), &[Color(0, 0, 255, 255)]);
+    assert_eq!(log.take().last(), Some(&Data(vec![0x00, 0x1F])));
+}
+
+#[test]
+fn st7789_init_sequence() {
+    let (mut panel, log) = panel(PanelConfig::ST7789_240X240);
+    panel.init(&mut NoDelay).unwrap();
+    assert_eq!(
+        log.take(),
+        [
+            Op::Reset(false),
+            Op::Reset(true),
+            Cmd(0x01),
+            Cmd(0x11),
+            Cmd(0x3A),
+            Data(vec![0x55]),
+            Cmd(0x36),
+            Data(vec![0x00]),
+            Cmd(0x21),
+            Cmd(0x13),
+            Cmd(0x29),
+        ]
+    );
+}
+
+#[test]
+fn ili9341_init_mirrors_columns_in_bgr() {
+    let (mut panel, log) = panel(PanelConfig::ILI9341_240X320);
+    panel.init(&mut NoDelay).unwrap();
+    let ops = log.take();
+    let madctl = ops.iter().position(|op| *op == Cmd(0x36)).unwrap();
+    assert_eq!(ops[madctl + 1], Data(vec![0x48]));
+    assert_eq!(ops.iter().filter(|op| matches!(op, Cmd(_))).count(), 23);
+}
+
+#[test]
+fn flush_addresses_offset_window_and_packs_rgb565() {
+    let (mut panel, log) = panel(PanelConfig::ST7789_135X240);
+    panel.flush(
+        rect(1, 2, 2, 1),
+        &[Color(255, 0, 0, 255), Color(0, 255, 0, 255)],
+    );
+    assert_eq!(
+        log.take(),
+        [
+            Cmd(0x2A),
+            Data(vec![0, 53, 0, 54]),
+            Cmd(0x2B),
+            Data(vec![0, 42, 0, 42]),
+            Cmd(0x2C),
+            Data(vec![0xF8, 0x00, 0x07, 0xE0]),
+        ]
+    );
+}
+
+#[test]
+fn rotation_moves_the_memory_offset() {
+    let (mut panel, log) = panel(PanelConfig::ST7789_240X240);
+    assert!(DisplayDriver::set_orientation(
+        &mut panel,
+        Orientation::new(Rotation::Deg180)
+    ));
+    panel.flush(rect(0, 0, 1, 1), &[Color(0, 0, 0, 255)]);
+    let ops = log.take();
+    assert_eq!(ops[..2], [Cmd(0x36), Data(vec![0xC0])]);
+    assert_eq!(ops[4..6], [Cmd(0x2B), Data(vec![0, 80, 0, 80])]);
+
+    panel
+        .set_orientation(Orientation::new(Rotation::Deg90))
+        .unwrap();
+    assert_eq!(panel.size(), (240, 240));
+    assert_eq!(log.take(), [Cmd(0x36), Data(vec![0x60])]);
+}
+
+#[test]
+fn large_areas_stream_in_line_buffer_chunks() {
+    let (mut panel, log) = panel(PanelConfig::ILI9488_320X480);
+    let colors = vec![Color(0xFF, 0x80, 0x01, 255); 1000];
+    panel.write_area(rect(0, 0, 100, 10), &colors).unwrap();
+    let chunks: Vec<_> = log
+        .take()
+        .into_iter()
+        .skip(5)
+        .map(|op| match op {
+            Data(bytes) => {
+                assert!(bytes.chunks(3).all(|px| px == [0xFC, 0x80, 0x00]));
+                bytes.len()
+            }
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+    assert_eq!(chunks, [1440, 1440, 120]);
+}
+
+#[cfg(feature = "mipi_dcs_async")]
+#[test]
+fn async_writes_match_blocking_ones() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn block_on<F: Future>(f: F) -> F::Output {
+        let mut f = pin!(f);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = f.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    let colors = vec![Color(10, 20, 30, 255); 300];
+    let (mut blocking, expected) = panel(PanelConfig::ILI9341_240X320);
+    blocking.init(&mut NoDelay).unwrap();
+    blocking.write_area(rect(5, 6, 30, 10), &colors).unwrap();
+
+    let (mut panel, log) = panel(PanelConfig::ILI9341_240X320);
+    block_on(panel.init_async(&mut NoDelay)).unwrap();
+    block_on(panel.write_area_async(rect(5, 6, 30, 10), &colors)).unwrap();
+    assert_eq!(log.take(), expected.take());
+}
@@ -0,0 +1,83 @@
+//! Tests for 1-bit framebuffers and color conversion.
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::{MonoBuffer, MonoLayout, MonoMode};
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+fn gray(v: u8) -> Color {
+    Color(v, v, v, 255)
+}
+
+#[test]
+fn layouts_pack_bits_like_their_controllers() {
+    let mut pages = MonoBuffer::new(4, 12, MonoLayout::Pages);
+    assert_eq!(pages.data().len(), 8);
+    pages.set(1, 0, true);
+    pages.set(1, 9, true);
+    assert_eq!(pages.data(), [0, 0x01, 0, 0, 0, 0x02, 0, 0]);
+
+    let mut rows = MonoBuffer::new(10, 2, MonoLayout::Rows);
+    assert_eq!(rows.stride(), 2);
+    rows.set(0, 0, true);
+    rows.set(9, 1, true);
+    assert_eq!(rows.data(), [0x80, 0, 0, 0x40]);
+    assert!(rows.get(9, 1));
+    assert!(!rows.get(8, 1));
+}
+
+#[test]
+fn threshold_uses_perceived_brightness() {
+    let mut buf = MonoBuffer::new(3, 1, MonoLayout::Rows);
+    buf.set_mode(MonoMode::Threshold(100));
+    buf.draw(
+        rect(0, 0, 3, 1),
+        &[Color(0, 255, 0, 255), Color(0, 0, 255, 255), gray(100)],
+    );
+    assert!(buf.get(0, 0));
+    assert!(!buf.get(1, 0));
+    assert!(buf.get(2, 0));
+}
+
+#[test]
+fn dither_lights_a_share_matching_brightness() {
+    for (level, lit) in [(0, 0), (64, 4), (128, 8), (192, 12), (255, 16)] {
+        let mut buf = MonoBuffer::new(4, 4, MonoLayout::Pages);
+        buf.set_mode(MonoMode::Dither);
+        buf.draw(rect(0, 0, 4, 4), &[gray(level); 16]);
+        let count = (0..16).filter(|i| buf.get(i % 4, i / 4)).count();
+        assert_eq!(count, lit, "level {level}");
+    }
+}
+
+#[test]
+fn dither_pattern_does_not_depend_on_draw_split() {
+    let colors = vec![gray(90); 64];
+    let mut whole = MonoBuffer::new(8, 8, MonoLayout::Rows);
+    whole.set_mode(MonoMode::Dither);
+    whole.draw(rect(0, 0, 8, 8), &colors);
+
+    let mut bands = MonoBuffer::new(8, 8, MonoLayout::Rows);
+    bands.set_mode(MonoMode::Dither);
+    bands.draw(rect(0, 0, 8, 3), &colors[..24]);
+    bands.draw(rect(0, 3, 8, 5), &colors[24..]);
+    assert_eq!(whole.data(), bands.data());
+}
+
+#[test]
+fn draw_clips_and_accumulates_dirty_area() {
+    let mut buf = MonoBuffer::new(8, 8, MonoLayout::Pages);
+    assert!(buf.take_dirty().is_none());
+    buf.draw(rect(-2, 6, 4, 4), &[gray(255); 16]);
+    buf.draw(rect(5, 1, 1, 1), &[gray(255)]);
+    assert!(buf.get(0, 6) && buf.get(1, 7) && buf.get(5, 1));
+    let dirty = buf.take_dirty().unwrap();
+    assert_eq!((dirty.x, dirty.y, dirty.width, dirty.height), (0, 1, 6, 7));
+    assert!(buf.take_dirty().is_none());
+}
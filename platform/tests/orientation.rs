@@ -0,0 +1,163 @@
+//! Tests for display rotation and mirroring.
+use rlvgl_core::{
+    WidgetNode,
+    event::Event,
+    renderer::Renderer,
+    widget::{Color, Rect, Widget},
+};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_platform::input::{InputDevice, OrientedInput};
+use rlvgl_platform::{Display, Orientation, RenderMode, Rotation};
+
+const W: usize = 8;
+const H: usize = 5;
+
+const ORIENTATIONS: [Rotation; 4] = [
+    Rotation::Deg0,
+    Rotation::Deg90,
+    Rotation::Deg180,
+    Rotation::Deg270,
+];
+
+fn all() -> impl Iterator<Item = Orientation> {
+    ORIENTATIONS.into_iter().flat_map(|r| {
+        [(false, false), (true, false), (false, true), (true, true)]
+            .map(|(h, v)| Orientation::new(r).flipped(h, v))
+    })
+}
+
+/// Paints every logical pixel in a color encoding its position.
+struct Gradient;
+
+impl Widget for Gradient {
+    fn bounds(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 100,
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        for y in 0..10 {
+            for x in 0..10 {
+                let rect = Rect {
+                    x,
+                    y,
+                    width: 1,
+                    height: 1,
+                };
+                renderer.fill_rect(rect, Color(x as u8, y as u8, 0, 255));
+            }
+        }
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+}
+
+#[test]
+fn logical_and_panel_points_round_trip() {
+    for o in all() {
+        let (w, h) = o.logical_size(W, H);
+        let size = (w as i32, h as i32);
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let (px, py) = o.to_physical((x, y), size);
+                assert!((0..W as i32).contains(&px) && (0..H as i32).contains(&py));
+                assert_eq!(o.to_logical((px, py), size), (x, y), "{o:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn software_rotation_writes_panel_pixels() {
+    for o in all() {
+        let root = WidgetNode::new(Gradient, Vec::new());
+        let mut buf = [Color(0, 0, 0, 0); 2 * W];
+        let mut display = Display::new(
+            BufferDisplay::new(W, H),
+            W,
+            H,
+            RenderMode::Partial,
+            &mut buf,
+            None,
+        );
+        display.set_orientation(o);
+        assert_eq!((display.width(), display.height()), o.logical_size(W, H));
+        display.refresh(&root);
+        let size = (display.width() as i32, display.height() as i32);
+        let panel = &display.driver().buffer;
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let (px, py) = o.to_physical((x, y), size);
+                assert_eq!(
+                    panel[py as usize * W + px as usize],
+                    Color(x as u8, y as u8, 0, 255),
+                    "{o:?} at ({x}, {y})"
+                );
+            }
+        }
+    }
+}
+
+/// Driver rotating in hardware that records the areas it receives.
+struct HwRotating {
+    orientation: Option<Orientation>,
+    areas: Vec<(i32, i32, i32, i32)>,
+}
+
+impl DisplayDriver for HwRotating {
+    fn flush(&mut self, area: Rect, _colors: &[Color]) {
+        self.areas.push((area.x, area.y, area.width, area.height));
+    }
+
+    fn set_orientation(&mut self, orientation: Orientation) -> bool {
+        self.orientation = Some(orientation);
+        true
+    }
+}
+
+#[test]
+fn hardware_rotation_receives_logical_areas() {
+    let root = WidgetNode::new(Gradient, Vec::new());
+    let mut buf = [Color(0, 0, 0, 0); W * H];
+    let driver = HwRotating {
+        orientation: None,
+        areas: Vec::new(),
+    };
+    let mut display = Display::new(driver, W, H, RenderMode::Full, &mut buf, None);
+    let o = Orientation::new(Rotation::Deg270);
+    display.set_orientation(o);
+    display.refresh(&root);
+    assert_eq!(display.driver().orientation, Some(o));
+    assert_eq!(display.driver().areas, [(0, 0, H as i32, W as i32)]);
+}
+
+struct Touches(Vec<Event>);
+
+impl InputDevice for Touches {
+    fn poll(&mut self) -> Option<Event> {
+        (!self.0.is_empty()).then(|| self.0.remove(0))
+    }
+}
+
+#[test]
+fn oriented_input_maps_touches_to_logical_space() {
+    let touches = Touches(vec![
+        Event::PointerDown { x: 7, y: 0 },
+        Event::Tick,
+        Event::PointerUp { x: 0, y: 4 },
+    ]);
+    let o = Orientation::new(Rotation::Deg90);
+    let mut input = OrientedInput::new(touches, W, H, o);
+    // Turned clockwise, the logical top-left corner sits at the panel's
+    // top-right one.
+    assert_eq!(input.poll(), Some(Event::PointerDown { x: 0, y: 0 }));
+    assert_eq!(input.poll(), Some(Event::Tick));
+    assert_eq!(input.poll(), Some(Event::PointerUp { x: 4, y: 7 }));
+    assert_eq!(input.poll(), None);
+}
@@ -0,0 +1,288 @@
+//! Tests for event recording and replay.
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use rlvgl_core::WidgetNode;
+use rlvgl_core::event::{Event, Gesture, GesturePhase, Key};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::input::{DummyInput, InputDevice};
+use rlvgl_platform::record::{DecodeError, TimedEvent};
+use rlvgl_platform::{BlitterRenderer, CpuBlitter, PixelFmt, Recorder, Recording, Replay, Surface};
+
+struct Events(Vec<Event>);
+
+impl InputDevice for Events {
+    fn poll(&mut self) -> Option<Event> {
+        (!self.0.is_empty()).then(|| self.0.remove(0))
+    }
+}
+
+fn every_event() -> Vec<Event> {
+    vec![
+        Event::Tick,
+        Event::PointerDown { x: -3, y: 70000 },
+        Event::PointerMove { x: 4, y: 5 },
+        Event::PointerUp { x: 4, y: 5 },
+        Event::TouchDown { id: 2, x: 1, y: 2 },
+        Event::TouchMove { id: 2, x: 3, y: 4 },
+        Event::TouchUp { id: 2, x: 3, y: 4 },
+        Event::Gesture(Gesture {
+            phase: GesturePhase::Update,
+            x: 10,
+            y: 20,
+            scale: 300,
+            angle: -45,
+        }),
+        Event::Encoder { steps: -2 },
+        Event::KeyDown {
+            key: Key::Character('é'),
+        },
+        Event::KeyUp {
+            key: Key::Function(12),
+        },
+        Event::KeyDown {
+            key: Key::Other(0xFFFF_FFFF),
+        },
+        Event::KeyUp { key: Key::Escape },
+    ]
+}
+
+fn sample() -> Recording {
+    let mut recording = Recording::new();
+    for (i, event) in every_event().into_iter().enumerate() {
+        recording.push(i as u32 * 150, event);
+    }
+    recording
+}
+
+#[test]
+fn recording_round_trips_through_bytes() {
+    let recording = sample();
+    let bytes = recording.to_bytes();
+    assert!(bytes.len() < 100, "{} bytes", bytes.len());
+    assert_eq!(Recording::from_bytes(&bytes), Ok(recording));
+}
+
+#[test]
+fn corrupt_recordings_are_rejected() {
+    let bytes = sample().to_bytes();
+    assert_eq!(Recording::from_bytes(b"RLVR"), Err(DecodeError::BadHeader));
+    assert_eq!(
+        Recording::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(DecodeError::Truncated)
+    );
+    let mut bad = bytes[..5].to_vec();
+    bad.extend_from_slice(&[0, 42]);
+    assert_eq!(Recording::from_bytes(&bad), Err(DecodeError::Invalid(6)));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn recording_round_trips_through_json() {
+    let recording = sample();
+    let json = recording.to_json();
+    assert!(json.contains(r#"{"time_ms":150,"event":{"PointerDown":{"x":-3,"y":70000}}}"#));
+    assert_eq!(Recording::from_json(&json).unwrap(), recording);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_times_out_of_order_are_raised() {
+    let json = r#"{"events":[{"time_ms":50,"event":"Tick"},{"time_ms":20,"event":"Tick"}]}"#;
+    let recording = Recording::from_json(json).unwrap();
+    let times: Vec<u32> = recording.events().iter().map(|e| e.time_ms).collect();
+    assert_eq!(times, [50, 50]);
+    let decoded = Recording::from_bytes(&recording.to_bytes()).unwrap();
+    assert_eq!(decoded, recording);
+}
+
+#[test]
+fn recorder_stamps_events_from_its_clock() {
+    let now = Rc::new(Cell::new(1000));
+    let clock = {
+        let now = now.clone();
+        move || now.get()
+    };
+    let mut input = Recorder::new(
+        Events(vec![
+            Event::PointerDown { x: 1, y: 2 },
+            Event::PointerUp { x: 1, y: 2 },
+        ]),
+        clock,
+    );
+    now.set(1016);
+    assert_eq!(input.tick(), Event::Tick);
+    assert_eq!(input.poll(), Some(Event::PointerDown { x: 1, y: 2 }));
+    now.set(1040);
+    assert_eq!(input.poll(), Some(Event::PointerUp { x: 1, y: 2 }));
+    assert_eq!(input.poll(), None);
+    let recording = input.take_recording();
+    assert_eq!(
+        recording.events(),
+        [
+            TimedEvent {
+                time_ms: 16,
+                event: Event::Tick
+            },
+            TimedEvent {
+                time_ms: 16,
+                event: Event::PointerDown { x: 1, y: 2 }
+            },
+            TimedEvent {
+                time_ms: 40,
+                event: Event::PointerUp { x: 1, y: 2 }
+            },
+        ]
+    );
+    assert!(input.recording().events().is_empty());
+}
+
+#[test]
+fn replay_follows_the_clock() {
+    let mut replay = Replay::new(sample());
+    assert_eq!(replay.poll(), Some(Event::Tick));
+    assert_eq!(replay.poll(), None);
+    assert_eq!(replay.next_time(), Some(150));
+    replay.set_time(300);
+    assert_eq!(
+        std::iter::from_fn(|| replay.poll()).count(),
+        2,
+        "events at 150 and 300 ms"
+    );
+    replay.finish();
+    assert_eq!(std::iter::from_fn(|| replay.poll()).count(), 10);
+    assert!(replay.is_finished());
+    replay.rewind();
+    assert_eq!(replay.poll(), Some(Event::Tick));
+}
+
+/// Bar whose length grows with ticks while the pointer is held.
+struct Meter {
+    held: bool,
+    level: i32,
+}
+
+impl Widget for Meter {
+    fn bounds(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: 16,
+            height: 4,
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds(), Color(0, 0, 0, 255));
+        renderer.fill_rect(
+            Rect {
+                x: 0,
+                y: 0,
+                width: self.level,
+                height: 4,
+            },
+            Color(255, 255, 255, 255),
+        );
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::PointerDown { .. } => self.held = true,
+            Event::PointerUp { .. } => self.held = false,
+            Event::Tick if self.held => self.level = (self.level + 1).min(16),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Dispatch the events of `input` to a new meter, rendering after each tick.
+fn run(input: &mut impl InputDevice) -> Vec<Vec<u8>> {
+    let mut root = WidgetNode {
+        widget: Rc::new(RefCell::new(Meter {
+            held: false,
+            level: 0,
+        })),
+        children: vec![],
+    };
+    let mut frames = Vec::new();
+    while let Some(event) = input.poll() {
+        root.dispatch_event(&event);
+        if event == Event::Tick {
+            let mut frame = vec![0u8; 16 * 4 * 4];
+            let mut blitter = CpuBlitter;
+            let surface = Surface::new(&mut frame, 16 * 4, PixelFmt::Argb8888, 16, 4);
+            let mut renderer: BlitterRenderer<'_, CpuBlitter, 4> =
+                BlitterRenderer::new(&mut blitter, surface);
+            root.draw(&mut renderer);
+            drop(renderer);
+            frames.push(frame);
+        }
+    }
+    frames
+}
+
+#[test]
+fn replay_reproduces_the_recorded_frames() {
+    let mut live = Vec::new();
+    for i in 0..12 {
+        match i {
+            2 => live.push(Event::PointerDown { x: 1, y: 1 }),
+            7 => live.push(Event::PointerUp { x: 1, y: 1 }),
+            _ => {}
+        }
+        live.push(Event::Tick);
+    }
+    let ms = Rc::new(Cell::new(0));
+    let mut recorder = Recorder::new(Events(live), {
+        let ms = ms.clone();
+        move || {
+            ms.set(ms.get() + 5);
+            ms.get()
+        }
+    });
+    let recorded = run(&mut recorder);
+    assert_eq!(recorded.len(), 12);
+    assert_ne!(recorded.first(), recorded.last());
+
+    let bytes = recorder.recording().to_bytes();
+    let mut replay = Replay::new(Recording::from_bytes(&bytes).unwrap());
+    replay.finish();
+    assert_eq!(run(&mut replay), recorded);
+}
+
+#[test]
+fn replay_keeps_the_recorded_tick_spacing() {
+    // Drive the recorder like the simulator: ticks every 16 ms from the
+    // frame loop and pushed window events in between.
+    let now = Rc::new(Cell::new(0));
+    let mut recorder = Recorder::new(DummyInput, {
+        let now = now.clone();
+        move || now.get()
+    });
+    for ms in 0..100 {
+        now.set(ms);
+        if ms % 16 == 0 {
+            assert_eq!(recorder.tick(), Event::Tick);
+        }
+        if ms == 40 {
+            recorder.record(&Event::PointerDown { x: 1, y: 1 });
+        }
+    }
+    let bytes = recorder.recording().to_bytes();
+
+    let mut replay = Replay::new(Recording::from_bytes(&bytes).unwrap());
+    let mut ticks = Vec::new();
+    for ms in 0..100 {
+        replay.set_time(ms);
+        while let Some(event) = replay.poll() {
+            if event == Event::Tick {
+                ticks.push(ms);
+            }
+        }
+    }
+    assert_eq!(ticks.first(), Some(&0));
+    let deltas: Vec<u32> = ticks.windows(2).map(|w| w[1] - w[0]).collect();
+    assert_eq!(deltas, [16; 6]);
+}
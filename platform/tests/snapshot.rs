@@ -0,0 +1,118 @@
+//! Tests for golden-image snapshot comparison.
+#![cfg(feature = "snapshot")]
+use std::path::PathBuf;
+
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::PixelFmt;
+use rlvgl_platform::snapshot::{Rgba, RgbaImage, Snapshot, SnapshotError, compare};
+
+/// Empty golden directory unique to `test`.
+fn golden_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlvgl-snapshot-{}-{test}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn square(r: &mut dyn Renderer) {
+    r.fill_rect(
+        Rect {
+            x: 2,
+            y: 1,
+            width: 3,
+            height: 2,
+        },
+        Color(200, 180, 150, 255),
+    );
+}
+
+#[test]
+fn renders_in_the_requested_format() {
+    let image = Snapshot::new(8, 4)
+        .with_background(Color(0, 0, 255, 255))
+        .render_with(square);
+    assert_eq!(image.dimensions(), (8, 4));
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+    assert_eq!(image.get_pixel(3, 2), &Rgba([200, 180, 150, 255]));
+
+    // RGB565 keeps five bits of red and blue and six of green.
+    let image = Snapshot::new(8, 4)
+        .with_format(PixelFmt::Rgb565)
+        .render_with(square);
+    assert_eq!(image.get_pixel(3, 2), &Rgba([200, 180, 144, 255]));
+
+    let image = Snapshot::new(8, 4)
+        .with_format(PixelFmt::I1)
+        .with_palette(&[0xFF00_0000, 0xFFFF_FFFF])
+        .render_with(square);
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0, 0, 0, 255]));
+    assert_eq!(image.get_pixel(3, 2), &Rgba([255, 255, 255, 255]));
+}
+
+#[test]
+fn compare_counts_pixels_beyond_the_tolerance() {
+    let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+    let mut actual = expected.clone();
+    actual.put_pixel(0, 0, Rgba([103, 100, 100, 255]));
+    actual.put_pixel(1, 0, Rgba([100, 90, 100, 255]));
+    let diff = compare(&actual, &expected, 3).unwrap();
+    assert_eq!((diff.differing, diff.max_delta), (1, 10));
+    assert_eq!(diff.image.get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+    assert_eq!(diff.image.get_pixel(0, 0), &Rgba([33, 33, 33, 255]));
+    assert!(compare(&actual, &RgbaImage::new(4, 5), 0).is_none());
+}
+
+#[test]
+fn failures_write_actual_expected_and_diff_images() {
+    let dir = golden_dir("failures");
+    let snap = Snapshot::new(8, 4).with_dir(&dir);
+    let actual = snap.render_with(square);
+    assert!(matches!(
+        snap.check("square", &actual),
+        Err(SnapshotError::Missing(_))
+    ));
+
+    std::fs::create_dir_all(&dir).unwrap();
+    actual.save(snap.golden_path("square")).unwrap();
+    assert!(snap.check("square", &actual).is_ok());
+
+    let shifted = snap.render_with(|r| {
+        r.fill_rect(
+            Rect {
+                x: 3,
+                y: 1,
+                width: 3,
+                height: 2,
+            },
+            Color(200, 180, 150, 255),
+        )
+    });
+    let err = snap.check("square", &shifted).unwrap_err();
+    assert!(matches!(
+        err,
+        SnapshotError::Mismatch {
+            differing: 4,
+            max_delta: 200
+        }
+    ));
+    for suffix in ["actual", "expected", "diff"] {
+        assert!(dir.join(format!("failures/square.{suffix}.png")).exists());
+    }
+
+    // A match within the allowed difference clears stale failure images.
+    let lenient = snap.with_max_diff_pixels(4);
+    assert!(lenient.check("square", &shifted).is_ok());
+    assert!(!dir.join("failures/square.diff.png").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn size_changes_are_reported() {
+    let dir = golden_dir("size");
+    std::fs::create_dir_all(&dir).unwrap();
+    let snap = Snapshot::new(8, 4).with_dir(&dir);
+    RgbaImage::new(4, 4).save(snap.golden_path("s")).unwrap();
+    let err = snap.check("s", &snap.render_with(square)).unwrap_err();
+    assert_eq!(err.to_string(), "golden is 4x4 but the snapshot is 8x4");
+    let _ = std::fs::remove_dir_all(&dir);
+}
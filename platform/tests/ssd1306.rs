@@ -0,0 +1,142 @@
+//! Tests for the SSD1306/SH1106 OLED driver against a recording I²C bus.
+#![cfg(feature = "ssd1306")]
+use std::cell::Cell;
+use std::rc::Rc;
+
+use embedded_hal::i2c::{self, I2c, Operation};
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::display::DisplayDriver;
+use rlvgl_platform::ssd1306::OledError;
+use rlvgl_platform::{MonoMode, Oled, OledController};
+
+#[derive(Default)]
+/// Recorded writes, and whether transfers currently fail.
+struct Bus(Vec<(u8, Vec<u8>)>, Rc<Cell<bool>>);
+
+impl i2c::ErrorType for Bus {
+    type Error = i2c::ErrorKind;
+}
+
+impl I2c for Bus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), i2c::ErrorKind> {
+        if self.1.get() {
+            return Err(i2c::ErrorKind::Other);
+        }
+        for op in operations {
+            if let Operation::Write(bytes) = op {
+                self.0.push((address, bytes.to_vec()));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn oled(controller: OledController) -> Oled<Bus> {
+    let mut oled = Oled::new(Bus::default(), controller, 128, 32);
+    oled.init().unwrap();
+    oled
+}
+
+fn writes(oled: Oled<Bus>) -> Vec<Vec<u8>> {
+    oled.release()
+        .0
+        .into_iter()
+        .map(|(_, bytes)| bytes)
+        .collect()
+}
+
+const WHITE: Color = Color(255, 255, 255, 255);
+
+#[test]
+fn init_configures_clears_every_page_and_switches_on() {
+    let w = writes(oled(OledController::Ssd1306));
+    assert_eq!(w.len(), 1 + 4 * 2 + 1);
+    assert_eq!(w[0][..6], [0x00, 0xAE, 0xD5, 0x80, 0xA8, 31]);
+    for page in 0..4 {
+        assert_eq!(w[1 + page * 2], [0x00, 0xB0 | page as u8, 0x00, 0x10]);
+        let data = &w[2 + page * 2];
+        assert_eq!(data.len(), 129);
+        assert_eq!(data[0], 0x40);
+        assert!(data[1..].iter().all(|&b| b == 0));
+    }
+    assert_eq!(w[9], [0x00, 0xAF]);
+}
+
+#[test]
+fn flush_sends_only_changed_columns_of_changed_pages() {
+    let mut oled = Oled::new(Bus::default(), OledController::Ssd1306, 128, 32);
+    oled.flush(
+        Rect {
+            x: 26,
+            y: 7,
+            width: 2,
+            height: 2,
+        },
+        &[WHITE; 4],
+    );
+    assert_eq!(
+        writes(oled),
+        [
+            vec![0x00, 0xB0, 0x0A, 0x11],
+            vec![0x40, 0x80, 0x80],
+            vec![0x00, 0xB1, 0x0A, 0x11],
+            vec![0x40, 0x01, 0x01],
+        ]
+    );
+}
+
+#[test]
+fn sh1106_skips_hidden_columns_and_honours_address() {
+    let mut oled = Oled::new(Bus::default(), OledController::Sh1106, 128, 64).with_address(0x3D);
+    oled.set_mode(MonoMode::Threshold(10));
+    oled.flush(
+        Rect {
+            x: 0,
+            y: 0,
+            width: 1,
+            height: 1,
+        },
+        &[Color(20, 20, 20, 255)],
+    );
+    let bus = oled.release().0;
+    assert!(bus.iter().all(|(addr, _)| *addr == 0x3D));
+    assert_eq!(bus[0].1, [0x00, 0xB0, 0x02, 0x10]);
+    assert_eq!(bus[1].1, [0x40, 0x01]);
+}
+
+#[test]
+fn failed_update_keeps_changes_for_the_next_one() {
+    let fail = Rc::new(Cell::new(true));
+    let mut oled = Oled::new(
+        Bus(Vec::new(), fail.clone()),
+        OledController::Ssd1306,
+        128,
+        32,
+    );
+    let area = Rect {
+        x: 3,
+        y: 0,
+        width: 1,
+        height: 1,
+    };
+    oled.flush(area, &[WHITE]);
+    assert!(oled.buffer().dirty().is_some());
+    assert_eq!(oled.update(), Err(OledError::I2c(i2c::ErrorKind::Other)));
+    fail.set(false);
+    oled.update().unwrap();
+    assert!(oled.buffer().dirty().is_none());
+    assert_eq!(
+        writes(oled),
+        [vec![0x00, 0xB0, 0x03, 0x10], vec![0x40, 0x01]]
+    );
+}
+
+#[test]
+#[should_panic(expected = "unsupported OLED size 128x0")]
+fn rejects_panels_without_rows() {
+    Oled::new(Bus::default(), OledController::Ssd1306, 128, 0);
+}
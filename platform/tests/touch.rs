@@ -0,0 +1,301 @@
+//! Tests for the touch controller drivers against mocked buses.
+#![cfg(feature = "touch")]
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::i2c::{self, I2c};
+use embedded_hal::spi::{self, SpiDevice};
+use rlvgl_core::event::Event;
+use rlvgl_platform::input::InputDevice;
+use rlvgl_platform::{Ft6x06, Gt911, TouchPoint, Xpt2046};
+
+/// Address and bytes of an I²C write that sets registers.
+type Write = (u8, Vec<u8>);
+
+/// I²C device with auto-incrementing registers addressed by `width` bytes.
+/// Clones share the registers, so tests can update them between polls.
+#[derive(Clone)]
+struct Regs {
+    width: usize,
+    mem: Rc<RefCell<BTreeMap<u16, u8>>>,
+    writes: Rc<RefCell<Vec<Write>>>,
+}
+
+impl Regs {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            mem: Rc::default(),
+            writes: Rc::default(),
+        }
+    }
+
+    fn load(&self, start: u16, bytes: &[u8]) {
+        let mut mem = self.mem.borrow_mut();
+        for (i, &b) in bytes.iter().enumerate() {
+            mem.insert(start + i as u16, b);
+        }
+    }
+
+    fn writes(&self) -> Vec<Write> {
+        self.writes.take()
+    }
+}
+
+impl i2c::ErrorType for Regs {
+    type Error = Infallible;
+}
+
+impl I2c for Regs {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [i2c::Operation<'_>],
+    ) -> Result<(), Infallible> {
+        let mut reg = 0u16;
+        for op in operations {
+            match op {
+                i2c::Operation::Write(bytes) => {
+                    reg = bytes[..self.width]
+                        .iter()
+                        .fold(0, |r, &b| (r << 8) | b as u16);
+                    if bytes.len() > self.width {
+                        self.writes.borrow_mut().push((address, bytes.to_vec()));
+                    }
+                }
+                i2c::Operation::Read(buf) => {
+                    for b in buf.iter_mut() {
+                        *b = self.mem.borrow().get(&reg).copied().unwrap_or(0);
+                        reg += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn gt911_reads_every_point_and_acknowledges_the_report() {
+    let regs = Regs::new(2);
+    regs.load(0x8140, b"911\0");
+    regs.load(0x814E, &[0x82]);
+    regs.load(
+        0x814F,
+        &[
+            3, 0x10, 0x01, 0x20, 0x00, 9, 0, 0, 7, 0x05, 0x00, 0xE0, 0x01, 9, 0, 0,
+        ],
+    );
+    let mut gt = Gt911::new(regs.clone());
+    assert_eq!(&gt.product_id().unwrap(), b"911\0");
+    assert_eq!(
+        gt.read_touches().unwrap().unwrap(),
+        [
+            TouchPoint {
+                id: 3,
+                x: 0x110,
+                y: 0x20
+            },
+            TouchPoint {
+                id: 7,
+                x: 5,
+                y: 0x1E0
+            },
+        ]
+    );
+    assert_eq!(regs.writes(), [(0x5D, vec![0x81, 0x4E, 0])]);
+}
+
+#[test]
+fn gt911_waits_for_buffer_ready() {
+    let regs = Regs::new(2);
+    regs.load(0x814E, &[0x01]);
+    regs.load(0x814F, &[0, 10, 0, 20, 0, 0, 0, 0]);
+    let mut gt = Gt911::new(regs.clone()).with_address(Gt911::<Regs>::ALT_ADDRESS);
+    assert_eq!(gt.read_touches().unwrap(), None);
+    assert_eq!(gt.poll(), None);
+    assert!(regs.writes().is_empty());
+}
+
+#[test]
+fn gt911_poll_tracks_the_first_touch_as_pointer() {
+    let regs = Regs::new(2);
+    let mut gt = Gt911::new(regs.clone());
+    // Pointer event among those a report produces, next to touch events.
+    let report = |gt: &mut Gt911<Regs>, status: u8, x: u8| {
+        regs.load(0x814E, &[status]);
+        regs.load(0x814F, &[0, x, 0, 50, 0, 0, 0, 0]);
+        std::iter::from_fn(|| gt.poll()).find(|e| {
+            matches!(
+                e,
+                Event::PointerDown { .. } | Event::PointerMove { .. } | Event::PointerUp { .. }
+            )
+        })
+    };
+    assert_eq!(
+        report(&mut gt, 0x81, 10),
+        Some(Event::PointerDown { x: 10, y: 50 })
+    );
+    assert_eq!(report(&mut gt, 0x81, 10), None);
+    assert_eq!(
+        report(&mut gt, 0x81, 12),
+        Some(Event::PointerMove { x: 12, y: 50 })
+    );
+    assert_eq!(
+        report(&mut gt, 0x80, 0),
+        Some(Event::PointerUp { x: 12, y: 50 })
+    );
+}
+
+#[test]
+fn gt911_poll_reports_every_finger() {
+    let regs = Regs::new(2);
+    let mut gt = Gt911::new(regs.clone());
+    regs.load(0x814E, &[0x82]);
+    regs.load(
+        0x814F,
+        &[1, 10, 0, 20, 0, 0, 0, 0, 2, 30, 0, 40, 0, 0, 0, 0],
+    );
+    let events: Vec<_> = (0..3).map(|_| gt.poll()).collect();
+    assert_eq!(
+        events,
+        [
+            Some(Event::TouchDown {
+                id: 1,
+                x: 10,
+                y: 20
+            }),
+            Some(Event::PointerDown { x: 10, y: 20 }),
+            Some(Event::TouchDown {
+                id: 2,
+                x: 30,
+                y: 40
+            }),
+        ]
+    );
+    regs.load(0x814E, &[0x00]);
+    assert_eq!(gt.poll(), None);
+}
+
+#[test]
+fn ft6x06_skips_lifted_points_and_invalid_counts() {
+    let regs = Regs::new(1);
+    regs.load(0xA3, &[0x64]);
+    // Point 1 lifted, point 2 in contact with id 1.
+    regs.load(
+        0x02,
+        &[
+            2, 0x40, 0x10, 0x00, 0x20, 0, 0, 0x81, 0x23, 0x10, 0x45, 0, 0,
+        ],
+    );
+    let mut ft = Ft6x06::new(regs.clone());
+    assert_eq!(ft.chip_id().unwrap(), 0x64);
+    assert_eq!(
+        ft.read_touches().unwrap(),
+        Some(
+            &[TouchPoint {
+                id: 1,
+                x: 0x123,
+                y: 0x045
+            }][..]
+        )
+    );
+    regs.load(0x02, &[0x0F]);
+    assert_eq!(ft.read_touches().unwrap(), None);
+    assert_eq!(ft.touches().len(), 1);
+}
+
+#[test]
+fn ft6x06_corrupt_count_does_not_lift_fingers() {
+    let regs = Regs::new(1);
+    regs.load(0x02, &[1, 0x80, 0x10, 0x00, 0x20, 0, 0]);
+    let mut ft = Ft6x06::new(regs.clone());
+    assert_eq!(
+        ft.poll(),
+        Some(Event::TouchDown {
+            id: 0,
+            x: 0x10,
+            y: 0x20
+        })
+    );
+    assert_eq!(ft.poll(), Some(Event::PointerDown { x: 0x10, y: 0x20 }));
+    regs.load(0x02, &[0x0F]);
+    assert_eq!(ft.poll(), None);
+    regs.load(0x02, &[0]);
+    assert_eq!(
+        ft.poll(),
+        Some(Event::TouchUp {
+            id: 0,
+            x: 0x10,
+            y: 0x20
+        })
+    );
+}
+
+/// XPT2046 answering each conversion command from a queue of readings.
+#[derive(Default)]
+struct Adc {
+    readings: HashMap<u8, Vec<u16>>,
+    conversions: usize,
+}
+
+impl Adc {
+    fn with(mut self, cmd: u8, values: &[u16]) -> Self {
+        self.readings.insert(cmd, values.to_vec());
+        self
+    }
+}
+
+impl spi::ErrorType for Adc {
+    type Error = Infallible;
+}
+
+impl SpiDevice for Adc {
+    fn transaction(&mut self, ops: &mut [spi::Operation<'_, u8>]) -> Result<(), Infallible> {
+        for op in ops {
+            if let spi::Operation::Transfer(read, write) = op {
+                let queue = self.readings.get_mut(&write[0]).unwrap();
+                let value = if queue.len() > 1 {
+                    queue.remove(0)
+                } else {
+                    queue[0]
+                };
+                read[..3].copy_from_slice(&[0, (value >> 5) as u8, (value << 3) as u8]);
+                self.conversions += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+const X: u8 = 0xD0;
+const Y: u8 = 0x90;
+const Z1: u8 = 0xB0;
+const Z2: u8 = 0xC0;
+
+#[test]
+fn xpt2046_takes_the_median_of_samples() {
+    let adc = Adc::default()
+        .with(Z1, &[600])
+        .with(Z2, &[3000])
+        .with(X, &[1000, 4000, 1010, 990, 20])
+        .with(Y, &[2000, 2003, 1998, 3500, 2001]);
+    let mut xpt = Xpt2046::new(adc);
+    assert_eq!(xpt.pressure().unwrap(), 1695);
+    assert_eq!(xpt.read().unwrap(), Some((1000, 2001)));
+    assert_eq!(xpt.release().conversions, 2 + 2 + 5 + 5 + 2);
+}
+
+#[test]
+fn xpt2046_ignores_light_pressure() {
+    let adc = Adc::default()
+        .with(Z1, &[0])
+        .with(Z2, &[4095])
+        .with(X, &[100])
+        .with(Y, &[100]);
+    let mut xpt = Xpt2046::new(adc).with_threshold(50).with_samples(3);
+    assert_eq!(xpt.read().unwrap(), None);
+    assert_eq!(xpt.poll(), None);
+}
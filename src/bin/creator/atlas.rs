@@ -0,0 +1,771 @@
+//! Atlas command for rlvgl-creator.
+//!
+//! Packs the images of a manifest group into one or more sprite sheets and
+//! emits a JSON or RON index plus Rust constants for
+//! `rlvgl_core::atlas::Atlas`.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use image::RgbaImage;
+use serde::Serialize;
+
+use crate::manifest::Manifest;
+use crate::{check, raw, scan};
+
+/// Serialization format of the atlas index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum IndexFormat {
+    /// `<group>.json`.
+    Json,
+    /// `<group>.ron`.
+    Ron,
+}
+
+impl IndexFormat {
+    /// Parse a format name.
+    pub(crate) fn parse(name: &str) -> Result<Self> {
+        match name {
+            "json" => Ok(Self::Json),
+            "ron" => Ok(Self::Ron),
+            other => bail!("Unknown atlas index format `{other}`"),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ron => "ron",
+        }
+    }
+}
+
+/// Packing options for [`run`].
+pub(crate) struct Options {
+    /// Maximum width and height of a sheet in pixels.
+    pub(crate) max_size: u32,
+    /// Transparent pixels kept between sprites.
+    pub(crate) padding: u32,
+    /// Whether to strip fully transparent borders from sprites.
+    pub(crate) trim: bool,
+    /// Whether sprites may be stored rotated 90° clockwise.
+    pub(crate) rotate: bool,
+    /// Format of the written index.
+    pub(crate) format: IndexFormat,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            max_size: 1024,
+            padding: 1,
+            trim: true,
+            rotate: false,
+            format: IndexFormat::Json,
+        }
+    }
+}
+
+/// Atlas index written next to the sheets.
+#[derive(Serialize, Debug)]
+struct Index {
+    group: String,
+    sheets: Vec<SheetInfo>,
+    sprites: Vec<Sprite>,
+}
+
+/// Sheet image and its dimensions.
+#[derive(Serialize, Debug)]
+struct SheetInfo {
+    file: String,
+    width: u32,
+    height: u32,
+}
+
+/// Placement of one source image, mirroring `rlvgl_core::atlas::AtlasRect`.
+#[derive(Serialize, Debug, Clone)]
+struct Sprite {
+    name: String,
+    source: String,
+    sheet: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    offset_x: u32,
+    offset_y: u32,
+    source_width: u32,
+    source_height: u32,
+    rotated: bool,
+}
+
+/// Axis-aligned rectangle used while packing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+impl Rect {
+    fn right(&self) -> u32 {
+        self.x + self.w
+    }
+
+    fn bottom(&self) -> u32 {
+        self.y + self.h
+    }
+
+    fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+
+    fn contains(&self, other: &Rect) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && self.right() >= other.right()
+            && self.bottom() >= other.bottom()
+    }
+}
+
+/// Sheet being filled with the MaxRects algorithm.
+struct Sheet {
+    free: Vec<Rect>,
+    width: u32,
+    height: u32,
+}
+
+impl Sheet {
+    /// Empty sheet; the free area includes trailing padding so sprites may
+    /// reach the `max_size` edge.
+    fn new(max_size: u32, padding: u32) -> Self {
+        Self {
+            free: vec![Rect {
+                x: 0,
+                y: 0,
+                w: max_size + padding,
+                h: max_size + padding,
+            }],
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// Best short side fit for a `w` × `h` block, as `(score, x, y, rotated)`.
+    fn find(&self, w: u32, h: u32, rotate: bool) -> Option<(u32, u32, u32, bool)> {
+        let mut best: Option<(u32, u32, u32, bool)> = None;
+        for free in &self.free {
+            for (bw, bh, rotated) in [(w, h, false), (h, w, true)] {
+                if rotated && (!rotate || w == h) {
+                    continue;
+                }
+                if bw > free.w || bh > free.h {
+                    continue;
+                }
+                let score = (free.w - bw).min(free.h - bh);
+                if best.is_none_or(|(s, ..)| score < s) {
+                    best = Some((score, free.x, free.y, rotated));
+                }
+            }
+        }
+        best
+    }
+
+    /// Reserve `used`, splitting every free rectangle it overlaps.
+    fn place(&mut self, used: Rect) {
+        let mut next = Vec::with_capacity(self.free.len() + 4);
+        for free in self.free.drain(..) {
+            if !free.intersects(&used) {
+                next.push(free);
+                continue;
+            }
+            if used.x > free.x {
+                next.push(Rect {
+                    w: used.x - free.x,
+                    ..free
+                });
+            }
+            if used.right() < free.right() {
+                next.push(Rect {
+                    x: used.right(),
+                    w: free.right() - used.right(),
+                    ..free
+                });
+            }
+            if used.y > free.y {
+                next.push(Rect {
+                    h: used.y - free.y,
+                    ..free
+                });
+            }
+            if used.bottom() < free.bottom() {
+                next.push(Rect {
+                    y: used.bottom(),
+                    h: free.bottom() - used.bottom(),
+                    ..free
+                });
+            }
+        }
+        // Drop rectangles covered by another one, keeping one of equal pairs.
+        self.free = next
+            .iter()
+            .enumerate()
+            .filter(|&(i, r)| {
+                !next
+                    .iter()
+                    .enumerate()
+                    .any(|(j, o)| i != j && o.contains(r) && (o != r || j < i))
+            })
+            .map(|(_, r)| *r)
+            .collect();
+    }
+}
+
+/// Source image after trimming.
+struct Input {
+    name: String,
+    source: String,
+    image: RgbaImage,
+    offset_x: u32,
+    offset_y: u32,
+    source_width: u32,
+    source_height: u32,
+}
+
+/// Pack the images of `group` into sheets under `out` and write the index
+/// and `<group>_atlas.rs` constants.
+pub(crate) fn run(
+    root: &Path,
+    manifest_path: &Path,
+    group: &str,
+    out: &Path,
+    opts: &Options,
+) -> Result<()> {
+    let manifest: Manifest = serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?;
+    let members = &manifest
+        .groups
+        .get(group)
+        .ok_or_else(|| anyhow!("Unknown group `{group}`"))?
+        .assets;
+    if members.is_empty() {
+        bail!("Group `{group}` has no assets");
+    }
+
+    let mut inputs = Vec::new();
+    let mut names = BTreeSet::new();
+    for rel in members {
+        let image = load(&root.join(rel))?;
+        let name = Path::new(rel)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(rel)
+            .to_string();
+        if !names.insert(name.clone()) {
+            bail!("Duplicate sprite name `{name}` in group `{group}`");
+        }
+        inputs.push(prepare(name, rel.clone(), image, opts.trim));
+    }
+
+    let Packed { sprites, sizes } = pack(&inputs, opts)?;
+    fs::create_dir_all(out)?;
+    let mut index = Index {
+        group: group.to_string(),
+        sheets: Vec::new(),
+        sprites: Vec::new(),
+    };
+    for (n, &(width, height)) in sizes.iter().enumerate() {
+        let mut sheet = RgbaImage::new(width.max(1), height.max(1));
+        for (sprite, input) in sprites.iter().zip(&inputs) {
+            if sprite.sheet as usize == n {
+                blit(&mut sheet, sprite, &input.image);
+            }
+        }
+        let file = format!("{group}-{n}.png");
+        sheet.save(out.join(&file))?;
+        index.sheets.push(SheetInfo {
+            file,
+            width: sheet.width(),
+            height: sheet.height(),
+        });
+    }
+    index.sprites = sprites;
+    index.sprites.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let index_path = out.join(format!("{group}.{}", opts.format.extension()));
+    let text = match opts.format {
+        IndexFormat::Json => serde_json::to_string_pretty(&index)?,
+        IndexFormat::Ron => to_ron(&index),
+    };
+    fs::write(&index_path, text)?;
+    fs::write(out.join(format!("{group}_atlas.rs")), to_rust(&index))?;
+    println!(
+        "Packed {} sprites into {} sheet(s) at {}",
+        index.sprites.len(),
+        index.sheets.len(),
+        out.display()
+    );
+
+    register_outputs(root, manifest_path, group, out)?;
+    check::run(root, manifest_path, true)
+}
+
+/// Load the first frame of an image or `.raw` sequence as RGBA8.
+fn load(path: &Path) -> Result<RgbaImage> {
+    if path.extension().and_then(|e| e.to_str()) == Some("raw") {
+        let seq = raw::Sequence::decode(path)?;
+        let frame = seq
+            .frames
+            .first()
+            .ok_or_else(|| anyhow!("`{}` has no frames", path.display()))?;
+        let mut image = RgbaImage::new(seq.max_width, seq.max_height);
+        let data = RgbaImage::from_raw(frame.width, frame.height, frame.data.clone())
+            .ok_or_else(|| anyhow!("`{}` is truncated", path.display()))?;
+        image::imageops::overlay(&mut image, &data, frame.x as i64, frame.y as i64);
+        Ok(image)
+    } else {
+        Ok(image::open(path)?.to_rgba8())
+    }
+}
+
+/// Optionally trim transparent borders, remembering the original box.
+fn prepare(name: String, source: String, image: RgbaImage, trim: bool) -> Input {
+    let (source_width, source_height) = image.dimensions();
+    let (x, y, w, h) = if trim {
+        opaque_bounds(&image)
+    } else {
+        (0, 0, source_width, source_height)
+    };
+    Input {
+        name,
+        source,
+        image: image::imageops::crop_imm(&image, x, y, w, h).to_image(),
+        offset_x: x,
+        offset_y: y,
+        source_width,
+        source_height,
+    }
+}
+
+/// Bounding box of pixels with non-zero alpha, empty for a blank image.
+fn opaque_bounds(image: &RgbaImage) -> (u32, u32, u32, u32) {
+    let (mut x0, mut y0, mut x1, mut y1) = (u32::MAX, u32::MAX, 0, 0);
+    for (x, y, p) in image.enumerate_pixels() {
+        if p[3] != 0 {
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x + 1);
+            y1 = y1.max(y + 1);
+        }
+    }
+    if x1 == 0 {
+        (0, 0, 0, 0)
+    } else {
+        (x0, y0, x1 - x0, y1 - y0)
+    }
+}
+
+/// Outcome of [`pack`].
+struct Packed {
+    /// Placements in input order.
+    sprites: Vec<Sprite>,
+    /// Used width and height of each sheet.
+    sizes: Vec<(u32, u32)>,
+}
+
+/// Place every input, opening new sheets as needed.
+fn pack(inputs: &[Input], opts: &Options) -> Result<Packed> {
+    let mut order: Vec<usize> = (0..inputs.len()).collect();
+    order.sort_by_key(|&i| {
+        let (w, h) = inputs[i].image.dimensions();
+        (
+            std::cmp::Reverse(w.max(h)),
+            std::cmp::Reverse(w * h),
+            inputs[i].name.clone(),
+        )
+    });
+
+    let mut sheets: Vec<Sheet> = Vec::new();
+    let mut placed = vec![None; inputs.len()];
+    for i in order {
+        let input = &inputs[i];
+        let (w, h) = input.image.dimensions();
+        let mut sprite = Sprite {
+            name: input.name.clone(),
+            source: input.source.clone(),
+            sheet: 0,
+            x: 0,
+            y: 0,
+            width: w,
+            height: h,
+            offset_x: input.offset_x,
+            offset_y: input.offset_y,
+            source_width: input.source_width,
+            source_height: input.source_height,
+            rotated: false,
+        };
+        if w == 0 || h == 0 {
+            placed[i] = Some(sprite);
+            continue;
+        }
+        let (pw, ph) = (w + opts.padding, h + opts.padding);
+        let mut spot = sheets
+            .iter()
+            .enumerate()
+            .find_map(|(n, s)| s.find(pw, ph, opts.rotate).map(|f| (n, f)));
+        if spot.is_none() {
+            let sheet = Sheet::new(opts.max_size, opts.padding);
+            let found = sheet.find(pw, ph, opts.rotate).ok_or_else(|| {
+                anyhow!(
+                    "Sprite `{}` ({w}x{h}) does not fit in a {} px sheet",
+                    input.name,
+                    opts.max_size
+                )
+            })?;
+            sheets.push(sheet);
+            spot = Some((sheets.len() - 1, found));
+        }
+        let (n, (_, x, y, rotated)) = spot.expect("sheet chosen");
+        let (bw, bh) = if rotated { (ph, pw) } else { (pw, ph) };
+        let sheet = &mut sheets[n];
+        sheet.place(Rect { x, y, w: bw, h: bh });
+        sheet.width = sheet.width.max(x + bw - opts.padding);
+        sheet.height = sheet.height.max(y + bh - opts.padding);
+        sprite.sheet = n as u32;
+        sprite.x = x;
+        sprite.y = y;
+        sprite.rotated = rotated;
+        placed[i] = Some(sprite);
+    }
+    if sheets.is_empty() {
+        sheets.push(Sheet::new(opts.max_size, opts.padding));
+    }
+    Ok(Packed {
+        sprites: placed
+            .into_iter()
+            .map(|s| s.expect("sprite placed"))
+            .collect(),
+        sizes: sheets.iter().map(|s| (s.width, s.height)).collect(),
+    })
+}
+
+/// Copy `image` into `sheet`, turning it clockwise for rotated sprites.
+fn blit(sheet: &mut RgbaImage, sprite: &Sprite, image: &RgbaImage) {
+    for (x, y, p) in image.enumerate_pixels() {
+        let (sx, sy) = if sprite.rotated {
+            (sprite.x + sprite.height - 1 - y, sprite.y + x)
+        } else {
+            (sprite.x + x, sprite.y + y)
+        };
+        sheet.put_pixel(sx, sy, *p);
+    }
+}
+
+/// Render the index as RON.
+fn to_ron(index: &Index) -> String {
+    let mut s = String::from("(\n");
+    let _ = writeln!(s, "    group: {:?},", index.group);
+    s.push_str("    sheets: [\n");
+    for sheet in &index.sheets {
+        let _ = writeln!(
+            s,
+            "        (file: {:?}, width: {}, height: {}),",
+            sheet.file, sheet.width, sheet.height
+        );
+    }
+    s.push_str("    ],\n    sprites: [\n");
+    for sp in &index.sprites {
+        let _ = writeln!(
+            s,
+            "        (name: {:?}, source: {:?}, sheet: {}, x: {}, y: {}, width: {}, \
+             height: {}, offset_x: {}, offset_y: {}, source_width: {}, \
+             source_height: {}, rotated: {}),",
+            sp.name,
+            sp.source,
+            sp.sheet,
+            sp.x,
+            sp.y,
+            sp.width,
+            sp.height,
+            sp.offset_x,
+            sp.offset_y,
+            sp.source_width,
+            sp.source_height,
+            sp.rotated
+        );
+    }
+    s.push_str("    ],\n)\n");
+    s
+}
+
+/// Identifier fragment in SCREAMING_SNAKE case.
+fn ident(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Render Rust constants for the sprites and a sorted lookup table.
+fn to_rust(index: &Index) -> String {
+    let prefix = ident(&index.group);
+    let mut s = format!(
+        "//! Sprite atlas `{}` generated by rlvgl-creator. Do not edit.\n\n\
+         use rlvgl_core::atlas::{{Atlas, AtlasRect}};\n\n",
+        index.group
+    );
+    let _ = writeln!(s, "/// Sheet images, indexed by `AtlasRect::sheet`.");
+    let files: Vec<String> = index
+        .sheets
+        .iter()
+        .map(|sh| format!("{:?}", sh.file))
+        .collect();
+    let _ = writeln!(
+        s,
+        "pub const {prefix}_SHEETS: [&str; {}] = [{}];",
+        files.len(),
+        files.join(", ")
+    );
+    for sp in &index.sprites {
+        let _ = write!(
+            s,
+            "\n/// Sprite `{}` from `{}`.\npub const {prefix}_{}: AtlasRect = AtlasRect {{\n    \
+             sheet: {},\n    x: {},\n    y: {},\n    width: {},\n    height: {},\n    \
+             offset_x: {},\n    offset_y: {},\n    source_width: {},\n    \
+             source_height: {},\n    rotated: {},\n}};\n",
+            sp.name,
+            sp.source,
+            ident(&sp.name),
+            sp.sheet,
+            sp.x,
+            sp.y,
+            sp.width,
+            sp.height,
+            sp.offset_x,
+            sp.offset_y,
+            sp.source_width,
+            sp.source_height,
+            sp.rotated
+        );
+    }
+    let _ = writeln!(
+        s,
+        "\n/// All sprites of atlas `{}`, sorted by name.\npub const {prefix}_ATLAS: Atlas<'static> = Atlas::new(&[",
+        index.group
+    );
+    for sp in &index.sprites {
+        let _ = writeln!(s, "    ({:?}, {prefix}_{}),", sp.name, ident(&sp.name));
+    }
+    s.push_str("]);\n");
+    s
+}
+
+/// Add files written under `out` to the manifest, inheriting the group's
+/// license, or the license all of its members share.
+fn register_outputs(root: &Path, manifest_path: &Path, group: &str, out: &Path) -> Result<()> {
+    let Ok(prefix) = out.strip_prefix(root) else {
+        return Ok(());
+    };
+    let prefix = prefix.to_string_lossy().to_string();
+    scan::run(root, manifest_path)?;
+    let mut manifest: Manifest = serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?;
+    let members = &manifest.groups[group];
+    let license = members.license.clone().or_else(|| {
+        let licenses: BTreeSet<_> = manifest
+            .assets
+            .iter()
+            .filter(|a| members.assets.contains(&a.path))
+            .map(|a| a.license.clone())
+            .collect();
+        match licenses.into_iter().collect::<Vec<_>>().as_slice() {
+            [Some(l)] => Some(l.clone()),
+            _ => None,
+        }
+    });
+    for asset in &mut manifest.assets {
+        if asset.license.is_none() && Path::new(&asset.path).starts_with(&prefix) {
+            asset.license = license.clone();
+        }
+    }
+    fs::write(manifest_path, serde_yaml::to_string(&manifest)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::Group;
+    use image::Rgba;
+    use tempfile::tempdir;
+
+    fn input(name: &str, w: u32, h: u32) -> Input {
+        let image = RgbaImage::from_pixel(w, h, Rgba([255, 0, 0, 255]));
+        prepare(name.into(), format!("icons/{name}.png"), image, true)
+    }
+
+    /// Sheet pixel holding sprite pixel `(x, y)`, as the runtime maps it.
+    fn sheet_pos(sp: &Sprite, x: u32, y: u32) -> (u32, u32) {
+        let rect = rlvgl_core::atlas::AtlasRect {
+            sheet: sp.sheet as u16,
+            x: sp.x as u16,
+            y: sp.y as u16,
+            width: sp.width as u16,
+            height: sp.height as u16,
+            offset_x: sp.offset_x as u16,
+            offset_y: sp.offset_y as u16,
+            source_width: sp.source_width as u16,
+            source_height: sp.source_height as u16,
+            rotated: sp.rotated,
+        };
+        let (sx, sy) = rect.sheet_pos(x as u16, y as u16);
+        (sx as u32, sy as u32)
+    }
+
+    /// Trimming records the offset of the opaque area.
+    #[test]
+    fn trims_transparent_borders() {
+        let mut image = RgbaImage::new(5, 4);
+        image.put_pixel(1, 2, Rgba([1, 2, 3, 255]));
+        image.put_pixel(3, 1, Rgba([4, 5, 6, 128]));
+        let trimmed = prepare("a".into(), "icons/a.png".into(), image, true);
+        assert_eq!(trimmed.image.dimensions(), (3, 2));
+        assert_eq!((trimmed.offset_x, trimmed.offset_y), (1, 1));
+        assert_eq!((trimmed.source_width, trimmed.source_height), (5, 4));
+        assert_eq!(trimmed.image.get_pixel(0, 1), &Rgba([1, 2, 3, 255]));
+    }
+
+    /// Sprites never overlap, respect padding and spill into new sheets.
+    #[test]
+    fn packs_without_overlap() {
+        let inputs = [input("a", 6, 6), input("b", 4, 3), input("c", 3, 3)];
+        let opts = Options {
+            max_size: 8,
+            padding: 1,
+            ..Default::default()
+        };
+        let Packed { sprites, sizes } = pack(&inputs, &opts).unwrap();
+        assert_eq!(sizes.len(), 2);
+        for (i, a) in sprites.iter().enumerate() {
+            assert!(a.x + a.width <= 8 && a.y + a.height <= 8);
+            for b in &sprites[i + 1..] {
+                let ra = Rect {
+                    x: a.x,
+                    y: a.y,
+                    w: a.width + 1,
+                    h: a.height + 1,
+                };
+                let rb = Rect {
+                    x: b.x,
+                    y: b.y,
+                    w: b.width + 1,
+                    h: b.height + 1,
+                };
+                assert!(a.sheet != b.sheet || !ra.intersects(&rb));
+            }
+        }
+        let big = Options {
+            max_size: 4,
+            ..Default::default()
+        };
+        assert!(pack(&inputs, &big).is_err());
+    }
+
+    /// Rotation lets a tall sprite share a row with a wide one.
+    #[test]
+    fn rotates_to_fit() {
+        let inputs = [input("wide", 4, 2), input("tall", 2, 4)];
+        let opts = Options {
+            max_size: 4,
+            padding: 0,
+            rotate: true,
+            ..Default::default()
+        };
+        let Packed { sprites, sizes } = pack(&inputs, &opts).unwrap();
+        assert_eq!(sizes, vec![(4, 4)]);
+        assert!(sprites.iter().any(|s| s.rotated));
+    }
+
+    /// The command writes sheets, an index and constants for a group.
+    #[test]
+    fn writes_sheets_index_and_consts() {
+        let tmp = tempdir().unwrap();
+        let icons = tmp.path().join("icons");
+        fs::create_dir_all(&icons).unwrap();
+        let mut arrow = RgbaImage::new(3, 2);
+        for (x, y, p) in arrow.enumerate_pixels_mut() {
+            *p = Rgba([x as u8 * 10, y as u8 * 10, 0, 255]);
+        }
+        arrow.save(icons.join("arrow.png")).unwrap();
+        let mut dot = RgbaImage::new(4, 4);
+        dot.put_pixel(2, 1, Rgba([0, 0, 255, 255]));
+        dot.save(icons.join("dot.png")).unwrap();
+
+        let manifest_path = tmp.path().join("manifest.yml");
+        scan::run(tmp.path(), &manifest_path).unwrap();
+        let mut manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest.groups.insert(
+            "ui".into(),
+            Group {
+                assets: vec!["icons/arrow.png".into(), "icons/dot.png".into()],
+                license: Some("MIT".into()),
+                ..Default::default()
+            },
+        );
+        fs::write(&manifest_path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+
+        let out = icons.join("atlas");
+        let opts = Options {
+            rotate: true,
+            format: IndexFormat::Ron,
+            ..Default::default()
+        };
+        run(tmp.path(), &manifest_path, "ui", &out, &opts).unwrap();
+
+        let ron = fs::read_to_string(out.join("ui.ron")).unwrap();
+        assert!(ron.contains("(file: \"ui-0.png\""));
+        let rust = fs::read_to_string(out.join("ui_atlas.rs")).unwrap();
+        assert!(rust.contains("pub const UI_DOT: AtlasRect"));
+        assert!(rust.contains("(\"arrow\", UI_ARROW),\n    (\"dot\", UI_DOT),"));
+
+        let sheet = image::open(out.join("ui-0.png")).unwrap().to_rgba8();
+        let inputs = [
+            prepare("arrow".into(), String::new(), arrow, true),
+            prepare("dot".into(), String::new(), dot, true),
+        ];
+        let sprites = pack(&inputs, &opts).unwrap().sprites;
+        let dot = &sprites[1];
+        assert_eq!(
+            (dot.width, dot.height, dot.offset_x, dot.offset_y),
+            (1, 1, 2, 1)
+        );
+        let arrow = &sprites[0];
+        for y in 0..2 {
+            for x in 0..3 {
+                let (sx, sy) = sheet_pos(arrow, x, y);
+                assert_eq!(sheet.get_pixel(sx, sy)[0], x as u8 * 10);
+                assert_eq!(sheet.get_pixel(sx, sy)[1], y as u8 * 10);
+            }
+        }
+
+        let manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        let entry = manifest
+            .assets
+            .iter()
+            .find(|a| a.path == "icons/atlas/ui-0.png")
+            .unwrap();
+        assert_eq!(entry.license.as_deref(), Some("MIT"));
+    }
+}
@@ -0,0 +1,370 @@
+//! Budget command for rlvgl-creator.
+//!
+//! Estimates flash and RAM use of manifest assets for a target preset and
+//! compares it against the preset's limits.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use serde::Serialize;
+
+use crate::manifest::{Asset, Manifest, Preset};
+
+/// Cost of a single asset on the target.
+#[derive(Serialize, Debug, PartialEq)]
+pub(crate) struct AssetCost {
+    /// Manifest path of the asset.
+    pub(crate) path: String,
+    /// Bytes stored in flash in the target format.
+    pub(crate) flash: u64,
+    /// Bytes of RAM needed to decode the asset, `0` when drawn in place.
+    pub(crate) ram: u64,
+}
+
+/// Summed cost of a manifest group.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub(crate) struct GroupCost {
+    /// Bytes stored in flash.
+    pub(crate) flash: u64,
+    /// Bytes of RAM if every member is decoded at once.
+    pub(crate) ram: u64,
+}
+
+/// Use of one memory against its optional limit.
+#[derive(Serialize, Debug, PartialEq)]
+pub(crate) struct Usage {
+    /// Estimated bytes in use.
+    pub(crate) used: u64,
+    /// Configured limit in bytes.
+    pub(crate) limit: Option<u64>,
+    /// Whether `used` exceeds `limit`.
+    pub(crate) over: bool,
+}
+
+impl Usage {
+    fn new(used: u64, limit: Option<u64>) -> Self {
+        Self {
+            used,
+            limit,
+            over: limit.is_some_and(|l| used > l),
+        }
+    }
+}
+
+/// Budget report for one target.
+#[derive(Serialize, Debug)]
+pub(crate) struct Report {
+    /// Target name.
+    pub(crate) target: String,
+    /// Per-asset costs in manifest order.
+    pub(crate) assets: Vec<AssetCost>,
+    /// Per-group totals.
+    pub(crate) groups: BTreeMap<String, GroupCost>,
+    /// Bytes of all full-screen framebuffers.
+    pub(crate) framebuffers: u64,
+    /// Bytes of the partial draw buffer.
+    pub(crate) draw_buffer: u64,
+    /// Flash taken by all assets.
+    pub(crate) flash: Usage,
+    /// RAM for display buffers plus the largest decoded asset.
+    pub(crate) ram: Usage,
+}
+
+impl Report {
+    /// Whether any limit is exceeded.
+    pub(crate) fn over_budget(&self) -> bool {
+        self.flash.over || self.ram.over
+    }
+}
+
+/// Print the budget for `target` and fail when it exceeds a limit.
+///
+/// `flash` and `ram` override the limits stored in the target preset.
+pub(crate) fn run(
+    root: &Path,
+    manifest_path: &Path,
+    target: Option<&str>,
+    flash: Option<u64>,
+    ram: Option<u64>,
+    json: bool,
+) -> Result<()> {
+    let manifest: Manifest = serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?;
+    let mut report = report(root, &manifest, target)?;
+    if flash.is_some() {
+        report.flash = Usage::new(report.flash.used, flash);
+    }
+    if ram.is_some() {
+        report.ram = Usage::new(report.ram.used, ram);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    if report.over_budget() {
+        bail!("Target `{}` is over budget", report.target);
+    }
+    Ok(())
+}
+
+/// Compute the budget of `manifest` assets for `target`, or for the only
+/// target with a preset when `None`.
+pub(crate) fn report(root: &Path, manifest: &Manifest, target: Option<&str>) -> Result<Report> {
+    let mut with_preset = manifest
+        .targets
+        .iter()
+        .filter_map(|t| t.preset.as_ref().map(|p| (t, p)));
+    let (target, preset) = match target {
+        Some(name) => {
+            let t = manifest
+                .targets
+                .iter()
+                .find(|t| t.name == name)
+                .ok_or_else(|| anyhow!("Unknown target `{name}`"))?;
+            let p = t
+                .preset
+                .as_ref()
+                .ok_or_else(|| anyhow!("Target `{name}` has no preset"))?;
+            (t, p)
+        }
+        None => match (with_preset.next(), with_preset.next()) {
+            (Some(found), None) => found,
+            (None, _) => bail!("No target with a preset in the manifest"),
+            (Some(_), Some(_)) => bail!("Several targets have presets; pass --target"),
+        },
+    };
+
+    let mut assets = Vec::new();
+    for asset in &manifest.assets {
+        assets.push(asset_cost(root, asset, preset)?);
+    }
+
+    let mut groups = BTreeMap::new();
+    for (name, group) in &manifest.groups {
+        let mut total = GroupCost::default();
+        for (asset, cost) in manifest.assets.iter().zip(&assets) {
+            let source = asset.variant.as_ref().map(|v| &v.source);
+            if group
+                .assets
+                .iter()
+                .any(|m| *m == asset.path || Some(m) == source)
+            {
+                total.flash += cost.flash;
+                total.ram += cost.ram;
+            }
+        }
+        groups.insert(name.clone(), total);
+    }
+
+    let (width, height) = (preset.width as u64, preset.height as u64);
+    let stride = (width * preset.color_depth as u64).div_ceil(8);
+    let framebuffers = stride * height * preset.framebuffers.unwrap_or(1) as u64;
+    let lines = preset
+        .draw_buffer_lines
+        .map(u64::from)
+        .unwrap_or(height.div_ceil(10));
+    let draw_buffer = stride * lines.min(height);
+    let flash = assets.iter().map(|a| a.flash).sum();
+    let peak = assets.iter().map(|a| a.ram).max().unwrap_or(0);
+
+    Ok(Report {
+        target: target.name.clone(),
+        assets,
+        groups,
+        framebuffers,
+        draw_buffer,
+        flash: Usage::new(flash, preset.flash_bytes),
+        ram: Usage::new(framebuffers + draw_buffer + peak, preset.ram_bytes),
+    })
+}
+
+/// Estimate the flash and decode cost of one asset.
+///
+/// Converted variants are stored as written and drawn in place when they
+/// are uncompressed and match the display depth. Source images and `.raw`
+/// files are charged at the display depth for both flash and decoding.
+/// Other files, such as packed fonts, are read in place.
+fn asset_cost(root: &Path, asset: &Asset, preset: &Preset) -> Result<AssetCost> {
+    let path = root.join(&asset.path);
+    let size = fs::metadata(&path)
+        .map_err(|e| anyhow!("`{}`: {e}", asset.path))?
+        .len();
+    let depth = preset.color_depth as u64;
+    let bitmap = |w: u64, h: u64| (w * depth).div_ceil(8) * h;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let (flash, ram) = if let Some(v) = &asset.variant {
+        let bits = crate::pixels::PixelFormat::parse(&v.format)
+            .ok()
+            .flatten()
+            .map_or(32, |f| f.bits() as u64);
+        let in_place = v.compression.as_deref().is_none_or(|c| c == "none") && bits == depth;
+        let ram = if in_place {
+            0
+        } else {
+            bitmap(v.width as u64, v.height as u64)
+        };
+        (size, ram)
+    } else if ext == "raw" {
+        let data = fs::read(&path)?;
+        let dim = |at: usize| {
+            data.get(at..at + 4)
+                .map(|b| u32::from_le_bytes(b.try_into().unwrap()) as u64)
+                .ok_or_else(|| anyhow!("`{}` is truncated", asset.path))
+        };
+        let cost = bitmap(dim(8)?, dim(12)?);
+        (cost, cost)
+    } else if matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "bmp" | "gif") {
+        let (w, h) = image::image_dimensions(&path)?;
+        let cost = bitmap(w as u64, h as u64);
+        (cost, cost)
+    } else {
+        (size, 0)
+    };
+    Ok(AssetCost {
+        path: asset.path.clone(),
+        flash,
+        ram,
+    })
+}
+
+fn print_report(report: &Report) {
+    println!("Budget for target `{}`", report.target);
+    for a in &report.assets {
+        println!("  {:<40} flash {:>10}  ram {:>10}", a.path, a.flash, a.ram);
+    }
+    if !report.groups.is_empty() {
+        println!("Groups:");
+        for (name, g) in &report.groups {
+            println!("  {:<40} flash {:>10}  ram {:>10}", name, g.flash, g.ram);
+        }
+    }
+    println!("Framebuffers: {} bytes", report.framebuffers);
+    println!("Draw buffer:  {} bytes", report.draw_buffer);
+    for (label, usage) in [("Flash", &report.flash), ("RAM", &report.ram)] {
+        match usage.limit {
+            Some(limit) => println!(
+                "{label}: {} / {limit} bytes{}",
+                usage.used,
+                if usage.over { " (over budget)" } else { "" }
+            ),
+            None => println!("{label}: {} bytes (no limit)", usage.used),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{Group, Target, Variant};
+    use tempfile::tempdir;
+
+    fn asset(path: &str) -> Asset {
+        Asset {
+            name: String::new(),
+            path: path.into(),
+            hash: String::new(),
+            license: None,
+            lottie: None,
+            frame_delay_ms: None,
+            loop_count: None,
+            export: None,
+            font: None,
+            variant: None,
+        }
+    }
+
+    fn preset(flash_bytes: Option<u64>, ram_bytes: Option<u64>) -> Preset {
+        Preset {
+            width: 20,
+            height: 10,
+            color_depth: 16,
+            storage: None,
+            flash_bytes,
+            ram_bytes,
+            framebuffers: Some(2),
+            draw_buffer_lines: None,
+        }
+    }
+
+    fn manifest(root: &Path, flash: Option<u64>, ram: Option<u64>) -> Manifest {
+        let icons = root.join("icons");
+        fs::create_dir_all(&icons).unwrap();
+        fs::create_dir_all(root.join("fonts")).unwrap();
+        image::RgbaImage::new(8, 4)
+            .save(icons.join("logo.png"))
+            .unwrap();
+        fs::write(icons.join("arrow_4.rgb565"), [0u8; 40]).unwrap();
+        fs::write(icons.join("arrow_2.rgb565"), [0u8; 12]).unwrap();
+        fs::write(root.join("fonts/ui-12.bin"), [0u8; 300]).unwrap();
+
+        let variant = |w, compression: Option<&str>| Variant {
+            source: "icons/arrow.png".into(),
+            width: w,
+            height: w,
+            format: "rgb565".into(),
+            premultiplied: false,
+            compression: compression.map(Into::into),
+            dither: None,
+            palette_group: None,
+        };
+        let mut m = Manifest::default();
+        m.assets.push(asset("icons/logo.png"));
+        let mut big = asset("icons/arrow_4.rgb565");
+        big.variant = Some(variant(4, None));
+        m.assets.push(big);
+        let mut small = asset("icons/arrow_2.rgb565");
+        small.variant = Some(variant(2, Some("rle")));
+        m.assets.push(small);
+        m.assets.push(asset("fonts/ui-12.bin"));
+        m.groups.insert(
+            "arrows".into(),
+            Group {
+                assets: vec!["icons/arrow.png".into()],
+                ..Default::default()
+            },
+        );
+        m.targets.push(Target {
+            name: "board".into(),
+            vendor_dir: "out".into(),
+            preset: Some(preset(flash, ram)),
+        });
+        m
+    }
+
+    /// Sizes follow the target depth, variants and in-place rules.
+    #[test]
+    fn computes_asset_and_buffer_costs() {
+        let tmp = tempdir().unwrap();
+        let m = manifest(tmp.path(), Some(1000), None);
+        let report = report(tmp.path(), &m, None).unwrap();
+        let costs: Vec<_> = report.assets.iter().map(|a| (a.flash, a.ram)).collect();
+        assert_eq!(costs, vec![(64, 64), (40, 0), (12, 8), (300, 0)]);
+        assert_eq!(report.groups["arrows"], GroupCost { flash: 52, ram: 8 });
+        assert_eq!(report.framebuffers, 2 * 40 * 10);
+        assert_eq!(report.draw_buffer, 40);
+        assert_eq!(report.flash, Usage::new(416, Some(1000)));
+        assert_eq!(report.ram.used, 800 + 40 + 64);
+        assert!(!report.over_budget());
+    }
+
+    /// Exceeding a limit fails the command.
+    #[test]
+    fn fails_when_over_budget() {
+        let tmp = tempdir().unwrap();
+        let m = manifest(tmp.path(), Some(400), Some(10_000));
+        let manifest_path = tmp.path().join("manifest.yml");
+        fs::write(&manifest_path, serde_yaml::to_string(&m).unwrap()).unwrap();
+        assert!(report(tmp.path(), &m, Some("board")).unwrap().flash.over);
+        assert!(run(tmp.path(), &manifest_path, None, None, None, true).is_err());
+        assert!(run(tmp.path(), &manifest_path, None, Some(416), None, false).is_ok());
+        assert!(report(tmp.path(), &m, Some("other")).is_err());
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides CLI utilities for managing rlvgl assets. Supports the `init`, `scan`, `check`,
 //! `vendor`, `convert`, `preview`, `add-target`, `sync`, `scaffold`, `apng`, `schema`, `fonts`,
-//! `svg`, and `lottie` commands to bootstrap asset directories, update a manifest, validate
+//! `svg`, `lottie`, `i18n`, and `reload` commands to bootstrap asset directories, update a manifest, validate
 //! asset policies, copy assets to build outputs, regenerate feature lists, generate thumbnails,
 //! register targets, build animations, pack fonts, render SVGs, import Lottie animations, and
 //! generate dual-mode crates.
@@ -14,22 +14,30 @@ use clap::{ArgAction, Parser, Subcommand};
 
 pub mod add_target;
 pub mod apng;
+pub mod atlas;
 pub mod board_import;
+pub mod budget;
 pub mod bsp_gen;
 pub mod check;
 pub mod convert;
 pub mod fonts;
 pub mod gen_lib;
+pub mod i18n;
 pub mod init;
 pub mod lottie;
 pub mod manifest;
+pub mod palette;
+pub mod pixels;
 pub mod preview;
 pub mod raw;
+#[cfg(unix)]
+pub mod reload;
 pub mod scaffold;
 pub mod scan;
 pub mod schema;
 pub mod svg;
 pub mod sync;
+pub mod ui_gen;
 pub mod util;
 pub mod vendor;
 
@@ -140,8 +148,85 @@ enum Command {
         #[arg(long, default_value_t = 0)]
         loops: u32,
     },
+    /// Pack a group's images into sprite atlas sheets
+    Atlas {
+        /// Root path containing assets
+        path: PathBuf,
+        /// Manifest group whose assets are packed
+        #[arg(long)]
+        group: String,
+        /// Directory for sheets, index and constants (default `<path>/icons/atlas`)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Maximum sheet width and height in pixels
+        #[arg(long, default_value_t = 1024)]
+        max_size: u32,
+        /// Transparent pixels between sprites
+        #[arg(long, default_value_t = 1)]
+        padding: u32,
+        /// Keep transparent borders instead of trimming them
+        #[arg(long)]
+        no_trim: bool,
+        /// Allow storing sprites rotated 90° clockwise
+        #[arg(long)]
+        rotate: bool,
+        /// Index format (`json` or `ron`)
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+    /// Estimate flash and RAM use for a target preset
+    Budget {
+        /// Root path containing assets
+        path: PathBuf,
+        /// Target whose preset is used (default: the only target with one)
+        #[arg(long)]
+        target: Option<String>,
+        /// Flash limit in bytes, overriding the preset
+        #[arg(long, value_name = "BYTES")]
+        flash: Option<u64>,
+        /// RAM limit in bytes, overriding the preset
+        #[arg(long, value_name = "BYTES")]
+        ram: Option<u64>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Tell a running simulator to reload changed assets
+    #[cfg(unix)]
+    Reload {
+        /// Asset names relative to the simulator's `--assets` directory
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Reload socket (default `$RLVGL_RELOAD_SOCKET` or `rlvgl-sim.sock` in the temp dir)
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
     /// Output a JSON schema for the manifest structure
     Schema,
+    /// Compile a declarative screen description into Rust
+    UiGen {
+        /// Screen description (YAML or JSON)
+        input: PathBuf,
+        /// Rust file to write (default: the input with an `.rs` extension)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Compile `.po` or XLIFF translations into Rust string tables
+    I18n {
+        /// Translation files (`.po` or `.xlf`)
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Rust file to write
+        #[arg(long)]
+        out: PathBuf,
+        /// Locale of the message ids, used as the last fallback
+        #[arg(long, default_value = "en")]
+        source_locale: String,
+        /// Font asset whose glyph set gains the translated characters
+        /// (`path` or `path=de,fr` to limit the locales)
+        #[arg(long = "font", value_name = "ASSET[=LOCALES]")]
+        fonts: Vec<String>,
+    },
     /// Font-related commands
     Fonts {
         #[command(subcommand)]
@@ -217,6 +302,21 @@ enum FontsCommand {
             default_value = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
         )]
         chars: String,
+        /// Glyph set specification with `U+XXXX-U+YYYY` ranges, replacing `--chars`
+        #[arg(long)]
+        glyphs: Option<String>,
+        /// Coverage bits per pixel (1, 2, 4 or 8)
+        #[arg(long, default_value_t = fonts::DEFAULT_BPP)]
+        bpp: u8,
+        /// Run-length encode glyph bitmaps
+        #[arg(long)]
+        rle: bool,
+        /// Snap advances and kerning to whole pixels
+        #[arg(long)]
+        hinting: bool,
+        /// Store kerning pairs between the packed glyphs
+        #[arg(long)]
+        kerning: bool,
     },
 }
 
@@ -338,10 +438,63 @@ pub fn run() -> Result<()> {
             delay,
             loops,
         } => apng::run(&frames, &out, delay, loops)?,
+        Command::Atlas {
+            path,
+            group,
+            out,
+            max_size,
+            padding,
+            no_trim,
+            rotate,
+            format,
+        } => {
+            let opts = atlas::Options {
+                max_size,
+                padding,
+                trim: !no_trim,
+                rotate,
+                format: atlas::IndexFormat::parse(&format)?,
+            };
+            let out = out.unwrap_or_else(|| path.join("icons/atlas"));
+            atlas::run(&path, &cli.manifest, &group, &out, &opts)?
+        }
+        Command::Budget {
+            path,
+            target,
+            flash,
+            ram,
+            json,
+        } => budget::run(&path, &cli.manifest, target.as_deref(), flash, ram, json)?,
+        #[cfg(unix)]
+        Command::Reload { names, socket } => reload::run(&names, socket.as_deref())?,
         Command::Schema => schema::run()?,
+        Command::UiGen { input, out } => ui_gen::run(&input, out.as_deref())?,
+        Command::I18n {
+            inputs,
+            out,
+            source_locale,
+            fonts,
+        } => i18n::run(&inputs, &out, &source_locale, &fonts, &cli.manifest)?,
         Command::Fonts { cmd } => match cmd {
-            FontsCommand::Pack { path, size, chars } => {
-                fonts::pack(&path, &cli.manifest, size as f32, &chars)?
+            FontsCommand::Pack {
+                path,
+                size,
+                chars,
+                glyphs,
+                bpp,
+                rle,
+                hinting,
+                kerning,
+            } => {
+                let defaults = manifest::FontOptions {
+                    glyphs: Some(glyphs.unwrap_or_else(|| fonts::glyph_spec(&chars))),
+                    sizes: vec![size as u32],
+                    hinting,
+                    packing: rle.then(|| "rle".into()),
+                    bpp: Some(bpp),
+                    kerning,
+                };
+                fonts::pack_with(&path, &cli.manifest, &defaults)?
             }
         },
         Command::Lottie { cmd } => match cmd {
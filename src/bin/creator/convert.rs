@@ -1,7 +1,11 @@
 //! Convert command for rlvgl-creator.
 //!
 //! Normalizes raster assets to raw RGBA sequences, caches conversions by
-//! content hash, and refreshes the manifest.
+//! content hash, and refreshes the manifest. Assets with export options are
+//! converted into one variant per requested size in the requested pixel
+//! format and compression, each recorded as its own manifest entry. Indexed
+//! formats use a palette generated per image, or one palette shared by every
+//! member of a group with `shared_palette` set.
 
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -10,10 +14,15 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use blake3::Hasher;
+use image::{DynamicImage, GenericImageView, imageops::FilterType};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use crate::manifest::{Asset, ExportOptions, Manifest, Variant};
+use crate::palette::{self, Dither};
+use crate::pixels::{self, Compression, Encoding, PixelFormat};
+use crate::util::const_name;
 use crate::{check, raw};
 
 /// Work item describing a source asset pending conversion.
@@ -23,6 +32,15 @@ struct Task {
     data: Vec<u8>,
     src_mtime: u64,
     hash: String,
+    /// Export options and license copied from the source manifest entry.
+    export: Option<ExportOptions>,
+    license: Option<String>,
+}
+
+/// Palette shared by the members of a manifest group.
+struct SharedPalette {
+    group: String,
+    colors: Vec<[u8; 4]>,
 }
 
 /// Result of a conversion including cache metadata and log message.
@@ -30,6 +48,7 @@ struct ConvResult {
     rel: String,
     entry: CacheEntry,
     log: String,
+    variants: Vec<Asset>,
 }
 
 /// Convert assets under the root to `.raw`, caching by content hash, and
@@ -37,6 +56,11 @@ struct ConvResult {
 pub(crate) fn run(root: &Path, manifest_path: &Path, force: bool) -> Result<()> {
     let cache_path = root.join(".cache/convert.json");
     let mut cache = Cache::load(&cache_path)?;
+    let mut manifest: Manifest = if manifest_path.exists() {
+        serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?
+    } else {
+        Manifest::default()
+    };
     let mut seen = HashSet::new();
     let mut tasks = Vec::new();
 
@@ -62,7 +86,7 @@ pub(crate) fn run(root: &Path, manifest_path: &Path, force: bool) -> Result<()>
             }
         }
 
-        if path.extension().and_then(|e| e.to_str()) == Some("raw") {
+        if is_output(path) {
             continue;
         }
 
@@ -107,7 +131,10 @@ pub(crate) fn run(root: &Path, manifest_path: &Path, force: bool) -> Result<()>
             }
         }
 
+        let source = manifest.assets.iter().find(|a| a.path == rel_str);
         tasks.push(Task {
+            export: source.and_then(|a| a.export.clone()),
+            license: source.and_then(|a| a.license.clone()),
             rel: rel_str,
             path: path.to_path_buf(),
             data,
@@ -117,34 +144,61 @@ pub(crate) fn run(root: &Path, manifest_path: &Path, force: bool) -> Result<()>
     }
 
     tasks.sort_by(|a, b| a.rel.cmp(&b.rel));
+    let shared = shared_palettes(&mut manifest, &tasks)?;
 
     let results: Vec<ConvResult> = tasks
         .into_par_iter()
         .map(|t| -> Result<ConvResult> {
             let img = image::load_from_memory(&t.data)?;
-            let dest = t.path.with_extension("raw");
-            raw::encode_image(img, &dest)?;
+            let written =
+                write_variants(img, &t.path, &t.rel, t.export.as_ref(), shared.get(&t.rel))?;
             fs::remove_file(&t.path)?;
 
-            let meta = fs::metadata(&dest)?;
-            let mtime = meta
-                .modified()
-                .unwrap_or(SystemTime::UNIX_EPOCH)
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
+            let mut outputs = Vec::new();
+            let mut variants = Vec::new();
+            let mut logs = Vec::new();
+            for (dest, variant) in written {
+                let meta = fs::metadata(&dest)?;
+                let mtime = meta
+                    .modified()
+                    .unwrap_or(SystemTime::UNIX_EPOCH)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let rel = dest.strip_prefix(root)?.to_path_buf();
+                let rel_str = rel.to_string_lossy().to_string();
+                logs.push(format!(
+                    "Converted {} -> {}",
+                    t.path.display(),
+                    dest.display()
+                ));
+                variants.push(Asset {
+                    name: const_name(&rel_str),
+                    path: rel_str,
+                    hash: blake3::hash(&fs::read(&dest)?).to_hex().to_string(),
+                    license: t.license.clone(),
+                    lottie: None,
+                    frame_delay_ms: None,
+                    loop_count: None,
+                    export: t.export.clone(),
+                    font: None,
+                    variant,
+                });
+                outputs.push(CacheOutput {
+                    path: rel,
+                    size: meta.len(),
+                    mtime,
+                });
+            }
             Ok(ConvResult {
                 rel: t.rel,
-                log: format!("Converted {} -> {}", t.path.display(), dest.display()),
+                log: logs.join("\n"),
                 entry: CacheEntry {
                     hash: t.hash,
                     src_mtime: t.src_mtime,
-                    outputs: vec![CacheOutput {
-                        path: dest.strip_prefix(root)?.to_path_buf(),
-                        size: meta.len(),
-                        mtime,
-                    }],
+                    outputs,
                 },
+                variants,
             })
         })
         .collect::<Result<Vec<_>>>()?;
@@ -153,16 +207,190 @@ pub(crate) fn run(root: &Path, manifest_path: &Path, force: bool) -> Result<()>
         println!("{}", r.log);
     }
 
+    let converted = !results.is_empty();
     for r in results {
+        for variant in r.variants {
+            manifest.assets.retain(|a| a.path != variant.path);
+            manifest.assets.push(variant);
+        }
         cache.entries.insert(r.rel, r.entry);
     }
 
     cache.retain(&seen);
     cache.save(&cache_path)?;
+    if converted {
+        fs::write(manifest_path, serde_yaml::to_string(&manifest)?)?;
+    }
     check::run(root, manifest_path, true)?;
     Ok(())
 }
 
+/// Whether `path` is a conversion output rather than a source asset.
+fn is_output(path: &Path) -> bool {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    ext == "raw" || PixelFormat::ALL.iter().any(|f| f.name() == ext)
+}
+
+/// Indexed export format requested by a task, if any.
+fn indexed_format(task: &Task) -> Result<Option<PixelFormat>> {
+    let Some(export) = &task.export else {
+        return Ok(None);
+    };
+    let format = PixelFormat::parse(export.color_space.as_deref().unwrap_or(""))?;
+    Ok(format.filter(|f| f.is_indexed()))
+}
+
+/// Resolve the palettes of groups with `shared_palette` set, keyed by the
+/// path of each pending task that uses one.
+///
+/// A palette already stored on the group is reused so later conversions stay
+/// consistent; otherwise one is generated from all pending indexed members,
+/// sized for the member with the fewest index bits, and stored on the group.
+fn shared_palettes(
+    manifest: &mut Manifest,
+    tasks: &[Task],
+) -> Result<HashMap<String, SharedPalette>> {
+    let mut shared = HashMap::new();
+    for (name, group) in manifest.groups.iter_mut() {
+        if !group.shared_palette {
+            continue;
+        }
+        let mut members = Vec::new();
+        for t in tasks {
+            if shared.contains_key(&t.rel) || !group.assets.contains(&t.rel) {
+                continue;
+            }
+            if let Some(format) = indexed_format(t)? {
+                members.push((t, format));
+            }
+        }
+        if members.is_empty() {
+            continue;
+        }
+        let colors = if group.palette.is_empty() {
+            let size = members
+                .iter()
+                .map(|(_, f)| f.palette_size())
+                .min()
+                .unwrap_or(256);
+            let mut samples = Vec::new();
+            for (t, _) in &members {
+                let mut rgba = image::load_from_memory(&t.data)?.to_rgba8().into_raw();
+                if t.export.as_ref().is_some_and(|e| e.premultiplied) {
+                    pixels::premultiply(&mut rgba);
+                }
+                samples.extend(pixels::to_pixels(&rgba));
+            }
+            let colors = palette::generate(&samples, size);
+            group.palette = colors.iter().map(|c| palette::to_hex(*c)).collect();
+            colors
+        } else {
+            group
+                .palette
+                .iter()
+                .map(|c| palette::from_hex(c))
+                .collect::<Result<Vec<_>>>()?
+        };
+        for (t, _) in members {
+            shared.insert(
+                t.rel.clone(),
+                SharedPalette {
+                    group: name.clone(),
+                    colors: colors.clone(),
+                },
+            );
+        }
+    }
+    Ok(shared)
+}
+
+/// Write every variant requested by `export` next to `src`, whose manifest
+/// path is `rel`.
+///
+/// Without a pixel format, premultiplication, compression, or dithering the
+/// variants are `.raw` files as before; otherwise they are `RLVGLPIX` files
+/// named after their format. Returns each output path with the variant
+/// metadata recorded in the manifest, which is `None` for a plain conversion.
+fn write_variants(
+    img: DynamicImage,
+    src: &Path,
+    rel: &str,
+    export: Option<&ExportOptions>,
+    shared: Option<&SharedPalette>,
+) -> Result<Vec<(PathBuf, Option<Variant>)>> {
+    let Some(export) = export else {
+        let dest = src.with_extension("raw");
+        raw::encode_image(img, &dest)?;
+        return Ok(vec![(dest, None)]);
+    };
+    let format = PixelFormat::parse(export.color_space.as_deref().unwrap_or(""))?;
+    let compression = Compression::parse(export.compression.as_deref())?;
+    let dither = Dither::parse(export.dither.as_deref())?;
+    let packed = format.is_some()
+        || export.premultiplied
+        || compression != Compression::None
+        || dither != Dither::None;
+    let format = format.unwrap_or(PixelFormat::Argb8888);
+    let encoding = Encoding {
+        format,
+        premultiplied: export.premultiplied,
+        compression,
+        dither,
+        palette: shared.map(|p| p.colors.as_slice()),
+    };
+
+    let (src_w, src_h) = img.dimensions();
+    let mut sizes = export.sizes.clone();
+    if sizes.is_empty() {
+        sizes.push(src_w);
+    }
+    let stem = src
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut out = Vec::new();
+    for width in sizes {
+        let width = width.max(1);
+        let height =
+            ((src_h as u64 * width as u64 + src_w as u64 / 2) / src_w.max(1) as u64).max(1) as u32;
+        let scaled = if (width, height) == (src_w, src_h) {
+            img.clone()
+        } else {
+            img.resize_exact(width, height, FilterType::Lanczos3)
+        };
+        let name = if export.sizes.is_empty() {
+            stem.clone()
+        } else {
+            format!("{stem}_{width}")
+        };
+        let variant = Variant {
+            source: rel.to_string(),
+            width,
+            height,
+            format: if packed { format.name() } else { "rgba8" }.to_string(),
+            premultiplied: export.premultiplied,
+            compression: (compression != Compression::None).then(|| compression.name().into()),
+            dither: (dither != Dither::None).then(|| dither.name().into()),
+            palette_group: shared.map(|p| p.group.clone()),
+        };
+        let dest = if packed {
+            let dest = src.with_file_name(format!("{name}.{}", format.name()));
+            let rgba = scaled.to_rgba8().into_raw();
+            pixels::write_variant(&dest, &rgba, width, height, &encoding)?;
+            dest
+        } else {
+            let dest = src.with_file_name(format!("{name}.raw"));
+            raw::encode_image(scaled, &dest)?;
+            dest
+        };
+        out.push((dest, Some(variant)));
+    }
+    Ok(out)
+}
+
 /// Persistent cache of conversions keyed by source path.
 #[derive(Default, Serialize, Deserialize)]
 struct Cache {
@@ -244,4 +472,115 @@ mod tests {
         let manifest: crate::manifest::Manifest = serde_yaml::from_str(&data).unwrap();
         assert_eq!(manifest.assets.len(), 3);
     }
+
+    /// Export options produce one packed variant per size with its own entry.
+    #[test]
+    fn export_options_write_variants() {
+        let tmp = tempdir().unwrap();
+        let icons = tmp.path().join("icons");
+        fs::create_dir_all(&icons).unwrap();
+        image::RgbaImage::from_pixel(4, 2, image::Rgba([255, 0, 0, 255]))
+            .save(icons.join("logo.png"))
+            .unwrap();
+
+        let manifest_path = tmp.path().join("manifest.yml");
+        scan::run(tmp.path(), &manifest_path).unwrap();
+        let mut manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest.assets[0].license = Some("MIT".into());
+        manifest.assets[0].export = Some(ExportOptions {
+            sizes: vec![4, 2],
+            color_space: Some("rgb565".into()),
+            premultiplied: false,
+            compression: Some("rle".into()),
+            dither: None,
+        });
+        fs::write(&manifest_path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+
+        super::run(tmp.path(), &manifest_path, false).unwrap();
+
+        let small = fs::read(icons.join("logo_2.rgb565")).unwrap();
+        assert_eq!(&small[..8], pixels::MAGIC);
+        assert_eq!(&small[8..16], &[2, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(&small[16..20], &[1, 0, 1, 0]);
+        // Two red pixels collapse into a single run.
+        assert_eq!(&small[24..], &[2, 0x00, 0xf8]);
+        assert!(icons.join("logo_4.rgb565").exists());
+        assert!(!icons.join("logo.png").exists());
+
+        let manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(manifest.assets.len(), 2);
+        let entry = manifest
+            .assets
+            .iter()
+            .find(|a| a.path == "icons/logo_2.rgb565")
+            .unwrap();
+        assert_eq!(entry.license.as_deref(), Some("MIT"));
+        let variant = entry.variant.as_ref().unwrap();
+        assert_eq!(variant.source, "icons/logo.png");
+        assert_eq!((variant.width, variant.height), (2, 1));
+        assert_eq!(variant.format, "rgb565");
+        assert_eq!(variant.compression.as_deref(), Some("rle"));
+    }
+
+    /// Indexed assets in a group with `shared_palette` use one stored palette.
+    #[test]
+    fn group_shares_generated_palette() {
+        let tmp = tempdir().unwrap();
+        let icons = tmp.path().join("icons");
+        fs::create_dir_all(&icons).unwrap();
+        image::RgbaImage::from_pixel(2, 1, image::Rgba([255, 0, 0, 255]))
+            .save(icons.join("a.png"))
+            .unwrap();
+        image::RgbaImage::from_pixel(2, 1, image::Rgba([0, 0, 255, 255]))
+            .save(icons.join("b.png"))
+            .unwrap();
+
+        let manifest_path = tmp.path().join("manifest.yml");
+        scan::run(tmp.path(), &manifest_path).unwrap();
+        let mut manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        for asset in &mut manifest.assets {
+            asset.license = Some("MIT".into());
+            asset.export = Some(ExportOptions {
+                color_space: Some("i1".into()),
+                dither: Some("floyd-steinberg".into()),
+                ..Default::default()
+            });
+        }
+        manifest.groups.insert(
+            "ui".into(),
+            crate::manifest::Group {
+                assets: vec!["icons/a.png".into(), "icons/b.png".into()],
+                shared_palette: true,
+                ..Default::default()
+            },
+        );
+        fs::write(&manifest_path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+
+        super::run(tmp.path(), &manifest_path, false).unwrap();
+
+        let manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(
+            manifest.groups["ui"].palette,
+            vec!["#0000ffff".to_string(), "#ff0000ff".to_string()]
+        );
+        let a = fs::read(icons.join("a.i1")).unwrap();
+        let b = fs::read(icons.join("b.i1")).unwrap();
+        // Both files carry the same two-entry palette.
+        assert_eq!(&a[20..30], &b[20..30]);
+        assert_eq!(&a[20..22], &[2, 0]);
+        assert_eq!(a[34..], [0b1100_0000]);
+        assert_eq!(b[34..], [0b0000_0000]);
+        let variant = manifest
+            .assets
+            .iter()
+            .find(|a| a.path == "icons/a.i1")
+            .and_then(|a| a.variant.as_ref())
+            .unwrap();
+        assert_eq!(variant.palette_group.as_deref(), Some("ui"));
+        assert_eq!(variant.dither.as_deref(), Some("floyd-steinberg"));
+    }
 }
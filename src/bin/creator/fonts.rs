@@ -1,16 +1,27 @@
 //! Fonts packing utilities for rlvgl-creator.
 //!
-//! Packs TTF/OTF fonts into bitmap `.bin` files and matching metrics `.json` descriptors.
+//! Rasterizes TTF/OTF fonts into `RLVGLFNT` bitmap fonts read by
+//! `rlvgl_core::font::BitmapFont`, plus a matching metrics `.json`
+//! descriptor. Per-font `font` options in the manifest select the glyph set,
+//! sizes, bit depth, packing, hinting and kerning; fonts without them use the
+//! command defaults.
 
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use fontdue::{Font, FontSettings};
 use serde::Serialize;
 use walkdir::WalkDir;
 
+use rlvgl_core::font::{FLAG_RLE, MAGIC, VERSION};
+
 use crate::check;
+use crate::manifest::{FontOptions, Manifest};
+
+/// Bit depth used when the options leave it unset.
+pub(crate) const DEFAULT_BPP: u8 = 4;
 
 #[derive(Serialize)]
 struct GlyphMetric {
@@ -21,8 +32,17 @@ struct GlyphMetric {
     offset: usize,
 }
 
-/// Pack font files under `root` into binary and JSON outputs.
-pub(crate) fn pack(root: &Path, manifest_path: &Path, size: f32, chars: &str) -> Result<()> {
+/// Pack font files under `root` into bitmap fonts and JSON metrics.
+///
+/// Fonts with `font` options in the manifest use them; all others use
+/// `defaults`. One `<stem>-<size>.bin` and `<stem>-<size>.json` pair is
+/// written per size.
+pub(crate) fn pack_with(root: &Path, manifest_path: &Path, defaults: &FontOptions) -> Result<()> {
+    let manifest: Manifest = if manifest_path.exists() {
+        serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?
+    } else {
+        Manifest::default()
+    };
     for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
         if !entry.file_type().is_file() {
@@ -33,52 +53,305 @@ pub(crate) fn pack(root: &Path, manifest_path: &Path, size: f32, chars: &str) ->
             _ => continue,
         }
 
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+        let opts = manifest
+            .assets
+            .iter()
+            .find(|a| a.path == rel)
+            .and_then(|a| a.font.as_ref())
+            .unwrap_or(defaults);
+
         let data = fs::read(path)?;
         let font = Font::from_bytes(data, FontSettings::default()).map_err(|e| anyhow!(e))?;
-
-        let mut bin = Vec::new();
-        let mut metrics = Vec::new();
-        let mut offset = 0usize;
-
-        for ch in chars.chars() {
-            let (m, bitmap) = font.rasterize(ch, size);
-            metrics.push(GlyphMetric {
-                ch,
-                width: m.width,
-                height: m.height,
-                advance: m.advance_width,
-                offset,
-            });
-            bin.extend_from_slice(&bitmap);
-            offset += bitmap.len();
+        let chars: Vec<char> = parse_glyphs(opts.glyphs.as_deref().unwrap_or("ascii"))?
+            .into_iter()
+            .filter(|&c| font.has_glyph(c))
+            .collect();
+        let mut sizes = opts.sizes.clone();
+        if sizes.is_empty() {
+            sizes.extend(&defaults.sizes);
         }
 
         let stem = path.file_stem().unwrap().to_string_lossy();
-        let bin_path = path.with_file_name(format!("{}-{}.bin", stem, size as usize));
-        let json_path = path.with_file_name(format!("{}-{}.json", stem, size as usize));
-        fs::write(&bin_path, &bin)?;
-        fs::write(&json_path, serde_json::to_vec(&metrics)?)?;
-        println!(
-            "Packed {} -> {}, {}",
-            path.display(),
-            bin_path.display(),
-            json_path.display()
-        );
+        for size in sizes {
+            let (bin, metrics) = encode_font(&font, &chars, size as f32, opts)?;
+            let bin_path = path.with_file_name(format!("{stem}-{size}.bin"));
+            let json_path = path.with_file_name(format!("{stem}-{size}.json"));
+            fs::write(&bin_path, &bin)?;
+            fs::write(&json_path, serde_json::to_vec(&metrics)?)?;
+            println!(
+                "Packed {} -> {}, {}",
+                path.display(),
+                bin_path.display(),
+                json_path.display()
+            );
+        }
     }
 
     // Refresh manifest with new font assets.
     check::run(root, manifest_path, true)?;
     Ok(())
 }
-#[cfg(all(test, feature = "regression"))]
+
+/// Glyph set specification selecting exactly the characters of `chars`.
+pub(crate) fn glyph_spec(chars: &str) -> String {
+    chars
+        .chars()
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Expand a glyph set specification into sorted characters.
+pub(crate) fn parse_glyphs(spec: &str) -> Result<BTreeSet<char>> {
+    let codepoint = |s: &str| -> Result<char> {
+        let hex = s.trim().trim_start_matches("U+").trim_start_matches("u+");
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| anyhow!("invalid codepoint `{s}`"))
+    };
+    let mut out = BTreeSet::new();
+    for item in spec.split(',') {
+        let trimmed = item.trim();
+        match trimmed.to_ascii_lowercase().as_str() {
+            "" => continue,
+            "ascii" => out.extend(' '..='~'),
+            "latin1" => out.extend('\u{a0}'..='\u{ff}'),
+            lower if lower.starts_with("u+") => match trimmed.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (codepoint(start)?, codepoint(end)?);
+                    if start > end {
+                        bail!("empty glyph range `{trimmed}`");
+                    }
+                    out.extend(start..=end);
+                }
+                None => {
+                    out.insert(codepoint(trimmed)?);
+                }
+            },
+            _ => out.extend(trimmed.chars()),
+        }
+    }
+    Ok(out)
+}
+
+/// Group sorted `chars` into `(first, len)` runs of consecutive codepoints.
+pub(crate) fn ranges(chars: &[char]) -> Vec<(u32, u16)> {
+    let mut out: Vec<(u32, u16)> = Vec::new();
+    for &c in chars {
+        match out.last_mut() {
+            Some((first, len)) if *first + *len as u32 == c as u32 && *len < u16::MAX => *len += 1,
+            _ => out.push((c as u32, 1)),
+        }
+    }
+    out
+}
+
+/// Quantize 8-bit coverage to `bpp` bits and pack it as the runtime reader
+/// expects: MSB-first bit packing, or `(run, value)` pairs when `rle` is set.
+fn pack_bitmap(coverage: &[u8], bpp: u8, rle: bool) -> Vec<u8> {
+    let max = (1u16 << bpp) - 1;
+    let values = coverage
+        .iter()
+        .map(|&c| ((c as u16 * max + 127) / 255) as u8);
+    let mut out = Vec::new();
+    if rle {
+        for v in values {
+            match out.len() {
+                n if n >= 2 && out[n - 1] == v && out[n - 2] < u8::MAX => out[n - 2] += 1,
+                _ => out.extend_from_slice(&[1, v]),
+            }
+        }
+    } else {
+        out.resize((coverage.len() * bpp as usize).div_ceil(8), 0);
+        for (i, v) in values.enumerate() {
+            let bit = i * bpp as usize;
+            out[bit / 8] |= v << (8 - bpp as usize - bit % 8);
+        }
+    }
+    out
+}
+
+/// Encode `chars` of `font` at `px` pixels as an `RLVGLFNT` file, returning
+/// it with the per-glyph metrics.
+fn encode_font(
+    font: &Font,
+    chars: &[char],
+    px: f32,
+    opts: &FontOptions,
+) -> Result<(Vec<u8>, Vec<GlyphMetric>)> {
+    let bpp = opts.bpp.unwrap_or(DEFAULT_BPP);
+    if !matches!(bpp, 1 | 2 | 4 | 8) {
+        bail!("unsupported font bpp {bpp}");
+    }
+    let rle = match opts.packing.as_deref().map(str::trim) {
+        None | Some("") | Some("none") => false,
+        Some("rle") => true,
+        Some(other) => bail!("unsupported font packing `{other}`"),
+    };
+    if chars.len() > u16::MAX as usize {
+        bail!("too many glyphs: {}", chars.len());
+    }
+    // Advances and kerning are stored in 1/16 pixel.
+    let fixed = |v: f32| {
+        let v = if opts.hinting { v.round() } else { v };
+        (v * 16.0).round() as i32
+    };
+
+    let lines = font.horizontal_line_metrics(px);
+    let ascent = lines.map_or(px, |l| l.ascent).round() as i16;
+    let descent = lines.map_or(0.0, |l| l.descent).round() as i16;
+    let line_height = lines.map_or(px, |l| l.new_line_size).round() as u16;
+
+    let mut glyphs = Vec::new();
+    let mut bitmaps = Vec::new();
+    let mut metrics = Vec::new();
+    for &ch in chars {
+        let (m, coverage) = font.rasterize(ch, px);
+        let offset = bitmaps.len();
+        glyphs.extend_from_slice(&(offset as u32).to_le_bytes());
+        for v in [
+            fixed(m.advance_width).clamp(0, u16::MAX as i32) as u16,
+            m.width as u16,
+            m.height as u16,
+            m.xmin as i16 as u16,
+            (m.ymin + m.height as i32) as i16 as u16,
+            0,
+        ] {
+            glyphs.extend_from_slice(&v.to_le_bytes());
+        }
+        bitmaps.extend(pack_bitmap(&coverage, bpp, rle));
+        metrics.push(GlyphMetric {
+            ch,
+            width: m.width,
+            height: m.height,
+            advance: m.advance_width,
+            offset,
+        });
+    }
+
+    let mut kerning = Vec::new();
+    if opts.kerning {
+        for (l, &left) in chars.iter().enumerate() {
+            for (r, &right) in chars.iter().enumerate() {
+                let adjust = font
+                    .horizontal_kern(left, right, px)
+                    .map_or(0, fixed)
+                    .clamp(i16::MIN as i32, i16::MAX as i32);
+                if adjust != 0 {
+                    kerning.extend_from_slice(&(l as u16).to_le_bytes());
+                    kerning.extend_from_slice(&(r as u16).to_le_bytes());
+                    kerning.extend_from_slice(&(adjust as i16).to_le_bytes());
+                }
+            }
+        }
+    }
+
+    let ranges = ranges(chars);
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&[VERSION, bpp, if rle { FLAG_RLE } else { 0 }, 0]);
+    for v in [
+        px.round() as u16,
+        ascent as u16,
+        descent as u16,
+        line_height,
+    ] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(&(ranges.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(chars.len() as u16).to_le_bytes());
+    out.extend_from_slice(&((kerning.len() / 6) as u32).to_le_bytes());
+    out.extend_from_slice(&(bitmaps.len() as u32).to_le_bytes());
+    let mut first_glyph = 0u16;
+    for (first, len) in ranges {
+        out.extend_from_slice(&first.to_le_bytes());
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&first_glyph.to_le_bytes());
+        first_glyph += len;
+    }
+    out.extend_from_slice(&glyphs);
+    out.extend_from_slice(&kerning);
+    out.extend_from_slice(&bitmaps);
+    Ok((out, metrics))
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
-    use crate::manifest::{Group, Manifest};
-    use blake3::hash;
-    use tempfile::tempdir;
+    use rlvgl_core::font::BitmapFont;
+
+    const DEJAVU: &str = "assets/fonts/DejaVuSans.ttf";
+
+    #[test]
+    fn parses_glyph_specs() {
+        let set = parse_glyphs("U+0041-U+0043, xyz,U+00e9,ascii").unwrap();
+        assert_eq!(set.len(), 95 + 1);
+        assert!(set.contains(&'\u{e9}') && set.contains(&'~'));
+        let set = parse_glyphs("U+4E2D,U+0030-U+0031").unwrap();
+        assert_eq!(set.into_iter().collect::<String>(), "01中");
+        assert!(parse_glyphs("U+0043-U+0041").is_err());
+        assert!(parse_glyphs("U+ZZ").is_err());
+        assert_eq!(ranges(&['a', 'b', 'c', 'x', 'y']), vec![(97, 3), (120, 2)]);
+    }
 
+    #[test]
+    fn packs_bitmaps_at_each_depth() {
+        assert_eq!(pack_bitmap(&[255, 0, 128], 1, false), vec![0b1010_0000]);
+        assert_eq!(
+            pack_bitmap(&[255, 85, 0, 170, 255], 2, false),
+            vec![0b1101_0010, 0b1100_0000]
+        );
+        assert_eq!(pack_bitmap(&[0, 0, 0, 255], 4, true), vec![3, 0, 1, 15]);
+        assert_eq!(pack_bitmap(&[7; 300], 8, true), vec![255, 7, 45, 7]);
+    }
+
+    /// Packed fonts load in the runtime reader with the requested options.
+    #[test]
+    fn encoded_font_roundtrips_through_core_reader() {
+        let font = Font::from_bytes(fs::read(DEJAVU).unwrap(), FontSettings::default()).unwrap();
+        let chars: Vec<char> = parse_glyphs("A,V,U+0020").unwrap().into_iter().collect();
+        let opts = FontOptions {
+            bpp: Some(2),
+            packing: Some("rle".into()),
+            hinting: true,
+            kerning: true,
+            ..Default::default()
+        };
+        let (bin, metrics) = encode_font(&font, &chars, 16.0, &opts).unwrap();
+        assert_eq!(metrics.len(), 3);
+
+        let loaded = BitmapFont::parse(&bin).unwrap();
+        assert_eq!(
+            (loaded.size, loaded.bpp(), loaded.glyph_count()),
+            (16, 2, 3)
+        );
+        assert!(loaded.ascent > 0 && loaded.descent < 0);
+        let a = loaded.glyph('A').unwrap();
+        let (m, _) = font.rasterize('A', 16.0);
+        assert_eq!((a.width as usize, a.height as usize), (m.width, m.height));
+        assert_eq!(a.advance % 16, 0);
+        assert_eq!(a.coverage().count(), m.width * m.height);
+        assert!(a.coverage().any(|c| c == 255));
+        let (ia, iv) = (
+            loaded.glyph_index('A').unwrap(),
+            loaded.glyph_index('V').unwrap(),
+        );
+        assert!(loaded.kerning(ia, iv) < 0);
+        assert!(loaded.glyph('B').is_none());
+    }
+
+    #[cfg(feature = "regression")]
     #[test]
     fn pack_generates_stable_bin_and_json() {
+        use crate::manifest::Group;
+        use blake3::hash;
+        use tempfile::tempdir;
+
         let dir = tempdir().unwrap();
         let root = dir.path();
         let fonts_dir = root.join("fonts");
@@ -99,12 +372,18 @@ mod tests {
                     "fonts/sample-12.json".into(),
                 ],
                 license: Some("MIT".into()),
+                ..Default::default()
             },
         );
         let manifest_path = root.join("manifest.yml");
         fs::write(&manifest_path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
 
-        pack(root, &manifest_path, 12.0, "AB").unwrap();
+        let defaults = FontOptions {
+            glyphs: Some("AB".into()),
+            sizes: vec![12],
+            ..Default::default()
+        };
+        pack_with(root, &manifest_path, &defaults).unwrap();
 
         let bin_hash = hash(&fs::read(fonts_dir.join("sample-12.bin")).unwrap()).to_hex();
         let json_hash = hash(&fs::read(fonts_dir.join("sample-12.json")).unwrap()).to_hex();
@@ -0,0 +1,756 @@
+//! i18n command for rlvgl-creator.
+//!
+//! Compiles gettext `.po` and XLIFF 1.2/2.0 translations into Rust string
+//! tables for `rlvgl_core::i18n`: one `StringId` constant per message and one
+//! `Catalog` per locale, with the message ids forming the source catalog.
+//! printf conversions (`%s`, `%d`, `%1$s`) become `{n}` placeholders. The
+//! command can also add the characters used by the translations to the glyph
+//! sets of font assets in the manifest.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Result, anyhow, bail};
+use regex::{Captures, Regex};
+use rlvgl_core::i18n::PluralRule;
+
+use crate::fonts;
+use crate::manifest::{FontOptions, Manifest};
+
+/// One message of a translation file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Message {
+    /// Disambiguating context (`msgctxt`).
+    pub(crate) context: Option<String>,
+    /// Source text.
+    pub(crate) id: String,
+    /// Source plural text, for plural messages.
+    pub(crate) plural: Option<String>,
+    /// Translated forms; empty strings are untranslated.
+    pub(crate) forms: Vec<String>,
+}
+
+impl Message {
+    /// Lookup key: the source text, prefixed by `context` and `\u{4}` as in
+    /// gettext.
+    pub(crate) fn key(&self) -> String {
+        match &self.context {
+            Some(ctx) => format!("{ctx}\u{4}{}", self.id),
+            None => self.id.clone(),
+        }
+    }
+
+    fn translated(&self) -> bool {
+        !self.forms.is_empty() && self.forms.iter().all(|f| !f.is_empty())
+    }
+}
+
+/// Messages of one translation file.
+#[derive(Debug, Default)]
+pub(crate) struct Translations {
+    /// Locale declared by the file, if any.
+    pub(crate) locale: Option<String>,
+    /// Messages in file order.
+    pub(crate) messages: Vec<Message>,
+}
+
+/// Compile `inputs` into Rust at `out` and update the glyph sets of `fonts`.
+///
+/// Each entry of `fonts` is a manifest asset path, optionally followed by
+/// `=` and a comma-separated list of locales whose characters it needs.
+pub(crate) fn run(
+    inputs: &[PathBuf],
+    out: &Path,
+    source_locale: &str,
+    fonts: &[String],
+    manifest_path: &Path,
+) -> Result<()> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let text = fs::read_to_string(input)?;
+        let mut tr = match input.extension().and_then(|e| e.to_str()) {
+            Some("po" | "pot") => parse_po(&text)?,
+            Some("xlf" | "xliff") => parse_xliff(&text)?,
+            _ => bail!("unsupported translation file `{}`", input.display()),
+        };
+        if tr.locale.is_none() {
+            tr.locale = input.file_stem().map(|s| s.to_string_lossy().to_string());
+        }
+        let name = input
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        files.push((name, tr));
+    }
+    let tables = Tables::new(source_locale, &files)?;
+    fs::write(out, tables.generate(&files)?)?;
+    println!(
+        "Generated {} messages in {} locales at {}",
+        tables.messages.len(),
+        tables.locales.len(),
+        out.display()
+    );
+    if !fonts.is_empty() {
+        update_fonts(&tables, fonts, manifest_path)?;
+    }
+    Ok(())
+}
+
+/// Messages merged across files, with the forms of every locale.
+pub(crate) struct Tables {
+    /// Source messages keyed by [`Message::key`].
+    messages: BTreeMap<String, Message>,
+    /// Locales in catalog order with their forms by key; the source locale
+    /// comes first.
+    locales: Vec<(String, BTreeMap<String, Vec<String>>)>,
+}
+
+impl Tables {
+    /// Merge `files`; later files override earlier ones of the same locale.
+    pub(crate) fn new(source_locale: &str, files: &[(String, Translations)]) -> Result<Self> {
+        let mut messages = BTreeMap::new();
+        let mut locales = vec![(source_locale.to_string(), BTreeMap::new())];
+        for (name, tr) in files {
+            let locale = tr.locale.clone().unwrap_or_default();
+            let rule = PluralRule::for_language(&locale);
+            let index = match locales.iter().position(|(l, _)| same_locale(l, &locale)) {
+                Some(i) => i,
+                None => {
+                    locales.push((locale.clone(), BTreeMap::new()));
+                    locales.len() - 1
+                }
+            };
+            for msg in &tr.messages {
+                let key = msg.key();
+                messages.entry(key.clone()).or_insert_with(|| Message {
+                    forms: Vec::new(),
+                    ..msg.clone()
+                });
+                if !msg.translated() {
+                    continue;
+                }
+                let expected = rule.categories().len();
+                if msg.plural.is_some() && msg.forms.len() != expected {
+                    bail!(
+                        "{name}: `{}` has {} plural forms but `{locale}` uses {expected}",
+                        msg.id,
+                        msg.forms.len()
+                    );
+                }
+                locales[index].1.insert(key, msg.forms.clone());
+            }
+        }
+        for (key, msg) in &messages {
+            let forms = locales[0].1.entry(key.clone()).or_default();
+            if forms.is_empty() {
+                forms.push(msg.id.clone());
+                forms.extend(msg.plural.clone());
+            }
+        }
+        Ok(Self { messages, locales })
+    }
+
+    /// Rust source defining the ids and catalogs.
+    pub(crate) fn generate(&self, files: &[(String, Translations)]) -> Result<String> {
+        if self.messages.len() > u16::MAX as usize {
+            bail!("too many messages: {}", self.messages.len());
+        }
+        let sources: Vec<_> = files.iter().map(|(n, _)| format!("`{n}`")).collect();
+        let mut s = format!(
+            "//! String tables generated by rlvgl-creator from {}. Do not edit.\n\n\
+             use rlvgl_core::i18n::{{Catalog, PluralRule, StringId}};\n",
+            sources.join(", ")
+        );
+        let mut names = BTreeSet::from(["KEYS".to_string(), "CATALOGS".to_string()]);
+        for (i, msg) in self.messages.values().enumerate() {
+            let name = unique(&mut names, ident(msg.context.as_deref().unwrap_or(&msg.id)));
+            let _ = write!(
+                s,
+                "\n/// `{}`\npub const {name}: StringId = StringId({i});\n",
+                summary(&msg.id)
+            );
+        }
+
+        s.push_str("\n/// Message keys in id order, which is sorted.\nconst KEYS: &[&str] = &[\n");
+        for key in self.messages.keys() {
+            let _ = writeln!(s, "    {key:?},");
+        }
+        s.push_str(
+            "];\n\n\
+             /// Id of the message whose source text is `key`, prefixed by its\n\
+             /// context and `\\u{4}` if it has one.\n\
+             pub fn id(key: &str) -> Option<StringId> {\n    \
+             KEYS.binary_search(&key).ok().map(|i| StringId(i as u16))\n}\n",
+        );
+
+        let mut catalogs = Vec::new();
+        for (locale, forms) in &self.locales {
+            let name = unique(&mut names, ident(locale));
+            let rule = PluralRule::for_language(locale);
+            let _ = write!(
+                s,
+                "\n/// `{locale}` messages.\npub const {name}: Catalog<'static> = Catalog::new(\n    \
+                 {locale:?},\n    PluralRule::{rule:?},\n    &[\n"
+            );
+            let entries: Vec<_> = self.messages.keys().map(|k| forms.get(k)).collect();
+            // Trailing untranslated messages need no entry.
+            let used = entries
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |i| i + 1);
+            for entry in &entries[..used] {
+                let list: Vec<_> = entry
+                    .map(|f| f.iter().map(|f| format!("{f:?}")).collect())
+                    .unwrap_or_default();
+                let _ = writeln!(s, "        &[{}],", list.join(", "));
+            }
+            s.push_str("    ],\n);\n");
+            catalogs.push(name);
+        }
+        let _ = write!(
+            s,
+            "\n/// All catalogs, source locale first.\n\
+             pub static CATALOGS: [Catalog<'static>; {}] = [{}];\n",
+            catalogs.len(),
+            catalogs.join(", ")
+        );
+        Ok(s)
+    }
+
+    /// Characters shown by the catalogs of `locales`, or of all locales
+    /// if empty.
+    pub(crate) fn chars(&self, locales: &[&str]) -> Result<BTreeSet<char>> {
+        for want in locales {
+            if !self.locales.iter().any(|(l, _)| same_locale(l, want)) {
+                bail!("no translations for locale `{want}`");
+            }
+        }
+        let mut out = BTreeSet::new();
+        for (locale, forms) in &self.locales {
+            if !locales.is_empty() && !locales.iter().any(|l| same_locale(l, locale)) {
+                continue;
+            }
+            for form in forms.values().flatten() {
+                let text = PLACEHOLDER.replace_all(form, "");
+                if text.len() != form.len() {
+                    // Placeholders are mostly filled with numbers.
+                    out.extend('0'..='9');
+                }
+                out.extend(text.chars().filter(|c| !c.is_control()));
+            }
+        }
+        Ok(out)
+    }
+}
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\d+\}").unwrap());
+
+static PRINTF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"%(?:(\d+)\$)?[-+ #0]*\d*(?:\.\d+)?(?:hh|h|ll|l|z|j|t|L)?([diouxXeEfFgGcsp%])")
+        .unwrap()
+});
+
+/// Replace printf conversions in `text` with `{n}` placeholders.
+pub(crate) fn printf_to_placeholders(text: &str) -> String {
+    let mut next = 0;
+    PRINTF
+        .replace_all(text, |c: &Captures<'_>| {
+            if &c[2] == "%" {
+                return "%".to_string();
+            }
+            let n = match c.get(1).and_then(|n| n.as_str().parse::<usize>().ok()) {
+                Some(n) => n.saturating_sub(1),
+                None => {
+                    next += 1;
+                    next - 1
+                }
+            };
+            format!("{{{n}}}")
+        })
+        .into_owned()
+}
+
+/// Parse a gettext `.po` file. Fuzzy and obsolete entries are skipped.
+pub(crate) fn parse_po(text: &str) -> Result<Translations> {
+    #[derive(Clone, Copy)]
+    enum Field {
+        Context,
+        Id,
+        Plural,
+        Str(usize),
+    }
+
+    #[derive(Default)]
+    struct Entry {
+        msg: Message,
+        has_id: bool,
+        has_str: bool,
+        fuzzy: bool,
+    }
+
+    fn finish(entry: Entry, out: &mut Translations) {
+        if !entry.has_id {
+            return;
+        }
+        let mut msg = entry.msg;
+        if msg.id.is_empty() && msg.context.is_none() {
+            let header = msg.forms.first().map(String::as_str).unwrap_or("");
+            out.locale = header
+                .lines()
+                .find_map(|l| l.strip_prefix("Language:"))
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty());
+            return;
+        }
+        if entry.fuzzy {
+            return;
+        }
+        msg.id = printf_to_placeholders(&msg.id);
+        msg.plural = msg.plural.as_deref().map(printf_to_placeholders);
+        for form in &mut msg.forms {
+            *form = printf_to_placeholders(form);
+        }
+        out.messages.push(msg);
+    }
+
+    let mut out = Translations::default();
+    let mut entry = Entry::default();
+    let mut field = None;
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let err = || anyhow!("line {}: invalid entry `{line}`", n + 1);
+        let starts_entry = line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("msgctxt")
+            || line.starts_with("msgid ");
+        if starts_entry && entry.has_str {
+            finish(std::mem::take(&mut entry), &mut out);
+            field = None;
+        }
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+        if let Some(flags) = line.strip_prefix("#,") {
+            entry.fuzzy |= flags.split(',').any(|f| f.trim() == "fuzzy");
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let (keyword, rest) = match line.find('"') {
+            Some(0) => ("", line),
+            Some(i) => (line[..i].trim(), &line[i..]),
+            None => return Err(err()),
+        };
+        let value = unquote(rest).ok_or_else(err)?;
+        let target = match keyword {
+            "" => field.ok_or_else(err)?,
+            "msgctxt" => Field::Context,
+            "msgid" => {
+                entry.has_id = true;
+                Field::Id
+            }
+            "msgid_plural" => Field::Plural,
+            "msgstr" => Field::Str(0),
+            k => {
+                let i = k
+                    .strip_prefix("msgstr[")
+                    .and_then(|k| k.strip_suffix(']'))
+                    .and_then(|i| i.parse().ok())
+                    .ok_or_else(err)?;
+                Field::Str(i)
+            }
+        };
+        let msg = &mut entry.msg;
+        let slot = match target {
+            Field::Context => msg.context.get_or_insert_with(String::new),
+            Field::Id => &mut msg.id,
+            Field::Plural => msg.plural.get_or_insert_with(String::new),
+            Field::Str(i) => {
+                entry.has_str = true;
+                if msg.forms.len() <= i {
+                    msg.forms.resize(i + 1, String::new());
+                }
+                &mut msg.forms[i]
+            }
+        };
+        slot.push_str(&value);
+        field = Some(target);
+    }
+    finish(entry, &mut out);
+    Ok(out)
+}
+
+/// Contents of a `"..."` string with C escapes resolved.
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c => c,
+        });
+    }
+    Some(out)
+}
+
+static XLIFF_LANG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?:target-language|trgLang)="([^"]+)""#).unwrap());
+static XLIFF_UNIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<(?:trans-)?unit\b[^>]*>(.*?)</(?:trans-)?unit>").unwrap());
+static XLIFF_SOURCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<source\b[^>]*>(.*?)</source>").unwrap());
+static XLIFF_TARGET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<target\b([^>]*)>(.*?)</target>").unwrap());
+static XLIFF_INLINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<(x|ph)\b[^>]*?\bid="([^"]*)"[^>]*/>|<(/?)[A-Za-z][^>]*>"#).unwrap()
+});
+static XML_ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#x[0-9A-Fa-f]+|#[0-9]+|[a-z]+);").unwrap());
+
+/// Parse an XLIFF 1.2 or 2.0 file.
+///
+/// `<x/>` and `<ph/>` placeholders become `{n}`, numbered in source order
+/// and matched by `id` in the target. Targets marked `needs-translation` or
+/// `new` are treated as untranslated.
+pub(crate) fn parse_xliff(text: &str) -> Result<Translations> {
+    let mut out = Translations {
+        locale: XLIFF_LANG.captures(text).map(|c| c[1].to_string()),
+        messages: Vec::new(),
+    };
+    for unit in XLIFF_UNIT.captures_iter(text) {
+        let body = &unit[1];
+        let source = XLIFF_SOURCE
+            .captures(body)
+            .ok_or_else(|| anyhow!("XLIFF unit without source: {}", summary(body)))?;
+        let mut ids = Vec::new();
+        let id = xliff_text(&source[1], &mut ids, true);
+        let mut forms = Vec::new();
+        if let Some(target) = XLIFF_TARGET.captures(body) {
+            let attrs = &target[1];
+            let pending = attrs.contains("needs-translation") || attrs.contains("\"new\"");
+            if !pending {
+                forms.push(xliff_text(&target[2], &mut ids, false));
+            }
+        }
+        out.messages.push(Message {
+            context: None,
+            id,
+            plural: None,
+            forms,
+        });
+    }
+    Ok(out)
+}
+
+/// Plain text of XLIFF content with inline placeholders numbered by `ids`.
+fn xliff_text(content: &str, ids: &mut Vec<String>, source: bool) -> String {
+    let text = XLIFF_INLINE.replace_all(content, |c: &Captures<'_>| {
+        let Some(id) = c.get(2) else {
+            return String::new();
+        };
+        let n = match ids.iter().position(|i| i == id.as_str()) {
+            Some(n) => n,
+            None if source => {
+                ids.push(id.as_str().to_string());
+                ids.len() - 1
+            }
+            None => return String::new(),
+        };
+        format!("{{{n}}}")
+    });
+    XML_ENTITY
+        .replace_all(&text, |c: &Captures<'_>| {
+            let name = &c[1];
+            let code = if let Some(hex) = name.strip_prefix("#x") {
+                u32::from_str_radix(hex, 16).ok()
+            } else if let Some(dec) = name.strip_prefix('#') {
+                dec.parse().ok()
+            } else {
+                None
+            };
+            match (name, code.and_then(char::from_u32)) {
+                (_, Some(c)) => c.to_string(),
+                ("lt", _) => "<".into(),
+                ("gt", _) => ">".into(),
+                ("amp", _) => "&".into(),
+                ("quot", _) => "\"".into(),
+                ("apos", _) => "'".into(),
+                _ => c[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Add the characters of the translations to the glyph sets of `fonts`.
+fn update_fonts(tables: &Tables, fonts: &[String], manifest_path: &Path) -> Result<()> {
+    let mut manifest: Manifest = serde_yaml::from_str(&fs::read_to_string(manifest_path)?)?;
+    for spec in fonts {
+        let (path, locales) = match spec.split_once('=') {
+            Some((path, locales)) => (path, locales.split(',').map(str::trim).collect()),
+            None => (spec.as_str(), Vec::new()),
+        };
+        let asset = manifest
+            .assets
+            .iter_mut()
+            .find(|a| a.path == path)
+            .ok_or_else(|| anyhow!("font `{path}` is not in the manifest"))?;
+        let opts = asset.font.get_or_insert_with(FontOptions::default);
+        let mut chars = fonts::parse_glyphs(opts.glyphs.as_deref().unwrap_or("ascii"))?;
+        chars.extend(tables.chars(&locales)?);
+        let chars: Vec<char> = chars.into_iter().collect();
+        let spec = fonts::ranges(&chars)
+            .into_iter()
+            .map(|(first, len)| match len {
+                1 => format!("U+{first:04X}"),
+                _ => format!("U+{first:04X}-U+{:04X}", first + len as u32 - 1),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        opts.glyphs = Some(spec);
+        println!("Font {path} now has {} glyphs", chars.len());
+    }
+    fs::write(manifest_path, serde_yaml::to_string(&manifest)?)?;
+    Ok(())
+}
+
+/// Whether two locale tags are equal, ignoring case and `-`/`_`.
+fn same_locale(a: &str, b: &str) -> bool {
+    a.replace('_', "-")
+        .eq_ignore_ascii_case(&b.replace('_', "-"))
+}
+
+/// Uppercase Rust constant name for `text`.
+fn ident(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_uppercase());
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+        if out.len() >= 32 {
+            break;
+        }
+    }
+    let out = out.trim_end_matches('_');
+    match out.chars().next() {
+        None => "MSG".into(),
+        Some(c) if c.is_ascii_digit() => format!("MSG_{out}"),
+        Some(_) => out.into(),
+    }
+}
+
+/// `name`, or `name` with the first free numeric suffix, recorded in `used`.
+fn unique(used: &mut BTreeSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{name}_{n}");
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+/// First line of `text`, shortened for doc comments.
+fn summary(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("").replace('`', "'");
+    match line.char_indices().nth(60) {
+        Some((i, _)) => format!("{}…", &line[..i]),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const DE_PO: &str = r#"
+msgid ""
+msgstr ""
+"Language: de\n"
+"Plural-Forms: nplurals=2; plural=(n != 1);\n"
+
+#: src/main.rs:10
+msgid "Save"
+msgstr "Speichern"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "Öffnen"
+
+#, fuzzy
+msgid "Quit"
+msgstr "Ende"
+
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] "%d Datei"
+msgstr[1] "%d Dateien"
+
+msgid "Copy %s to %s (100%%)"
+msgstr ""
+"Kopiere %2$s "
+"nach %1$s\t(100%%)"
+
+#~ msgid "Old"
+#~ msgstr "Alt"
+"#;
+
+    const FR_XLF: &str = r#"<?xml version="1.0"?>
+<xliff version="1.2"><file source-language="en" target-language="fr"><body>
+  <trans-unit id="1"><source>Save</source><target>Enregistrer</target></trans-unit>
+  <trans-unit id="2"><source>Copy <x id="a"/> to <x id="b"/> (100%)</source>
+    <target>Copier <x id="a"/> vers <x id="b"/> &amp; &#233;</target></trans-unit>
+  <trans-unit id="3"><source>Help</source><target state="needs-translation">Aide</target></trans-unit>
+</body></file></xliff>"#;
+
+    const RU_XLF: &str = r#"<xliff version="2.0" srcLang="en" trgLang="ru">
+<file id="f"><unit id="save"><segment><source>Save</source>
+<target>Сохранить</target></segment></unit></file></xliff>"#;
+
+    /// Entries, headers, escapes, contexts, plurals and flags are read.
+    #[test]
+    fn parses_po_files() {
+        let tr = parse_po(DE_PO).unwrap();
+        assert_eq!(tr.locale.as_deref(), Some("de"));
+        let ids: Vec<_> = tr.messages.iter().map(Message::key).collect();
+        assert_eq!(
+            ids,
+            [
+                "Save",
+                "menu\u{4}Open",
+                "{0} file",
+                "Copy {0} to {1} (100%)"
+            ]
+        );
+        let plural = &tr.messages[2];
+        assert_eq!(plural.plural.as_deref(), Some("{0} files"));
+        assert_eq!(plural.forms, ["{0} Datei", "{0} Dateien"]);
+        assert_eq!(tr.messages[3].forms, ["Kopiere {1} nach {0}\t(100%)"]);
+        assert!(parse_po("msgid Save").is_err());
+    }
+
+    /// printf conversions map to positional placeholders.
+    #[test]
+    fn converts_printf_conversions() {
+        assert_eq!(printf_to_placeholders("%s of %5.2f%%"), "{0} of {1}%");
+        assert_eq!(printf_to_placeholders("%2$s %1$lu"), "{1} {0}");
+        assert_eq!(printf_to_placeholders("{0} stays"), "{0} stays");
+    }
+
+    /// Both XLIFF versions are read with placeholders and entities.
+    #[test]
+    fn parses_xliff_files() {
+        let tr = parse_xliff(FR_XLF).unwrap();
+        assert_eq!(tr.locale.as_deref(), Some("fr"));
+        assert_eq!(tr.messages[1].id, "Copy {0} to {1} (100%)");
+        assert_eq!(tr.messages[1].forms, ["Copier {0} vers {1} & é"]);
+        assert!(tr.messages[2].forms.is_empty());
+        let tr = parse_xliff(RU_XLF).unwrap();
+        assert_eq!(tr.locale.as_deref(), Some("ru"));
+        assert_eq!(tr.messages[0].forms, ["Сохранить"]);
+    }
+
+    /// Ids and catalogs share one sorted message order with fallbacks left
+    /// empty.
+    #[test]
+    fn generates_catalogs() {
+        let files = vec![
+            ("de.po".to_string(), parse_po(DE_PO).unwrap()),
+            ("fr.xlf".to_string(), parse_xliff(FR_XLF).unwrap()),
+        ];
+        let tables = Tables::new("en", &files).unwrap();
+        let code = tables.generate(&files).unwrap();
+        assert!(
+            code.starts_with(
+                "//! String tables generated by rlvgl-creator from `de.po`, `fr.xlf`."
+            )
+        );
+        assert!(code.contains(
+            "/// `Copy {0} to {1} (100%)`\npub const COPY_0_TO_1_100: StringId = StringId(0);"
+        ));
+        assert!(code.contains("pub const SAVE: StringId = StringId(2);"));
+        assert!(code.contains("pub const MENU: StringId = StringId(3);\n"));
+        assert!(code.contains("pub const MSG_0_FILE: StringId = StringId(4);"));
+        assert!(code.contains("    \"menu\\u{4}Open\",\n"));
+        assert!(code.contains(
+            "pub const EN: Catalog<'static> = Catalog::new(\n    \"en\",\n    PluralRule::OneOther,"
+        ));
+        assert!(code.contains("        &[\"{0} file\", \"{0} files\"],\n"));
+        assert!(code.contains("        &[\"{0} Datei\", \"{0} Dateien\"],\n"));
+        assert!(code.contains("PluralRule::OneWithZero,\n    &[\n        &[\"Copier {0} vers {1} & é\"],\n        &[],\n        &[\"Enregistrer\"],\n    ],"));
+        assert!(code.contains("pub static CATALOGS: [Catalog<'static>; 3] = [EN, DE, FR];"));
+
+        let bad = "msgid \"a\"\nmsgid_plural \"b\"\nmsgstr[0] \"x\"\n";
+        let mut files = vec![("pl.po".to_string(), parse_po(bad).unwrap())];
+        files[0].1.locale = Some("pl".into());
+        let err = Tables::new("en", &files).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "pl.po: `a` has 1 plural forms but `pl` uses 3"
+        );
+    }
+
+    /// The command writes the tables and extends font glyph sets.
+    #[test]
+    fn writes_tables_and_font_glyphs() {
+        let tmp = tempdir().unwrap();
+        let de = tmp.path().join("de.po");
+        let ru = tmp.path().join("messages.xlf");
+        fs::write(&de, DE_PO).unwrap();
+        fs::write(&ru, RU_XLF).unwrap();
+        let manifest_path = tmp.path().join("manifest.yml");
+        fs::write(
+            &manifest_path,
+            "assets:\n  - { path: fonts/ui.ttf, hash: x, font: { glyphs: \"U+0041\" } }\n  - { path: fonts/cyr.ttf, hash: y }\n",
+        )
+        .unwrap();
+        let out = tmp.path().join("strings.rs");
+        run(
+            &[de, ru],
+            &out,
+            "en",
+            &["fonts/ui.ttf=de".into(), "fonts/cyr.ttf=ru".into()],
+            &manifest_path,
+        )
+        .unwrap();
+        assert!(
+            fs::read_to_string(&out)
+                .unwrap()
+                .contains("pub const RU: Catalog")
+        );
+        let manifest: Manifest =
+            serde_yaml::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        let glyphs = |i: usize| {
+            manifest.assets[i]
+                .font
+                .as_ref()
+                .unwrap()
+                .glyphs
+                .clone()
+                .unwrap()
+        };
+        let ui = fonts::parse_glyphs(&glyphs(0)).unwrap();
+        assert!(ui.contains(&'Ö') && ui.contains(&'7') && ui.contains(&'A'));
+        assert!(!ui.contains(&'С') && !ui.contains(&'\t'));
+        let cyr = fonts::parse_glyphs(&glyphs(1)).unwrap();
+        assert!(cyr.contains(&'С') && cyr.contains(&'~'));
+        assert!(glyphs(1).starts_with("U+0020-U+007E,"));
+
+        let err = run(&[], &out, "en", &["fonts/ui.ttf=ja".into()], &manifest_path).unwrap_err();
+        assert_eq!(err.to_string(), "no translations for locale `ja`");
+    }
+}
@@ -91,32 +91,70 @@ pub(crate) struct ExportOptions {
     /// Target widths in pixels for resized outputs.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub(crate) sizes: Vec<u32>,
-    /// Desired output color space (e.g., `srgb`).
+    /// Desired output color space: `srgb` keeps RGBA8, or a pixel format
+    /// (`argb8888`, `rgb565`, `l8`, `a8`, `a4`, or indexed `i1`, `i2`, `i4`,
+    /// `i8`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) color_space: Option<String>,
     /// Whether to write pixels with premultiplied alpha.
     #[serde(default)]
     pub(crate) premultiplied: bool,
-    /// Compression algorithm identifier, if any.
+    /// Compression algorithm identifier (`none`, `rle`, `lz4`), if any.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) compression: Option<String>,
+    /// Dithering applied when reducing color depth (`none`, `bayer`,
+    /// `floyd-steinberg`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) dither: Option<String>,
+}
+
+/// Description of a converted output produced from a source asset.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub(crate) struct Variant {
+    /// Path of the source asset the variant was converted from.
+    pub(crate) source: String,
+    /// Width in pixels.
+    pub(crate) width: u32,
+    /// Height in pixels.
+    pub(crate) height: u32,
+    /// Pixel format name, `rgba8` for `.raw` outputs.
+    pub(crate) format: String,
+    /// Whether color channels are premultiplied by alpha.
+    #[serde(default)]
+    pub(crate) premultiplied: bool,
+    /// Compression applied to the pixel data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) compression: Option<String>,
+    /// Dithering used when reducing color depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) dither: Option<String>,
+    /// Group whose shared palette indexes the pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) palette_group: Option<String>,
 }
 
 /// Font processing options for font assets.
 #[derive(Default, Serialize, Deserialize, JsonSchema, Clone)]
 pub(crate) struct FontOptions {
-    /// Optional glyph set specification.
+    /// Glyph set: comma-separated literal characters, `U+XXXX` codepoints,
+    /// `U+XXXX-U+YYYY` ranges, or the named sets `ascii` and `latin1`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) glyphs: Option<String>,
-    /// Target font sizes in points.
+    /// Target font sizes in pixels.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub(crate) sizes: Vec<u32>,
-    /// Whether to apply font hinting during packing.
+    /// Whether to snap advances and kerning to whole pixels.
     #[serde(default)]
     pub(crate) hinting: bool,
-    /// Packing strategy identifier, if any.
+    /// Bitmap packing: `none` (default) or `rle`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) packing: Option<String>,
+    /// Coverage bits per pixel (1, 2, 4 or 8; default 4).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) bpp: Option<u8>,
+    /// Whether to store kerning pairs between the selected glyphs.
+    #[serde(default)]
+    pub(crate) kerning: bool,
 }
 
 /// Asset entry with path, hash, and generated constant name.
@@ -147,6 +185,9 @@ pub(crate) struct Asset {
     /// Optional font settings for font assets.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) font: Option<FontOptions>,
+    /// Conversion details when this asset is a generated variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) variant: Option<Variant>,
 }
 
 /// Group of assets sharing an optional license.
@@ -158,6 +199,13 @@ pub(crate) struct Group {
     /// SPDX license identifier applying to the group.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) license: Option<String>,
+    /// Whether indexed assets in the group share one palette.
+    #[serde(default)]
+    pub(crate) shared_palette: bool,
+    /// Shared palette as `#rrggbbaa` colors, generated on first conversion
+    /// and reused afterwards.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) palette: Vec<String>,
 }
 
 /// Hardware preset describing sizing and storage constraints.
@@ -172,6 +220,19 @@ pub(crate) struct Preset {
     /// Storage backend identifier.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) storage: Option<String>,
+    /// Flash available for assets in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) flash_bytes: Option<u64>,
+    /// RAM available for display buffers and decoded assets in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ram_bytes: Option<u64>,
+    /// Number of full-screen framebuffers (default 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) framebuffers: Option<u8>,
+    /// Height of the partial draw buffer in lines (default a tenth of the
+    /// screen).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) draw_buffer_lines: Option<u16>,
 }
 
 /// Target output configuration.
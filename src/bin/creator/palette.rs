@@ -0,0 +1,256 @@
+//! Palette generation and dithering for rlvgl-creator.
+//!
+//! Palettes are built with median cut over RGBA8 pixels. Dithering spreads
+//! the error of snapping each pixel to a color the target format can store,
+//! either with a 4×4 Bayer threshold matrix or Floyd–Steinberg error
+//! diffusion.
+
+use anyhow::{Result, bail};
+
+/// Dithering algorithm applied before quantizing pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Dither {
+    /// Snap every pixel to its closest color.
+    None,
+    /// Ordered dithering with a 4×4 Bayer matrix.
+    Bayer,
+    /// Floyd–Steinberg error diffusion.
+    FloydSteinberg,
+}
+
+impl Dither {
+    /// Parse a manifest dithering name; an unset value means none.
+    pub(crate) fn parse(name: Option<&str>) -> Result<Self> {
+        match name.map(|n| n.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("none") => Ok(Dither::None),
+            Some("bayer") | Some("ordered") => Ok(Dither::Bayer),
+            Some("floyd-steinberg") | Some("floyd_steinberg") | Some("fs") => {
+                Ok(Dither::FloydSteinberg)
+            }
+            Some(other) => bail!("unsupported dither `{other}`"),
+        }
+    }
+
+    /// Lowercase name used in manifests.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Dither::None => "none",
+            Dither::Bayer => "bayer",
+            Dither::FloydSteinberg => "floyd-steinberg",
+        }
+    }
+}
+
+/// 4×4 Bayer threshold matrix.
+const BAYER4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Snap RGBA8 pixels in place to colors produced by `quantize`.
+///
+/// `spread` is the distance between neighbouring representable values of
+/// each channel and scales the Bayer thresholds; it is ignored by the other
+/// algorithms.
+pub(crate) fn dither(
+    rgba: &mut [u8],
+    width: u32,
+    dither: Dither,
+    spread: [f32; 4],
+    quantize: impl Fn([u8; 4]) -> [u8; 4],
+) {
+    let width = width.max(1) as usize;
+    match dither {
+        Dither::None => {
+            for px in rgba.chunks_exact_mut(4) {
+                let q = quantize([px[0], px[1], px[2], px[3]]);
+                px.copy_from_slice(&q);
+            }
+        }
+        Dither::Bayer => {
+            for (i, px) in rgba.chunks_exact_mut(4).enumerate() {
+                let t = (BAYER4[(i / width) % 4][(i % width) % 4] as f32 + 0.5) / 16.0 - 0.5;
+                let mut c = [0u8; 4];
+                for k in 0..4 {
+                    c[k] = (px[k] as f32 + t * spread[k]).round().clamp(0.0, 255.0) as u8;
+                }
+                px.copy_from_slice(&quantize(c));
+            }
+        }
+        Dither::FloydSteinberg => {
+            let mut work: Vec<[f32; 4]> = rgba
+                .chunks_exact(4)
+                .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32, p[3] as f32])
+                .collect();
+            let height = work.len() / width;
+            for y in 0..height {
+                for x in 0..width {
+                    let i = y * width + x;
+                    let old = work[i].map(|c| c.round().clamp(0.0, 255.0) as u8);
+                    let new = quantize(old);
+                    rgba[i * 4..i * 4 + 4].copy_from_slice(&new);
+                    let err: [f32; 4] = core::array::from_fn(|k| work[i][k] - new[k] as f32);
+                    let mut spread_to = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        if nx < 0 || nx as usize >= width || y + dy >= height {
+                            return;
+                        }
+                        let n = &mut work[(y + dy) * width + nx as usize];
+                        for k in 0..4 {
+                            n[k] += err[k] * weight;
+                        }
+                    };
+                    spread_to(1, 0, 7.0 / 16.0);
+                    spread_to(-1, 1, 3.0 / 16.0);
+                    spread_to(0, 1, 5.0 / 16.0);
+                    spread_to(1, 1, 1.0 / 16.0);
+                }
+            }
+        }
+    }
+}
+
+/// Build a palette of at most `colors` entries for RGBA8 `pixels`.
+///
+/// Images with few enough distinct colors keep them exactly; otherwise the
+/// color space is split by median cut and each box contributes its average.
+/// Entries are sorted so the result does not depend on pixel order.
+pub(crate) fn generate(pixels: &[[u8; 4]], colors: usize) -> Vec<[u8; 4]> {
+    let colors = colors.max(1);
+    let mut unique = pixels.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.len() <= colors {
+        return unique;
+    }
+
+    let mut boxes = vec![pixels.to_vec()];
+    while boxes.len() < colors {
+        // Split the box whose widest channel spans the largest range.
+        let Some((idx, channel, _)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| {
+                let (k, range) = (0..4)
+                    .map(|k| {
+                        let lo = b.iter().map(|p| p[k]).min().unwrap_or(0);
+                        let hi = b.iter().map(|p| p[k]).max().unwrap_or(0);
+                        (k, hi - lo)
+                    })
+                    .max_by_key(|&(_, r)| r)
+                    .unwrap_or((0, 0));
+                (i, k, range)
+            })
+            .filter(|&(_, _, range)| range > 0)
+            .max_by_key(|&(_, _, range)| range)
+        else {
+            break;
+        };
+        let mut b = boxes.swap_remove(idx);
+        b.sort_unstable_by_key(|p| p[channel]);
+        let upper = b.split_off(b.len() / 2);
+        boxes.push(b);
+        boxes.push(upper);
+    }
+
+    let mut palette: Vec<[u8; 4]> = boxes
+        .iter()
+        .map(|b| {
+            let mut sum = [0u64; 4];
+            for p in b {
+                for k in 0..4 {
+                    sum[k] += p[k] as u64;
+                }
+            }
+            let n = b.len().max(1) as u64;
+            sum.map(|s| ((s + n / 2) / n) as u8)
+        })
+        .collect();
+    palette.sort_unstable();
+    palette.dedup();
+    palette
+}
+
+/// Index of the palette entry closest to `px`.
+pub(crate) fn nearest(palette: &[[u8; 4]], px: [u8; 4]) -> usize {
+    let dist = |c: &[u8; 4]| {
+        (0..4)
+            .map(|k| {
+                let d = c[k] as i32 - px[k] as i32;
+                (d * d) as u32
+            })
+            .sum::<u32>()
+    };
+    (0..palette.len())
+        .min_by_key(|&i| dist(&palette[i]))
+        .unwrap_or(0)
+}
+
+/// Format a palette entry as `#rrggbbaa`.
+pub(crate) fn to_hex(c: [u8; 4]) -> String {
+    format!("#{:02x}{:02x}{:02x}{:02x}", c[0], c[1], c[2], c[3])
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` palette entry.
+pub(crate) fn from_hex(s: &str) -> Result<[u8; 4]> {
+    let hex = s.trim().trim_start_matches('#');
+    let value = u32::from_str_radix(hex, 16);
+    match (hex.len(), value) {
+        (6, Ok(v)) => Ok(((v << 8) | 0xff).to_be_bytes()),
+        (8, Ok(v)) => Ok(v.to_be_bytes()),
+        _ => bail!("invalid palette color `{s}`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_exact_colors_when_they_fit() {
+        let px = [[9, 9, 9, 255], [1, 2, 3, 255], [9, 9, 9, 255]];
+        assert_eq!(generate(&px, 4), vec![[1, 2, 3, 255], [9, 9, 9, 255]]);
+    }
+
+    #[test]
+    fn median_cut_separates_clusters() {
+        let mut px = Vec::new();
+        for i in 0..10u8 {
+            px.push([i, 0, 0, 255]);
+            px.push([250 - i, 250, 0, 255]);
+        }
+        let palette = generate(&px, 2);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette[nearest(&palette, [0, 0, 0, 255])], [5, 0, 0, 255]);
+        assert_eq!(
+            palette[nearest(&palette, [255, 255, 0, 255])],
+            [246, 250, 0, 255]
+        );
+    }
+
+    #[test]
+    fn dithering_preserves_average_tone() {
+        let palette = [[0, 0, 0, 255], [255, 255, 255, 255]];
+        let quantize = |c| palette[nearest(&palette, c)];
+        for mode in [Dither::Bayer, Dither::FloydSteinberg] {
+            let mut rgba = [128u8, 128, 128, 255].repeat(64);
+            dither(&mut rgba, 8, mode, [255.0, 255.0, 255.0, 0.0], quantize);
+            let white = rgba.chunks_exact(4).filter(|p| p[0] == 255).count();
+            assert!((28..=36).contains(&white), "{mode:?}: {white}");
+        }
+        let mut rgba = [128u8, 128, 128, 255].repeat(4);
+        dither(&mut rgba, 2, Dither::None, [0.0; 4], quantize);
+        assert!(rgba.chunks_exact(4).all(|p| p[0] == 255));
+    }
+
+    #[test]
+    fn parses_names_and_hex_colors() {
+        assert_eq!(
+            Dither::parse(Some("Floyd-Steinberg")).unwrap(),
+            Dither::FloydSteinberg
+        );
+        assert_eq!(Dither::parse(None).unwrap(), Dither::None);
+        assert!(Dither::parse(Some("noise")).is_err());
+        assert_eq!(from_hex("#102030").unwrap(), [0x10, 0x20, 0x30, 0xff]);
+        assert_eq!(to_hex(from_hex("#10203040").unwrap()), "#10203040");
+        assert!(from_hex("#12345").is_err());
+    }
+}
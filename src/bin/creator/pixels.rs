@@ -0,0 +1,647 @@
+//! Pixel format conversion and compression for rlvgl-creator.
+//!
+//! Converted variants that request a specific pixel format, premultiplied
+//! alpha, or compression are written to an `RLVGLPIX` container:
+//!
+//! ```text
+//! "RLVGLPIX", u32 width, u32 height, u8 format, u8 flags, u8 compression,
+//!   u8 reserved, [u16 palette_len, palette_len * u32 argb8888,]
+//!   u32 payload_len, payload
+//! ```
+//!
+//! All integers are little-endian. Bit 0 of `flags` marks premultiplied
+//! alpha. The palette is present only for indexed formats. Pixels are packed
+//! exactly as `rlvgl_platform::CpuBlitter` reads them from a surface whose
+//! stride is [`PixelFormat::stride`]. Firmware reads the files with
+//! `rlvgl_core::pix::PixImage`.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use rlvgl_core::pix::{FLAG_PREMULTIPLIED, MAGIC};
+
+use crate::palette::{self, Dither};
+
+/// Target pixel formats matching `rlvgl_platform::PixelFmt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PixelFormat {
+    /// 32-bit ARGB stored as a little-endian `u32`.
+    Argb8888,
+    /// 16-bit RGB565 stored as a little-endian `u16`.
+    Rgb565,
+    /// 8-bit grayscale.
+    L8,
+    /// 8-bit alpha.
+    A8,
+    /// 4-bit alpha, two pixels per byte with the left pixel in the high nibble.
+    A4,
+    /// 1-bit palette indices, eight pixels per byte.
+    I1,
+    /// 2-bit palette indices, four pixels per byte.
+    I2,
+    /// 4-bit palette indices, two pixels per byte.
+    I4,
+    /// 8-bit palette indices.
+    I8,
+}
+
+impl PixelFormat {
+    /// Every supported format in container id order.
+    pub(crate) const ALL: [PixelFormat; 9] = [
+        PixelFormat::Argb8888,
+        PixelFormat::Rgb565,
+        PixelFormat::L8,
+        PixelFormat::A8,
+        PixelFormat::A4,
+        PixelFormat::I1,
+        PixelFormat::I2,
+        PixelFormat::I4,
+        PixelFormat::I8,
+    ];
+
+    /// Parse a manifest color space name.
+    ///
+    /// Returns `Ok(None)` for names that keep the default RGBA8 `.raw`
+    /// output.
+    pub(crate) fn parse(name: &str) -> Result<Option<Self>> {
+        let name = name.trim().to_ascii_lowercase();
+        if matches!(name.as_str(), "" | "srgb" | "rgba8" | "rgba8888") {
+            return Ok(None);
+        }
+        match Self::ALL.into_iter().find(|f| f.name() == name) {
+            Some(f) => Ok(Some(f)),
+            None => bail!("unsupported color space `{name}`"),
+        }
+    }
+
+    /// Lowercase name used in manifests and file extensions.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            PixelFormat::Argb8888 => "argb8888",
+            PixelFormat::Rgb565 => "rgb565",
+            PixelFormat::L8 => "l8",
+            PixelFormat::A8 => "a8",
+            PixelFormat::A4 => "a4",
+            PixelFormat::I1 => "i1",
+            PixelFormat::I2 => "i2",
+            PixelFormat::I4 => "i4",
+            PixelFormat::I8 => "i8",
+        }
+    }
+
+    /// Bits stored per pixel.
+    pub(crate) fn bits(self) -> usize {
+        match self {
+            PixelFormat::Argb8888 => 32,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::L8 | PixelFormat::A8 | PixelFormat::I8 => 8,
+            PixelFormat::A4 | PixelFormat::I4 => 4,
+            PixelFormat::I2 => 2,
+            PixelFormat::I1 => 1,
+        }
+    }
+
+    /// Whether pixels are palette indices.
+    pub(crate) fn is_indexed(self) -> bool {
+        matches!(
+            self,
+            PixelFormat::I1 | PixelFormat::I2 | PixelFormat::I4 | PixelFormat::I8
+        )
+    }
+
+    /// Largest palette an indexed format can address.
+    pub(crate) fn palette_size(self) -> usize {
+        if self.is_indexed() {
+            1 << self.bits()
+        } else {
+            0
+        }
+    }
+
+    /// Closest RGBA8 value this format stores for `px`.
+    fn quantize(self, palette: &[[u8; 4]], px: [u8; 4]) -> [u8; 4] {
+        let level = |v: u8, bits: u32| {
+            let max = (1u32 << bits) - 1;
+            let q = (v as u32 * max + 127) / 255;
+            // Replicate the high bits so truncating back yields `q`.
+            ((q << (8 - bits)) | (q >> (2 * bits).saturating_sub(8))) as u8
+        };
+        let [r, g, b, a] = px;
+        match self {
+            PixelFormat::Argb8888 | PixelFormat::A8 => px,
+            PixelFormat::Rgb565 => [level(r, 5), level(g, 6), level(b, 5), 255],
+            PixelFormat::L8 => {
+                let l = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+                [l, l, l, 255]
+            }
+            PixelFormat::A4 => [r, g, b, level(a, 4)],
+            _ => palette
+                .get(palette::nearest(palette, px))
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Distance between neighbouring stored values of each channel.
+    fn spread(self, palette: &[[u8; 4]]) -> [f32; 4] {
+        match self {
+            PixelFormat::Argb8888 | PixelFormat::A8 | PixelFormat::L8 => [0.0; 4],
+            PixelFormat::Rgb565 => [255.0 / 31.0, 255.0 / 63.0, 255.0 / 31.0, 0.0],
+            PixelFormat::A4 => [0.0, 0.0, 0.0, 17.0],
+            _ => {
+                // Treat the palette as an evenly spaced color cube.
+                let step = 255.0 / ((palette.len() as f32).cbrt().max(2.0) - 1.0);
+                [step, step, step, 0.0]
+            }
+        }
+    }
+
+    /// Identifier stored in the container header.
+    pub(crate) fn id(self) -> u8 {
+        Self::ALL.iter().position(|f| *f == self).unwrap_or(0) as u8
+    }
+
+    /// Bytes per row of `width` pixels.
+    pub(crate) fn stride(self, width: u32) -> usize {
+        let w = width as usize;
+        match self {
+            PixelFormat::Argb8888 => w * 4,
+            PixelFormat::Rgb565 => w * 2,
+            PixelFormat::L8 | PixelFormat::A8 => w,
+            _ => (w * self.bits()).div_ceil(8),
+        }
+    }
+
+    /// Size of the unit repeated by RLE: one pixel, or one byte for packed
+    /// formats.
+    fn unit(self) -> usize {
+        match self {
+            PixelFormat::Argb8888 => 4,
+            PixelFormat::Rgb565 => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Compression applied to a pixel payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    /// Store pixels as is.
+    None,
+    /// `(count, unit)` pairs where `unit` is one pixel.
+    Rle,
+    /// LZ4 block format without a frame header.
+    Lz4,
+}
+
+impl Compression {
+    /// Parse a manifest compression name; an unset value means none.
+    pub(crate) fn parse(name: Option<&str>) -> Result<Self> {
+        match name.map(|n| n.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("none") => Ok(Compression::None),
+            Some("rle") => Ok(Compression::Rle),
+            Some("lz4") => Ok(Compression::Lz4),
+            Some(other) => bail!("unsupported compression `{other}`"),
+        }
+    }
+
+    /// Lowercase name used in manifests.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Rle => "rle",
+            Compression::Lz4 => "lz4",
+        }
+    }
+
+    /// Identifier stored in the container header.
+    pub(crate) fn id(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Multiply the color channels of RGBA8 pixels by their alpha in place.
+pub(crate) fn premultiply(rgba: &mut [u8]) {
+    for px in rgba.chunks_exact_mut(4) {
+        let a = px[3] as u16;
+        for c in &mut px[..3] {
+            *c = ((*c as u16 * a + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Pack RGBA8 pixels into `format` row by row.
+///
+/// Indexed formats store the index of the closest `palette` entry.
+pub(crate) fn encode_pixels(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    palette: &[[u8; 4]],
+) -> Vec<u8> {
+    let stride = format.stride(width);
+    let mut out = vec![0u8; stride * height as usize];
+    for (i, px) in rgba.chunks_exact(4).enumerate() {
+        let (x, y) = (i % width as usize, i / width as usize);
+        let row = &mut out[y * stride..(y + 1) * stride];
+        let [r, g, b, a] = [px[0], px[1], px[2], px[3]];
+        match format {
+            PixelFormat::Argb8888 => {
+                let argb = u32::from_be_bytes([a, r, g, b]);
+                row[x * 4..x * 4 + 4].copy_from_slice(&argb.to_le_bytes());
+            }
+            PixelFormat::Rgb565 => {
+                let c = ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3);
+                row[x * 2..x * 2 + 2].copy_from_slice(&c.to_le_bytes());
+            }
+            PixelFormat::L8 => row[x] = ((r as u32 + g as u32 + b as u32) / 3) as u8,
+            PixelFormat::A8 => row[x] = a,
+            PixelFormat::A4 => pack(row, x, 4, a >> 4),
+            _ => {
+                let index = palette::nearest(palette, [r, g, b, a]) as u8;
+                pack(row, x, format.bits(), index);
+            }
+        }
+    }
+    out
+}
+
+/// Store `value` as the `bits`-wide pixel `x` of `row`, leftmost pixel in the
+/// high bits of each byte.
+fn pack(row: &mut [u8], x: usize, bits: usize, value: u8) {
+    if bits == 8 {
+        row[x] = value;
+        return;
+    }
+    let shift = 8 - bits - (x * bits) % 8;
+    let mask = (((1u16 << bits) - 1) << shift) as u8;
+    let byte = &mut row[x * bits / 8];
+    *byte = (*byte & !mask) | ((value << shift) & mask);
+}
+
+/// How RGBA8 pixels are turned into a variant file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Encoding<'a> {
+    /// Stored pixel format.
+    pub(crate) format: PixelFormat,
+    /// Whether color channels are premultiplied by alpha first.
+    pub(crate) premultiplied: bool,
+    /// Compression applied to the packed pixels.
+    pub(crate) compression: Compression,
+    /// Dithering used when reducing color depth.
+    pub(crate) dither: Dither,
+    /// Palette for indexed formats; generated per image when `None`.
+    pub(crate) palette: Option<&'a [[u8; 4]]>,
+}
+
+/// Compress `data` whose RLE unit is `unit` bytes.
+pub(crate) fn compress(data: &[u8], compression: Compression, unit: usize) -> Vec<u8> {
+    match compression {
+        Compression::None => data.to_vec(),
+        Compression::Rle => rle_encode(data, unit),
+        Compression::Lz4 => lz4_compress(data),
+    }
+}
+
+/// Split RGBA8 bytes into pixels.
+pub(crate) fn to_pixels(rgba: &[u8]) -> Vec<[u8; 4]> {
+    rgba.chunks_exact(4)
+        .map(|p| [p[0], p[1], p[2], p[3]])
+        .collect()
+}
+
+/// Write RGBA8 pixels as an `RLVGLPIX` variant file.
+pub(crate) fn write_variant(
+    path: &Path,
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    encoding: &Encoding,
+) -> Result<()> {
+    let format = encoding.format;
+    let mut pixels = rgba.to_vec();
+    if encoding.premultiplied {
+        premultiply(&mut pixels);
+    }
+    let palette = match encoding.palette {
+        Some(p) => p.to_vec(),
+        None if format.is_indexed() => {
+            palette::generate(&to_pixels(&pixels), format.palette_size())
+        }
+        None => Vec::new(),
+    };
+    if palette.len() > format.palette_size() {
+        bail!(
+            "palette has {} colors but {} holds at most {}",
+            palette.len(),
+            format.name(),
+            format.palette_size()
+        );
+    }
+    if encoding.dither != Dither::None {
+        palette::dither(
+            &mut pixels,
+            width,
+            encoding.dither,
+            format.spread(&palette),
+            |px| format.quantize(&palette, px),
+        );
+    }
+    let packed = encode_pixels(&pixels, width, height, format, &palette);
+    let payload = compress(&packed, encoding.compression, format.unit());
+
+    let mut out = Vec::with_capacity(26 + palette.len() * 4 + payload.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&width.to_le_bytes());
+    out.extend_from_slice(&height.to_le_bytes());
+    let flags = if encoding.premultiplied {
+        FLAG_PREMULTIPLIED
+    } else {
+        0
+    };
+    out.extend_from_slice(&[format.id(), flags, encoding.compression.id(), 0]);
+    if format.is_indexed() {
+        out.extend_from_slice(&(palette.len() as u16).to_le_bytes());
+        for [r, g, b, a] in &palette {
+            out.extend_from_slice(&u32::from_be_bytes([*a, *r, *g, *b]).to_le_bytes());
+        }
+    }
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(&payload);
+    fs::write(path, out)?;
+    Ok(())
+}
+
+/// Run-length encode `data` as `(count, unit)` pairs of up to 255 units.
+fn rle_encode(data: &[u8], unit: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut units = data.chunks(unit).peekable();
+    while let Some(cur) = units.next() {
+        let mut run = 1u8;
+        while run < u8::MAX && units.peek() == Some(&cur) {
+            units.next();
+            run += 1;
+        }
+        out.push(run);
+        out.extend_from_slice(cur);
+    }
+    out
+}
+
+/// Append an LZ4 length continuation for `len` beyond the 4-bit token field.
+fn lz4_push_len(out: &mut Vec<u8>, mut len: usize) {
+    while len >= 255 {
+        out.push(255);
+        len -= 255;
+    }
+    out.push(len as u8);
+}
+
+/// Append one LZ4 sequence; `matched` is `None` for the final literals.
+fn lz4_sequence(out: &mut Vec<u8>, literals: &[u8], matched: Option<(usize, usize)>) {
+    let lit = literals.len();
+    let ml = matched.map_or(0, |(_, len)| len - 4);
+    out.push(((lit.min(15) as u8) << 4) | ml.min(15) as u8);
+    if lit >= 15 {
+        lz4_push_len(out, lit - 15);
+    }
+    out.extend_from_slice(literals);
+    if let Some((offset, _)) = matched {
+        out.extend_from_slice(&(offset as u16).to_le_bytes());
+        if ml >= 15 {
+            lz4_push_len(out, ml - 15);
+        }
+    }
+}
+
+/// Compress `src` into a raw LZ4 block using greedy hash matching.
+fn lz4_compress(src: &[u8]) -> Vec<u8> {
+    const HASH_BITS: u32 = 12;
+    // The format requires the last match to start 12 bytes before the end
+    // and the last 5 bytes to be literals.
+    let match_limit = src.len().saturating_sub(12);
+    let end_limit = src.len().saturating_sub(5);
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut out = Vec::new();
+    let mut anchor = 0;
+    let mut i = 0;
+    while i < match_limit {
+        let seq = u32::from_le_bytes([src[i], src[i + 1], src[i + 2], src[i + 3]]);
+        let h = (seq.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize;
+        let cand = table[h];
+        table[h] = i;
+        if cand == usize::MAX || i - cand > 0xffff || src[cand..cand + 4] != src[i..i + 4] {
+            i += 1;
+            continue;
+        }
+        let mut len = 4;
+        while i + len < end_limit && src[cand + len] == src[i + len] {
+            len += 1;
+        }
+        lz4_sequence(&mut out, &src[anchor..i], Some((i - cand, len)));
+        i += len;
+        anchor = i;
+    }
+    lz4_sequence(&mut out, &src[anchor..], None);
+    out
+}
+
+/// Decompress a raw LZ4 block.
+#[cfg(test)]
+fn lz4_decompress(mut src: &[u8]) -> Vec<u8> {
+    fn read_len(src: &mut &[u8], mut len: usize) -> usize {
+        if len == 15 {
+            loop {
+                let b = src[0];
+                *src = &src[1..];
+                len += b as usize;
+                if b != 255 {
+                    break;
+                }
+            }
+        }
+        len
+    }
+    let mut out: Vec<u8> = Vec::new();
+    while let Some((&token, rest)) = src.split_first() {
+        src = rest;
+        let lit = read_len(&mut src, (token >> 4) as usize);
+        out.extend_from_slice(&src[..lit]);
+        src = &src[lit..];
+        if src.is_empty() {
+            break;
+        }
+        let offset = u16::from_le_bytes([src[0], src[1]]) as usize;
+        src = &src[2..];
+        let len = read_len(&mut src, (token & 0x0f) as usize) + 4;
+        let start = out.len() - offset;
+        for k in 0..len {
+            out.push(out[start + k]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_formats_like_cpu_blitter() {
+        let rgba = [255, 0, 0, 255, 30, 60, 90, 0x80];
+        assert_eq!(
+            encode_pixels(&rgba, 2, 1, PixelFormat::Argb8888, &[]),
+            vec![0, 0, 255, 255, 90, 60, 30, 0x80]
+        );
+        assert_eq!(
+            encode_pixels(&rgba, 2, 1, PixelFormat::Rgb565, &[]),
+            vec![0x00, 0xf8, 0xeb, 0x19]
+        );
+        assert_eq!(
+            encode_pixels(&rgba, 2, 1, PixelFormat::L8, &[]),
+            vec![85, 60]
+        );
+        assert_eq!(
+            encode_pixels(&rgba, 2, 1, PixelFormat::A8, &[]),
+            vec![255, 0x80]
+        );
+        assert_eq!(encode_pixels(&rgba, 2, 1, PixelFormat::A4, &[]), vec![0xf8]);
+        assert_eq!(
+            encode_pixels(&rgba[..4], 1, 1, PixelFormat::A4, &[]),
+            vec![0xf0]
+        );
+    }
+
+    #[test]
+    fn writes_indexed_variant_with_palette() {
+        let rgba = [0, 0, 0, 255, 255, 255, 255, 255, 250, 250, 250, 255];
+        assert_eq!(
+            encode_pixels(&rgba, 3, 1, PixelFormat::I2, &[]),
+            vec![0b0000_0000]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("icon.i1");
+        let encoding = Encoding {
+            format: PixelFormat::I1,
+            premultiplied: false,
+            compression: Compression::None,
+            dither: Dither::None,
+            palette: None,
+        };
+        write_variant(&path, &rgba, 3, 1, &encoding).unwrap();
+        let data = fs::read(&path).unwrap();
+        assert_eq!(data[16], PixelFormat::I1.id());
+        assert_eq!(&data[20..22], &[2, 0]);
+        assert_eq!(&data[22..26], &0xff00_0000u32.to_le_bytes());
+        assert_eq!(&data[26..30], &0xfffd_fdfdu32.to_le_bytes());
+        assert_eq!(&data[30..], &[1, 0, 0, 0, 0b0110_0000]);
+
+        let palette = [[0, 0, 0, 255]; 3];
+        let encoding = Encoding {
+            palette: Some(&palette),
+            ..encoding
+        };
+        assert!(write_variant(&path, &rgba, 3, 1, &encoding).is_err());
+    }
+
+    #[test]
+    fn parses_manifest_names() {
+        assert_eq!(
+            PixelFormat::parse("RGB565").unwrap(),
+            Some(PixelFormat::Rgb565)
+        );
+        assert_eq!(PixelFormat::parse("srgb").unwrap(), None);
+        assert_eq!(PixelFormat::parse("i4").unwrap(), Some(PixelFormat::I4));
+        assert_eq!(PixelFormat::I4.stride(3), 2);
+        assert!(PixelFormat::parse("yuv").is_err());
+        assert_eq!(Compression::parse(None).unwrap(), Compression::None);
+        assert_eq!(Compression::parse(Some("LZ4")).unwrap(), Compression::Lz4);
+        assert!(Compression::parse(Some("zip")).is_err());
+    }
+
+    #[test]
+    fn premultiplies_color_channels() {
+        let mut px = [200, 100, 50, 128];
+        premultiply(&mut px);
+        assert_eq!(px, [100, 50, 25, 128]);
+    }
+
+    #[test]
+    fn rle_groups_whole_pixels() {
+        let data = [1, 2, 1, 2, 1, 2, 3, 4];
+        assert_eq!(rle_encode(&data, 2), vec![3, 1, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn variants_round_trip_through_the_core_reader() {
+        use rlvgl_core::pix::PixImage;
+
+        let (width, height) = (5, 3);
+        let rgba: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                let v = if i % 5 < 3 { 200 } else { (i * 40) as u8 };
+                [v, v / 2, 255 - v, 128 + (i % 2) as u8 * 127]
+            })
+            .collect();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("variant.pix");
+        for format in PixelFormat::ALL {
+            for compression in [Compression::None, Compression::Rle, Compression::Lz4] {
+                let encoding = Encoding {
+                    format,
+                    premultiplied: format == PixelFormat::Argb8888,
+                    compression,
+                    dither: Dither::None,
+                    palette: None,
+                };
+                write_variant(&path, &rgba, width, height, &encoding).unwrap();
+                let data = fs::read(&path).unwrap();
+                let image = PixImage::parse(&data).unwrap();
+                assert_eq!((image.width, image.height), (width, height));
+                assert_eq!(image.format.id(), format.id());
+                assert_eq!(image.compression.id(), compression.id());
+                assert_eq!(image.premultiplied, encoding.premultiplied);
+                assert_eq!(image.stride(), format.stride(width));
+
+                let palette: Vec<[u8; 4]> = image
+                    .palette()
+                    .iter()
+                    .map(|argb| {
+                        let [b, g, r, a] = argb.to_le_bytes();
+                        [r, g, b, a]
+                    })
+                    .collect();
+                let mut pixels = rgba.clone();
+                if encoding.premultiplied {
+                    premultiply(&mut pixels);
+                }
+                let packed = encode_pixels(&pixels, width, height, format, &palette);
+                assert_eq!(
+                    image.decode().unwrap(),
+                    packed,
+                    "{format:?} {compression:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lz4_roundtrips() {
+        let mut data = vec![7u8; 600];
+        data.extend((0..300).map(|i| (i * 7 % 251) as u8));
+        data.extend_from_slice(&[1, 2, 3]);
+        let packed = lz4_compress(&data);
+        assert!(packed.len() < data.len() / 2);
+        assert_eq!(lz4_decompress(&packed), data);
+        for len in 0..20 {
+            let small: Vec<u8> = (0..len).map(|i| (i % 3) as u8).collect();
+            assert_eq!(lz4_decompress(&lz4_compress(&small)), small);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Reload command for rlvgl-creator.
+//!
+//! Announces changed assets to a running `rlvgl-sim`, which rereads them
+//! and rebuilds the widgets using them without restarting.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rlvgl_platform::hot_reload;
+
+/// Send `names`, relative to the simulator's asset root, to `socket` or the
+/// default reload socket.
+pub(crate) fn run(names: &[String], socket: Option<&Path>) -> Result<()> {
+    let socket = socket
+        .map(Path::to_path_buf)
+        .unwrap_or_else(hot_reload::default_socket_path);
+    let names: Vec<&str> = names.iter().map(|n| n.trim_start_matches("./")).collect();
+    hot_reload::send(&socket, &names)
+        .with_context(|| format!("no simulator listening on {}", socket.display()))?;
+    println!("Announced {} assets on {}", names.len(), socket.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hot_reload::ReloadListener;
+    use tempfile::tempdir;
+
+    /// Names reach a listener, and a missing listener is reported.
+    #[test]
+    fn announces_names() {
+        let tmp = tempdir().unwrap();
+        let socket = tmp.path().join("sim.sock");
+        let err = run(&["a.png".into()], Some(&socket)).unwrap_err();
+        assert!(err.to_string().starts_with("no simulator listening on"));
+
+        let mut listener = ReloadListener::bind(&socket).unwrap();
+        run(&["./icons/a.png".into(), "main.yml".into()], Some(&socket)).unwrap();
+        assert_eq!(listener.poll(), ["icons/a.png", "main.yml"]);
+    }
+}
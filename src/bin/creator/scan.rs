@@ -76,6 +76,7 @@ pub(crate) fn run(root: &Path, manifest_path: &Path) -> Result<()> {
                     loop_count: None,
                     export: None,
                     font: None,
+                    variant: None,
                 });
                 changed.push(rel_str);
             }
@@ -0,0 +1,310 @@
+//! ui-gen command for rlvgl-creator.
+//!
+//! Compiles a declarative screen description (YAML or JSON) into a Rust
+//! function that builds the matching `WidgetNode` tree.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_ui::screen::{Error, Kind, Placed, Screen};
+
+/// Compile `input` into Rust, written to `out` or `<input>.rs`.
+pub(crate) fn run(input: &Path, out: Option<&Path>) -> Result<()> {
+    let screen: Screen = serde_yaml::from_str(&fs::read_to_string(input)?)?;
+    let file = input
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let code = generate(&screen, &file)?;
+    let out = out
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(input).with_extension("rs"));
+    fs::write(&out, code)?;
+    println!("Generated screen `{}` at {}", screen.name, out.display());
+    Ok(())
+}
+
+/// Rust source for a `build_<name>` function creating `screen`.
+pub(crate) fn generate(screen: &Screen, source: &str) -> Result<String> {
+    let placed = screen.place().map_err(|e| anyhow!("{e}"))?;
+    let uses_images = uses_images(&placed);
+    let mut s = format!(
+        "//! Screen `{}` generated by rlvgl-creator from `{source}`. Do not edit.\n\n\
+         use rlvgl_core::WidgetNode;\n\n\
+         /// Looks up an image asset path, giving `(width, height, pixels)`.\n\
+         pub type ImageLookup =\n    \
+         dyn Fn(&str) -> Option<(i32, i32, &'static [rlvgl_core::widget::Color])>;\n\n\
+         /// Build the `{}` screen.\n///\n\
+         /// Like `Screen::build`, this fails with the path of the first image\n\
+         /// `images` does not resolve.\n",
+        screen.name, screen.name
+    );
+    let _ = writeln!(
+        s,
+        "pub fn build_{}({}images: &ImageLookup) -> Result<WidgetNode, &'static str> {{",
+        ident(&screen.name),
+        if uses_images { "" } else { "_" }
+    );
+    s.push_str("    Ok(");
+    node(&mut s, &placed, 1)?;
+    s.push_str(")\n}\n");
+    Ok(s)
+}
+
+fn uses_images(placed: &Placed<'_>) -> bool {
+    placed.node.kind == Kind::Image || placed.children.iter().any(uses_images)
+}
+
+/// Lowercase identifier fragment.
+fn ident(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn rect(r: Rect) -> String {
+    format!(
+        "rlvgl_core::widget::Rect {{ x: {}, y: {}, width: {}, height: {} }}",
+        r.x, r.y, r.width, r.height
+    )
+}
+
+fn color(c: Color) -> String {
+    format!(
+        "rlvgl_core::widget::Color({}, {}, {}, {})",
+        c.0, c.1, c.2, c.3
+    )
+}
+
+fn style(s: Style) -> String {
+    format!(
+        "rlvgl_core::style::Style {{ bg_color: {}, border_color: {}, border_width: {} }}",
+        color(s.bg_color),
+        color(s.border_color),
+        s.border_width
+    )
+}
+
+/// Field receiving the node's `text_color`, if the widget has one.
+fn text_color_field(kind: Kind) -> Option<&'static str> {
+    match kind {
+        Kind::Label | Kind::Checkbox | Kind::Radio | Kind::List => Some("text_color"),
+        _ => None,
+    }
+}
+
+/// Field receiving the node's accent `color`, if the widget has one.
+fn accent_field(kind: Kind) -> Option<&'static str> {
+    match kind {
+        Kind::Checkbox => Some("check_color"),
+        Kind::Radio => Some("dot_color"),
+        Kind::Switch | Kind::Slider => Some("knob_color"),
+        Kind::Progress => Some("bar_color"),
+        Kind::Spinner => Some("arc_color"),
+        _ => None,
+    }
+}
+
+/// Emit a `WidgetNode::new(widget, children)` expression.
+fn node(s: &mut String, placed: &Placed<'_>, depth: usize) -> Result<()> {
+    let pad = "    ".repeat(depth);
+    let n = placed.node;
+    let b = rect(placed.bounds);
+    let text = format!("{:?}", n.text.as_deref().unwrap_or_default());
+    let (min, max) = (n.min.unwrap_or(0), n.max.unwrap_or(100));
+    let mut prelude = Vec::new();
+    let ctor = match n.kind {
+        Kind::Container => format!("rlvgl_widgets::container::Container::new({b})"),
+        Kind::Label => format!("rlvgl_widgets::label::Label::new({text}, {b})"),
+        Kind::Button => format!("rlvgl_widgets::button::Button::new({text}, {b})"),
+        Kind::Checkbox => format!("rlvgl_widgets::checkbox::Checkbox::new({text}, {b})"),
+        Kind::Radio => format!("rlvgl_widgets::radio::Radio::new({text}, {b})"),
+        Kind::Switch => format!("rlvgl_widgets::switch::Switch::new({b})"),
+        Kind::Slider => format!("rlvgl_widgets::slider::Slider::new({b}, {min}, {max})"),
+        Kind::Progress => {
+            format!("rlvgl_widgets::progress::ProgressBar::new({b}, {min}, {max})")
+        }
+        Kind::Led => format!(
+            "rlvgl_widgets::led::Led::new({b}, {})",
+            color(placed.color.unwrap_or(Color(0, 255, 0, 255)))
+        ),
+        Kind::Spinner => format!(
+            "rlvgl_widgets::spinner::Spinner::new({b}, {}, {})",
+            n.period_ms.unwrap_or(1000),
+            n.arc_length.unwrap_or(90)
+        ),
+        Kind::List => format!("rlvgl_widgets::list::List::new({b})"),
+        Kind::Image => {
+            // Without an asset there is no path to look up at runtime.
+            let asset = n
+                .asset
+                .as_deref()
+                .ok_or_else(|| anyhow!("{}", Error::UnknownImage(String::new())))?;
+            prelude.push(format!(
+                "let (width, height, pixels) = images({asset:?}).ok_or({asset:?})?;"
+            ));
+            format!("rlvgl_widgets::image::Image::new({b}, width, height, pixels)")
+        }
+    };
+
+    let mut stmts = Vec::new();
+    if let Some(st) = placed.style {
+        if n.kind == Kind::Button {
+            stmts.push(format!("*w.style_mut() = {};", style(st)));
+        } else {
+            stmts.push(format!("w.style = {};", style(st)));
+        }
+    }
+    if let (Some(field), Some(c)) = (text_color_field(n.kind), placed.text_color) {
+        stmts.push(format!("w.{field} = {};", color(c)));
+    }
+    if let (Some(field), Some(c)) = (accent_field(n.kind), placed.color) {
+        stmts.push(format!("w.{field} = {};", color(c)));
+    }
+    match n.kind {
+        Kind::Checkbox if n.checked => stmts.push("w.set_checked(true);".into()),
+        Kind::Radio if n.checked => stmts.push("w.set_selected(true);".into()),
+        Kind::Switch if n.checked => stmts.push("w.set_on(true);".into()),
+        Kind::Slider | Kind::Progress => {
+            if let Some(v) = n.value {
+                stmts.push(format!("w.set_value({v});"));
+            }
+        }
+        Kind::List => {
+            for item in &n.items {
+                stmts.push(format!("w.add_item({item:?});"));
+            }
+        }
+        _ => {}
+    }
+
+    let _ = writeln!(s, "WidgetNode::new(");
+    if let Some(id) = &n.id {
+        let _ = writeln!(s, "{pad}    // {id}");
+    }
+    if prelude.is_empty() && stmts.is_empty() {
+        let _ = writeln!(s, "{pad}    {ctor},");
+    } else {
+        let _ = writeln!(s, "{pad}    {{");
+        for line in &prelude {
+            let _ = writeln!(s, "{pad}        {line}");
+        }
+        if stmts.is_empty() {
+            let _ = writeln!(s, "{pad}        {ctor}");
+        } else {
+            let _ = writeln!(s, "{pad}        let mut w = {ctor};");
+            for line in &stmts {
+                let _ = writeln!(s, "{pad}        {line}");
+            }
+            let _ = writeln!(s, "{pad}        w");
+        }
+        let _ = writeln!(s, "{pad}    }},");
+    }
+    if placed.children.is_empty() {
+        let _ = writeln!(s, "{pad}    Default::default(),");
+    } else {
+        let _ = writeln!(s, "{pad}    [");
+        for child in &placed.children {
+            let _ = write!(s, "{pad}        ");
+            node(s, child, depth + 2)?;
+            s.push_str(",\n");
+        }
+        let _ = writeln!(s, "{pad}    ]");
+        let _ = writeln!(s, "{pad}    .into(),");
+    }
+    let _ = write!(s, "{pad})");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SCREEN: &str = r##"
+name: main
+width: 120
+height: 80
+styles:
+  card:
+    bg_color: "#202020"
+    border_width: 1
+    text_color: "#ffffff"
+root:
+  type: container
+  style: card
+  layout: { direction: column, spacing: 4, padding: 2 }
+  children:
+    - { type: label, id: title, text: "Hello \"rlvgl\"", size: [0, 12], style: card }
+    - { type: slider, size: [60, 10], value: 40, color: "#ff0000" }
+    - { type: image, asset: icons/logo.png, bounds: [100, 0, 16, 16] }
+    - type: list
+      items: [one, two]
+"##;
+
+    /// The generated function mirrors the resolved layout and properties.
+    #[test]
+    fn generates_widget_tree() {
+        let screen: Screen = serde_yaml::from_str(SCREEN).unwrap();
+        let code = generate(&screen, "main.yml").unwrap();
+        assert!(code.contains(
+            "pub fn build_main(images: &ImageLookup) -> Result<WidgetNode, &'static str> {\n    \
+             Ok(WidgetNode::new(\n"
+        ));
+        assert!(code.contains(
+            "rlvgl_widgets::label::Label::new(\"Hello \\\"rlvgl\\\"\", \
+             rlvgl_core::widget::Rect { x: 2, y: 2, width: 116, height: 12 })"
+        ));
+        assert!(code.contains("w.text_color = rlvgl_core::widget::Color(255, 255, 255, 255);"));
+        assert!(code.contains("Rect { x: 2, y: 18, width: 60, height: 10 }, 0, 100)"));
+        assert!(code.contains("w.knob_color = rlvgl_core::widget::Color(255, 0, 0, 255);"));
+        assert!(code.contains("w.set_value(40);"));
+        assert!(code.contains("images(\"icons/logo.png\").ok_or(\"icons/logo.png\")?;"));
+        assert!(code.contains("Rect { x: 100, y: 0, width: 16, height: 16 }"));
+        assert!(code.contains("w.add_item(\"one\");\n"));
+        assert!(code.contains("Rect { x: 2, y: 32, width: 116, height: 46 }"));
+        assert!(code.contains("// title"));
+    }
+
+    /// Placement errors are reported and the output file defaults next to
+    /// the input.
+    #[test]
+    fn writes_output_and_reports_errors() {
+        let tmp = tempdir().unwrap();
+        let input = tmp.path().join("main.yml");
+        fs::write(&input, SCREEN).unwrap();
+        run(&input, None).unwrap();
+        let code = fs::read_to_string(tmp.path().join("main.rs")).unwrap();
+        assert!(code.starts_with("//! Screen `main` generated by rlvgl-creator from `main.yml`."));
+
+        fs::write(&input, SCREEN.replace("style: card }", "style: missing }")).unwrap();
+        let err = run(&input, None).unwrap_err();
+        assert_eq!(err.to_string(), "unknown style `missing`");
+
+        fs::write(&input, SCREEN.replace("asset: icons/logo.png, ", "")).unwrap();
+        let err = run(&input, None).unwrap_err();
+        assert_eq!(err.to_string(), "unknown image ``");
+    }
+
+    /// The generated code compiled by `tests/creator_ui_gen.rs` is what the
+    /// generator emits for its screen.
+    #[test]
+    fn checked_in_output_is_current() {
+        let yaml = include_str!("../../../tests/data/ui_gen/main.yml");
+        let screen: Screen = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            generate(&screen, "main.yml").unwrap(),
+            include_str!("../../../tests/data/ui_gen/main.rs")
+        );
+    }
+}
@@ -69,6 +69,10 @@ pub(crate) struct CreatorApp {
     pub(crate) layout_open: bool,
     /// Items placed in the layout editor.
     pub(crate) layout_items: Vec<LayoutItem>,
+    /// Whether the central panel shows the screen editor.
+    pub(crate) screen_mode: bool,
+    /// Screen editor state.
+    pub(crate) screen_editor: ScreenEditor,
 }
 
 impl CreatorApp {
@@ -110,6 +114,11 @@ impl CreatorApp {
                     .as_ref()
                     .and_then(|e| e.compression.clone())
                     .unwrap_or_default();
+                let export_dither = a
+                    .export
+                    .as_ref()
+                    .and_then(|e| e.dither.clone())
+                    .unwrap_or_default();
                 let anim_delay_ms = a.frame_delay_ms.map(|d| d.to_string()).unwrap_or_default();
                 let anim_loops = a.loop_count.map(|l| l.to_string()).unwrap_or_default();
                 let lottie_mode = a
@@ -142,6 +151,13 @@ impl CreatorApp {
                     .as_ref()
                     .and_then(|f| f.packing.clone())
                     .unwrap_or_default();
+                let font_bpp = a
+                    .font
+                    .as_ref()
+                    .and_then(|f| f.bpp)
+                    .map(|b| b.to_string())
+                    .unwrap_or_default();
+                let font_kerning = a.font.as_ref().map(|f| f.kerning).unwrap_or(false);
                 AssetMeta {
                     license: a.license.clone(),
                     hash: a.hash.clone(),
@@ -152,6 +168,7 @@ impl CreatorApp {
                     export_color_space,
                     export_premultiplied,
                     export_compression,
+                    export_dither,
                     anim_delay_ms,
                     anim_loops,
                     lottie_mode,
@@ -159,6 +176,8 @@ impl CreatorApp {
                     font_sizes,
                     font_hinting,
                     font_packing,
+                    font_bpp,
+                    font_kerning,
                 }
             })
             .collect::<Vec<_>>();
@@ -229,6 +248,8 @@ impl CreatorApp {
             screen_preset: None,
             layout_open: false,
             layout_items: Vec::new(),
+            screen_mode: false,
+            screen_editor: ScreenEditor::new(),
         };
         app.generate_thumbnails();
         app
@@ -529,6 +550,8 @@ impl CreatorApp {
                     let mut new_app = Self::new(manifest, path);
                     new_app.toasts = self.toasts.clone();
                     new_app.new_group = self.new_group.clone();
+                    new_app.screen_mode = self.screen_mode;
+                    std::mem::swap(&mut new_app.screen_editor, &mut self.screen_editor);
                     *self = new_app;
                 }
             }
@@ -18,6 +18,9 @@ use egui::{ColorImage, TextureHandle, Vec2};
 use image::{GenericImageView, ImageFormat};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use rfd::{FileDialog, MessageButtons, MessageDialog, MessageDialogResult};
+use rlvgl_platform::PixelFmt;
+#[cfg(unix)]
+use rlvgl_platform::hot_reload;
 use serde_yaml::from_reader;
 
 #[path = "../creator/add_target.rs"]
@@ -70,6 +73,9 @@ use app::CreatorApp;
 mod commands;
 mod menus;
 mod presets;
+mod screen_editor;
+use screen_editor::ScreenEditor;
+mod screen_render;
 mod update;
 mod wizard;
 
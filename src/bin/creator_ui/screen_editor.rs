@@ -0,0 +1,621 @@
+//! Screen editing mode for rlvgl-creator UI.
+//!
+//! The canvas shows the screen exactly as [`screen_render::render`] draws it
+//! for the selected screen preset. Widgets are picked and dragged on the
+//! canvas, edited in the inspector and saved back to the screen file read by
+//! `rlvgl-creator ui-gen`.
+
+use super::*;
+use rlvgl_core::widget::Color;
+use rlvgl_ui::screen::{Direction, ImageData, Kind, Layout, Node, Screen};
+use screen_render::{node_at, node_mut, placed_at, render};
+use std::time::SystemTime;
+
+/// State of the screen editor.
+pub(crate) struct ScreenEditor {
+    /// Screen file being edited, once opened or saved.
+    pub(crate) path: Option<PathBuf>,
+    /// Screen description being edited.
+    pub(crate) screen: Screen,
+    /// Child index path of the selected node.
+    pub(crate) selected: Option<Vec<usize>>,
+    /// Widget type placed by the "Add" button.
+    pub(crate) new_kind: Kind,
+    /// Rendered screen.
+    pub(crate) texture: Option<TextureHandle>,
+    /// Whether the texture must be rendered again.
+    pub(crate) stale: bool,
+    /// Screen preset the texture was rendered for.
+    pub(crate) preset: Option<usize>,
+    /// Canvas zoom factor.
+    pub(crate) zoom: f32,
+    /// Drag in progress: pointer start in screen pixels and the dragged
+    /// node's original offset.
+    pub(crate) drag: Option<(egui::Pos2, [i32; 2])>,
+    /// Error from the last render.
+    pub(crate) error: Option<String>,
+    /// Whether the screen has unsaved changes.
+    pub(crate) modified: bool,
+    /// Decoded image assets by path with the file's modification time.
+    /// Widgets borrow pixels for `'static`, so each version of an asset is
+    /// decoded and leaked once, and only again after the file changes.
+    pub(crate) images: BTreeMap<String, (SystemTime, ImageData)>,
+}
+
+impl ScreenEditor {
+    /// Empty editor holding a blank screen sized for the first preset.
+    pub(crate) fn new() -> Self {
+        let preset = &SCREEN_PRESETS[0];
+        Self {
+            path: None,
+            screen: blank_screen(preset.width as i32, preset.height as i32),
+            selected: None,
+            new_kind: Kind::Label,
+            texture: None,
+            stale: true,
+            preset: None,
+            zoom: 2.0,
+            drag: None,
+            error: None,
+            modified: false,
+            images: BTreeMap::new(),
+        }
+    }
+}
+
+fn blank_screen(width: i32, height: i32) -> Screen {
+    Screen {
+        name: "main".to_string(),
+        width,
+        height,
+        styles: BTreeMap::new(),
+        root: Node::new(Kind::Container),
+    }
+}
+
+/// Widget of `kind` with a default size and content. Images use `asset`
+/// with its size when given.
+fn new_node(kind: Kind, asset: Option<(String, u32, u32)>) -> Node {
+    let mut node = Node::new(kind);
+    let (w, h) = match kind {
+        Kind::Container | Kind::List => (120, 80),
+        Kind::Label | Kind::Button => (80, 24),
+        Kind::Checkbox | Kind::Radio => (100, 20),
+        Kind::Switch => (40, 20),
+        Kind::Slider | Kind::Progress => (120, 12),
+        Kind::Led => (16, 16),
+        Kind::Spinner | Kind::Image => (32, 32),
+    };
+    node.bounds = Some([8, 8, w, h]);
+    match kind {
+        Kind::Label | Kind::Button | Kind::Checkbox | Kind::Radio => {
+            let name = kind.name();
+            node.text = Some(format!("{}{}", name[..1].to_uppercase(), &name[1..]));
+        }
+        Kind::List => node.items = vec!["Item".to_string()],
+        Kind::Image => {
+            if let Some((path, w, h)) = asset {
+                if w > 0 && h > 0 {
+                    node.bounds = Some([8, 8, w as i32, h as i32]);
+                }
+                node.asset = Some(path);
+            }
+        }
+        _ => {}
+    }
+    node
+}
+
+fn collect_assets(node: &Node, out: &mut Vec<String>) {
+    if let Some(asset) = &node.asset {
+        out.push(asset.clone());
+    }
+    for child in &node.children {
+        collect_assets(child, out);
+    }
+}
+
+/// Single-line editor for an optional string; empty text clears it.
+fn opt_text(ui: &mut egui::Ui, label: &str, value: &mut Option<String>) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let mut text = value.clone().unwrap_or_default();
+        let changed = ui.text_edit_singleline(&mut text).changed();
+        if changed {
+            *value = (!text.is_empty()).then_some(text);
+        }
+        changed
+    })
+    .inner
+}
+
+/// Number editor for an optional value, unset while unchecked.
+fn opt_value<T: egui::emath::Numeric + Default>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<T>,
+) -> bool {
+    ui.horizontal(|ui| {
+        let mut set = value.is_some();
+        let mut changed = ui.checkbox(&mut set, label).changed();
+        if set {
+            let v = value.get_or_insert_with(T::default);
+            changed |= ui.add(egui::DragValue::new(v)).changed();
+        } else {
+            *value = None;
+        }
+        changed
+    })
+    .inner
+}
+
+impl CreatorApp {
+    /// Switch between asset browsing and screen editing.
+    pub(crate) fn toggle_screen_mode(&mut self) {
+        self.screen_mode = !self.screen_mode;
+        if self.screen_mode && self.screen_preset.is_none() {
+            self.screen_preset = Some(0);
+        }
+    }
+
+    /// Load a screen file into the editor.
+    fn open_screen(&mut self, path: PathBuf) -> Result<()> {
+        let screen: Screen = serde_yaml::from_str(&fs::read_to_string(&path)?)?;
+        let ed = &mut self.screen_editor;
+        ed.screen = screen;
+        ed.path = Some(path);
+        ed.selected = None;
+        ed.stale = true;
+        ed.modified = false;
+        // Keep the file's own size until another preset is picked.
+        ed.preset = self.screen_preset;
+        Ok(())
+    }
+
+    /// Write the screen to its file, asking for a path if it has none or
+    /// `ask` is set. JSON is written for `.json` paths, YAML otherwise.
+    fn save_screen(&mut self, ask: bool) -> Result<()> {
+        let ed = &mut self.screen_editor;
+        let path = match &ed.path {
+            Some(path) if !ask => path.clone(),
+            _ => match FileDialog::new()
+                .add_filter("screen", &["yml", "yaml", "json"])
+                .set_file_name(format!("{}.yml", ed.screen.name))
+                .save_file()
+            {
+                Some(path) => path,
+                None => return Ok(()),
+            },
+        };
+        let text = if path.extension().is_some_and(|e| e == "json") {
+            serde_json::to_string_pretty(&ed.screen)?
+        } else {
+            serde_yaml::to_string(&ed.screen)?
+        };
+        fs::write(&path, text)?;
+        let msg = format!("Saved {}", path.display());
+        ed.path = Some(path);
+        ed.modified = false;
+        self.toasts.push((msg, Instant::now()));
+        Ok(())
+    }
+
+    /// Decode image assets used by the screen that are not cached yet or
+    /// changed on disk since they were decoded.
+    fn load_screen_images(&mut self) {
+        let mut paths = Vec::new();
+        collect_assets(&self.screen_editor.screen.root, &mut paths);
+        for path in paths {
+            let file = self.raw_dir.join(&path);
+            let Ok(mtime) = fs::metadata(&file).and_then(|m| m.modified()) else {
+                self.screen_editor.images.remove(&path);
+                continue;
+            };
+            if self
+                .screen_editor
+                .images
+                .get(&path)
+                .is_some_and(|(cached, _)| *cached == mtime)
+            {
+                continue;
+            }
+            if let Ok(img) = image::open(&file) {
+                let rgba = img.to_rgba8();
+                let (w, h) = rgba.dimensions();
+                let pixels: Vec<Color> = rgba
+                    .pixels()
+                    .map(|p| Color(p[0], p[1], p[2], p[3]))
+                    .collect();
+                let pixels: &'static [Color] = Box::leak(pixels.into_boxed_slice());
+                self.screen_editor
+                    .images
+                    .insert(path, (mtime, (w as i32, h as i32, pixels)));
+            }
+        }
+    }
+
+    /// Render the screen into the editor texture if it changed.
+    fn refresh_screen(&mut self, ctx: &egui::Context) {
+        if self.screen_editor.preset != self.screen_preset {
+            if let Some(i) = self.screen_preset {
+                let (w, h) = (
+                    SCREEN_PRESETS[i].width as i32,
+                    SCREEN_PRESETS[i].height as i32,
+                );
+                let screen = &mut self.screen_editor.screen;
+                if (screen.width, screen.height) != (w, h) {
+                    (screen.width, screen.height) = (w, h);
+                    self.screen_editor.modified = true;
+                }
+            }
+            self.screen_editor.preset = self.screen_preset;
+            self.screen_editor.stale = true;
+        }
+        if !self.screen_editor.stale {
+            return;
+        }
+        self.load_screen_images();
+        let format = self
+            .screen_preset
+            .map(|i| SCREEN_PRESETS[i].format)
+            .unwrap_or(PixelFmt::Argb8888);
+        let ed = &mut self.screen_editor;
+        let images = &ed.images;
+        // Unresolved images are drawn empty, as in generated code.
+        let resolve = |path: &str| -> Option<ImageData> {
+            Some(images.get(path).map_or((0, 0, &[][..]), |(_, data)| *data))
+        };
+        match render(&ed.screen, format, &resolve) {
+            Ok(rgba) => {
+                let size = [
+                    ed.screen.width.max(1) as usize,
+                    ed.screen.height.max(1) as usize,
+                ];
+                let image = ColorImage::from_rgba_unmultiplied(size, &rgba);
+                ed.texture =
+                    Some(ctx.load_texture("screen_editor", image, egui::TextureOptions::NEAREST));
+                ed.error = None;
+            }
+            Err(e) => ed.error = Some(e.to_string()),
+        }
+        ed.stale = false;
+    }
+
+    /// Add a widget of the chosen type to the selected container, or next
+    /// to the selected widget.
+    fn add_screen_node(&mut self) {
+        let asset = self.selected().map(|idx| {
+            (
+                self.manifest.assets[idx].path.clone(),
+                self.meta[idx].width,
+                self.meta[idx].height,
+            )
+        });
+        let ed = &mut self.screen_editor;
+        let mut parent = ed.selected.clone().unwrap_or_default();
+        if node_mut(&mut ed.screen.root, &parent).is_some_and(|n| n.kind != Kind::Container) {
+            parent.pop();
+        }
+        let Some(node) = node_mut(&mut ed.screen.root, &parent) else {
+            return;
+        };
+        node.children.push(new_node(ed.new_kind, asset));
+        parent.push(node.children.len() - 1);
+        ed.selected = Some(parent);
+        ed.stale = true;
+        ed.modified = true;
+    }
+
+    /// Draw the screen editor toolbar and canvas.
+    pub(crate) fn show_screen_canvas(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let name = self
+                .screen_editor
+                .path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_else(|| "untitled".to_string());
+            let marker = if self.screen_editor.modified { "*" } else { "" };
+            ui.heading(format!("Screen: {name}{marker}"));
+            let label = self
+                .screen_preset
+                .map(|i| SCREEN_PRESETS[i].name)
+                .unwrap_or("File size");
+            egui::ComboBox::from_id_salt("screen_editor_preset")
+                .selected_text(label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.screen_preset, None, "File size");
+                    for (i, preset) in SCREEN_PRESETS.iter().enumerate() {
+                        ui.selectable_value(&mut self.screen_preset, Some(i), preset.name);
+                    }
+                });
+            if ui.button("New").clicked() {
+                let ed = &mut self.screen_editor;
+                ed.screen = blank_screen(ed.screen.width, ed.screen.height);
+                ed.path = None;
+                ed.selected = None;
+                ed.stale = true;
+                ed.modified = false;
+            }
+            if ui.button("Open").clicked()
+                && let Some(path) = FileDialog::new()
+                    .add_filter("screen", &["yml", "yaml", "json"])
+                    .pick_file()
+                && let Err(e) = self.open_screen(path)
+            {
+                self.toasts
+                    .push((format!("Open failed: {}", e), Instant::now()));
+            }
+            for (label, ask) in [("Save", false), ("Save As", true)] {
+                if ui.button(label).clicked()
+                    && let Err(e) = self.save_screen(ask)
+                {
+                    self.toasts
+                        .push((format!("Save failed: {}", e), Instant::now()));
+                }
+            }
+            ui.separator();
+            egui::ComboBox::from_id_salt("screen_editor_kind")
+                .selected_text(self.screen_editor.new_kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in Kind::ALL {
+                        ui.selectable_value(&mut self.screen_editor.new_kind, kind, kind.name());
+                    }
+                });
+            if ui.button("Add").clicked() {
+                self.add_screen_node();
+            }
+            ui.separator();
+            ui.add(egui::Slider::new(&mut self.screen_editor.zoom, 1.0..=8.0).text("Zoom"));
+        });
+
+        self.refresh_screen(ctx);
+        if let Some(err) = &self.screen_editor.error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+        let Some(texture) = self.screen_editor.texture.clone() else {
+            return;
+        };
+        let zoom = self.screen_editor.zoom;
+        let (response, painter) =
+            ui.allocate_painter(ui.available_size(), egui::Sense::click_and_drag());
+        let img_rect = egui::Rect::from_min_size(
+            response.rect.min + Vec2::splat(8.0),
+            texture.size_vec2() * zoom,
+        );
+        painter.image(
+            texture.id(),
+            img_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+        let to_screen = |pos: egui::Pos2| ((pos - img_rect.min) / zoom).to_pos2();
+
+        let ed = &mut self.screen_editor;
+        let pointer = response.interact_pointer_pos().map(to_screen);
+        let (hit, selected_rect) = match ed.screen.place() {
+            Ok(placed) => {
+                let hit = pointer
+                    .filter(|_| response.clicked() || response.drag_started())
+                    .map(|p| node_at(&placed, p.x.floor() as i32, p.y.floor() as i32));
+                let selected_rect = ed
+                    .selected
+                    .as_deref()
+                    .and_then(|path| placed_at(&placed, path))
+                    .map(|p| p.bounds);
+                (hit, selected_rect)
+            }
+            Err(_) => (None, None),
+        };
+        if let Some(hit) = hit {
+            ed.selected = hit;
+        }
+        if response.drag_started() {
+            ed.drag = match (pointer, ed.selected.as_deref()) {
+                (Some(start), Some(path)) => node_mut(&mut ed.screen.root, path)
+                    .and_then(|n| n.bounds)
+                    .map(|[x, y, _, _]| (start, [x, y])),
+                _ => None,
+            };
+        }
+        if response.dragged()
+            && let (Some((start, [x0, y0])), Some(pos), Some(path)) =
+                (ed.drag, pointer, ed.selected.as_deref())
+            && let Some(b) = node_mut(&mut ed.screen.root, path).and_then(|n| n.bounds.as_mut())
+        {
+            let delta = pos - start;
+            let xy = [x0 + delta.x.round() as i32, y0 + delta.y.round() as i32];
+            if b[..2] != xy {
+                b[..2].copy_from_slice(&xy);
+                ed.stale = true;
+                ed.modified = true;
+            }
+        }
+        if response.drag_stopped() {
+            ed.drag = None;
+        }
+        if let Some(r) = selected_rect {
+            let rect = egui::Rect::from_min_size(
+                img_rect.min + Vec2::new(r.x as f32, r.y as f32) * zoom,
+                Vec2::new(r.width as f32, r.height as f32) * zoom,
+            );
+            painter.rect_stroke(rect, 0.0, egui::Stroke::new(2.0, egui::Color32::YELLOW));
+        }
+    }
+
+    /// Draw the property inspector for the selected screen node.
+    pub(crate) fn show_screen_inspector(&mut self, ui: &mut egui::Ui) {
+        let asset = self
+            .selected()
+            .map(|idx| self.manifest.assets[idx].path.clone());
+        let ed = &mut self.screen_editor;
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Screen name:");
+            changed |= ui.text_edit_singleline(&mut ed.screen.name).changed();
+        });
+        ui.separator();
+        let Some(path) = ed.selected.clone() else {
+            ui.label("Select a widget");
+            ed.modified |= changed;
+            return;
+        };
+        // Offset within the parent, used to detach a node from its layout.
+        let offset = ed.screen.place().ok().and_then(|placed| {
+            let node = placed_at(&placed, &path)?.bounds;
+            let parent = placed_at(&placed, path.split_last().map_or(&[], |(_, p)| p))?.bounds;
+            Some([
+                node.x - parent.x,
+                node.y - parent.y,
+                node.width,
+                node.height,
+            ])
+        });
+        let styles: Vec<String> = ed.screen.styles.keys().cloned().collect();
+        let Some(node) = node_mut(&mut ed.screen.root, &path) else {
+            ed.selected = None;
+            return;
+        };
+        let mut delete = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let kind = node.kind;
+            ui.label(format!("Type: {}", kind.name()));
+            changed |= opt_text(ui, "Id:", &mut node.id);
+            if let Some(b) = node.bounds.as_mut() {
+                ui.horizontal(|ui| {
+                    ui.label("Bounds:");
+                    for v in b.iter_mut() {
+                        changed |= ui.add(egui::DragValue::new(v)).changed();
+                    }
+                });
+            } else if path.is_empty() {
+                ui.label("Fills the screen");
+            } else {
+                ui.label("Positioned by the parent layout");
+                ui.horizontal(|ui| {
+                    let mut set = node.size.is_some();
+                    changed |= ui.checkbox(&mut set, "Size").changed();
+                    if set {
+                        for v in node.size.get_or_insert([0, 0]).iter_mut() {
+                            changed |= ui.add(egui::DragValue::new(v)).changed();
+                        }
+                    } else {
+                        node.size = None;
+                    }
+                });
+                if offset.is_some() && ui.button("Detach from layout").clicked() {
+                    node.bounds = offset;
+                    changed = true;
+                }
+            }
+            egui::ComboBox::from_label("Style")
+                .selected_text(node.style.clone().unwrap_or_else(|| "None".to_string()))
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut node.style, None, "None").changed();
+                    for name in &styles {
+                        changed |= ui
+                            .selectable_value(&mut node.style, Some(name.clone()), name)
+                            .changed();
+                    }
+                });
+            if matches!(
+                kind,
+                Kind::Label | Kind::Button | Kind::Checkbox | Kind::Radio
+            ) {
+                changed |= opt_text(ui, "Text:", &mut node.text);
+            }
+            if matches!(
+                kind,
+                Kind::Label | Kind::Checkbox | Kind::Radio | Kind::List
+            ) {
+                changed |= opt_text(ui, "Text color:", &mut node.text_color);
+            }
+            if matches!(
+                kind,
+                Kind::Checkbox
+                    | Kind::Radio
+                    | Kind::Switch
+                    | Kind::Slider
+                    | Kind::Progress
+                    | Kind::Led
+                    | Kind::Spinner
+            ) {
+                changed |= opt_text(ui, "Accent color:", &mut node.color);
+            }
+            if matches!(kind, Kind::Checkbox | Kind::Radio | Kind::Switch) {
+                changed |= ui.checkbox(&mut node.checked, "Checked").changed();
+            }
+            if matches!(kind, Kind::Slider | Kind::Progress) {
+                changed |= opt_value(ui, "Value", &mut node.value);
+                changed |= opt_value(ui, "Min", &mut node.min);
+                changed |= opt_value(ui, "Max", &mut node.max);
+            }
+            if kind == Kind::Spinner {
+                changed |= opt_value(ui, "Period (ms)", &mut node.period_ms);
+                changed |= opt_value(ui, "Arc length", &mut node.arc_length);
+            }
+            if kind == Kind::List {
+                ui.label("Items (one per line):");
+                let mut text = node.items.join("\n");
+                if ui.text_edit_multiline(&mut text).changed() {
+                    node.items = if text.is_empty() {
+                        Vec::new()
+                    } else {
+                        text.split('\n').map(str::to_string).collect()
+                    };
+                    changed = true;
+                }
+            }
+            if kind == Kind::Image {
+                changed |= opt_text(ui, "Asset:", &mut node.asset);
+                if let Some(asset) = &asset
+                    && ui.button("Use selected asset").clicked()
+                {
+                    node.asset = Some(asset.clone());
+                    changed = true;
+                }
+            }
+            if kind == Kind::Container {
+                let mut has_layout = node.layout.is_some();
+                if ui.checkbox(&mut has_layout, "Layout").changed() {
+                    node.layout = has_layout.then_some(Layout {
+                        direction: Direction::Column,
+                        spacing: 0,
+                        padding: 0,
+                    });
+                    changed = true;
+                }
+                if let Some(layout) = node.layout.as_mut() {
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .selectable_value(&mut layout.direction, Direction::Column, "Column")
+                            .changed();
+                        changed |= ui
+                            .selectable_value(&mut layout.direction, Direction::Row, "Row")
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Spacing:");
+                        changed |= ui.add(egui::DragValue::new(&mut layout.spacing)).changed();
+                        ui.label("Padding:");
+                        changed |= ui.add(egui::DragValue::new(&mut layout.padding)).changed();
+                    });
+                }
+            }
+            ui.separator();
+            delete = !path.is_empty() && ui.button("Delete").clicked();
+        });
+        if let (true, Some((last, parent))) = (delete, path.split_last()) {
+            if let Some(parent) = node_mut(&mut ed.screen.root, parent) {
+                parent.children.remove(*last);
+            }
+            ed.selected = None;
+            changed = true;
+        }
+        if changed {
+            ed.stale = true;
+            ed.modified = true;
+        }
+    }
+}
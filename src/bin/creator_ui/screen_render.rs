@@ -0,0 +1,91 @@
+//! Off-screen rendering of declarative screens for the screen editor.
+//!
+//! Screens are built with [`Screen::build`] and drawn by the same
+//! [`BlitterRenderer`] and [`CpuBlitter`] used on devices, into a surface of
+//! the target pixel format. The result is converted to RGBA for display.
+
+use rlvgl_platform::{BlitRect, Blitter, BlitterRenderer, CpuBlitter, PixelFmt, Surface};
+use rlvgl_ui::screen::{Error, ImageData, Node, Placed, Screen};
+
+/// Render `screen` at its own size in `format` and return RGBA8 pixels.
+pub(crate) fn render(
+    screen: &Screen,
+    format: PixelFmt,
+    images: &dyn Fn(&str) -> Option<ImageData>,
+) -> Result<Vec<u8>, Error> {
+    let built = screen.build(images)?;
+    let (width, height) = (screen.width.max(1) as u32, screen.height.max(1) as u32);
+    let stride = (width as usize * format.bits_per_pixel()).div_ceil(8);
+    let palette = palette(format);
+    let mut buf = vec![0u8; stride * height as usize];
+    let mut blitter = CpuBlitter;
+    {
+        let surface = Surface::new(&mut buf, stride, format, width, height).with_palette(&palette);
+        let mut renderer: BlitterRenderer<'_, CpuBlitter, 16> =
+            BlitterRenderer::new(&mut blitter, surface);
+        built.root.draw(&mut renderer);
+    }
+
+    let mut argb = vec![0u8; (width * height * 4) as usize];
+    let src = Surface::new(&mut buf, stride, format, width, height).with_palette(&palette);
+    let mut dst = Surface::new(
+        &mut argb,
+        width as usize * 4,
+        PixelFmt::Argb8888,
+        width,
+        height,
+    );
+    let area = BlitRect {
+        x: 0,
+        y: 0,
+        w: width,
+        h: height,
+    };
+    blitter.blit(&src, area, &mut dst, (0, 0));
+    for px in argb.chunks_exact_mut(4) {
+        // Little-endian ARGB8888 is stored as B, G, R, A.
+        px.swap(0, 2);
+        px[3] = 255;
+    }
+    Ok(argb)
+}
+
+/// Grey ramp used for indexed formats, which have no palette of their own.
+fn palette(format: PixelFmt) -> Vec<u32> {
+    if !format.is_indexed() {
+        return Vec::new();
+    }
+    let levels = 1u32 << format.bits_per_pixel();
+    (0..levels)
+        .map(|i| {
+            let v = i * 255 / (levels - 1);
+            0xff00_0000 | (v << 16) | (v << 8) | v
+        })
+        .collect()
+}
+
+/// Child index path of the innermost node containing `(x, y)`.
+pub(crate) fn node_at(placed: &Placed<'_>, x: i32, y: i32) -> Option<Vec<usize>> {
+    let b = placed.bounds;
+    if x < b.x || y < b.y || x >= b.x + b.width || y >= b.y + b.height {
+        return None;
+    }
+    // Later children draw on top, so they win.
+    for (i, child) in placed.children.iter().enumerate().rev() {
+        if let Some(mut path) = node_at(child, x, y) {
+            path.insert(0, i);
+            return Some(path);
+        }
+    }
+    Some(Vec::new())
+}
+
+/// Placed node at `path` below `placed`.
+pub(crate) fn placed_at<'p, 'a>(placed: &'p Placed<'a>, path: &[usize]) -> Option<&'p Placed<'a>> {
+    path.iter().try_fold(placed, |p, &i| p.children.get(i))
+}
+
+/// Node at `path` below `root`.
+pub(crate) fn node_mut<'a>(root: &'a mut Node, path: &[usize]) -> Option<&'a mut Node> {
+    path.iter().try_fold(root, |n, &i| n.children.get_mut(i))
+}
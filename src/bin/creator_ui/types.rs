@@ -10,6 +10,8 @@ pub(crate) struct ScreenPreset {
     pub(crate) width: u32,
     /// Height of the screen in pixels.
     pub(crate) height: u32,
+    /// Framebuffer pixel format the screen editor renders in.
+    pub(crate) format: PixelFmt,
 }
 
 /// Collection of built-in screen presets.
@@ -17,6 +19,7 @@ pub(crate) const SCREEN_PRESETS: &[ScreenPreset] = &[ScreenPreset {
     name: "stm32h7-480x272",
     width: 480,
     height: 272,
+    format: PixelFmt::Rgb565,
 }];
 
 /// Node within the asset directory tree.
@@ -40,6 +43,7 @@ pub(crate) struct AssetMeta {
     pub(crate) export_color_space: String,
     pub(crate) export_premultiplied: bool,
     pub(crate) export_compression: String,
+    pub(crate) export_dither: String,
     pub(crate) anim_delay_ms: String,
     pub(crate) anim_loops: String,
     pub(crate) lottie_mode: String,
@@ -47,6 +51,8 @@ pub(crate) struct AssetMeta {
     pub(crate) font_sizes: String,
     pub(crate) font_hinting: bool,
     pub(crate) font_packing: String,
+    pub(crate) font_bpp: String,
+    pub(crate) font_kerning: bool,
 }
 
 /// Positioned asset within the layout editor.
@@ -6,10 +6,15 @@ impl App for CreatorApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.handle_dropped_files(ctx);
 
+        let mut changed = Vec::new();
         while let Ok(res) = self.thumb_rx.try_recv() {
             if let Ok(event) = res {
+                let announce = event.kind.is_create() || event.kind.is_modify();
                 for path in event.paths {
                     if let Ok(rel) = path.strip_prefix(&self.raw_dir) {
+                        if announce {
+                            changed.push(rel.to_string_lossy().replace('\\', "/"));
+                        }
                         if let Some(idx) = self
                             .manifest
                             .assets
@@ -28,6 +33,11 @@ impl App for CreatorApp {
                                     let mut new_app = Self::new(manifest, path);
                                     new_app.toasts = self.toasts.clone();
                                     new_app.new_group = self.new_group.clone();
+                                    new_app.screen_mode = self.screen_mode;
+                                    std::mem::swap(
+                                        &mut new_app.screen_editor,
+                                        &mut self.screen_editor,
+                                    );
                                     *self = new_app;
                                 }
                             }
@@ -36,6 +46,22 @@ impl App for CreatorApp {
                 }
             }
         }
+        // Redraw the screen canvas so it picks up edited images.
+        if changed
+            .iter()
+            .any(|p| self.screen_editor.images.contains_key(p))
+        {
+            self.screen_editor.stale = true;
+        }
+        #[cfg(unix)]
+        if !changed.is_empty() {
+            changed.sort();
+            changed.dedup();
+            let names: Vec<&str> = changed.iter().map(String::as_str).collect();
+            // A simulator started with `--assets=assets/raw` reloads them; it
+            // is fine if none is running.
+            let _ = hot_reload::send(hot_reload::default_socket_path(), &names);
+        }
 
         egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
@@ -63,6 +89,12 @@ impl App for CreatorApp {
                 if ui.button("Layout Editor").clicked() {
                     self.layout_open = !self.layout_open;
                 }
+                if ui
+                    .selectable_label(self.screen_mode, "Screen Editor")
+                    .clicked()
+                {
+                    self.toggle_screen_mode();
+                }
             });
         });
 
@@ -122,6 +154,10 @@ impl App for CreatorApp {
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            if self.screen_mode {
+                self.show_screen_canvas(ctx, ui);
+                return;
+            }
             ui.horizontal(|ui| {
                 ui.heading("Preview");
                 let label = self
@@ -195,6 +231,10 @@ impl App for CreatorApp {
         egui::SidePanel::right("inspector").show(ctx, |ui| {
             ui.heading("Inspector");
             ui.separator();
+            if self.screen_mode {
+                self.show_screen_inspector(ui);
+                return;
+            }
             if let Some(idx) = self.selected() {
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     let asset = &self.manifest.assets[idx];
@@ -237,17 +277,22 @@ impl App for CreatorApp {
                         let meta = &mut self.meta[idx];
                         ui.label("Sizes (px, comma separated):");
                         export_changed |= ui.text_edit_singleline(&mut meta.export_sizes).changed();
-                        ui.label("Color space:");
+                        ui.label(
+                            "Color space (srgb, argb8888, rgb565, l8, a8, a4, i1, i2, i4, i8):",
+                        );
                         export_changed |= ui
                             .text_edit_singleline(&mut meta.export_color_space)
                             .changed();
                         export_changed |= ui
                             .checkbox(&mut meta.export_premultiplied, "Premultiplied alpha")
                             .changed();
-                        ui.label("Compression:");
+                        ui.label("Compression (none, rle, lz4):");
                         export_changed |= ui
                             .text_edit_singleline(&mut meta.export_compression)
                             .changed();
+                        ui.label("Dither (none, bayer, floyd-steinberg):");
+                        export_changed |=
+                            ui.text_edit_singleline(&mut meta.export_dither).changed();
                         if export_changed {
                             let sizes_vec = meta
                                 .export_sizes
@@ -267,11 +312,17 @@ impl App for CreatorApp {
                                 } else {
                                     Some(meta.export_compression.clone())
                                 },
+                                dither: if meta.export_dither.is_empty() {
+                                    None
+                                } else {
+                                    Some(meta.export_dither.clone())
+                                },
                             };
                             self.manifest.assets[idx].export = if export_opts.sizes.is_empty()
                                 && export_opts.color_space.is_none()
                                 && !export_opts.premultiplied
                                 && export_opts.compression.is_none()
+                                && export_opts.dither.is_none()
                             {
                                 None
                             } else {
@@ -327,11 +378,14 @@ impl App for CreatorApp {
                         let meta = &mut self.meta[idx];
                         ui.label("Glyph set:");
                         font_changed |= ui.text_edit_singleline(&mut meta.font_glyphs).changed();
-                        ui.label("Sizes (pt, comma separated):");
+                        ui.label("Sizes (px, comma separated):");
                         font_changed |= ui.text_edit_singleline(&mut meta.font_sizes).changed();
                         font_changed |= ui.checkbox(&mut meta.font_hinting, "Hinting").changed();
-                        ui.label("Packing:");
+                        ui.label("Packing (none, rle):");
                         font_changed |= ui.text_edit_singleline(&mut meta.font_packing).changed();
+                        ui.label("Bits per pixel (1, 2, 4, 8):");
+                        font_changed |= ui.text_edit_singleline(&mut meta.font_bpp).changed();
+                        font_changed |= ui.checkbox(&mut meta.font_kerning, "Kerning").changed();
                         if font_changed {
                             let sizes_vec = meta
                                 .font_sizes
@@ -351,11 +405,15 @@ impl App for CreatorApp {
                                 } else {
                                     Some(meta.font_packing.clone())
                                 },
+                                bpp: meta.font_bpp.trim().parse().ok(),
+                                kerning: meta.font_kerning,
                             };
                             self.manifest.assets[idx].font = if font_opts.glyphs.is_none()
                                 && font_opts.sizes.is_empty()
                                 && !font_opts.hinting
                                 && font_opts.packing.is_none()
+                                && font_opts.bpp.is_none()
+                                && !font_opts.kerning
                             {
                                 None
                             } else {
@@ -393,12 +451,16 @@ impl App for CreatorApp {
                         ui.text_edit_singleline(&mut self.fonts_pack_chars);
                     });
                     if ui.button("Pack").clicked() {
-                        let size = self.fonts_pack_size.trim().parse().unwrap_or(32.0);
-                        let res = fonts::pack(
+                        let size = self.fonts_pack_size.trim().parse().unwrap_or(32);
+                        let defaults = crate::manifest::FontOptions {
+                            glyphs: Some(fonts::glyph_spec(&self.fonts_pack_chars)),
+                            sizes: vec![size],
+                            ..Default::default()
+                        };
+                        let res = fonts::pack_with(
                             Path::new(&self.fonts_pack_root),
                             Path::new(&self.manifest_path),
-                            size,
-                            &self.fonts_pack_chars,
+                            &defaults,
                         );
                         self.show_feedback("Fonts Pack", res);
                     }
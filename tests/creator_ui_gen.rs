@@ -0,0 +1,30 @@
+//! Compiles the checked-in output of `rlvgl-creator ui-gen` and builds it.
+//!
+//! `tests/data/ui_gen/main.rs` is generated from `main.yml` next to it; the
+//! generator's own tests check that it is current.
+
+// Formatting the module would make it differ from the generator output.
+#[rustfmt::skip]
+#[path = "data/ui_gen/main.rs"]
+mod generated;
+
+use rlvgl_core::widget::Color;
+
+static LOGO: [Color; 4] = [Color(255, 0, 0, 255); 4];
+
+#[test]
+fn generated_screen_builds_with_its_images() {
+    let root =
+        generated::build_main(&|path| (path == "icons/logo.png").then_some((2, 2, &LOGO[..])))
+            .unwrap();
+    assert_eq!(root.widget.borrow().bounds().width, 120);
+    assert_eq!(root.children.len(), 6);
+}
+
+#[test]
+fn generated_screen_reports_unknown_images() {
+    assert_eq!(
+        generated::build_main(&|_| None).err(),
+        Some("icons/logo.png")
+    );
+}
@@ -0,0 +1,67 @@
+//! Screen editor rendering through the rlvgl blitter renderer.
+#![cfg(feature = "creator_ui")]
+
+#[path = "../src/bin/creator_ui/screen_render.rs"]
+mod screen_render;
+
+use rlvgl_platform::PixelFmt;
+use rlvgl_ui::screen::{Kind, Screen};
+use screen_render::{node_at, node_mut, placed_at, render};
+
+const SCREEN: &str = r##"
+name: main
+width: 40
+height: 20
+styles:
+  red: { bg_color: "#ff0000" }
+  blue: { bg_color: "#0000ff" }
+root:
+  type: container
+  style: red
+  children:
+    - type: container
+      style: blue
+      bounds: [30, 10, 20, 20]
+      children:
+        - { type: led, bounds: [2, 2, 4, 4], color: "#00ff00" }
+"##;
+
+fn pixel(rgba: &[u8], width: usize, x: usize, y: usize) -> [u8; 4] {
+    let i = (y * width + x) * 4;
+    rgba[i..i + 4].try_into().unwrap()
+}
+
+#[test]
+fn renders_in_target_format_and_clips_to_screen() {
+    let screen: Screen = serde_yaml::from_str(SCREEN).unwrap();
+    let rgba = render(&screen, PixelFmt::Rgb565, &|_| None).unwrap();
+    assert_eq!(rgba.len(), 40 * 20 * 4);
+    assert_eq!(pixel(&rgba, 40, 0, 0), [248, 0, 0, 255]);
+    // The blue panel hangs off the bottom-right corner and is cut there.
+    assert_eq!(pixel(&rgba, 40, 39, 19), [0, 0, 248, 255]);
+    assert_eq!(pixel(&rgba, 40, 29, 19), [248, 0, 0, 255]);
+
+    let argb = render(&screen, PixelFmt::Argb8888, &|_| None).unwrap();
+    assert_eq!(pixel(&argb, 40, 0, 0), [255, 0, 0, 255]);
+    let mono = render(&screen, PixelFmt::I1, &|_| None).unwrap();
+    assert_eq!(pixel(&mono, 40, 0, 0), [0, 0, 0, 255]);
+}
+
+#[test]
+fn picks_and_edits_nodes_by_path() {
+    let mut screen: Screen = serde_yaml::from_str(SCREEN).unwrap();
+    {
+        let placed = screen.place().unwrap();
+        assert_eq!(node_at(&placed, 33, 13), Some(vec![0, 0]));
+        assert_eq!(node_at(&placed, 31, 11), Some(vec![0]));
+        assert_eq!(node_at(&placed, 1, 1), Some(vec![]));
+        assert_eq!(node_at(&placed, 40, 1), None);
+        assert_eq!(placed_at(&placed, &[0, 0]).unwrap().bounds.x, 32);
+        assert!(placed_at(&placed, &[1]).is_none());
+    }
+    let led = node_mut(&mut screen.root, &[0, 0]).unwrap();
+    assert_eq!(led.kind, Kind::Led);
+    led.bounds = Some([0, 0, 4, 4]);
+    let placed = screen.place().unwrap();
+    assert_eq!(node_at(&placed, 30, 10), Some(vec![0, 0]));
+}
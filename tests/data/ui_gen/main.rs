@@ -0,0 +1,76 @@
+//! Screen `main` generated by rlvgl-creator from `main.yml`. Do not edit.
+
+use rlvgl_core::WidgetNode;
+
+/// Looks up an image asset path, giving `(width, height, pixels)`.
+pub type ImageLookup =
+    dyn Fn(&str) -> Option<(i32, i32, &'static [rlvgl_core::widget::Color])>;
+
+/// Build the `main` screen.
+///
+/// Like `Screen::build`, this fails with the path of the first image
+/// `images` does not resolve.
+pub fn build_main(images: &ImageLookup) -> Result<WidgetNode, &'static str> {
+    Ok(WidgetNode::new(
+        {
+            let mut w = rlvgl_widgets::container::Container::new(rlvgl_core::widget::Rect { x: 0, y: 0, width: 120, height: 80 });
+            w.style = rlvgl_core::style::Style { bg_color: rlvgl_core::widget::Color(32, 32, 32, 255), border_color: rlvgl_core::widget::Color(0, 0, 0, 255), border_width: 1 };
+            w
+        },
+        [
+            WidgetNode::new(
+                // title
+                {
+                    let mut w = rlvgl_widgets::label::Label::new("Hello", rlvgl_core::widget::Rect { x: 2, y: 2, width: 116, height: 12 });
+                    w.style = rlvgl_core::style::Style { bg_color: rlvgl_core::widget::Color(32, 32, 32, 255), border_color: rlvgl_core::widget::Color(0, 0, 0, 255), border_width: 1 };
+                    w.text_color = rlvgl_core::widget::Color(255, 255, 255, 255);
+                    w
+                },
+                Default::default(),
+            ),
+            WidgetNode::new(
+                {
+                    let mut w = rlvgl_widgets::button::Button::new("OK", rlvgl_core::widget::Rect { x: 2, y: 18, width: 40, height: 12 });
+                    *w.style_mut() = rlvgl_core::style::Style { bg_color: rlvgl_core::widget::Color(32, 32, 32, 255), border_color: rlvgl_core::widget::Color(0, 0, 0, 255), border_width: 1 };
+                    w
+                },
+                Default::default(),
+            ),
+            WidgetNode::new(
+                {
+                    let mut w = rlvgl_widgets::checkbox::Checkbox::new("Wi-Fi", rlvgl_core::widget::Rect { x: 2, y: 34, width: 116, height: 10 });
+                    w.check_color = rlvgl_core::widget::Color(0, 255, 0, 255);
+                    w.set_checked(true);
+                    w
+                },
+                Default::default(),
+            ),
+            WidgetNode::new(
+                {
+                    let mut w = rlvgl_widgets::slider::Slider::new(rlvgl_core::widget::Rect { x: 2, y: 48, width: 60, height: 10 }, 0, 100);
+                    w.knob_color = rlvgl_core::widget::Color(255, 0, 0, 255);
+                    w.set_value(40);
+                    w
+                },
+                Default::default(),
+            ),
+            WidgetNode::new(
+                {
+                    let (width, height, pixels) = images("icons/logo.png").ok_or("icons/logo.png")?;
+                    rlvgl_widgets::image::Image::new(rlvgl_core::widget::Rect { x: 100, y: 0, width: 16, height: 16 }, width, height, pixels)
+                },
+                Default::default(),
+            ),
+            WidgetNode::new(
+                {
+                    let mut w = rlvgl_widgets::list::List::new(rlvgl_core::widget::Rect { x: 2, y: 62, width: 116, height: 16 });
+                    w.add_item("one");
+                    w.add_item("two");
+                    w
+                },
+                Default::default(),
+            ),
+        ]
+        .into(),
+    ))
+}
@@ -16,8 +16,11 @@ pub mod event;
 pub mod icon;
 pub mod input;
 pub mod layout;
+pub mod message_box;
 pub mod modal;
 pub mod radio;
+#[cfg(feature = "screen")]
+pub mod screen;
 pub mod style;
 pub mod switch;
 pub mod tag;
@@ -36,6 +39,7 @@ pub use event::{OnClick, Slider};
 pub use icon::{Icon, lookup};
 pub use input::{Input, Textarea};
 pub use layout::{BoxLayout, Grid, HStack, VStack};
+pub use message_box::{MessageBox, MessageBoxResult};
 pub use modal::Modal;
 pub use radio::Radio;
 pub use rlvgl_widgets::button::Button;
@@ -0,0 +1,431 @@
+// SPDX-License-Identifier: MIT
+//! Message box component for rlvgl-ui.
+//!
+//! Built from [`Container`](rlvgl_widgets::container::Container),
+//! [`Label`](rlvgl_widgets::label::Label) and
+//! [`Button`](rlvgl_widgets::button::Button) widgets to provide confirm
+//! dialogs with a title, body text, a row of buttons and an optional close
+//! button. While open, the message box covers the screen with a backdrop and
+//! consumes all pointer and key events so underlying widgets stay inert.
+//!
+//! Because [`WidgetNode`](rlvgl_core::WidgetNode) dispatches events in draw
+//! order, route events to the message box before the rest of the tree:
+//!
+//! ```ignore
+//! if !msgbox.handle_event(&event) {
+//!     root.dispatch_event(&event);
+//! }
+//! ```
+
+use alloc::{boxed::Box, vec::Vec};
+use rlvgl_core::{
+    event::{Event, Key},
+    renderer::Renderer,
+    widget::{Color, Rect, Widget},
+};
+use rlvgl_widgets::{button::Button, container::Container, label::Label};
+
+use crate::icon::lookup;
+
+/// Height of the title row in pixels.
+const TITLE_HEIGHT: i32 = 20;
+/// Height of the button row in pixels.
+const BUTTON_HEIGHT: i32 = 24;
+/// Gap between buttons and around the panel content in pixels.
+const PADDING: i32 = 4;
+
+/// Outcome reported when a message box is dismissed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBoxResult {
+    /// The button with the given index in the button row was pressed.
+    Button(usize),
+    /// The dialog was dismissed with the close button or the Escape key.
+    Closed,
+}
+
+/// Callback type invoked when the message box is dismissed.
+type ResultCallback = Box<dyn FnMut(MessageBoxResult)>;
+
+/// Modal dialog with a title, message and a configurable button row.
+pub struct MessageBox {
+    screen: Rect,
+    /// Color painted over the whole screen while the dialog is open.
+    ///
+    /// Renderers fill rectangles without blending, so a translucent color
+    /// would replace the screen rather than dim it; keep it opaque.
+    pub backdrop_color: Color,
+    panel: Container,
+    title: Label,
+    body: Label,
+    buttons: Vec<Button>,
+    close: Option<Button>,
+    focused: usize,
+    open: bool,
+    result: Option<MessageBoxResult>,
+    on_result: Option<ResultCallback>,
+}
+
+impl MessageBox {
+    /// Create an open message box centered on `screen`.
+    ///
+    /// The panel covers two thirds of the screen width and half of its height
+    /// until overridden with [`panel`](Self::panel).
+    pub fn new(title: &str, text: &str, screen: Rect) -> Self {
+        let width = screen.width * 2 / 3;
+        let height = screen.height / 2;
+        let panel = Rect {
+            x: screen.x + (screen.width - width) / 2,
+            y: screen.y + (screen.height - height) / 2,
+            width,
+            height,
+        };
+        let mut mbox = Self {
+            screen,
+            backdrop_color: Color(48, 48, 48, 255),
+            panel: Container::new(panel),
+            title: Label::new(title, panel),
+            body: Label::new(text, panel),
+            buttons: Vec::new(),
+            close: None,
+            focused: 0,
+            open: true,
+            result: None,
+            on_result: None,
+        };
+        mbox.layout();
+        mbox
+    }
+
+    /// Place the dialog panel at explicit bounds.
+    pub fn panel(mut self, bounds: Rect) -> Self {
+        self.panel = Container::new(bounds);
+        self.layout();
+        self
+    }
+
+    /// Replace the button row with buttons using the given labels.
+    ///
+    /// Buttons share the panel width equally and are reported by index in
+    /// [`MessageBoxResult::Button`].
+    pub fn buttons(mut self, labels: &[&str]) -> Self {
+        let dummy = self.panel.bounds();
+        self.buttons = labels.iter().map(|l| Button::new(*l, dummy)).collect();
+        self.focused = 0;
+        self.layout();
+        self
+    }
+
+    /// Show or hide the close button in the title row.
+    pub fn close_button(mut self, enabled: bool) -> Self {
+        self.close = enabled.then(|| Button::new(lookup("close").unwrap_or("X"), self.screen));
+        self.layout();
+        self
+    }
+
+    /// Register a callback invoked with the result when the dialog closes.
+    pub fn on_result<F: FnMut(MessageBoxResult) + 'static>(mut self, handler: F) -> Self {
+        self.on_result = Some(Box::new(handler));
+        self
+    }
+
+    /// Immutable access to the panel style.
+    pub fn style(&self) -> &rlvgl_core::style::Style {
+        &self.panel.style
+    }
+
+    /// Mutable access to the panel style.
+    pub fn style_mut(&mut self) -> &mut rlvgl_core::style::Style {
+        &mut self.panel.style
+    }
+
+    /// Update the title text.
+    pub fn set_title(&mut self, text: &str) {
+        self.title.set_text(text);
+    }
+
+    /// Retrieve the title text.
+    pub fn title(&self) -> &str {
+        self.title.text()
+    }
+
+    /// Update the body text.
+    pub fn set_text(&mut self, text: &str) {
+        self.body.set_text(text);
+    }
+
+    /// Retrieve the body text.
+    pub fn text(&self) -> &str {
+        self.body.text()
+    }
+
+    /// Labels of the buttons in the button row.
+    pub fn button_labels(&self) -> impl Iterator<Item = &str> {
+        self.buttons.iter().map(|b| b.text())
+    }
+
+    /// Bounds of the button at `index`, if present.
+    pub fn button_bounds(&self, index: usize) -> Option<Rect> {
+        self.buttons.get(index).map(|b| b.bounds())
+    }
+
+    /// Bounds of the close button, if enabled.
+    pub fn close_bounds(&self) -> Option<Rect> {
+        self.close.as_ref().map(|b| b.bounds())
+    }
+
+    /// Index of the button activated by the Enter key.
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Show the dialog again and clear the previous result.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.result = None;
+    }
+
+    /// Whether the dialog is currently shown.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Result of the last dismissal, if any.
+    pub fn result(&self) -> Option<MessageBoxResult> {
+        self.result
+    }
+
+    /// Dismiss the dialog with `result` and notify the callback.
+    pub fn close(&mut self, result: MessageBoxResult) {
+        if !self.open {
+            return;
+        }
+        self.open = false;
+        self.result = Some(result);
+        if let Some(cb) = self.on_result.as_mut() {
+            cb(result);
+        }
+    }
+
+    /// Recompute the bounds of the title, body and buttons from the panel.
+    fn layout(&mut self) {
+        let p = self.panel.bounds();
+        let close_w = if self.close.is_some() {
+            TITLE_HEIGHT
+        } else {
+            0
+        };
+        let title = Rect {
+            x: p.x + PADDING,
+            y: p.y + PADDING,
+            width: p.width - 2 * PADDING - close_w,
+            height: TITLE_HEIGHT,
+        };
+        let row_y = p.y + p.height - PADDING - BUTTON_HEIGHT;
+        let body_y = title.y + TITLE_HEIGHT + PADDING;
+        let body = Rect {
+            x: p.x + PADDING,
+            y: body_y,
+            width: p.width - 2 * PADDING,
+            height: (row_y - PADDING - body_y).max(0),
+        };
+        self.title = relabel(&self.title, title);
+        self.body = relabel(&self.body, body);
+
+        if let Some(close) = self.close.as_mut() {
+            let rect = Rect {
+                x: title.x + title.width,
+                y: title.y,
+                width: close_w,
+                height: TITLE_HEIGHT,
+            };
+            *close = rebutton(close, rect);
+        }
+
+        let count = self.buttons.len() as i32;
+        if count == 0 {
+            return;
+        }
+        let avail = p.width - PADDING * (count + 1);
+        let width = avail / count;
+        for (i, btn) in self.buttons.iter_mut().enumerate() {
+            let rect = Rect {
+                x: p.x + PADDING + i as i32 * (width + PADDING),
+                y: row_y,
+                width,
+                height: BUTTON_HEIGHT,
+            };
+            *btn = rebutton(btn, rect);
+        }
+    }
+}
+
+/// Clone a label's text and style into new bounds.
+fn relabel(label: &Label, bounds: Rect) -> Label {
+    let mut new = Label::new(label.text(), bounds);
+    new.style = label.style;
+    new.text_color = label.text_color;
+    new
+}
+
+/// Clone a button's text and style into new bounds.
+fn rebutton(button: &Button, bounds: Rect) -> Button {
+    let mut new = Button::new(button.text(), bounds);
+    *new.style_mut() = *button.style();
+    new
+}
+
+impl Widget for MessageBox {
+    fn bounds(&self) -> Rect {
+        self.screen
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        if !self.open {
+            return;
+        }
+        renderer.fill_rect(self.screen, self.backdrop_color);
+        self.panel.draw(renderer);
+        self.title.draw(renderer);
+        self.body.draw(renderer);
+        if let Some(close) = &self.close {
+            close.draw(renderer);
+        }
+        for btn in &self.buttons {
+            btn.draw(renderer);
+        }
+    }
+
+    /// Consume every input event while open; ticks pass through.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.open {
+            return false;
+        }
+        match event {
+            Event::Tick => return false,
+            Event::PointerUp { x, y } => {
+                if self
+                    .close
+                    .as_ref()
+                    .is_some_and(|c| c.bounds().contains(*x, *y))
+                {
+                    self.close(MessageBoxResult::Closed);
+                } else if let Some(i) = self
+                    .buttons
+                    .iter()
+                    .position(|b| b.bounds().contains(*x, *y))
+                {
+                    self.focused = i;
+                    self.close(MessageBoxResult::Button(i));
+                }
+            }
+            Event::KeyDown { key } => match key {
+                Key::ArrowLeft if self.focused > 0 => self.focused -= 1,
+                Key::ArrowRight if self.focused + 1 < self.buttons.len() => self.focused += 1,
+                Key::Enter if !self.buttons.is_empty() => {
+                    self.close(MessageBoxResult::Button(self.focused))
+                }
+                Key::Escape if self.close.is_some() => self.close(MessageBoxResult::Closed),
+                _ => {}
+            },
+            _ => {}
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    const SCREEN: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 300,
+        height: 200,
+    };
+
+    fn center(r: Rect) -> (i32, i32) {
+        (r.x + r.width / 2, r.y + r.height / 2)
+    }
+
+    #[test]
+    fn buttons_report_index() {
+        let got = Rc::new(Cell::new(None));
+        let g = got.clone();
+        let mut mbox = MessageBox::new("Reset", "Erase all settings?", SCREEN)
+            .buttons(&["Cancel", "Reset"])
+            .on_result(move |r| g.set(Some(r)));
+        let (x, y) = center(mbox.button_bounds(1).unwrap());
+        assert!(mbox.handle_event(&Event::PointerUp { x, y }));
+        assert_eq!(got.get(), Some(MessageBoxResult::Button(1)));
+        assert!(!mbox.is_open());
+    }
+
+    #[test]
+    fn blocks_input_while_open() {
+        let clicked = Rc::new(Cell::new(false));
+        let c = clicked.clone();
+        let mut under = Button::new("under", SCREEN);
+        under.set_on_click(move |_| c.set(true));
+        let mut mbox = MessageBox::new("t", "b", SCREEN).buttons(&["Ok"]);
+
+        let evt = Event::PointerUp { x: 1, y: 1 };
+        if !mbox.handle_event(&evt) {
+            under.handle_event(&evt);
+        }
+        assert!(!clicked.get());
+        assert!(mbox.is_open());
+        assert!(!mbox.handle_event(&Event::Tick));
+
+        mbox.close(MessageBoxResult::Closed);
+        if !mbox.handle_event(&evt) {
+            under.handle_event(&evt);
+        }
+        assert!(clicked.get());
+    }
+
+    #[test]
+    fn close_button_and_keys() {
+        let mut mbox = MessageBox::new("t", "b", SCREEN)
+            .buttons(&["No", "Yes"])
+            .close_button(true);
+        let (x, y) = center(mbox.close_bounds().unwrap());
+        mbox.handle_event(&Event::PointerUp { x, y });
+        assert_eq!(mbox.result(), Some(MessageBoxResult::Closed));
+
+        mbox.open();
+        mbox.handle_event(&Event::KeyDown {
+            key: Key::ArrowRight,
+        });
+        mbox.handle_event(&Event::KeyDown { key: Key::Enter });
+        assert_eq!(mbox.result(), Some(MessageBoxResult::Button(1)));
+    }
+
+    #[test]
+    fn buttons_fit_inside_panel() {
+        let mbox = MessageBox::new("t", "b", SCREEN).buttons(&["A", "B", "C"]);
+        let first = mbox.button_bounds(0).unwrap();
+        let last = mbox.button_bounds(2).unwrap();
+        assert!(first.x >= SCREEN.width / 6);
+        assert!(last.x + last.width <= SCREEN.width * 5 / 6);
+        assert_eq!(mbox.button_labels().count(), 3);
+    }
+
+    #[test]
+    fn backdrop_is_opaque() {
+        struct Fills(Vec<(Rect, Color)>);
+        impl Renderer for Fills {
+            fn fill_rect(&mut self, rect: Rect, color: Color) {
+                self.0.push((rect, color));
+            }
+            fn draw_text(&mut self, _: (i32, i32), _: &str, _: Color) {}
+        }
+        let mut fills = Fills(Vec::new());
+        MessageBox::new("t", "b", SCREEN).draw(&mut fills);
+        let (rect, color) = fills.0[0];
+        assert_eq!((rect.width, rect.height), (SCREEN.width, SCREEN.height));
+        assert_eq!(color.3, 255);
+    }
+}
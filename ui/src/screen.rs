@@ -0,0 +1,696 @@
+// SPDX-License-Identifier: MIT
+//! Declarative screen descriptions turned into [`WidgetNode`] trees.
+//!
+//! A [`Screen`] names a root [`Node`] whose children are widgets from
+//! [`rlvgl-widgets`](rlvgl_widgets). Each node has explicit `bounds` relative
+//! to its parent or is positioned by the parent's [`Layout`]. Nodes can use
+//! named styles and refer to image assets by path. The types deserialize
+//! from any serde format; `rlvgl-creator ui-gen` compiles YAML files to Rust
+//! and [`Screen::build`] creates the tree at runtime. Enabled via the
+//! optional `screen` feature flag.
+
+use alloc::{
+    collections::BTreeMap,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cell::RefCell, fmt};
+
+use rlvgl_core::{
+    WidgetNode,
+    style::Style,
+    widget::{Color, Rect, Widget},
+};
+use rlvgl_widgets::{
+    button::Button, checkbox::Checkbox, container::Container, image::Image, label::Label, led::Led,
+    list::List, progress::ProgressBar, radio::Radio, slider::Slider, spinner::Spinner,
+    switch::Switch,
+};
+use serde::{Deserialize, Serialize};
+
+/// Errors raised while loading or placing a screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The description could not be parsed.
+    Parse(String),
+    /// A node refers to a style missing from [`Screen::styles`].
+    UnknownStyle(String),
+    /// A color is not `#rrggbb` or `#rrggbbaa`.
+    BadColor(String),
+    /// A node has neither `bounds` nor a parent layout.
+    MissingBounds(String),
+    /// An image asset could not be resolved.
+    UnknownImage(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "invalid screen description: {e}"),
+            Error::UnknownStyle(s) => write!(f, "unknown style `{s}`"),
+            Error::BadColor(c) => write!(f, "invalid color `{c}`"),
+            Error::MissingBounds(n) => write!(f, "`{n}` needs bounds or a parent layout"),
+            Error::UnknownImage(a) => write!(f, "unknown image `{a}`"),
+        }
+    }
+}
+
+/// Top-level screen description.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Screen {
+    /// Screen name, used for generated function names.
+    pub name: String,
+    /// Screen width, the root size when it has no `bounds`.
+    #[serde(default)]
+    pub width: i32,
+    /// Screen height, the root size when it has no `bounds`.
+    #[serde(default)]
+    pub height: i32,
+    /// Named styles nodes may refer to.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub styles: BTreeMap<String, StyleSpec>,
+    /// Root of the widget tree.
+    pub root: Node,
+}
+
+/// Style entry; unset fields keep [`Style::default`] values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StyleSpec {
+    /// Background color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg_color: Option<String>,
+    /// Border color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<String>,
+    /// Border width in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_width: Option<u8>,
+    /// Text color for widgets that draw text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+}
+
+/// Widget type of a [`Node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// [`Container`].
+    Container,
+    /// [`Label`] showing `text`.
+    Label,
+    /// [`Button`] showing `text`.
+    Button,
+    /// [`Checkbox`] with `text` and `checked`.
+    Checkbox,
+    /// [`Radio`] with `text` and `checked`.
+    Radio,
+    /// [`Switch`], on when `checked`.
+    Switch,
+    /// [`Slider`] over `min..=max` at `value`.
+    Slider,
+    /// [`ProgressBar`] over `min..=max` at `value`.
+    Progress,
+    /// [`Led`] lit in `color`.
+    Led,
+    /// [`Spinner`] turning every `period_ms`.
+    Spinner,
+    /// [`List`] of `items`.
+    List,
+    /// [`Image`] of the `asset` image.
+    Image,
+}
+
+impl Kind {
+    /// Every widget type, in declaration order.
+    pub const ALL: [Kind; 12] = [
+        Kind::Container,
+        Kind::Label,
+        Kind::Button,
+        Kind::Checkbox,
+        Kind::Radio,
+        Kind::Switch,
+        Kind::Slider,
+        Kind::Progress,
+        Kind::Led,
+        Kind::Spinner,
+        Kind::List,
+        Kind::Image,
+    ];
+
+    /// Name used in screen files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Container => "container",
+            Kind::Label => "label",
+            Kind::Button => "button",
+            Kind::Checkbox => "checkbox",
+            Kind::Radio => "radio",
+            Kind::Switch => "switch",
+            Kind::Slider => "slider",
+            Kind::Progress => "progress",
+            Kind::Led => "led",
+            Kind::Spinner => "spinner",
+            Kind::List => "list",
+            Kind::Image => "image",
+        }
+    }
+}
+
+/// Direction children of a [`Layout`] are stacked in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// Top to bottom.
+    Column,
+    /// Left to right.
+    Row,
+}
+
+/// Rule placing the children of a node that have no `bounds`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layout {
+    /// Stacking direction.
+    pub direction: Direction,
+    /// Gap between children in pixels.
+    #[serde(default)]
+    pub spacing: i32,
+    /// Inset from every edge of the node in pixels.
+    #[serde(default)]
+    pub padding: i32,
+}
+
+/// Widget entry in a screen description.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Node {
+    /// Widget type.
+    #[serde(rename = "type")]
+    pub kind: Kind,
+    /// Identifier for looking the widget up after [`Screen::build`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `[x, y, width, height]` relative to the parent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[i32; 4]>,
+    /// `[width, height]` inside a parent layout; `0` fills the remaining
+    /// space along that axis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<[i32; 2]>,
+    /// Name of an entry in [`Screen::styles`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// Text of labels, buttons, checkboxes and radios.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Text color, overriding the style's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+    /// Accent color: LED, knob, bar, arc, check mark or radio dot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Initial slider or progress value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<i32>,
+    /// Lower slider or progress bound (default 0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i32>,
+    /// Upper slider or progress bound (default 100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i32>,
+    /// Initial checkbox, radio or switch state.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub checked: bool,
+    /// List entries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<String>,
+    /// Image asset path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    /// Spinner revolution period in milliseconds (default 1000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period_ms: Option<u32>,
+    /// Spinner arc length in degrees (default 90).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arc_length: Option<i32>,
+    /// Placement rule for children without `bounds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Layout>,
+    /// Child widgets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Node of `kind` with no bounds, properties or children.
+    pub fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            id: None,
+            bounds: None,
+            size: None,
+            style: None,
+            text: None,
+            text_color: None,
+            color: None,
+            value: None,
+            min: None,
+            max: None,
+            checked: false,
+            items: Vec::new(),
+            asset: None,
+            period_ms: None,
+            arc_length: None,
+            layout: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn label(&self) -> String {
+        self.id
+            .clone()
+            .unwrap_or_else(|| self.kind.name().to_string())
+    }
+}
+
+/// Node with absolute bounds and resolved colors.
+#[derive(Debug)]
+pub struct Placed<'a> {
+    /// Source description.
+    pub node: &'a Node,
+    /// Absolute bounds on the screen.
+    pub bounds: Rect,
+    /// Named style, if the node has one.
+    pub style: Option<Style>,
+    /// Text color from the node or its style.
+    pub text_color: Option<Color>,
+    /// Accent color from the node.
+    pub color: Option<Color>,
+    /// Placed children.
+    pub children: Vec<Placed<'a>>,
+}
+
+/// Image pixels returned by the asset resolver: width, height and pixels.
+pub type ImageData = (i32, i32, &'static [Color]);
+
+/// Widget tree created by [`Screen::build`].
+pub struct Built {
+    /// Root of the tree.
+    pub root: WidgetNode,
+    /// Widgets with an `id`, by id.
+    pub ids: BTreeMap<String, Rc<RefCell<dyn Widget>>>,
+}
+
+/// Parse `#rrggbb` or `#rrggbbaa`.
+pub fn parse_color(text: &str) -> Result<Color, Error> {
+    let bad = || Error::BadColor(text.to_string());
+    let hex = text.strip_prefix('#').ok_or_else(bad)?;
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return Err(bad());
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| bad());
+    let alpha = if hex.len() == 8 { byte(6)? } else { 255 };
+    Ok(Color(byte(0)?, byte(2)?, byte(4)?, alpha))
+}
+
+fn color(text: &Option<String>) -> Result<Option<Color>, Error> {
+    text.as_deref().map(parse_color).transpose()
+}
+
+impl Screen {
+    /// Parse a JSON screen description.
+    pub fn from_json(text: &str) -> Result<Self, Error> {
+        serde_json::from_str(text).map_err(|e| Error::Parse(e.to_string()))
+    }
+
+    /// Resolve absolute bounds, styles and colors of every node.
+    pub fn place(&self) -> Result<Placed<'_>, Error> {
+        let screen = Rect {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        };
+        self.place_node(&self.root, screen, Some(screen))
+    }
+
+    fn place_node<'a>(
+        &self,
+        node: &'a Node,
+        parent: Rect,
+        slot: Option<Rect>,
+    ) -> Result<Placed<'a>, Error> {
+        let bounds = match (node.bounds, slot) {
+            (Some([x, y, width, height]), _) => Rect {
+                x: parent.x + x,
+                y: parent.y + y,
+                width,
+                height,
+            },
+            (None, Some(slot)) => slot,
+            (None, None) => return Err(Error::MissingBounds(node.label())),
+        };
+        let spec = match &node.style {
+            Some(name) => Some(
+                self.styles
+                    .get(name)
+                    .ok_or_else(|| Error::UnknownStyle(name.clone()))?,
+            ),
+            None => None,
+        };
+        let style = spec.map(style_from_spec).transpose()?;
+        let text_color = match color(&node.text_color)? {
+            Some(c) => Some(c),
+            None => color(&spec.and_then(|s| s.text_color.clone()))?,
+        };
+
+        let mut children = Vec::with_capacity(node.children.len());
+        let mut cursor = 0;
+        for child in &node.children {
+            let slot = match (node.layout, child.bounds) {
+                (Some(layout), None) => {
+                    let slot = layout_slot(bounds, layout, cursor, child.size);
+                    cursor += match layout.direction {
+                        Direction::Column => slot.height,
+                        Direction::Row => slot.width,
+                    } + layout.spacing;
+                    Some(slot)
+                }
+                _ => None,
+            };
+            children.push(self.place_node(child, bounds, slot)?);
+        }
+
+        Ok(Placed {
+            node,
+            bounds,
+            style,
+            text_color,
+            color: color(&node.color)?,
+            children,
+        })
+    }
+
+    /// Create the widget tree. `images` resolves image asset paths.
+    pub fn build(&self, images: &dyn Fn(&str) -> Option<ImageData>) -> Result<Built, Error> {
+        let placed = self.place()?;
+        let mut ids = BTreeMap::new();
+        let root = build_node(&placed, images, &mut ids)?;
+        Ok(Built { root, ids })
+    }
+}
+
+fn style_from_spec(spec: &StyleSpec) -> Result<Style, Error> {
+    let mut style = Style::default();
+    if let Some(c) = color(&spec.bg_color)? {
+        style.bg_color = c;
+    }
+    if let Some(c) = color(&spec.border_color)? {
+        style.border_color = c;
+    }
+    if let Some(w) = spec.border_width {
+        style.border_width = w;
+    }
+    Ok(style)
+}
+
+/// Area for the child at `cursor` along the main axis of `layout`.
+fn layout_slot(parent: Rect, layout: Layout, cursor: i32, size: Option<[i32; 2]>) -> Rect {
+    let [w, h] = size.unwrap_or([0, 0]);
+    let inner_w = parent.width - 2 * layout.padding;
+    let inner_h = parent.height - 2 * layout.padding;
+    let (x, y) = (parent.x + layout.padding, parent.y + layout.padding);
+    let fill = |v: i32, rest: i32| if v > 0 { v } else { rest.max(0) };
+    match layout.direction {
+        Direction::Column => Rect {
+            x,
+            y: y + cursor,
+            width: fill(w, inner_w),
+            height: fill(h, inner_h - cursor),
+        },
+        Direction::Row => Rect {
+            x: x + cursor,
+            y,
+            width: fill(w, inner_w - cursor),
+            height: fill(h, inner_h),
+        },
+    }
+}
+
+fn build_node(
+    placed: &Placed<'_>,
+    images: &dyn Fn(&str) -> Option<ImageData>,
+    ids: &mut BTreeMap<String, Rc<RefCell<dyn Widget>>>,
+) -> Result<WidgetNode, Error> {
+    let node = placed.node;
+    let b = placed.bounds;
+    let text = node.text.clone().unwrap_or_default();
+    let (min, max) = (node.min.unwrap_or(0), node.max.unwrap_or(100));
+    let widget: Rc<RefCell<dyn Widget>> = match node.kind {
+        Kind::Container => {
+            let mut w = Container::new(b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Label => {
+            let mut w = Label::new(text, b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.text_color {
+                w.text_color = c;
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Button => {
+            let mut w = Button::new(text, b);
+            if let Some(s) = placed.style {
+                *w.style_mut() = s;
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Checkbox => {
+            let mut w = Checkbox::new(text, b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.text_color {
+                w.text_color = c;
+            }
+            if let Some(c) = placed.color {
+                w.check_color = c;
+            }
+            w.set_checked(node.checked);
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Radio => {
+            let mut w = Radio::new(text, b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.text_color {
+                w.text_color = c;
+            }
+            if let Some(c) = placed.color {
+                w.dot_color = c;
+            }
+            w.set_selected(node.checked);
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Switch => {
+            let mut w = Switch::new(b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.color {
+                w.knob_color = c;
+            }
+            w.set_on(node.checked);
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Slider => {
+            let mut w = Slider::new(b, min, max);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.color {
+                w.knob_color = c;
+            }
+            if let Some(v) = node.value {
+                w.set_value(v);
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Progress => {
+            let mut w = ProgressBar::new(b, min, max);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.color {
+                w.bar_color = c;
+            }
+            if let Some(v) = node.value {
+                w.set_value(v);
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Led => {
+            let mut w = Led::new(b, placed.color.unwrap_or(Color(0, 255, 0, 255)));
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Spinner => {
+            let mut w = Spinner::new(
+                b,
+                node.period_ms.unwrap_or(1000),
+                node.arc_length.unwrap_or(90),
+            );
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.color {
+                w.arc_color = c;
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::List => {
+            let mut w = List::new(b);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            if let Some(c) = placed.text_color {
+                w.text_color = c;
+            }
+            for item in &node.items {
+                w.add_item(item.clone());
+            }
+            Rc::new(RefCell::new(w))
+        }
+        Kind::Image => {
+            let asset = node.asset.clone().unwrap_or_default();
+            let (width, height, pixels) =
+                images(&asset).ok_or_else(|| Error::UnknownImage(asset.clone()))?;
+            let mut w = Image::new(b, width, height, pixels);
+            if let Some(s) = placed.style {
+                w.style = s;
+            }
+            Rc::new(RefCell::new(w))
+        }
+    };
+    if let Some(id) = &node.id {
+        ids.insert(id.clone(), widget.clone());
+    }
+    let children = placed
+        .children
+        .iter()
+        .map(|c| build_node(c, images, ids))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(WidgetNode { widget, children })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rlvgl_core::event::Event;
+
+    const SCREEN: &str = r##"{
+        "name": "main",
+        "width": 100,
+        "height": 80,
+        "styles": { "card": { "bg_color": "#102030", "text_color": "#ffffffff" } },
+        "root": {
+            "type": "container",
+            "children": [
+                { "type": "label", "id": "title", "text": "Hi", "bounds": [4, 2, 50, 10],
+                  "style": "card" },
+                { "type": "container", "bounds": [0, 20, 100, 60],
+                  "layout": { "direction": "column", "spacing": 2, "padding": 5 },
+                  "children": [
+                      { "type": "button", "id": "ok", "text": "OK", "size": [0, 20] },
+                      { "type": "slider", "size": [40, 8], "value": 30, "color": "#ff0000" },
+                      { "type": "list", "items": ["a", "b"] }
+                  ] }
+            ]
+        }
+    }"##;
+
+    fn rect(r: Rect) -> (i32, i32, i32, i32) {
+        (r.x, r.y, r.width, r.height)
+    }
+
+    #[test]
+    fn places_absolute_and_layout_children() {
+        let screen = Screen::from_json(SCREEN).unwrap();
+        let root = screen.place().unwrap();
+        assert_eq!(rect(root.bounds), (0, 0, 100, 80));
+        let title = &root.children[0];
+        assert_eq!(rect(title.bounds), (4, 2, 50, 10));
+        assert_eq!(title.style.unwrap().bg_color, Color(0x10, 0x20, 0x30, 255));
+        assert_eq!(title.text_color, Some(Color(255, 255, 255, 255)));
+        let column = &root.children[1].children;
+        assert_eq!(rect(column[0].bounds), (5, 25, 90, 20));
+        assert_eq!(rect(column[1].bounds), (5, 47, 40, 8));
+        assert_eq!(rect(column[2].bounds), (5, 57, 90, 18));
+        assert_eq!(column[1].color, Some(Color(255, 0, 0, 255)));
+    }
+
+    #[test]
+    fn builds_tree_with_ids() {
+        let screen = Screen::from_json(SCREEN).unwrap();
+        let mut built = screen.build(&|_| None).unwrap();
+        assert_eq!(built.root.children.len(), 2);
+        assert_eq!(built.root.children[1].children.len(), 3);
+        assert_eq!(rect(built.ids["ok"].borrow().bounds()), (5, 25, 90, 20));
+        let click = Event::PointerUp { x: 10, y: 30 };
+        assert!(built.root.dispatch_event(&click));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            parse_color("#12345"),
+            Err(Error::BadColor("#12345".to_string()))
+        );
+        let mut screen = Screen::from_json(SCREEN).unwrap();
+        screen.root.children[0].style = Some("missing".to_string());
+        assert_eq!(
+            screen.place().unwrap_err(),
+            Error::UnknownStyle("missing".to_string())
+        );
+        screen.root.children[0].style = None;
+        screen.root.children[0].bounds = None;
+        assert_eq!(
+            screen.place().unwrap_err(),
+            Error::MissingBounds("title".to_string())
+        );
+        screen.root.children[0] = Node {
+            kind: Kind::Image,
+            asset: Some("icons/logo.png".to_string()),
+            ..screen.root.children[1].children[0].clone()
+        };
+        screen.root.children[0].bounds = Some([0, 0, 1, 1]);
+        assert!(matches!(
+            screen.build(&|_| None),
+            Err(Error::UnknownImage(a)) if a == "icons/logo.png"
+        ));
+    }
+
+    #[test]
+    fn serializes_only_set_fields() {
+        let mut node = Node::new(Kind::Label);
+        node.text = Some("Hi".to_string());
+        node.bounds = Some([1, 2, 3, 4]);
+        let json = serde_json::to_string(&node).unwrap();
+        assert_eq!(json, r#"{"type":"label","bounds":[1,2,3,4],"text":"Hi"}"#);
+        let back: Node = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.bounds, Some([1, 2, 3, 4]));
+        assert!(back.children.is_empty() && !back.checked);
+    }
+}
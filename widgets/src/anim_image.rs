@@ -0,0 +1,201 @@
+//! Animated image player for GIF, APNG and Dash Lottie frames.
+use rlvgl_core::event::{DEFAULT_TICK_MS, Event};
+use rlvgl_core::frames::FrameSource;
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Rect, Widget};
+
+use crate::shapes::mix;
+
+/// Shortest time a frame is shown; smaller delays are raised to this.
+pub const MIN_FRAME_MS: u32 = 20;
+
+/// Plays a [`FrameSource`] driven by [`Event::Tick`].
+///
+/// Each frame stays visible for its own delay and playback stops after the
+/// source's loop count. Whenever the visible frame changes the widget reports
+/// its bounds through [`Widget::take_dirty`] so only its area is redrawn.
+/// Transparent pixels show `style.bg_color`.
+pub struct AnimImage<S: FrameSource> {
+    bounds: Rect,
+    /// Background shown behind transparent pixels.
+    pub style: Style,
+    source: S,
+    frame: usize,
+    elapsed: u32,
+    tick_ms: u32,
+    playing: bool,
+    plays: u32,
+    dirty: bool,
+}
+
+impl<S: FrameSource> AnimImage<S> {
+    /// Create a player showing the first frame of `source` and start it.
+    pub fn new(bounds: Rect, source: S) -> Self {
+        let mut anim = Self {
+            bounds,
+            style: Style::default(),
+            source,
+            frame: 0,
+            elapsed: 0,
+            tick_ms: DEFAULT_TICK_MS,
+            playing: true,
+            plays: 0,
+            dirty: true,
+        };
+        if !anim.source.seek(0) {
+            anim.playing = false;
+        }
+        anim
+    }
+
+    /// Set the time represented by one [`Event::Tick`] in milliseconds.
+    pub fn set_tick_period(&mut self, ms: u32) {
+        self.tick_ms = ms;
+    }
+
+    /// Resume playback, restarting if the last loop has finished.
+    pub fn play(&mut self) {
+        if self.finished() {
+            self.plays = 0;
+            self.seek(0);
+        }
+        self.playing = self.source.frame_count() > 0;
+    }
+
+    /// Stop advancing frames while keeping the current one visible.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Whether frames are currently advancing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Index of the visible frame.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Number of completed loops since playback started.
+    pub fn plays(&self) -> u32 {
+        self.plays
+    }
+
+    /// Show frame `index` and restart its delay.
+    ///
+    /// Returns `false` and leaves the current frame unchanged if the source
+    /// cannot produce it.
+    pub fn seek(&mut self, index: usize) -> bool {
+        if !self.source.seek(index) {
+            return false;
+        }
+        self.dirty |= index != self.frame;
+        self.frame = index;
+        self.elapsed = 0;
+        true
+    }
+
+    /// Access the frame source.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Mutable access to the frame source.
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Advance playback by `delta_ms` milliseconds.
+    pub fn tick(&mut self, delta_ms: u32) {
+        if !self.playing {
+            return;
+        }
+        self.elapsed = self.elapsed.saturating_add(delta_ms);
+        loop {
+            let delay = self.source.delay_ms(self.frame).max(MIN_FRAME_MS);
+            if self.elapsed < delay {
+                break;
+            }
+            self.elapsed -= delay;
+            let mut next = self.frame + 1;
+            if next >= self.source.frame_count() {
+                self.plays += 1;
+                if self.finished() {
+                    self.playing = false;
+                    self.elapsed = 0;
+                    break;
+                }
+                next = 0;
+            }
+            if !self.source.seek(next) {
+                self.playing = false;
+                break;
+            }
+            self.dirty |= next != self.frame;
+            self.frame = next;
+        }
+    }
+
+    /// Whether every requested loop has been played.
+    fn finished(&self) -> bool {
+        let loops = self.source.loop_count();
+        loops != 0 && self.plays >= loops
+    }
+}
+
+impl<S: FrameSource> Widget for AnimImage<S> {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let bg = self.style.bg_color;
+        renderer.fill_rect(self.bounds, bg);
+        let (fw, fh) = self.source.size();
+        let stride = fw as usize;
+        let w = (fw as i32).min(self.bounds.width);
+        let h = (fh as i32).min(self.bounds.height);
+        let pixels = self.source.pixels();
+        for y in 0..h {
+            let Some(row) = pixels.get(y as usize * stride..y as usize * stride + w as usize)
+            else {
+                break;
+            };
+            // Merge equal neighbours into one rectangle per run.
+            let mut x = 0;
+            while x < w {
+                let px = row[x as usize];
+                let mut end = x + 1;
+                while end < w && row[end as usize] == px {
+                    end += 1;
+                }
+                if px.3 != 0 {
+                    let color = if px.3 == 255 { px } else { mix(px, bg, px.3) };
+                    renderer.fill_rect(
+                        Rect {
+                            x: self.bounds.x + x,
+                            y: self.bounds.y + y,
+                            width: end - x,
+                            height: 1,
+                        },
+                        color,
+                    );
+                }
+                x = end;
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Tick = event {
+            self.tick(self.tick_ms);
+        }
+        false
+    }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+}
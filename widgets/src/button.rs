@@ -54,8 +54,7 @@ impl Button {
 
     /// Check if the given coordinates are inside the button's bounds.
     fn inside_bounds(&self, x: i32, y: i32) -> bool {
-        let b = self.bounds;
-        x >= b.x && x < b.x + b.width && y >= b.y && y < b.y + b.height
+        self.bounds.contains(x, y)
     }
 }
 
@@ -82,4 +81,12 @@ impl Widget for Button {
         }
         false
     }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        self.label.take_dirty()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        Button::set_text(self, text);
+    }
 }
@@ -0,0 +1,421 @@
+//! Month calendar widget and date helpers.
+use alloc::{boxed::Box, format, vec::Vec};
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+/// English month names indexed from January.
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Two-letter weekday names starting on Sunday.
+pub const DAY_NAMES: [&str; 7] = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+
+/// Calendar date in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    /// Full year, e.g. `2024`.
+    pub year: i32,
+    /// Month in the range `1..=12`.
+    pub month: u8,
+    /// Day of the month starting at `1`.
+    pub day: u8,
+}
+
+impl Date {
+    /// Create a date, clamping month and day to valid values.
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        let month = month.clamp(1, 12);
+        let day = day.clamp(1, days_in_month(year, month));
+        Self { year, month, day }
+    }
+
+    /// Day of the week with `0` for Sunday.
+    pub fn weekday(&self) -> u8 {
+        // Sakamoto's method.
+        const T: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let y = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let m = self.month as i32;
+        (y + y.div_euclid(4) - y.div_euclid(100)
+            + y.div_euclid(400)
+            + T[(m - 1) as usize]
+            + self.day as i32)
+            .rem_euclid(7) as u8
+    }
+
+    /// Return the date `days` days later (or earlier when negative).
+    pub fn add_days(self, days: i32) -> Self {
+        let mut d = self;
+        let mut left = days;
+        while left > 0 {
+            if d.day < days_in_month(d.year, d.month) {
+                d.day += 1;
+            } else {
+                d = first_of_next_month(d.year, d.month);
+            }
+            left -= 1;
+        }
+        while left < 0 {
+            if d.day > 1 {
+                d.day -= 1;
+            } else {
+                let (y, m) = prev_month(d.year, d.month);
+                d = Date {
+                    year: y,
+                    month: m,
+                    day: days_in_month(y, m),
+                };
+            }
+            left += 1;
+        }
+        d
+    }
+}
+
+/// Whether `year` is a Gregorian leap year.
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in `month` of `year`.
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn prev_month(year: i32, month: u8) -> (i32, u8) {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+fn next_month(year: i32, month: u8) -> (i32, u8) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
+
+fn first_of_next_month(year: i32, month: u8) -> Date {
+    let (year, month) = next_month(year, month);
+    Date {
+        year,
+        month,
+        day: 1,
+    }
+}
+
+type SelectHandler = Box<dyn FnMut(Date)>;
+
+/// Month grid with navigation header, today marker and highlighted dates.
+///
+/// The header shows the month with previous/next arrows, followed by a row of
+/// weekday names and a 7x6 grid of days. Tapping a day selects it; arrow keys
+/// move the selection and `Enter` confirms it. Each confirmed selection
+/// invokes the handler registered with [`Calendar::set_on_select`].
+pub struct Calendar {
+    bounds: Rect,
+    /// Background style; `border_color` paints the header bar.
+    pub style: Style,
+    /// Color of day numbers and labels.
+    pub text_color: Color,
+    /// Background of today's cell.
+    pub today_color: Color,
+    /// Background of highlighted dates.
+    pub highlight_color: Color,
+    /// Background of the selected date.
+    pub selected_color: Color,
+    /// First column weekday with `0` for Sunday.
+    pub first_weekday: u8,
+    year: i32,
+    month: u8,
+    today: Option<Date>,
+    selected: Option<Date>,
+    highlighted: Vec<Date>,
+    on_select: Option<SelectHandler>,
+}
+
+impl Calendar {
+    /// Create a calendar showing the month containing `today`.
+    pub fn new(bounds: Rect, today: Date) -> Self {
+        Self {
+            bounds,
+            style: Style::default(),
+            text_color: Color(0, 0, 0, 255),
+            today_color: Color(200, 200, 200, 255),
+            highlight_color: Color(255, 220, 120, 255),
+            selected_color: Color(120, 170, 255, 255),
+            first_weekday: 0,
+            year: today.year,
+            month: today.month,
+            today: Some(today),
+            selected: None,
+            highlighted: Vec::new(),
+            on_select: None,
+        }
+    }
+
+    /// Year and month currently displayed.
+    pub fn shown_month(&self) -> (i32, u8) {
+        (self.year, self.month)
+    }
+
+    /// Display a specific month.
+    pub fn show_month(&mut self, year: i32, month: u8) {
+        self.year = year;
+        self.month = month.clamp(1, 12);
+    }
+
+    /// Navigate to the previous month.
+    pub fn prev_month(&mut self) {
+        (self.year, self.month) = prev_month(self.year, self.month);
+    }
+
+    /// Navigate to the next month.
+    pub fn next_month(&mut self) {
+        (self.year, self.month) = next_month(self.year, self.month);
+    }
+
+    /// Date marked as today.
+    pub fn today(&self) -> Option<Date> {
+        self.today
+    }
+
+    /// Change or clear the date marked as today.
+    pub fn set_today(&mut self, today: Option<Date>) {
+        self.today = today;
+    }
+
+    /// Replace the list of highlighted dates.
+    pub fn set_highlighted(&mut self, dates: &[Date]) {
+        self.highlighted = dates.to_vec();
+    }
+
+    /// Dates currently highlighted.
+    pub fn highlighted(&self) -> &[Date] {
+        &self.highlighted
+    }
+
+    /// Currently selected date.
+    pub fn selected(&self) -> Option<Date> {
+        self.selected
+    }
+
+    /// Select a date programmatically and show its month.
+    pub fn set_selected(&mut self, date: Option<Date>) {
+        self.selected = date;
+        if let Some(d) = date {
+            self.show_month(d.year, d.month);
+        }
+    }
+
+    /// Register a callback invoked when the user selects a day.
+    pub fn set_on_select<F: FnMut(Date) + 'static>(&mut self, handler: F) {
+        self.on_select = Some(Box::new(handler));
+    }
+
+    /// Height of the header and weekday rows in pixels.
+    fn header_height(&self) -> i32 {
+        self.bounds.height / 8
+    }
+
+    /// Width of one day column in pixels.
+    fn cell_width(&self) -> i32 {
+        self.bounds.width / 7
+    }
+
+    /// Height of one week row in pixels.
+    fn cell_height(&self) -> i32 {
+        (self.bounds.height - 2 * self.header_height()) / 6
+    }
+
+    /// Column of the first day of the shown month.
+    fn first_column(&self) -> i32 {
+        let wd = Date::new(self.year, self.month, 1).weekday();
+        ((wd + 7 - self.first_weekday % 7) % 7) as i32
+    }
+
+    /// Bounds of the previous-month arrow in the header.
+    pub fn prev_bounds(&self) -> Rect {
+        Rect {
+            x: self.bounds.x,
+            y: self.bounds.y,
+            width: self.cell_width(),
+            height: self.header_height(),
+        }
+    }
+
+    /// Bounds of the next-month arrow in the header.
+    pub fn next_bounds(&self) -> Rect {
+        Rect {
+            x: self.bounds.x + self.bounds.width - self.cell_width(),
+            y: self.bounds.y,
+            width: self.cell_width(),
+            height: self.header_height(),
+        }
+    }
+
+    /// Bounds of the grid cell for `day` in the shown month.
+    pub fn day_bounds(&self, day: u8) -> Option<Rect> {
+        if day == 0 || day > days_in_month(self.year, self.month) {
+            return None;
+        }
+        let slot = self.first_column() + day as i32 - 1;
+        Some(Rect {
+            x: self.bounds.x + (slot % 7) * self.cell_width(),
+            y: self.bounds.y + 2 * self.header_height() + (slot / 7) * self.cell_height(),
+            width: self.cell_width(),
+            height: self.cell_height(),
+        })
+    }
+
+    /// Translate a point to the day of the shown month under it.
+    fn day_at(&self, x: i32, y: i32) -> Option<u8> {
+        let gx = x - self.bounds.x;
+        let gy = y - self.bounds.y - 2 * self.header_height();
+        if gx < 0 || gy < 0 || gx >= 7 * self.cell_width() || gy >= 6 * self.cell_height() {
+            return None;
+        }
+        let slot = (gy / self.cell_height()) * 7 + gx / self.cell_width();
+        let day = slot - self.first_column() + 1;
+        (day >= 1 && day <= days_in_month(self.year, self.month) as i32).then_some(day as u8)
+    }
+
+    /// Select `date`, show its month and notify the handler.
+    fn select(&mut self, date: Date) {
+        self.set_selected(Some(date));
+        if let Some(cb) = self.on_select.as_mut() {
+            cb(date);
+        }
+    }
+}
+
+impl Widget for Calendar {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.style.bg_color);
+
+        let hh = self.header_height();
+        renderer.fill_rect(
+            Rect {
+                x: self.bounds.x,
+                y: self.bounds.y,
+                width: self.bounds.width,
+                height: hh,
+            },
+            self.style.border_color,
+        );
+        let prev = self.prev_bounds();
+        let next = self.next_bounds();
+        renderer.draw_text((prev.x + 2, prev.y + hh), "<", self.text_color);
+        renderer.draw_text((next.x + 2, next.y + hh), ">", self.text_color);
+        let title = format!("{} {}", MONTH_NAMES[self.month as usize - 1], self.year);
+        renderer.draw_text(
+            (self.bounds.x + self.cell_width() + 2, self.bounds.y + hh),
+            &title,
+            self.text_color,
+        );
+
+        let cw = self.cell_width();
+        for col in 0..7 {
+            let name = DAY_NAMES[((col as u8 + self.first_weekday) % 7) as usize];
+            renderer.draw_text(
+                (self.bounds.x + col * cw + 2, self.bounds.y + 2 * hh),
+                name,
+                self.text_color,
+            );
+        }
+
+        for day in 1..=days_in_month(self.year, self.month) {
+            let Some(cell) = self.day_bounds(day) else {
+                continue;
+            };
+            let date = Date {
+                year: self.year,
+                month: self.month,
+                day,
+            };
+            let fill = if self.selected == Some(date) {
+                Some(self.selected_color)
+            } else if self.highlighted.contains(&date) {
+                Some(self.highlight_color)
+            } else if self.today == Some(date) {
+                Some(self.today_color)
+            } else {
+                None
+            };
+            if let Some(color) = fill {
+                renderer.fill_rect(cell, color);
+            }
+            renderer.draw_text(
+                (cell.x + 2, cell.y + cell.height),
+                &format!("{day}"),
+                self.text_color,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::PointerUp { x, y } => {
+                if self.prev_bounds().contains(*x, *y) {
+                    self.prev_month();
+                } else if self.next_bounds().contains(*x, *y) {
+                    self.next_month();
+                } else if let Some(day) = self.day_at(*x, *y) {
+                    self.select(Date::new(self.year, self.month, day));
+                } else {
+                    return false;
+                }
+                true
+            }
+            Event::KeyDown { key } => {
+                let base = self
+                    .selected
+                    .or(self.today)
+                    .unwrap_or(Date::new(self.year, self.month, 1));
+                let target = match key {
+                    Key::ArrowLeft => base.add_days(-1),
+                    Key::ArrowRight => base.add_days(1),
+                    Key::ArrowUp => base.add_days(-7),
+                    Key::ArrowDown => base.add_days(7),
+                    Key::Enter => {
+                        self.select(base);
+                        return true;
+                    }
+                    _ => return false,
+                };
+                self.set_selected(Some(target));
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub struct Checkbox {
     /// Color of the check mark when selected.
     pub check_color: Color,
     checked: bool,
+    dirty: bool,
 }
 
 impl Checkbox {
@@ -28,6 +29,7 @@ impl Checkbox {
             text_color: Color(0, 0, 0, 255),
             check_color: Color(0, 0, 0, 255),
             checked: false,
+            dirty: false,
         }
     }
 
@@ -40,6 +42,17 @@ impl Checkbox {
     pub fn set_checked(&mut self, value: bool) {
         self.checked = value;
     }
+
+    /// Update the label text of the checkbox.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.dirty = true;
+    }
+
+    /// Retrieve the current label text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl Widget for Checkbox {
@@ -81,16 +94,20 @@ impl Widget for Checkbox {
 
     /// Toggle the checked state when clicked.
     fn handle_event(&mut self, event: &Event) -> bool {
-        if let Event::PointerUp { x, y } = event {
-            let inside = *x >= self.bounds.x
-                && *x < self.bounds.x + self.bounds.width
-                && *y >= self.bounds.y
-                && *y < self.bounds.y + self.bounds.height;
-            if inside {
-                self.checked = !self.checked;
-                return true;
-            }
+        if let Event::PointerUp { x, y } = event
+            && self.bounds.contains(*x, *y)
+        {
+            self.checked = !self.checked;
+            return true;
         }
         false
     }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+
+    fn set_text(&mut self, text: &str) {
+        Checkbox::set_text(self, text);
+    }
 }
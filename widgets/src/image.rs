@@ -1,10 +1,22 @@
 //! Simple pixel-buffer image widget.
-use rlvgl_core::event::Event;
+use rlvgl_core::atlas::AtlasRect;
+use rlvgl_core::event::{Event, GesturePhase};
+use rlvgl_core::gesture::SCALE_NONE;
+use rlvgl_core::math::{TRIG_SCALE, cos, sin};
 use rlvgl_core::renderer::Renderer;
 use rlvgl_core::style::Style;
 use rlvgl_core::widget::{Color, Rect, Widget};
 
-/// Display a raw pixel buffer.
+/// Smallest zoom a pinch gesture can reach, a quarter of the original size.
+const MIN_ZOOM: i32 = SCALE_NONE / 4;
+/// Largest zoom a pinch gesture can reach.
+const MAX_ZOOM: i32 = SCALE_NONE * 16;
+
+/// Display a raw pixel buffer, or one sprite of an atlas sheet.
+///
+/// The image can be zoomed and rotated around the center of its bounds,
+/// either directly or, once [`set_gestures`](Self::set_gestures) is
+/// enabled, by two-finger pinch and rotate gestures.
 pub struct Image<'a> {
     bounds: Rect,
     /// Styling for the image background.
@@ -12,6 +24,12 @@ pub struct Image<'a> {
     width: i32,
     height: i32,
     pixels: &'a [Color],
+    region: Option<AtlasRect>,
+    zoom: i32,
+    angle: i32,
+    gestures: bool,
+    /// Zoom and angle when the current gesture started.
+    gesture_base: Option<(i32, i32)>,
 }
 
 impl<'a> Image<'a> {
@@ -23,6 +41,129 @@ impl<'a> Image<'a> {
             width,
             height,
             pixels,
+            region: None,
+            zoom: SCALE_NONE,
+            angle: 0,
+            gestures: false,
+            gesture_base: None,
+        }
+    }
+
+    /// Create an image showing `region` of an atlas sheet whose pixels are
+    /// `pixels`, `width` × `height` in size.
+    pub fn from_atlas(
+        bounds: Rect,
+        width: i32,
+        height: i32,
+        pixels: &'a [Color],
+        region: AtlasRect,
+    ) -> Self {
+        let mut image = Self::new(bounds, width, height, pixels);
+        image.region = Some(region);
+        image
+    }
+
+    /// Show only `region` of the pixel buffer, or the whole buffer for `None`.
+    pub fn set_region(&mut self, region: Option<AtlasRect>) {
+        self.region = region;
+    }
+
+    /// Sprite currently shown, if any.
+    pub fn region(&self) -> Option<AtlasRect> {
+        self.region
+    }
+
+    /// Scale the image by `zoom` / 256, so 256 shows it at its original size.
+    pub fn set_zoom(&mut self, zoom: i32) {
+        self.zoom = zoom.max(1);
+    }
+
+    /// Current zoom in 1/256 units.
+    pub fn zoom(&self) -> i32 {
+        self.zoom
+    }
+
+    /// Rotate the image clockwise by `angle` degrees.
+    pub fn set_angle(&mut self, angle: i32) {
+        self.angle = angle.rem_euclid(360);
+    }
+
+    /// Current clockwise rotation in degrees, `0..360`.
+    pub fn angle(&self) -> i32 {
+        self.angle
+    }
+
+    /// Follow pinch and rotate gestures that start over the image.
+    pub fn set_gestures(&mut self, enabled: bool) {
+        self.gestures = enabled;
+        self.gesture_base = None;
+    }
+
+    /// Color the untransformed image shows at `(x, y)` relative to its
+    /// bounds.
+    fn sample(&self, x: i32, y: i32) -> Option<Color> {
+        let idx = match self.region {
+            Some(region) => {
+                let (x, y) = (x - region.offset_x as i32, y - region.offset_y as i32);
+                if x < 0 || y < 0 || x >= region.width as i32 || y >= region.height as i32 {
+                    return None;
+                }
+                let (sx, sy) = region.sheet_pos(x as u16, y as u16);
+                if sx as i32 >= self.width || sy as i32 >= self.height {
+                    return None;
+                }
+                sy as usize * self.width as usize + sx as usize
+            }
+            None => {
+                if x < 0 || y < 0 || x >= self.width || y >= self.height {
+                    return None;
+                }
+                (y * self.width + x) as usize
+            }
+        };
+        self.pixels.get(idx).copied()
+    }
+
+    /// Draw zoomed and rotated around the center of the bounds by mapping
+    /// each destination pixel back to the nearest source pixel.
+    ///
+    /// Widgets only see the core [`Renderer`], so this cannot use the
+    /// platform's transformed blits, which need a blitter and a `Surface`.
+    fn draw_transformed(&self, renderer: &mut dyn Renderer) {
+        let (w, h) = (self.bounds.width as i64, self.bounds.height as i64);
+        let (sin, cos) = (sin(self.angle) as i64, cos(self.angle) as i64);
+        let denom = 2 * TRIG_SCALE as i64 * self.zoom as i64;
+        for y in 0..self.bounds.height {
+            for x in 0..self.bounds.width {
+                // Pixel centers relative to the pivot, in half pixels.
+                let dx = 2 * x as i64 + 1 - w;
+                let dy = 2 * y as i64 + 1 - h;
+                let sx = (dx * cos + dy * sin) * SCALE_NONE as i64;
+                let sy = (dy * cos - dx * sin) * SCALE_NONE as i64;
+                let sx = (sx + w * denom / 2).div_euclid(denom) as i32;
+                let sy = (sy + h * denom / 2).div_euclid(denom) as i32;
+                if let Some(color) = self.sample(sx, sy) {
+                    let pixel_rect = Rect {
+                        x: self.bounds.x + x,
+                        y: self.bounds.y + y,
+                        width: 1,
+                        height: 1,
+                    };
+                    renderer.fill_rect(pixel_rect, color);
+                }
+            }
+        }
+    }
+
+    fn draw_pixel(&self, renderer: &mut dyn Renderer, x: i32, y: i32, idx: usize) {
+        if let Some(color) = self.pixels.get(idx).copied() {
+            let pixel_rect = Rect {
+                x: self.bounds.x + x,
+                y: self.bounds.y + y,
+                width: 1,
+                height: 1,
+            };
+            renderer.fill_rect(pixel_rect, color);
         }
     }
 }
@@ -34,24 +175,54 @@ impl<'a> Widget for Image<'a> {
 
     fn draw(&self, renderer: &mut dyn Renderer) {
         renderer.fill_rect(self.bounds, self.style.bg_color);
+        if self.zoom != SCALE_NONE || self.angle != 0 {
+            self.draw_transformed(renderer);
+            return;
+        }
+        if let Some(region) = self.region {
+            // Trimmed borders stay background; the sprite sits at its offset.
+            for y in 0..region.height {
+                for x in 0..region.width {
+                    let (sx, sy) = region.sheet_pos(x, y);
+                    if sx as i32 >= self.width || sy as i32 >= self.height {
+                        continue;
+                    }
+                    let idx = sy as usize * self.width as usize + sx as usize;
+                    let (dx, dy) = (region.offset_x + x, region.offset_y + y);
+                    self.draw_pixel(renderer, dx as i32, dy as i32, idx);
+                }
+            }
+            return;
+        }
         for y in 0..self.height {
             for x in 0..self.width {
                 let idx = (y * self.width + x) as usize;
-                if let Some(color) = self.pixels.get(idx).copied() {
-                    let pixel_rect = Rect {
-                        x: self.bounds.x + x,
-                        y: self.bounds.y + y,
-                        width: 1,
-                        height: 1,
-                    };
-                    renderer.fill_rect(pixel_rect, color);
-                }
+                self.draw_pixel(renderer, x, y, idx);
             }
         }
     }
 
-    /// Images are purely visual and do not handle events.
-    fn handle_event(&mut self, _event: &Event) -> bool {
-        false
+    /// Images only handle gestures, and only when enabled.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Gesture(gesture) = event else {
+            return false;
+        };
+        if !self.gestures {
+            return false;
+        }
+        if gesture.phase == GesturePhase::Start {
+            let inside = self.bounds.contains(gesture.x, gesture.y);
+            self.gesture_base = inside.then_some((self.zoom, self.angle));
+        }
+        let Some((zoom, angle)) = self.gesture_base else {
+            return false;
+        };
+        self.zoom = (zoom as i64 * gesture.scale as i64 / SCALE_NONE as i64)
+            .clamp(MIN_ZOOM as i64, MAX_ZOOM as i64) as i32;
+        self.set_angle(angle + gesture.angle);
+        if gesture.phase == GesturePhase::End {
+            self.gesture_base = None;
+        }
+        true
     }
 }
@@ -13,6 +13,7 @@ pub struct Label {
     pub style: Style,
     /// Color used to render the text.
     pub text_color: Color,
+    dirty: bool,
 }
 
 impl Label {
@@ -23,12 +24,14 @@ impl Label {
             text: text.into(),
             style: Style::default(),
             text_color: Color(0, 0, 0, 255),
+            dirty: false,
         }
     }
 
     /// Update the text displayed by the label.
     pub fn set_text(&mut self, text: impl Into<String>) {
         self.text = text.into();
+        self.dirty = true;
     }
 
     /// Retrieve the current label text.
@@ -54,4 +57,12 @@ impl Widget for Label {
     fn handle_event(&mut self, _event: &Event) -> bool {
         false
     }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+
+    fn set_text(&mut self, text: &str) {
+        Label::set_text(self, text);
+    }
 }
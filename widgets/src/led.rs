@@ -0,0 +1,113 @@
+//! Round LED indicator widget.
+use rlvgl_core::event::Event;
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+use crate::shapes::{fill_circle, mix};
+
+/// Lowest brightness actually rendered so an "off" LED stays visible.
+pub const BRIGHT_MIN: u8 = 80;
+/// Brightness used by [`Led::on`].
+pub const BRIGHT_MAX: u8 = 255;
+
+/// Colored indicator light with adjustable brightness and glow.
+pub struct Led {
+    bounds: Rect,
+    /// Background style; the glow fades into `bg_color`.
+    pub style: Style,
+    /// Color of the LED at full brightness.
+    pub color: Color,
+    /// Maximum width of the glow halo in pixels.
+    pub glow: i32,
+    brightness: u8,
+}
+
+impl Led {
+    /// Create a new LED that is switched on.
+    pub fn new(bounds: Rect, color: Color) -> Self {
+        Self {
+            bounds,
+            style: Style::default(),
+            color,
+            glow: 4,
+            brightness: BRIGHT_MAX,
+        }
+    }
+
+    /// Current brightness in the range `0..=255`.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Set the brightness; `0` is off and `255` fully lit.
+    pub fn set_brightness(&mut self, value: u8) {
+        self.brightness = value;
+    }
+
+    /// Switch the LED fully on.
+    pub fn on(&mut self) {
+        self.brightness = BRIGHT_MAX;
+    }
+
+    /// Switch the LED off.
+    pub fn off(&mut self) {
+        self.brightness = 0;
+    }
+
+    /// Toggle between on and off.
+    pub fn toggle(&mut self) {
+        if self.is_on() {
+            self.off();
+        } else {
+            self.on();
+        }
+    }
+
+    /// Whether the brightness is above the dim threshold.
+    pub fn is_on(&self) -> bool {
+        self.brightness > BRIGHT_MIN
+    }
+
+    /// Brightness mapped into the rendered `BRIGHT_MIN..=BRIGHT_MAX` range.
+    fn level(&self) -> u8 {
+        let span = (BRIGHT_MAX - BRIGHT_MIN) as u16;
+        BRIGHT_MIN + (self.brightness as u16 * span / 255) as u8
+    }
+}
+
+impl Widget for Led {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.style.bg_color);
+
+        let cx = self.bounds.x + self.bounds.width / 2;
+        let cy = self.bounds.y + self.bounds.height / 2;
+        let radius = self.bounds.width.min(self.bounds.height) / 2;
+        let glow = self.glow.clamp(0, radius / 2) * self.brightness as i32 / 255;
+        let core = radius - self.glow.clamp(0, radius / 2);
+
+        // Paint the halo from the outside in so inner rings cover outer ones.
+        for i in (1..=glow).rev() {
+            let weight = (self.brightness as i32 * (glow - i + 1) / (2 * (glow + 1))) as u8;
+            fill_circle(
+                renderer,
+                cx,
+                cy,
+                core + i,
+                mix(self.color, self.style.bg_color, weight),
+            );
+        }
+
+        let body = mix(self.color, Color(0, 0, 0, self.color.3), self.level());
+        fill_circle(renderer, cx, cy, core, body);
+    }
+
+    /// LEDs are display only and ignore events.
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+}
@@ -4,8 +4,12 @@
 
 extern crate alloc;
 
+/// Animated image player widget.
+pub mod anim_image;
 /// Clickable button widget.
 pub mod button;
+/// Month calendar widget and date helpers.
+pub mod calendar;
 /// Checkbox widget for boolean options.
 pub mod checkbox;
 /// Container widget for layout grouping.
@@ -14,13 +18,28 @@ pub mod container;
 pub mod image;
 /// Text label widget.
 pub mod label;
+/// Round LED indicator widget.
+pub mod led;
 /// Scrollable list widget.
 pub mod list;
+/// Lottie animation player widget.
+#[cfg(feature = "lottie")]
+pub mod lottie;
+/// Date and time pickers built from rollers.
+pub mod picker;
 /// Progress bar widget.
 pub mod progress;
 /// Radio button widget for mutually exclusive options.
 pub mod radio;
+/// Scrollable option roller widget.
+pub mod roller;
+/// Circle and arc drawing helpers.
+mod shapes;
 /// Slider widget for numeric input.
 pub mod slider;
+/// Numeric spinbox with digit editing.
+pub mod spinbox;
+/// Animated loading spinner widget.
+pub mod spinner;
 /// Binary on/off switch widget.
 pub mod switch;
@@ -0,0 +1,259 @@
+//! Lottie animation player widget.
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use rlvgl_core::event::{DEFAULT_TICK_MS, Event};
+use rlvgl_core::lottie::{Document, Error};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+use crate::shapes::mix;
+
+/// Lottie animation widget.
+///
+/// The document is parsed once and frames are rendered at the widget's size
+/// as ticks advance playback at the file's frame rate scaled by the speed.
+/// Rendered frames are kept in a cache limited to a byte budget so short
+/// looping animations only render each frame once. Partially transparent
+/// pixels are blended over `style.bg_color`.
+pub struct Lottie {
+    bounds: Rect,
+    /// Background drawn behind transparent pixels.
+    pub style: Style,
+    doc: Document,
+    pixels: Vec<Color>,
+    fps: f64,
+    total: usize,
+    segment: (usize, usize),
+    /// Milliseconds of playback into the segment, already scaled by speed.
+    elapsed: f64,
+    speed: f64,
+    looping: bool,
+    playing: bool,
+    frame: usize,
+    tick_ms: u32,
+    cache: VecDeque<(usize, Vec<Color>)>,
+    cache_budget: usize,
+    dirty: bool,
+}
+
+impl Lottie {
+    /// Parse `json` and create a looping player rendering at `bounds` size.
+    pub fn from_json(bounds: Rect, json: &str) -> Result<Self, Error> {
+        let size = (bounds.width.max(0) as usize, bounds.height.max(0) as usize);
+        let doc = Document::from_json(json, size.0, size.1)?;
+        let total = doc.total_frames();
+        let fps = doc.frame_rate();
+        let mut lottie = Self {
+            bounds,
+            style: Style::default(),
+            doc,
+            pixels: Vec::new(),
+            fps: if fps > 0.0 { fps } else { 30.0 },
+            total,
+            segment: (0, total),
+            elapsed: 0.0,
+            speed: 1.0,
+            looping: true,
+            playing: true,
+            frame: 0,
+            tick_ms: DEFAULT_TICK_MS,
+            cache: VecDeque::new(),
+            cache_budget: 0,
+            dirty: true,
+        };
+        lottie.render(0);
+        Ok(lottie)
+    }
+
+    /// Total number of frames in the document.
+    pub fn total_frames(&self) -> usize {
+        self.total
+    }
+
+    /// Frame rate declared by the document.
+    pub fn frame_rate(&self) -> f64 {
+        self.fps
+    }
+
+    /// Frame currently shown.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Set the time represented by one [`Event::Tick`] in milliseconds.
+    pub fn set_tick_period(&mut self, ms: u32) {
+        self.tick_ms = ms;
+    }
+
+    /// Resume playback, restarting the segment if it already finished.
+    pub fn play(&mut self) {
+        if !self.looping && self.frame + 1 >= self.segment.1 {
+            self.seek(self.segment.0);
+        }
+        self.playing = true;
+    }
+
+    /// Stop advancing while keeping the current frame visible.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Whether playback is advancing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Repeat the segment instead of stopping on its last frame.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Playback speed multiplier; `1.0` follows the document's frame rate.
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Change the playback speed; values are clamped to at least `0.01`.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.max(0.01);
+    }
+
+    /// Restrict playback to frames `start..end` and jump to `start`.
+    pub fn set_segment(&mut self, start: usize, end: usize) {
+        let end = end.min(self.total);
+        let start = start.min(end.saturating_sub(1));
+        self.segment = (start, end.max(start + 1));
+        self.seek(start);
+    }
+
+    /// Current segment as `(start, end)` with `end` exclusive.
+    pub fn segment(&self) -> (usize, usize) {
+        self.segment
+    }
+
+    /// Show `frame`, clamped to the segment, and restart timing from it.
+    pub fn seek(&mut self, frame: usize) {
+        let frame = frame.clamp(self.segment.0, self.segment.1.saturating_sub(1));
+        self.elapsed = (frame - self.segment.0) as f64 * 1000.0 / self.fps;
+        self.render(frame);
+    }
+
+    /// Limit the frame cache to roughly `bytes` of pixel data.
+    ///
+    /// A budget smaller than one frame disables caching.
+    pub fn set_cache_budget(&mut self, bytes: usize) {
+        self.cache_budget = bytes;
+        self.trim_cache();
+    }
+
+    /// Number of frames currently cached.
+    pub fn cached_frames(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Pixels of the current frame in row-major order with straight alpha.
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Advance playback by `delta_ms` milliseconds.
+    pub fn tick(&mut self, delta_ms: u32) {
+        if !self.playing {
+            return;
+        }
+        let (start, end) = self.segment;
+        let span = (end - start) as f64 * 1000.0 / self.fps;
+        self.elapsed += delta_ms as f64 * self.speed;
+        if self.elapsed >= span {
+            if self.looping && span > 0.0 {
+                self.elapsed %= span;
+            } else {
+                self.elapsed = span;
+                self.playing = false;
+            }
+        }
+        let offset = (self.elapsed * self.fps / 1000.0) as usize;
+        self.render((start + offset).min(end.saturating_sub(1)));
+    }
+
+    /// Bytes used by one rendered frame.
+    fn frame_bytes(&self) -> usize {
+        self.bounds.width.max(0) as usize * self.bounds.height.max(0) as usize * 4
+    }
+
+    /// Drop the oldest cached frames until the cache fits the budget.
+    fn trim_cache(&mut self) {
+        let max = self.cache_budget / self.frame_bytes().max(1);
+        while self.cache.len() > max {
+            self.cache.pop_front();
+        }
+    }
+
+    /// Make `frame` current, using the cache when possible.
+    fn render(&mut self, frame: usize) {
+        if frame == self.frame && !self.pixels.is_empty() {
+            return;
+        }
+        self.frame = frame;
+        self.dirty = true;
+        if let Some((_, px)) = self.cache.iter().find(|(f, _)| *f == frame) {
+            self.pixels.clone_from(px);
+            return;
+        }
+        self.doc.render_into(frame, &mut self.pixels);
+        if self.frame_bytes() <= self.cache_budget {
+            self.cache.push_back((frame, self.pixels.clone()));
+            self.trim_cache();
+        }
+    }
+}
+
+impl Widget for Lottie {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let bg = self.style.bg_color;
+        renderer.fill_rect(self.bounds, bg);
+        let w = self.bounds.width.max(0) as usize;
+        if w == 0 {
+            return;
+        }
+        for (y, row) in self.pixels.chunks_exact(w).enumerate() {
+            // Merge equal neighbours into one rectangle per run.
+            let mut x = 0;
+            while x < w {
+                let px = row[x];
+                let mut end = x + 1;
+                while end < w && row[end] == px {
+                    end += 1;
+                }
+                if px.3 != 0 {
+                    renderer.fill_rect(
+                        Rect {
+                            x: self.bounds.x + x as i32,
+                            y: self.bounds.y + y as i32,
+                            width: (end - x) as i32,
+                            height: 1,
+                        },
+                        mix(px, bg, px.3),
+                    );
+                }
+                x = end;
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Tick = event {
+            self.tick(self.tick_ms);
+        }
+        false
+    }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+}
@@ -0,0 +1,267 @@
+//! Date and time pickers assembled from [`Roller`](crate::roller::Roller)s.
+use alloc::{boxed::Box, format, vec::Vec};
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Rect, Widget};
+
+use crate::calendar::{Date, MONTH_NAMES, days_in_month};
+use crate::roller::Roller;
+
+/// Number of rows shown by picker rollers.
+const VISIBLE_ROWS: i32 = 3;
+
+/// Wall-clock time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    /// Hour in the range `0..=23`.
+    pub hour: u8,
+    /// Minute in the range `0..=59`.
+    pub minute: u8,
+}
+
+/// Split `bounds` into `n` equal columns.
+fn columns(bounds: Rect, n: i32) -> Vec<Rect> {
+    let w = bounds.width / n;
+    (0..n)
+        .map(|i| Rect {
+            x: bounds.x + i * w,
+            y: bounds.y,
+            width: w,
+            height: bounds.height,
+        })
+        .collect()
+}
+
+/// Route `event` to the rollers of a picker.
+///
+/// Pointer events go to the roller under the pointer, which becomes active.
+/// `ArrowLeft`/`ArrowRight` switch the active roller and other keys are
+/// forwarded to it.
+fn route(rollers: &mut [&mut Roller], active: &mut usize, event: &Event) -> bool {
+    match event {
+        Event::KeyDown {
+            key: Key::ArrowLeft,
+        } => {
+            *active = active.saturating_sub(1);
+            true
+        }
+        Event::KeyDown {
+            key: Key::ArrowRight,
+        } => {
+            *active = (*active + 1).min(rollers.len() - 1);
+            true
+        }
+        Event::KeyDown { .. } | Event::KeyUp { .. } => rollers[*active].handle_event(event),
+        _ => {
+            for (i, roller) in rollers.iter_mut().enumerate() {
+                if roller.handle_event(event) {
+                    *active = i;
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Year, month and day rollers side by side.
+///
+/// `ArrowLeft`/`ArrowRight` pick a roller and `ArrowUp`/`ArrowDown` change
+/// it. The day roller is rebuilt whenever the year or month changes so it only
+/// offers valid days.
+pub struct DatePicker {
+    bounds: Rect,
+    first_year: i32,
+    year: Roller,
+    month: Roller,
+    day: Roller,
+    active: usize,
+    on_change: Option<Box<dyn FnMut(Date)>>,
+}
+
+impl DatePicker {
+    /// Create a picker for years `first_year..=last_year` showing `date`.
+    pub fn new(bounds: Rect, first_year: i32, last_year: i32, date: Date) -> Self {
+        let cols = columns(bounds, 3);
+        let mut year = Roller::new(cols[0], VISIBLE_ROWS);
+        year.set_options((first_year..=last_year.max(first_year)).map(|y| format!("{y}")));
+        let mut month = Roller::new(cols[1], VISIBLE_ROWS);
+        month.set_options(MONTH_NAMES.iter().map(|m| &m[..3]));
+        month.infinite = true;
+        let mut day = Roller::new(cols[2], VISIBLE_ROWS);
+        day.infinite = true;
+        let mut picker = Self {
+            bounds,
+            first_year,
+            year,
+            month,
+            day,
+            active: 0,
+            on_change: None,
+        };
+        picker.set_date(date);
+        picker
+    }
+
+    /// Currently selected date.
+    pub fn date(&self) -> Date {
+        Date::new(
+            self.first_year + self.year.selected() as i32,
+            self.month.selected() as u8 + 1,
+            self.day.selected() as u8 + 1,
+        )
+    }
+
+    /// Show `date`, clamped to the configured year range. Out-of-range
+    /// months and days are clamped as by [`Date::new`].
+    pub fn set_date(&mut self, date: Date) {
+        let date = Date::new(date.year, date.month, date.day);
+        self.year
+            .set_selected((date.year - self.first_year).max(0) as usize);
+        self.month.set_selected(date.month as usize - 1);
+        self.refresh_days();
+        self.day.set_selected(date.day as usize - 1);
+    }
+
+    /// Register a callback invoked when the user changes the date.
+    pub fn set_on_change<F: FnMut(Date) + 'static>(&mut self, handler: F) {
+        self.on_change = Some(Box::new(handler));
+    }
+
+    /// Access the year, month and day rollers for styling.
+    pub fn rollers_mut(&mut self) -> [&mut Roller; 3] {
+        [&mut self.year, &mut self.month, &mut self.day]
+    }
+
+    /// Rebuild the day options for the selected year and month.
+    fn refresh_days(&mut self) {
+        let year = self.first_year + self.year.selected() as i32;
+        let days = days_in_month(year, self.month.selected() as u8 + 1);
+        if self.day.options().len() != days as usize {
+            self.day.set_options((1..=days).map(|d| format!("{d}")));
+        }
+    }
+}
+
+impl Widget for DatePicker {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        self.year.draw(renderer);
+        self.month.draw(renderer);
+        self.day.draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let before = self.date();
+        let handled = route(
+            &mut [&mut self.year, &mut self.month, &mut self.day],
+            &mut self.active,
+            event,
+        );
+        self.refresh_days();
+        let after = self.date();
+        if !handled || after == before {
+            return handled;
+        }
+        if let Some(cb) = self.on_change.as_mut() {
+            cb(after);
+        }
+        handled
+    }
+}
+
+/// Hour and minute rollers side by side.
+///
+/// Keys are routed like [`DatePicker`]'s.
+pub struct TimePicker {
+    bounds: Rect,
+    minute_step: u8,
+    hour: Roller,
+    minute: Roller,
+    active: usize,
+    on_change: Option<Box<dyn FnMut(Time)>>,
+}
+
+impl TimePicker {
+    /// Create a 24-hour picker offering minutes in multiples of `minute_step`.
+    pub fn new(bounds: Rect, minute_step: u8, time: Time) -> Self {
+        let minute_step = minute_step.clamp(1, 30);
+        let cols = columns(bounds, 2);
+        let mut hour = Roller::new(cols[0], VISIBLE_ROWS);
+        hour.set_options((0..24).map(|h| format!("{h:02}")));
+        hour.infinite = true;
+        let mut minute = Roller::new(cols[1], VISIBLE_ROWS);
+        minute.set_options(
+            (0..60)
+                .step_by(minute_step as usize)
+                .map(|m| format!("{m:02}")),
+        );
+        minute.infinite = true;
+        let mut picker = Self {
+            bounds,
+            minute_step,
+            hour,
+            minute,
+            active: 0,
+            on_change: None,
+        };
+        picker.set_time(time);
+        picker
+    }
+
+    /// Currently selected time.
+    pub fn time(&self) -> Time {
+        Time {
+            hour: self.hour.selected() as u8,
+            minute: self.minute.selected() as u8 * self.minute_step,
+        }
+    }
+
+    /// Show `time`, rounding minutes down to the configured step.
+    pub fn set_time(&mut self, time: Time) {
+        self.hour.set_selected(time.hour.min(23) as usize);
+        self.minute
+            .set_selected((time.minute.min(59) / self.minute_step) as usize);
+    }
+
+    /// Register a callback invoked when the user changes the time.
+    pub fn set_on_change<F: FnMut(Time) + 'static>(&mut self, handler: F) {
+        self.on_change = Some(Box::new(handler));
+    }
+
+    /// Access the hour and minute rollers for styling.
+    pub fn rollers_mut(&mut self) -> [&mut Roller; 2] {
+        [&mut self.hour, &mut self.minute]
+    }
+}
+
+impl Widget for TimePicker {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        self.hour.draw(renderer);
+        self.minute.draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let before = self.time();
+        let handled = route(
+            &mut [&mut self.hour, &mut self.minute],
+            &mut self.active,
+            event,
+        );
+        let after = self.time();
+        if !handled || after == before {
+            return handled;
+        }
+        if let Some(cb) = self.on_change.as_mut() {
+            cb(after);
+        }
+        handled
+    }
+}
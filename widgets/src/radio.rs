@@ -18,6 +18,7 @@ pub struct Radio {
     /// Color of the inner dot when selected.
     pub dot_color: Color,
     selected: bool,
+    dirty: bool,
 }
 
 impl Radio {
@@ -30,6 +31,7 @@ impl Radio {
             text_color: Color(0, 0, 0, 255),
             dot_color: Color(0, 0, 0, 255),
             selected: false,
+            dirty: false,
         }
     }
 
@@ -42,6 +44,17 @@ impl Radio {
     pub fn set_selected(&mut self, value: bool) {
         self.selected = value;
     }
+
+    /// Update the label text of the radio button.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.dirty = true;
+    }
+
+    /// Retrieve the current label text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl Widget for Radio {
@@ -79,18 +92,22 @@ impl Widget for Radio {
     }
 
     fn handle_event(&mut self, event: &Event) -> bool {
-        if let Event::PointerUp { x, y } = event {
-            let inside = *x >= self.bounds.x
-                && *x < self.bounds.x + self.bounds.width
-                && *y >= self.bounds.y
-                && *y < self.bounds.y + self.bounds.height;
-            if inside {
-                self.selected = !self.selected;
-                return true;
-            }
+        if let Event::PointerUp { x, y } = event
+            && self.bounds.contains(*x, *y)
+        {
+            self.selected = !self.selected;
+            return true;
         }
         false
     }
+
+    fn take_dirty(&mut self) -> Option<Rect> {
+        core::mem::take(&mut self.dirty).then_some(self.bounds)
+    }
+
+    fn set_text(&mut self, text: &str) {
+        Radio::set_text(self, text);
+    }
 }
 
 #[cfg(test)]
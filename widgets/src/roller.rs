@@ -0,0 +1,196 @@
+//! Scrollable option roller.
+use alloc::{boxed::Box, string::String, vec::Vec};
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+type ChangeHandler = Box<dyn FnMut(usize)>;
+
+/// Vertical drum of options with the selected one centered.
+///
+/// Tapping a row above or below the center selects it, dragging scrolls by
+/// whole rows and `ArrowUp`/`ArrowDown` step through the options. In
+/// infinite mode the options wrap around at both ends.
+pub struct Roller {
+    bounds: Rect,
+    /// Background style; `border_color` fills the selection band.
+    pub style: Style,
+    /// Color used for option text.
+    pub text_color: Color,
+    /// Color used for the selected option text.
+    pub selected_text_color: Color,
+    /// Wrap around at both ends instead of stopping.
+    pub infinite: bool,
+    options: Vec<String>,
+    selected: usize,
+    visible: i32,
+    drag_y: Option<i32>,
+    dragged: bool,
+    on_change: Option<ChangeHandler>,
+}
+
+impl Roller {
+    /// Create a roller showing `visible` rows; even counts are rounded up.
+    pub fn new(bounds: Rect, visible: i32) -> Self {
+        Self {
+            bounds,
+            style: Style::default(),
+            text_color: Color(0, 0, 0, 255),
+            selected_text_color: Color(255, 255, 255, 255),
+            infinite: false,
+            options: Vec::new(),
+            selected: 0,
+            visible: visible.max(1) | 1,
+            drag_y: None,
+            dragged: false,
+            on_change: None,
+        }
+    }
+
+    /// Replace all options and keep the selection in range.
+    pub fn set_options<I, S>(&mut self, options: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options = options.into_iter().map(Into::into).collect();
+        self.selected = self.selected.min(self.options.len().saturating_sub(1));
+    }
+
+    /// Return all options.
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Index of the selected option.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Text of the selected option, if any options exist.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    /// Select an option without invoking the change handler.
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.options.len().saturating_sub(1));
+    }
+
+    /// Register a callback invoked when the user changes the selection.
+    pub fn set_on_change<F: FnMut(usize) + 'static>(&mut self, handler: F) {
+        self.on_change = Some(Box::new(handler));
+    }
+
+    /// Height of one option row in pixels.
+    fn row_height(&self) -> i32 {
+        (self.bounds.height / self.visible).max(1)
+    }
+
+    /// Move the selection by `delta` rows and notify on change.
+    fn shift(&mut self, delta: i32) {
+        let len = self.options.len() as i32;
+        if len == 0 || delta == 0 {
+            return;
+        }
+        let target = self.selected as i32 + delta;
+        let next = if self.infinite {
+            target.rem_euclid(len)
+        } else {
+            target.clamp(0, len - 1)
+        } as usize;
+        if next != self.selected {
+            self.selected = next;
+            if let Some(cb) = self.on_change.as_mut() {
+                cb(next);
+            }
+        }
+    }
+}
+
+impl Widget for Roller {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.style.bg_color);
+        let row_h = self.row_height();
+        let mid = self.visible / 2;
+        renderer.fill_rect(
+            Rect {
+                x: self.bounds.x,
+                y: self.bounds.y + mid * row_h,
+                width: self.bounds.width,
+                height: row_h,
+            },
+            self.style.border_color,
+        );
+        let len = self.options.len() as i32;
+        for row in 0..self.visible {
+            let idx = self.selected as i32 + row - mid;
+            let idx = if self.infinite && len > 0 {
+                idx.rem_euclid(len)
+            } else if idx < 0 || idx >= len {
+                continue;
+            } else {
+                idx
+            };
+            let color = if row == mid {
+                self.selected_text_color
+            } else {
+                self.text_color
+            };
+            let pos = (self.bounds.x + 2, self.bounds.y + (row + 1) * row_h);
+            renderer.draw_text(pos, &self.options[idx as usize], color);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::PointerDown { x, y } if self.bounds.contains(*x, *y) => {
+                self.drag_y = Some(*y);
+                self.dragged = false;
+                true
+            }
+            Event::PointerMove { y, .. } => {
+                let Some(start) = self.drag_y else {
+                    return false;
+                };
+                let rows = (*y - start) / self.row_height();
+                if rows != 0 {
+                    // Dragging down reveals earlier options.
+                    self.shift(-rows);
+                    self.drag_y = Some(start + rows * self.row_height());
+                    self.dragged = true;
+                }
+                true
+            }
+            Event::PointerUp { x, y } => {
+                let was_dragging = self.drag_y.take().is_some();
+                if self.dragged {
+                    self.dragged = false;
+                    return true;
+                }
+                if !self.bounds.contains(*x, *y) {
+                    return was_dragging;
+                }
+                let row = (*y - self.bounds.y) / self.row_height();
+                self.shift(row - self.visible / 2);
+                true
+            }
+            Event::KeyDown { key: Key::ArrowUp } => {
+                self.shift(-1);
+                true
+            }
+            Event::KeyDown {
+                key: Key::ArrowDown,
+            } => {
+                self.shift(1);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Shape rasterization helpers shared by round widgets.
+//!
+//! The [`Renderer`] trait only fills rectangles, so circles and arcs are
+//! decomposed into horizontal spans and small squares.
+
+use rlvgl_core::math::{TRIG_SCALE, cos, sin, sqrt};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect};
+
+/// Fill a circle of `radius` centered at `(cx, cy)` using one span per row.
+pub(crate) fn fill_circle(
+    renderer: &mut dyn Renderer,
+    cx: i32,
+    cy: i32,
+    radius: i32,
+    color: Color,
+) {
+    if radius <= 0 {
+        return;
+    }
+    let r2 = (radius * radius) as u32;
+    for dy in -radius..radius {
+        // Sample at the row center so the disc stays symmetric.
+        let y = 2 * dy + 1;
+        let half = sqrt(r2.saturating_sub((y * y / 4) as u32)) as i32;
+        if half == 0 {
+            continue;
+        }
+        renderer.fill_rect(
+            Rect {
+                x: cx - half,
+                y: cy + dy,
+                width: 2 * half,
+                height: 1,
+            },
+            color,
+        );
+    }
+}
+
+/// Draw an arc of `width` pixels along a circle of `radius`.
+///
+/// Angles are in degrees, clockwise from the positive x axis, matching LVGL.
+pub(crate) fn draw_arc(
+    renderer: &mut dyn Renderer,
+    center: (i32, i32),
+    radius: i32,
+    start: i32,
+    length: i32,
+    width: i32,
+    color: Color,
+) {
+    if radius <= 0 || width <= 0 || length <= 0 {
+        return;
+    }
+    // Step so neighbouring dots overlap by at least half their size.
+    let step = ((width * 57) / (2 * radius)).max(1);
+    let r = radius - width / 2;
+    let mut a = start;
+    while a <= start + length {
+        let x = center.0 + r * cos(a) / TRIG_SCALE;
+        let y = center.1 + r * sin(a) / TRIG_SCALE;
+        renderer.fill_rect(
+            Rect {
+                x: x - width / 2,
+                y: y - width / 2,
+                width,
+                height: width,
+            },
+            color,
+        );
+        a += step;
+    }
+}
+
+/// Linearly mix `fg` over `bg` with weight `t` (`255` yields `fg`).
+pub(crate) fn mix(fg: Color, bg: Color, t: u8) -> Color {
+    let t = t as u16;
+    let inv = 255 - t;
+    let m = |a: u8, b: u8| ((a as u16 * t + b as u16 * inv) / 255) as u8;
+    Color(m(fg.0, bg.0), m(fg.1, bg.1), m(fg.2, bg.2), m(fg.3, bg.3))
+}
@@ -87,11 +87,7 @@ impl Widget for Slider {
             return false;
         };
 
-        if *y < self.bounds.y
-            || *y >= self.bounds.y + self.bounds.height
-            || *x < self.bounds.x
-            || *x >= self.bounds.x + self.bounds.width
-        {
+        if !self.bounds.contains(*x, *y) {
             return false;
         }
 
@@ -0,0 +1,271 @@
+//! Numeric spinbox with per-digit editing.
+use alloc::string::String;
+use core::fmt::Write;
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+/// Largest number of digits a spinbox can display.
+pub const MAX_DIGITS: u8 = 10;
+
+/// Fixed-point number input with `-`/`+` buttons and a digit cursor.
+///
+/// The value is an integer; `decimals` of its digits are shown after the
+/// decimal point. The cursor selects the digit changed by one step, so the
+/// step is always a power of ten.
+///
+/// Keys: `ArrowUp`/`ArrowDown` increment and decrement, `ArrowLeft` and
+/// `ArrowRight` move the cursor, and `Enter` moves it to the next lower digit
+/// with wrap-around so a rotary encoder with a push button can edit every
+/// digit. [`Event::Encoder`] steps increment when turned clockwise and
+/// decrement otherwise.
+pub struct Spinbox {
+    bounds: Rect,
+    /// Style of the background and button faces.
+    pub style: Style,
+    /// Color used for the value text.
+    pub text_color: Color,
+    /// Approximate glyph advance used to underline the cursor digit.
+    pub char_width: i32,
+    /// Wrap to the other end of the range instead of clamping.
+    pub rollover: bool,
+    min: i32,
+    max: i32,
+    value: i32,
+    digits: u8,
+    decimals: u8,
+    cursor: u8,
+}
+
+impl Spinbox {
+    /// Create a spinbox for `min..=max` showing `digits` digits.
+    pub fn new(bounds: Rect, min: i32, max: i32, digits: u8) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        Self {
+            bounds,
+            style: Style::default(),
+            text_color: Color(0, 0, 0, 255),
+            char_width: 8,
+            rollover: false,
+            min,
+            max,
+            value: 0.clamp(min, max),
+            digits: digits.clamp(1, MAX_DIGITS),
+            decimals: 0,
+            cursor: 0,
+        }
+    }
+
+    /// Current value as an unscaled integer.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Set the value, clamped to the range.
+    pub fn set_value(&mut self, val: i32) {
+        self.value = val.clamp(self.min, self.max);
+    }
+
+    /// Change the allowed range and clamp the current value.
+    pub fn set_range(&mut self, min: i32, max: i32) {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        self.min = min;
+        self.max = max;
+        self.set_value(self.value);
+    }
+
+    /// Number of digits displayed after the decimal point.
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Set how many of the digits follow the decimal point.
+    pub fn set_decimals(&mut self, decimals: u8) {
+        self.decimals = decimals.min(self.digits - 1);
+    }
+
+    /// Position of the cursor digit counted from the least significant one.
+    pub fn cursor(&self) -> u8 {
+        self.cursor
+    }
+
+    /// Move the cursor to digit `pos`, clamped to the displayed digits.
+    pub fn set_cursor(&mut self, pos: u8) {
+        self.cursor = pos.min(self.digits - 1);
+    }
+
+    /// Amount added by one increment, `10^cursor`.
+    pub fn step(&self) -> i32 {
+        10i32.pow(self.cursor as u32)
+    }
+
+    /// Place the cursor on the highest power of ten not above `step`.
+    pub fn set_step(&mut self, step: i32) {
+        let mut pos = 0;
+        let mut s = step.max(1) / 10;
+        while s > 0 {
+            pos += 1;
+            s /= 10;
+        }
+        self.set_cursor(pos);
+    }
+
+    /// Add one step to the value.
+    pub fn increment(&mut self) {
+        self.apply(self.value.saturating_add(self.step()), self.min);
+    }
+
+    /// Subtract one step from the value.
+    pub fn decrement(&mut self) {
+        self.apply(self.value.saturating_sub(self.step()), self.max);
+    }
+
+    /// Store `next`, wrapping to `wrap` when out of range and rollover is on.
+    fn apply(&mut self, next: i32, wrap: i32) {
+        if self.rollover && (next > self.max || next < self.min) {
+            self.value = wrap;
+        } else {
+            self.set_value(next);
+        }
+    }
+
+    /// Format the value with sign, zero padding and decimal point.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        if self.min < 0 {
+            out.push(if self.value < 0 { '-' } else { '+' });
+        }
+        let mut digits = String::new();
+        // Writing to a String cannot fail.
+        let _ = write!(
+            digits,
+            "{:0width$}",
+            self.value.unsigned_abs(),
+            width = self.digits as usize
+        );
+        let int_len = digits.len() - self.decimals as usize;
+        out.push_str(&digits[..int_len]);
+        if self.decimals > 0 {
+            out.push('.');
+            out.push_str(&digits[int_len..]);
+        }
+        out
+    }
+
+    /// Bounds of the decrement button on the left edge.
+    pub fn minus_bounds(&self) -> Rect {
+        Rect {
+            x: self.bounds.x,
+            y: self.bounds.y,
+            width: self.bounds.height,
+            height: self.bounds.height,
+        }
+    }
+
+    /// Bounds of the increment button on the right edge.
+    pub fn plus_bounds(&self) -> Rect {
+        Rect {
+            x: self.bounds.x + self.bounds.width - self.bounds.height,
+            y: self.bounds.y,
+            width: self.bounds.height,
+            height: self.bounds.height,
+        }
+    }
+}
+
+impl Widget for Spinbox {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.style.bg_color);
+
+        let minus = self.minus_bounds();
+        let plus = self.plus_bounds();
+        renderer.fill_rect(minus, self.style.border_color);
+        renderer.fill_rect(plus, self.style.border_color);
+        let bg = self.style.bg_color;
+        let bar = |r: Rect| Rect {
+            x: r.x + r.width / 4,
+            y: r.y + r.height / 2 - 1,
+            width: r.width / 2,
+            height: 2,
+        };
+        renderer.fill_rect(bar(minus), bg);
+        renderer.fill_rect(bar(plus), bg);
+        renderer.fill_rect(
+            Rect {
+                x: plus.x + plus.width / 2 - 1,
+                y: plus.y + plus.height / 4,
+                width: 2,
+                height: plus.height / 2,
+            },
+            bg,
+        );
+
+        let text = self.text();
+        let text_x = minus.x + minus.width + 2;
+        let baseline = self.bounds.y + self.bounds.height;
+        renderer.draw_text((text_x, baseline), &text, self.text_color);
+
+        // Index of the cursor digit counted from the end of the string.
+        let mut from_end = self.cursor as i32 + 1;
+        if self.decimals > 0 && self.cursor >= self.decimals {
+            from_end += 1;
+        }
+        let col = text.chars().count() as i32 - from_end;
+        renderer.fill_rect(
+            Rect {
+                x: text_x + col * self.char_width,
+                y: baseline - 2,
+                width: self.char_width,
+                height: 2,
+            },
+            self.text_color,
+        );
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::PointerUp { x, y } => {
+                if self.minus_bounds().contains(*x, *y) {
+                    self.decrement();
+                } else if self.plus_bounds().contains(*x, *y) {
+                    self.increment();
+                } else {
+                    return self.bounds.contains(*x, *y);
+                }
+                true
+            }
+            Event::KeyDown { key } => {
+                match key {
+                    Key::ArrowUp => self.increment(),
+                    Key::ArrowDown => self.decrement(),
+                    Key::ArrowLeft => self.set_cursor(self.cursor + 1),
+                    Key::ArrowRight => self.cursor = self.cursor.saturating_sub(1),
+                    Key::Enter => {
+                        self.cursor = match self.cursor {
+                            0 => self.digits - 1,
+                            c => c - 1,
+                        }
+                    }
+                    _ => return false,
+                }
+                true
+            }
+            Event::Encoder { steps } => {
+                for _ in 0..steps.unsigned_abs() {
+                    if *steps > 0 {
+                        self.increment();
+                    } else {
+                        self.decrement();
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Rotating arc loading indicator.
+use rlvgl_core::event::{DEFAULT_TICK_MS, Event};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::style::Style;
+use rlvgl_core::widget::{Color, Rect, Widget};
+
+use crate::shapes::draw_arc;
+
+/// Spinning arc driven by [`Event::Tick`].
+///
+/// The track is drawn in `style.border_color` and the moving arc in
+/// `arc_color`. Each tick advances the animation by the configured tick
+/// period so one revolution takes `period_ms`.
+pub struct Spinner {
+    bounds: Rect,
+    /// Background and track style.
+    pub style: Style,
+    /// Color of the rotating arc.
+    pub arc_color: Color,
+    /// Thickness of the track and arc in pixels.
+    pub arc_width: i32,
+    period_ms: u32,
+    arc_length: i32,
+    tick_ms: u32,
+    elapsed: u32,
+}
+
+impl Spinner {
+    /// Create a spinner completing one turn every `period_ms` with an arc
+    /// spanning `arc_length` degrees.
+    pub fn new(bounds: Rect, period_ms: u32, arc_length: i32) -> Self {
+        Self {
+            bounds,
+            style: Style::default(),
+            arc_color: Color(0, 0, 0, 255),
+            arc_width: 4,
+            period_ms: period_ms.max(1),
+            arc_length: arc_length.clamp(0, 360),
+            tick_ms: DEFAULT_TICK_MS,
+            elapsed: 0,
+        }
+    }
+
+    /// Set the time represented by one [`Event::Tick`] in milliseconds.
+    pub fn set_tick_period(&mut self, ms: u32) {
+        self.tick_ms = ms;
+    }
+
+    /// Advance the animation by `delta_ms` milliseconds.
+    pub fn tick(&mut self, delta_ms: u32) {
+        let elapsed = self.elapsed as u64 + delta_ms as u64;
+        self.elapsed = (elapsed % self.period_ms as u64) as u32;
+    }
+
+    /// Start angle of the arc in degrees.
+    pub fn angle(&self) -> i32 {
+        (self.elapsed as u64 * 360 / self.period_ms as u64) as i32
+    }
+
+    /// Angular length of the arc in degrees.
+    pub fn arc_length(&self) -> i32 {
+        self.arc_length
+    }
+}
+
+impl Widget for Spinner {
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_rect(self.bounds, self.style.bg_color);
+        let center = (
+            self.bounds.x + self.bounds.width / 2,
+            self.bounds.y + self.bounds.height / 2,
+        );
+        let radius = self.bounds.width.min(self.bounds.height) / 2;
+        draw_arc(
+            renderer,
+            center,
+            radius,
+            0,
+            360,
+            self.arc_width,
+            self.style.border_color,
+        );
+        draw_arc(
+            renderer,
+            center,
+            radius,
+            self.angle(),
+            self.arc_length,
+            self.arc_width,
+            self.arc_color,
+        );
+    }
+
+    /// Advance on ticks without consuming them so siblings keep animating.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Tick = event {
+            self.tick(self.tick_ms);
+        }
+        false
+    }
+}
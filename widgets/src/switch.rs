@@ -67,15 +67,11 @@ impl Widget for Switch {
     }
 
     fn handle_event(&mut self, event: &Event) -> bool {
-        if let Event::PointerUp { x, y } = event {
-            let inside = *x >= self.bounds.x
-                && *x < self.bounds.x + self.bounds.width
-                && *y >= self.bounds.y
-                && *y < self.bounds.y + self.bounds.height;
-            if inside {
-                self.on = !self.on;
-                return true;
-            }
+        if let Event::PointerUp { x, y } = event
+            && self.bounds.contains(*x, *y)
+        {
+            self.on = !self.on;
+            return true;
         }
         false
     }
@@ -0,0 +1,125 @@
+//! Tests for animated image playback and drawing.
+use rlvgl_core::event::Event;
+use rlvgl_core::frames::{Frame, FrameSource, Frames};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_widgets::anim_image::AnimImage;
+
+struct DisplayRenderer<'a> {
+    display: &'a mut BufferDisplay,
+}
+
+impl<'a> Renderer for DisplayRenderer<'a> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let colors = vec![color; (rect.width * rect.height) as usize];
+        self.display.flush(rect, &colors);
+    }
+
+    fn draw_text(&mut self, _pos: (i32, i32), _text: &str, _color: Color) {}
+}
+
+const RED: Color = Color(255, 0, 0, 255);
+const GREEN: Color = Color(0, 255, 0, 255);
+const CLEAR: Color = Color(0, 0, 0, 0);
+
+fn rect() -> Rect {
+    Rect {
+        x: 2,
+        y: 1,
+        width: 2,
+        height: 1,
+    }
+}
+
+/// Two 2x1 frames shown for 100 ms and 200 ms.
+fn frames(loop_count: u32) -> Frames {
+    let mut anim = Frames::new(
+        2,
+        1,
+        vec![
+            Frame {
+                pixels: vec![RED, RED],
+                delay_ms: 100,
+            },
+            Frame {
+                pixels: vec![GREEN, CLEAR],
+                delay_ms: 200,
+            },
+        ],
+    );
+    anim.loop_count = loop_count;
+    anim
+}
+
+#[test]
+fn ticks_honor_frame_delays_without_consuming() {
+    let mut img = AnimImage::new(rect(), frames(0));
+    img.set_tick_period(50);
+    assert!(!img.handle_event(&Event::Tick));
+    assert_eq!(img.frame(), 0);
+    img.handle_event(&Event::Tick);
+    assert_eq!(img.frame(), 1);
+    img.tick(150);
+    assert_eq!(img.frame(), 1);
+    img.tick(50);
+    assert_eq!(img.frame(), 0);
+    assert_eq!(img.plays(), 1);
+    assert!(img.is_playing());
+}
+
+#[test]
+fn stops_after_loop_count_and_restarts_on_play() {
+    let mut img = AnimImage::new(rect(), frames(2));
+    img.tick(600);
+    assert!(!img.is_playing());
+    assert_eq!(img.frame(), 1);
+    assert_eq!(img.plays(), 2);
+    img.play();
+    assert!(img.is_playing());
+    assert_eq!(img.frame(), 0);
+    assert_eq!(img.plays(), 0);
+}
+
+#[test]
+fn pause_and_seek() {
+    let mut img = AnimImage::new(rect(), frames(0));
+    img.pause();
+    img.tick(1000);
+    assert_eq!(img.frame(), 0);
+    assert!(img.seek(1));
+    assert_eq!(img.source().pixels()[0], GREEN);
+    assert!(!img.seek(5));
+    assert_eq!(img.frame(), 1);
+}
+
+#[test]
+fn reports_only_own_bounds_when_frame_changes() {
+    let mut img = AnimImage::new(rect(), frames(0));
+    assert!(img.take_dirty().is_some());
+    assert!(img.take_dirty().is_none());
+    img.tick(50);
+    assert!(img.take_dirty().is_none());
+    img.tick(50);
+    let dirty = img.take_dirty().unwrap();
+    assert_eq!((dirty.x, dirty.y, dirty.width, dirty.height), (2, 1, 2, 1));
+}
+
+#[test]
+fn draws_current_frame_over_background() {
+    let bg = Color(9, 9, 9, 255);
+    let mut img = AnimImage::new(rect(), frames(0));
+    img.style.bg_color = bg;
+    let mut display = BufferDisplay::new(5, 2);
+    img.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    assert_eq!(&display.buffer[7..9], &[RED, RED]);
+
+    img.seek(1);
+    img.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    assert_eq!(&display.buffer[7..9], &[GREEN, bg]);
+    assert_eq!(display.buffer[0], Color(0, 0, 0, 255));
+}
@@ -0,0 +1,69 @@
+//! Tests for calendar date math, navigation and selection.
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::widget::{Rect, Widget};
+use rlvgl_widgets::calendar::{Calendar, Date, days_in_month};
+
+fn rect() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 140,
+        height: 160,
+    }
+}
+
+fn center(r: Rect) -> (i32, i32) {
+    (r.x + r.width / 2, r.y + r.height / 2)
+}
+
+#[test]
+fn date_helpers() {
+    assert_eq!(days_in_month(2024, 2), 29);
+    assert_eq!(days_in_month(1900, 2), 28);
+    assert_eq!(Date::new(2024, 1, 1).weekday(), 1);
+    assert_eq!(Date::new(2000, 2, 29).weekday(), 2);
+    assert_eq!(Date::new(2024, 2, 28).add_days(2), Date::new(2024, 3, 1));
+    assert_eq!(Date::new(2024, 1, 1).add_days(-1), Date::new(2023, 12, 31));
+    assert_eq!(Date::new(2023, 2, 31).day, 28);
+}
+
+#[test]
+fn calendar_grid_starts_on_weekday() {
+    // June 2024 starts on a Saturday.
+    let mut cal = Calendar::new(rect(), Date::new(2024, 6, 15));
+    let first = cal.day_bounds(1).unwrap();
+    assert_eq!(first.x, 6 * 20);
+    cal.first_weekday = 1;
+    assert_eq!(cal.day_bounds(1).unwrap().x, 5 * 20);
+    assert!(cal.day_bounds(31).is_none());
+}
+
+#[test]
+fn calendar_navigates_and_selects() {
+    let picked = Rc::new(Cell::new(None));
+    let p = picked.clone();
+    let mut cal = Calendar::new(rect(), Date::new(2024, 1, 10));
+    cal.set_on_select(move |d| p.set(Some(d)));
+
+    let (x, y) = center(cal.prev_bounds());
+    assert!(cal.handle_event(&Event::PointerUp { x, y }));
+    assert_eq!(cal.shown_month(), (2023, 12));
+
+    let (x, y) = center(cal.day_bounds(25).unwrap());
+    assert!(cal.handle_event(&Event::PointerUp { x, y }));
+    assert_eq!(picked.get(), Some(Date::new(2023, 12, 25)));
+
+    // Keyboard selection crosses into the next month.
+    for _ in 0..7 {
+        cal.handle_event(&Event::KeyDown {
+            key: Key::ArrowRight,
+        });
+    }
+    assert_eq!(cal.selected(), Some(Date::new(2024, 1, 1)));
+    assert_eq!(cal.shown_month(), (2024, 1));
+    cal.handle_event(&Event::KeyDown { key: Key::Enter });
+    assert_eq!(picked.get(), Some(Date::new(2024, 1, 1)));
+}
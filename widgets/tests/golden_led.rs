@@ -0,0 +1,55 @@
+//! Golden tests for LED rendering.
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_widgets::led::Led;
+
+struct DisplayRenderer<'a> {
+    display: &'a mut BufferDisplay,
+}
+
+impl<'a> Renderer for DisplayRenderer<'a> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let colors = vec![color; (rect.width * rect.height) as usize];
+        self.display.flush(rect, &colors);
+    }
+
+    fn draw_text(&mut self, _pos: (i32, i32), _text: &str, _color: Color) {}
+}
+
+fn render(led: &Led) -> BufferDisplay {
+    let mut display = BufferDisplay::new(20, 20);
+    led.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    display
+}
+
+#[test]
+fn led_brightness_dims_color_and_glow() {
+    let red = Color(255, 0, 0, 255);
+    let mut led = Led::new(
+        Rect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 20,
+        },
+        red,
+    );
+    led.style.bg_color = Color(0, 0, 0, 255);
+
+    let lit = render(&led);
+    assert_eq!(lit.buffer[10 * 20 + 10], red);
+    // Glow ring just outside the core is tinted but dimmer than the core.
+    let halo = lit.buffer[10 * 20 + 17];
+    assert!(halo.0 > 0 && halo.0 < 255);
+    assert_eq!(lit.buffer[0], Color(0, 0, 0, 255));
+
+    led.off();
+    assert!(!led.is_on());
+    let dark = render(&led);
+    let center = dark.buffer[10 * 20 + 10];
+    assert!(center.0 > 0 && center.0 < 255);
+    assert_eq!(dark.buffer[10 * 20 + 17], Color(0, 0, 0, 255));
+}
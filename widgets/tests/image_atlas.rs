@@ -0,0 +1,94 @@
+//! Tests for drawing atlas sub-rects with the image widget.
+use rlvgl_core::atlas::AtlasRect;
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_widgets::image::Image;
+
+struct DisplayRenderer<'a> {
+    display: &'a mut BufferDisplay,
+}
+
+impl<'a> Renderer for DisplayRenderer<'a> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let colors = vec![color; (rect.width * rect.height) as usize];
+        self.display.flush(rect, &colors);
+    }
+
+    fn draw_text(&mut self, _pos: (i32, i32), _text: &str, _color: Color) {}
+}
+
+const BG: Color = Color(255, 255, 255, 255);
+
+fn px(v: u8) -> Color {
+    Color(v, 0, 0, 255)
+}
+
+/// 4x3 sheet whose pixels encode their own index.
+fn sheet() -> Vec<Color> {
+    (0..12).map(px).collect()
+}
+
+fn bounds() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 3,
+        height: 3,
+    }
+}
+
+fn render(image: &Image) -> Vec<Color> {
+    let mut display = BufferDisplay::new(3, 3);
+    let mut renderer = DisplayRenderer {
+        display: &mut display,
+    };
+    image.draw(&mut renderer);
+    display.buffer
+}
+
+#[test]
+fn draws_trimmed_region_at_offset() {
+    let pixels = sheet();
+    let region = AtlasRect {
+        sheet: 0,
+        x: 1,
+        y: 1,
+        width: 2,
+        height: 2,
+        offset_x: 1,
+        offset_y: 0,
+        source_width: 3,
+        source_height: 3,
+        rotated: false,
+    };
+    let image = Image::from_atlas(bounds(), 4, 3, &pixels, region);
+    assert_eq!(image.region(), Some(region));
+    assert_eq!(
+        render(&image),
+        vec![BG, px(5), px(6), BG, px(9), px(10), BG, BG, BG]
+    );
+}
+
+#[test]
+fn draws_rotated_region_upright() {
+    let pixels = sheet();
+    let mut image = Image::new(bounds(), 4, 3, &pixels);
+    // A 3x2 sprite stored clockwise in the 2x3 block at (0, 0).
+    image.set_region(Some(AtlasRect {
+        sheet: 0,
+        x: 0,
+        y: 0,
+        width: 3,
+        height: 2,
+        offset_x: 0,
+        offset_y: 0,
+        source_width: 3,
+        source_height: 2,
+        rotated: true,
+    }));
+    assert_eq!(
+        render(&image),
+        vec![px(1), px(5), px(9), px(0), px(4), px(8), BG, BG, BG]
+    );
+}
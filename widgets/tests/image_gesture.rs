@@ -0,0 +1,115 @@
+//! Tests for image zoom, rotation and gesture handling.
+use rlvgl_core::event::{Event, Gesture, GesturePhase};
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_widgets::image::Image;
+
+struct DisplayRenderer<'a> {
+    display: &'a mut BufferDisplay,
+}
+
+impl<'a> Renderer for DisplayRenderer<'a> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let colors = vec![color; (rect.width * rect.height) as usize];
+        self.display.flush(rect, &colors);
+    }
+
+    fn draw_text(&mut self, _pos: (i32, i32), _text: &str, _color: Color) {}
+}
+
+/// Pixel `i` of a 4×4 image is gray level `i`.
+fn pixels() -> Vec<Color> {
+    (0..16).map(|i| Color(i, i, i, 255)).collect()
+}
+
+fn render(image: &Image) -> Vec<u8> {
+    let mut display = BufferDisplay::new(4, 4);
+    image.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    display.buffer.iter().map(|c| c.0).collect()
+}
+
+fn image(pixels: &[Color]) -> Image<'_> {
+    Image::new(
+        Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        },
+        4,
+        4,
+        pixels,
+    )
+}
+
+fn gesture(phase: GesturePhase, scale: i32, angle: i32) -> Event {
+    Event::Gesture(Gesture {
+        phase,
+        x: 2,
+        y: 2,
+        scale,
+        angle,
+    })
+}
+
+#[test]
+fn rotation_turns_the_image_clockwise() {
+    let pixels = pixels();
+    let mut img = image(&pixels);
+    img.set_angle(90);
+    assert_eq!(
+        render(&img),
+        [12, 8, 4, 0, 13, 9, 5, 1, 14, 10, 6, 2, 15, 11, 7, 3]
+    );
+    img.set_angle(-180);
+    assert_eq!(img.angle(), 180);
+    assert_eq!(render(&img), (0..16).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn zoom_scales_around_the_center() {
+    let pixels = pixels();
+    let mut img = image(&pixels);
+    img.set_zoom(512);
+    assert_eq!(
+        render(&img),
+        [5, 5, 6, 6, 5, 5, 6, 6, 9, 9, 10, 10, 9, 9, 10, 10]
+    );
+}
+
+#[test]
+fn gestures_are_ignored_unless_enabled() {
+    let pixels = pixels();
+    let mut img = image(&pixels);
+    assert!(!img.handle_event(&gesture(GesturePhase::Start, 256, 0)));
+    assert!(!img.handle_event(&gesture(GesturePhase::Update, 512, 0)));
+    assert_eq!(img.zoom(), 256);
+}
+
+#[test]
+fn gestures_apply_to_the_zoom_and_angle_at_start() {
+    let pixels = pixels();
+    let mut img = image(&pixels);
+    img.set_gestures(true);
+    img.set_zoom(512);
+    assert!(img.handle_event(&gesture(GesturePhase::Start, 256, 0)));
+    assert!(img.handle_event(&gesture(GesturePhase::Update, 384, 30)));
+    assert_eq!((img.zoom(), img.angle()), (768, 30));
+    assert!(img.handle_event(&gesture(GesturePhase::End, 128, -45)));
+    assert_eq!((img.zoom(), img.angle()), (256, 315));
+    assert!(!img.handle_event(&gesture(GesturePhase::Update, 512, 0)));
+
+    // Gestures starting outside the image leave it alone.
+    assert!(!img.handle_event(&Event::Gesture(Gesture {
+        phase: GesturePhase::Start,
+        x: 40,
+        y: 2,
+        scale: 256,
+        angle: 0,
+    })));
+    assert!(!img.handle_event(&gesture(GesturePhase::Update, 4096, 0)));
+    assert_eq!(img.zoom(), 256);
+}
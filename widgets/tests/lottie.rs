@@ -0,0 +1,59 @@
+//! Tests for the Lottie player widget.
+#![cfg(feature = "lottie")]
+use rlvgl_core::event::Event;
+use rlvgl_core::widget::{Rect, Widget};
+use rlvgl_widgets::lottie::Lottie;
+
+/// One second at ten frames per second.
+const TEN_FRAMES_JSON: &str =
+    "{\"v\":\"5.7\",\"fr\":10,\"ip\":0,\"op\":10,\"w\":4,\"h\":4,\"layers\":[]}";
+
+fn rect() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 4,
+        height: 4,
+    }
+}
+
+#[test]
+fn widget_rejects_invalid_json() {
+    assert!(Lottie::from_json(rect(), "not json").is_err());
+}
+
+#[test]
+fn ticks_follow_frame_rate_and_speed() {
+    let mut lottie = Lottie::from_json(rect(), TEN_FRAMES_JSON).unwrap();
+    assert_eq!(lottie.pixels().len(), 16);
+    lottie.set_tick_period(100);
+    assert!(!lottie.handle_event(&Event::Tick));
+    assert_eq!(lottie.frame(), 1);
+    lottie.set_speed(2.0);
+    lottie.tick(100);
+    assert_eq!(lottie.frame(), 3);
+}
+
+#[test]
+fn segment_stops_without_looping() {
+    let mut lottie = Lottie::from_json(rect(), TEN_FRAMES_JSON).unwrap();
+    lottie.set_segment(2, 5);
+    lottie.set_looping(false);
+    lottie.tick(1000);
+    assert_eq!(lottie.frame(), 4);
+    assert!(!lottie.is_playing());
+    lottie.play();
+    assert_eq!(lottie.frame(), 2);
+}
+
+#[test]
+fn cache_respects_budget() {
+    let mut lottie = Lottie::from_json(rect(), TEN_FRAMES_JSON).unwrap();
+    lottie.set_cache_budget(3 * 16 * 4);
+    for _ in 0..5 {
+        lottie.tick(100);
+    }
+    assert_eq!(lottie.cached_frames(), 3);
+    lottie.set_cache_budget(0);
+    assert_eq!(lottie.cached_frames(), 0);
+}
@@ -0,0 +1,86 @@
+//! Tests for roller based date and time pickers.
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::widget::{Rect, Widget};
+use rlvgl_widgets::calendar::Date;
+use rlvgl_widgets::picker::{DatePicker, Time, TimePicker};
+use rlvgl_widgets::roller::Roller;
+
+fn rect() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 90,
+        height: 30,
+    }
+}
+
+fn key(key: Key) -> Event {
+    Event::KeyDown { key }
+}
+
+#[test]
+fn roller_tap_drag_and_wrap() {
+    let mut r = Roller::new(rect(), 3);
+    r.set_options(["a", "b", "c", "d"]);
+    // Tap on the bottom row selects the next option.
+    assert!(r.handle_event(&Event::PointerUp { x: 5, y: 25 }));
+    assert_eq!(r.selected_text(), Some("b"));
+    // Dragging up by two rows scrolls forward.
+    r.handle_event(&Event::PointerDown { x: 5, y: 25 });
+    r.handle_event(&Event::PointerMove { x: 5, y: 5 });
+    r.handle_event(&Event::PointerUp { x: 5, y: 5 });
+    assert_eq!(r.selected(), 3);
+    r.handle_event(&key(Key::ArrowDown));
+    assert_eq!(r.selected(), 3);
+    r.infinite = true;
+    r.handle_event(&key(Key::ArrowDown));
+    assert_eq!(r.selected(), 0);
+}
+
+#[test]
+fn date_picker_clamps_days_to_month() {
+    let mut p = DatePicker::new(rect(), 2020, 2030, Date::new(2024, 1, 31));
+    // Move to the month roller and step to February.
+    p.handle_event(&key(Key::ArrowRight));
+    p.handle_event(&key(Key::ArrowDown));
+    assert_eq!(p.date(), Date::new(2024, 2, 29));
+    p.handle_event(&key(Key::ArrowLeft));
+    p.handle_event(&key(Key::ArrowDown));
+    assert_eq!(p.date(), Date::new(2025, 2, 28));
+}
+
+#[test]
+fn date_picker_clamps_invalid_dates() {
+    let zero = Date {
+        year: 2024,
+        month: 0,
+        day: 0,
+    };
+    let p = DatePicker::new(rect(), 2020, 2030, zero);
+    assert_eq!(p.date(), Date::new(2024, 1, 1));
+    let mut p = DatePicker::new(rect(), 2020, 2030, Date::new(2024, 1, 1));
+    p.set_date(Date {
+        year: 2023,
+        month: 14,
+        day: 40,
+    });
+    assert_eq!(p.date(), Date::new(2023, 12, 31));
+}
+
+#[test]
+fn time_picker_steps_minutes() {
+    let mut p = TimePicker::new(
+        rect(),
+        15,
+        Time {
+            hour: 23,
+            minute: 50,
+        },
+    );
+    assert_eq!(p.time().minute, 45);
+    p.handle_event(&key(Key::ArrowDown));
+    assert_eq!(p.time().hour, 0);
+    p.handle_event(&key(Key::ArrowRight));
+    p.handle_event(&key(Key::ArrowDown));
+    assert_eq!(p.time(), Time { hour: 0, minute: 0 });
+}
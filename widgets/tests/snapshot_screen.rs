@@ -0,0 +1,58 @@
+//! Snapshot test of a small screen against golden PNGs in
+//! `tests/snapshots`. Run with `RLVGL_BLESS=1` to update them.
+use rlvgl_core::WidgetNode;
+use rlvgl_core::widget::{Color, Rect};
+use rlvgl_platform::PixelFmt;
+use rlvgl_platform::snapshot::Snapshot;
+use rlvgl_widgets::checkbox::Checkbox;
+use rlvgl_widgets::container::Container;
+use rlvgl_widgets::progress::ProgressBar;
+use rlvgl_widgets::slider::Slider;
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+fn screen() -> WidgetNode {
+    let mut panel = Container::new(rect(0, 0, 64, 48));
+    panel.style.bg_color = Color(30, 40, 60, 255);
+    let mut slider = Slider::new(rect(4, 4, 56, 10), 0, 100);
+    slider.style.bg_color = Color(30, 40, 60, 255);
+    slider.style.border_color = Color(120, 130, 150, 255);
+    slider.knob_color = Color(250, 200, 40, 255);
+    slider.set_value(30);
+    let mut progress = ProgressBar::new(rect(4, 20, 56, 6), 0, 100);
+    progress.style.bg_color = Color(60, 60, 60, 255);
+    progress.bar_color = Color(40, 200, 90, 255);
+    progress.set_value(70);
+    let mut checkbox = Checkbox::new("", rect(4, 32, 12, 12));
+    checkbox.style.bg_color = Color(255, 255, 255, 255);
+    checkbox.style.border_color = Color(0, 0, 0, 255);
+    checkbox.check_color = Color(0, 120, 255, 255);
+    checkbox.set_checked(true);
+    WidgetNode::new(
+        panel,
+        vec![
+            WidgetNode::new(slider, vec![]),
+            WidgetNode::new(progress, vec![]),
+            WidgetNode::new(checkbox, vec![]),
+        ],
+    )
+}
+
+#[test]
+fn screen_matches_argb8888_golden() {
+    Snapshot::new(64, 48).assert_matches("screen_argb8888", &screen());
+}
+
+#[test]
+fn screen_matches_rgb565_golden() {
+    Snapshot::new(64, 48)
+        .with_format(PixelFmt::Rgb565)
+        .assert_matches("screen_rgb565", &screen());
+}
@@ -0,0 +1,76 @@
+//! Tests for spinbox stepping, formatting and input handling.
+use rlvgl_core::event::{Event, Key};
+use rlvgl_core::widget::{Rect, Widget};
+use rlvgl_widgets::spinbox::Spinbox;
+
+fn rect() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 100,
+        height: 20,
+    }
+}
+
+#[test]
+fn spinbox_formats_sign_and_decimal_point() {
+    let mut s = Spinbox::new(rect(), -9999, 9999, 4);
+    s.set_decimals(2);
+    s.set_value(-123);
+    assert_eq!(s.text(), "-01.23");
+    s.set_value(5);
+    assert_eq!(s.text(), "+00.05");
+}
+
+#[test]
+fn spinbox_digit_cursor_sets_step() {
+    let mut s = Spinbox::new(rect(), 0, 999, 3);
+    s.set_step(10);
+    assert_eq!(s.cursor(), 1);
+    s.increment();
+    assert_eq!(s.value(), 10);
+    assert!(s.handle_event(&Event::KeyDown {
+        key: Key::ArrowLeft
+    }));
+    assert!(s.handle_event(&Event::KeyDown { key: Key::ArrowUp }));
+    assert_eq!(s.value(), 110);
+    // Enter walks the cursor down and wraps back to the top digit.
+    for expected in [1, 0, 2] {
+        s.handle_event(&Event::KeyDown { key: Key::Enter });
+        assert_eq!(s.cursor(), expected);
+    }
+}
+
+#[test]
+fn spinbox_clamps_or_rolls_over() {
+    let mut s = Spinbox::new(rect(), 0, 20, 2);
+    s.set_value(15);
+    s.set_step(10);
+    s.increment();
+    assert_eq!(s.value(), 20);
+    s.rollover = true;
+    s.increment();
+    assert_eq!(s.value(), 0);
+    s.decrement();
+    assert_eq!(s.value(), 20);
+}
+
+#[test]
+fn spinbox_buttons_change_value() {
+    let mut s = Spinbox::new(rect(), 0, 10, 2);
+    assert!(s.handle_event(&Event::PointerUp { x: 95, y: 10 }));
+    assert!(s.handle_event(&Event::PointerUp { x: 95, y: 10 }));
+    assert_eq!(s.value(), 2);
+    assert!(s.handle_event(&Event::PointerUp { x: 5, y: 10 }));
+    assert_eq!(s.value(), 1);
+    assert!(!s.handle_event(&Event::PointerUp { x: 5, y: 30 }));
+}
+
+#[test]
+fn spinbox_follows_encoder_steps() {
+    let mut s = Spinbox::new(rect(), 0, 99, 2);
+    assert!(s.handle_event(&Event::Encoder { steps: 3 }));
+    assert_eq!(s.value(), 3);
+    assert!(s.handle_event(&Event::Encoder { steps: -1 }));
+    assert_eq!(s.value(), 2);
+}
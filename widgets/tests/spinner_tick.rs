@@ -0,0 +1,72 @@
+//! Tests for spinner animation driven by ticks.
+use rlvgl_core::event::Event;
+use rlvgl_core::renderer::Renderer;
+use rlvgl_core::widget::{Color, Rect, Widget};
+use rlvgl_platform::display::{BufferDisplay, DisplayDriver};
+use rlvgl_widgets::spinner::Spinner;
+
+struct DisplayRenderer<'a> {
+    display: &'a mut BufferDisplay,
+}
+
+impl<'a> Renderer for DisplayRenderer<'a> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let colors = vec![color; (rect.width * rect.height) as usize];
+        self.display.flush(rect, &colors);
+    }
+
+    fn draw_text(&mut self, _pos: (i32, i32), _text: &str, _color: Color) {}
+}
+
+fn rect() -> Rect {
+    Rect {
+        x: 0,
+        y: 0,
+        width: 40,
+        height: 40,
+    }
+}
+
+#[test]
+fn spinner_advances_without_consuming_ticks() {
+    let mut s = Spinner::new(rect(), 1000, 60);
+    s.set_tick_period(250);
+    assert!(!s.handle_event(&Event::Tick));
+    assert_eq!(s.angle(), 90);
+    for _ in 0..3 {
+        s.handle_event(&Event::Tick);
+    }
+    assert_eq!(s.angle(), 0);
+}
+
+#[test]
+fn spinner_draws_arc_at_current_angle() {
+    let arc = Color(255, 0, 0, 255);
+    let mut s = Spinner::new(rect(), 1000, 30);
+    s.arc_color = arc;
+    s.style.bg_color = Color(0, 0, 0, 255);
+    s.style.border_color = Color(9, 9, 9, 255);
+
+    let mut display = BufferDisplay::new(40, 40);
+    s.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    // Angle 0 points right: the arc covers the right edge, not the bottom.
+    assert_eq!(display.buffer[20 * 40 + 37], arc);
+    assert_eq!(display.buffer[37 * 40 + 20], Color(9, 9, 9, 255));
+
+    s.tick(250);
+    s.draw(&mut DisplayRenderer {
+        display: &mut display,
+    });
+    assert_eq!(display.buffer[37 * 40 + 20], arc);
+}
+
+#[test]
+fn spinner_handles_periods_beyond_half_the_u32_range() {
+    let period = 3_000_000_000;
+    let mut s = Spinner::new(rect(), period, 60);
+    s.tick(period - 1);
+    s.tick(period - 1);
+    assert_eq!(s.angle(), (360 * (period as u64 - 2) / period as u64) as i32);
+}
@@ -0,0 +1,50 @@
+//! Tests for switching widget text with i18n bindings.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rlvgl_core::i18n::{Bindings, Catalog, PluralRule, StringId, Translator};
+use rlvgl_core::widget::{Rect, Widget};
+use rlvgl_widgets::{button::Button, checkbox::Checkbox, label::Label, radio::Radio};
+
+static CATALOGS: [Catalog<'static>; 2] = [
+    Catalog::new("en", PluralRule::OneOther, &[&["Save"], &["Wi-Fi"]]),
+    Catalog::new("fr", PluralRule::OneWithZero, &[&["Enregistrer"], &[]]),
+];
+
+fn rect(y: i32) -> Rect {
+    Rect {
+        x: 0,
+        y,
+        width: 40,
+        height: 10,
+    }
+}
+
+#[test]
+fn bound_widgets_follow_language() {
+    let label = Rc::new(RefCell::new(Label::new("", rect(0))));
+    let button = Rc::new(RefCell::new(Button::new("", rect(10))));
+    let check = Rc::new(RefCell::new(Checkbox::new("", rect(20))));
+    let radio = Rc::new(RefCell::new(Radio::new("", rect(30))));
+    let mut bindings = Bindings::new();
+    bindings.bind(label.clone(), StringId(0));
+    bindings.bind(button.clone(), StringId(0));
+    bindings.bind(check.clone(), StringId(1));
+    bindings.bind(radio.clone(), StringId(1));
+    assert_eq!(bindings.len(), 4);
+
+    let mut tr = Translator::new(&CATALOGS);
+    let area = bindings.update(&tr).unwrap();
+    assert_eq!((area.y, area.height), (0, 40));
+    assert_eq!(label.borrow().text(), "Save");
+    assert_eq!(label.borrow_mut().take_dirty().map(|r| r.y), Some(0));
+    assert!(label.borrow_mut().take_dirty().is_none());
+    assert!(bindings.update(&tr).is_none());
+
+    tr.set_locale("fr");
+    assert!(bindings.update(&tr).is_some());
+    assert_eq!(button.borrow().text(), "Enregistrer");
+    assert_eq!(button.borrow_mut().take_dirty().map(|r| r.y), Some(10));
+    assert_eq!(check.borrow().text(), "Wi-Fi");
+    assert_eq!(radio.borrow_mut().take_dirty().map(|r| r.y), Some(30));
+}